@@ -31,7 +31,7 @@ impl FullAccessTypeContext {
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self {
             global_context: previous_context.global_context,
-            signer_account_id: previous_context.owner_account_id.into(),
+            signer_account_id: previous_context.owner_account_id,
             permission: unc_primitives::account::AccessKeyPermission::FullAccess,
         })
     }
@@ -69,7 +69,7 @@ pub struct FunctionCallTypeContext {
     global_context: crate::GlobalContext,
     signer_account_id: unc_primitives::types::AccountId,
     allowance: Option<crate::types::unc_token::UncToken>,
-    receiver_account_id: crate::types::account_id::AccountId,
+    receiver_account_id: unc_primitives::types::AccountId,
     method_names: crate::types::vec_string::VecString,
 }
 
@@ -78,11 +78,14 @@ impl FunctionCallTypeContext {
         previous_context: super::AddKeyCommandContext,
         scope: &<FunctionCallType as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         Ok(Self {
+            receiver_account_id,
             global_context: previous_context.global_context,
-            signer_account_id: previous_context.owner_account_id.into(),
+            signer_account_id: previous_context.owner_account_id,
             allowance: scope.allowance,
-            receiver_account_id: scope.receiver_account_id.clone(),
             method_names: scope.method_names.clone(),
         })
     }
@@ -62,10 +62,7 @@ impl From<PrintKeypairToTerminalContext> for crate::commands::ActionContext {
                 |_prepolulated_unsinged_transaction, _network_config| Ok(()),
             ),
             on_before_sending_transaction_callback: std::sync::Arc::new(
-                |_signed_transaction, _network_config, _message| Ok(()),
-            ),
-            on_after_sending_transaction_callback: std::sync::Arc::new(
-                move |_outcome_view, _network_config| {
+                move |_signed_transaction, _network_config, _message| {
                     eprintln!("\n--------------------  Access key info ------------------\n");
                     eprintln!(
                         "Master Seed Phrase: {}\nSeed Phrase HD Path: {}\nImplicit Account ID: {}\nPublic Key: {}\nSECRET KEYPAIR: {}",
@@ -79,6 +76,9 @@ impl From<PrintKeypairToTerminalContext> for crate::commands::ActionContext {
                     Ok(())
                 },
             ),
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                |_outcome_view, _network_config| Ok(()),
+            ),
         }
     }
 }
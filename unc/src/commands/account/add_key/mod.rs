@@ -21,7 +21,7 @@ pub struct AddKeyCommand {
 #[derive(Debug, Clone)]
 pub struct AddKeyCommandContext {
     global_context: crate::GlobalContext,
-    owner_account_id: crate::types::account_id::AccountId,
+    owner_account_id: unc_primitives::types::AccountId,
 }
 
 impl AddKeyCommandContext {
@@ -29,9 +29,12 @@ impl AddKeyCommandContext {
         previous_context: crate::GlobalContext,
         scope: &<AddKeyCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id = scope
+            .owner_account_id
+            .resolve(&previous_context.config, None)?;
         Ok(Self {
+            owner_account_id,
             global_context: previous_context,
-            owner_account_id: scope.owner_account_id.clone(),
         })
     }
 }
@@ -0,0 +1,79 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AddAliasContext)]
+pub struct AddAlias {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Enter the alias (lowercase letters, digits, '-' and '_'):
+    alias: String,
+    /// Which account should the alias point to?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Map the alias to the account on this network only (e.g. testnet)
+    network_name: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// A note to remember what the account is for
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddAliasContext;
+
+impl AddAliasContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AddAlias as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if !crate::common::is_valid_alias(&scope.alias) {
+            color_eyre::eyre::bail!(
+                "Invalid alias \"{}\": use lowercase letters, digits, '-' and '_'",
+                scope.alias
+            );
+        }
+        let credentials_home_dir = &previous_context.config.credentials_home_dir;
+        let mut address_book = crate::common::get_address_book(credentials_home_dir)?;
+        let entry = address_book.aliases.entry(scope.alias.clone()).or_default();
+        let account_id: unc_primitives::types::AccountId =
+            scope.account_id.resolve(&previous_context.config, None)?;
+        match &scope.network_name {
+            Some(network_name) => {
+                entry
+                    .networks
+                    .insert(network_name.clone(), account_id.clone());
+            }
+            None => entry.account_id = Some(account_id.clone()),
+        }
+        if let Some(note) = &scope.note {
+            entry.note.clone_from(note);
+        }
+        crate::common::save_address_book(credentials_home_dir, &address_book)?;
+
+        crate::util::print_success(&match &scope.network_name {
+            Some(network_name) => format!(
+                "@{} now points to <{account_id}> on network <{network_name}>",
+                scope.alias
+            ),
+            None => format!("@{} now points to <{account_id}>", scope.alias),
+        });
+        Ok(Self)
+    }
+}
+
+impl AddAlias {
+    fn input_alias(_context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        Ok(Some(
+            inquire::Text::new("Enter the alias (lowercase letters, digits, '-' and '_'):")
+                .with_validator(|alias: &str| {
+                    if crate::common::is_valid_alias(alias) {
+                        Ok(inquire::validator::Validation::Valid)
+                    } else {
+                        Ok(inquire::validator::Validation::Invalid(
+                            "Use lowercase letters, digits, '-' and '_'".into(),
+                        ))
+                    }
+                })
+                .prompt()?,
+        ))
+    }
+}
@@ -0,0 +1,36 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ExportAddressBookContext)]
+pub struct ExportAddressBook {
+    /// Where do you want to save the address book?
+    file_path: crate::types::path_buf::PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportAddressBookContext;
+
+impl ExportAddressBookContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ExportAddressBook as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let address_book =
+            crate::common::get_address_book(&previous_context.config.credentials_home_dir)?;
+        let file_path = &scope.file_path.0;
+        if let Some(dir) = file_path.parent() {
+            std::fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Failed to create directory: {dir:?}"))?;
+        }
+        std::fs::write(file_path, serde_json::to_string_pretty(&address_book)?)
+            .wrap_err_with(|| format!("Failed to write to file: {file_path:?}"))?;
+
+        crate::util::print_success(&format!(
+            "{} aliases are saved to {}",
+            address_book.aliases.len(),
+            file_path.display()
+        ));
+        Ok(Self)
+    }
+}
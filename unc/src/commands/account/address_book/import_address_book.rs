@@ -0,0 +1,164 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ImportAddressBookContext)]
+pub struct ImportAddressBook {
+    /// What is the path to the address book file?
+    file_path: crate::types::path_buf::PathBuf,
+    /// Replace the aliases that point to other accounts in your address book
+    #[interactive_clap(long)]
+    overwrite: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportAddressBookContext;
+
+impl ImportAddressBookContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ImportAddressBook as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let file_path = &scope.file_path.0;
+        let imported: crate::common::AddressBook = serde_json::from_str(
+            &std::fs::read_to_string(file_path)
+                .wrap_err_with(|| format!("Failed to read file: {file_path:?}"))?,
+        )
+        .wrap_err_with(|| format!("Failed to parse the address book: {file_path:?}"))?;
+        if let Some(alias) = imported
+            .aliases
+            .keys()
+            .find(|alias| !crate::common::is_valid_alias(alias))
+        {
+            color_eyre::eyre::bail!("Invalid alias \"{alias}\" in {}", file_path.display());
+        }
+
+        let credentials_home_dir = &previous_context.config.credentials_home_dir;
+        let mut address_book = crate::common::get_address_book(credentials_home_dir)?;
+        let conflicts = merge_address_book(&mut address_book, imported, scope.overwrite);
+        crate::common::save_address_book(credentials_home_dir, &address_book)?;
+
+        for conflict in &conflicts {
+            eprintln!("{conflict}");
+        }
+        if !conflicts.is_empty() && !scope.overwrite {
+            eprintln!("The conflicting mappings are kept (pass `--overwrite` to replace them).");
+        }
+        crate::util::print_success(&format!(
+            "The address book is imported from {}",
+            file_path.display()
+        ));
+        Ok(Self)
+    }
+}
+
+/// Merges the imported aliases into the address book, returning the conflicts found.
+fn merge_address_book(
+    address_book: &mut crate::common::AddressBook,
+    imported: crate::common::AddressBook,
+    overwrite: bool,
+) -> Vec<String> {
+    let mut conflicts = vec![];
+    for (alias, imported_entry) in imported.aliases {
+        let entry = address_book.aliases.entry(alias.clone()).or_default();
+        match (&entry.account_id, imported_entry.account_id) {
+            (Some(account_id), Some(imported_account_id)) if *account_id != imported_account_id => {
+                conflicts.push(format!(
+                    "@{alias} points to <{account_id}>, but to <{imported_account_id}> in the file"
+                ));
+                if overwrite {
+                    entry.account_id = Some(imported_account_id);
+                }
+            }
+            (_, Some(imported_account_id)) => entry.account_id = Some(imported_account_id),
+            (_, None) => {}
+        }
+        for (network_name, imported_account_id) in imported_entry.networks {
+            match entry.networks.get(&network_name) {
+                Some(account_id) if *account_id != imported_account_id => {
+                    conflicts.push(format!(
+                        "@{alias} points to <{account_id}> on network <{network_name}>, but to <{imported_account_id}> in the file"
+                    ));
+                    if overwrite {
+                        entry.networks.insert(network_name, imported_account_id);
+                    }
+                }
+                _ => {
+                    entry.networks.insert(network_name, imported_account_id);
+                }
+            }
+        }
+        if entry.note.is_empty() || overwrite && !imported_entry.note.is_empty() {
+            entry.note = imported_entry.note;
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_and_resolve_aliases() {
+        let mut address_book: crate::common::AddressBook = serde_json::from_str(
+            r#"{"aliases":{"treasury":{"networks":{"mainnet":"treasury.unc","testnet":"treasury.testnet"}}}}"#,
+        )
+        .unwrap();
+        let imported: crate::common::AddressBook = serde_json::from_str(
+            r#"{"aliases":{"treasury":{"networks":{"testnet":"other.testnet"},"note":"team funds"},"bob":{"account_id":"bob.unc"}}}"#,
+        )
+        .unwrap();
+
+        let conflicts = merge_address_book(&mut address_book, imported, false);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(address_book.aliases["treasury"].note, "team funds");
+        assert_eq!(
+            address_book
+                .resolve("treasury:testnet", None)
+                .unwrap()
+                .as_str(),
+            "treasury.testnet"
+        );
+        assert_eq!(
+            address_book
+                .resolve("treasury", Some("mainnet"))
+                .unwrap()
+                .as_str(),
+            "treasury.unc"
+        );
+        assert!(address_book.resolve("treasury", None).is_err());
+        assert!(address_book.resolve("treasury:localnet", None).is_err());
+        assert_eq!(
+            address_book.resolve("bob:testnet", None).unwrap().as_str(),
+            "bob.unc"
+        );
+        assert_eq!(
+            address_book.resolve("bob", None).unwrap().as_str(),
+            "bob.unc"
+        );
+        assert_eq!(
+            address_book.aliases_of(&"treasury.unc".parse().unwrap()),
+            vec!["@treasury:mainnet"]
+        );
+    }
+
+    #[test]
+    fn network_specific_alias_is_not_resolved_on_other_networks() {
+        let address_book: crate::common::AddressBook = serde_json::from_str(
+            r#"{"aliases":{"faucet":{"networks":{"testnet":"faucet.testnet"}}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            address_book
+                .resolve("faucet", Some("testnet"))
+                .unwrap()
+                .as_str(),
+            "faucet.testnet"
+        );
+        assert!(address_book.resolve("faucet", Some("mainnet")).is_err());
+        assert!(address_book.resolve("faucet", None).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod add_alias;
+mod export_address_book;
+mod import_address_book;
+mod remove_alias;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct AddressBookCommands {
+    #[interactive_clap(subcommand)]
+    address_book_actions: AddressBookActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// What do you want to do with the address book?
+pub enum AddressBookActions {
+    #[strum_discriminants(strum(
+        message = "add      - Add an alias (or map it to an account on one network)"
+    ))]
+    /// Add an alias (or map it to an account on one network)
+    Add(self::add_alias::AddAlias),
+    #[strum_discriminants(strum(
+        message = "remove   - Remove an alias (or its mapping on one network)"
+    ))]
+    /// Remove an alias (or its mapping on one network)
+    Remove(self::remove_alias::RemoveAlias),
+    #[strum_discriminants(strum(message = "export   - Save the address book to a file"))]
+    /// Save the address book to a file
+    Export(self::export_address_book::ExportAddressBook),
+    #[strum_discriminants(strum(
+        message = "import   - Merge the address book from a file into yours"
+    ))]
+    /// Merge the address book from a file into yours
+    Import(self::import_address_book::ImportAddressBook),
+}
@@ -0,0 +1,68 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RemoveAliasContext)]
+pub struct RemoveAlias {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which alias do you want to remove?
+    alias: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Remove only the mapping of the alias on this network
+    network_name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoveAliasContext;
+
+impl RemoveAliasContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RemoveAlias as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let alias = scope.alias.trim_start_matches('@');
+        let credentials_home_dir = &previous_context.config.credentials_home_dir;
+        let mut address_book = crate::common::get_address_book(credentials_home_dir)?;
+        let Some(entry) = address_book.aliases.get_mut(alias) else {
+            color_eyre::eyre::bail!("There is no alias @{alias} in the address book");
+        };
+        match &scope.network_name {
+            Some(network_name) => {
+                if entry.networks.remove(network_name).is_none() {
+                    color_eyre::eyre::bail!(
+                        "The alias @{alias} has no mapping on network <{network_name}>"
+                    );
+                }
+                if entry.is_empty() {
+                    address_book.aliases.remove(alias);
+                }
+            }
+            None => {
+                address_book.aliases.remove(alias);
+            }
+        }
+        crate::common::save_address_book(credentials_home_dir, &address_book)?;
+
+        crate::util::print_success(&match &scope.network_name {
+            Some(network_name) => {
+                format!("The mapping of @{alias} on network <{network_name}> is removed")
+            }
+            None => format!("The alias @{alias} is removed"),
+        });
+        Ok(Self)
+    }
+}
+
+impl RemoveAlias {
+    fn input_alias(context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        let aliases = crate::common::get_address_book(&context.config.credentials_home_dir)?
+            .aliases
+            .into_keys()
+            .collect::<Vec<_>>();
+        if aliases.is_empty() {
+            color_eyre::eyre::bail!("The address book is empty");
+        }
+        Ok(Some(
+            inquire::Select::new("Which alias do you want to remove?", aliases).prompt()?,
+        ))
+    }
+}
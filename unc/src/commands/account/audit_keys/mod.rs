@@ -0,0 +1,438 @@
+use color_eyre::eyre::Context;
+use colored::Colorize;
+use prettytable::Table;
+use unc_primitives::views::AccessKeyPermissionView;
+
+use crate::common::RpcQueryResponseExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AuditKeysContext)]
+pub struct AuditKeys {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Audit only these accounts (comma-separated) instead of all the accounts you have used
+    accounts: Option<crate::types::vec_string::VecString>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Public keys (comma-separated) to recognize even though they are not stored locally (e.g. Ledger keys)
+    trusted_keys: Option<crate::types::public_key_list::PublicKeyList>,
+    #[interactive_clap(long)]
+    /// Print the commands to delete the unrecognized keys (one batch transaction per account)
+    delete_unknown: bool,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct AuditKeysContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl AuditKeysContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AuditKeys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let credentials_home_dir = previous_context.config.credentials_home_dir.clone();
+        let mut account_ids = match &scope.accounts {
+            Some(accounts) => accounts
+                .0
+                .iter()
+                .map(|account_id| {
+                    account_id
+                        .parse()
+                        .wrap_err_with(|| format!("Invalid account ID: {account_id}"))
+                })
+                .collect::<color_eyre::eyre::Result<Vec<unc_primitives::types::AccountId>>>()?,
+            None => crate::common::get_used_account_list(&credentials_home_dir)
+                .into_iter()
+                .map(|used_account| used_account.account_id)
+                .collect(),
+        };
+        account_ids.sort();
+        account_ids.dedup();
+        if account_ids.is_empty() {
+            color_eyre::eyre::bail!(
+                "There are no accounts to audit: pass `--accounts` or use some accounts first"
+            );
+        }
+
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let account_ids = account_ids.clone();
+            let trusted_keys: Vec<unc_crypto::PublicKey> = scope
+                .trusted_keys
+                .clone()
+                .map(Into::into)
+                .unwrap_or_default();
+            let delete_unknown = scope.delete_unknown;
+
+            move |network_config, block_reference| {
+                let json_rpc_client = network_config.json_rpc_client();
+                let mut access_key_lists = crate::common::block_on_concurrently(
+                    account_ids.iter().map(|account_id| {
+                        let json_rpc_client = &json_rpc_client;
+                        async move {
+                            let access_keys = match json_rpc_client
+                                .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+                                    block_reference: block_reference.clone(),
+                                    request: unc_primitives::views::QueryRequest::ViewAccessKeyList {
+                                        account_id: account_id.clone(),
+                                    },
+                                })
+                                .await
+                            {
+                                Ok(response) => Some(response.access_key_list_view()?.keys),
+                                Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+                                    unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                                        unc_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+                                    ),
+                                )) => None,
+                                Err(err) => {
+                                    return Err(color_eyre::eyre::Report::from(err)).wrap_err_with(|| {
+                                        format!("Failed to fetch query AccessKeyList for {account_id}")
+                                    })
+                                }
+                            };
+                            Ok((account_id, access_keys))
+                        }
+                    }),
+                )?;
+                access_key_lists.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                let mut audited_accounts = vec![];
+                let mut missing_accounts = vec![];
+                for (account_id, access_keys) in access_key_lists {
+                    let Some(access_keys) = access_keys else {
+                        missing_accounts.push(account_id.clone());
+                        continue;
+                    };
+                    audited_accounts.push(audit_account_keys(account_id, access_keys, |public_key| {
+                        if trusted_keys.contains(public_key) {
+                            Some(KeyStorage::Trusted)
+                        } else {
+                            find_local_key(network_config, &credentials_home_dir, account_id, public_key)
+                        }
+                    }));
+                }
+
+                display_audit(&audited_accounts);
+                if !missing_accounts.is_empty() {
+                    eprintln!(
+                        "\nThese accounts do not exist on network <{}>: {}",
+                        network_config.network_name,
+                        missing_accounts.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+                    );
+                }
+                if delete_unknown {
+                    print_delete_commands(&audited_accounts, &network_config.network_name);
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: account_ids,
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<AuditKeysContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: AuditKeysContext) -> Self {
+        item.0
+    }
+}
+
+/// Where the private part of an access key is available to us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyStorage {
+    Keychain,
+    LegacyKeychain,
+    /// Passed with `--trusted-keys`.
+    Trusted,
+}
+
+impl std::fmt::Display for KeyStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Keychain => write!(f, "keychain"),
+            Self::LegacyKeychain => write!(f, "legacy keychain"),
+            Self::Trusted => write!(f, "trusted"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AuditedKey {
+    access_key: unc_primitives::views::AccessKeyInfoView,
+    storage: Option<KeyStorage>,
+}
+
+impl AuditedKey {
+    fn is_full_access(&self) -> bool {
+        matches!(
+            self.access_key.access_key.permission,
+            AccessKeyPermissionView::FullAccess
+        )
+    }
+}
+
+#[derive(Debug)]
+struct AuditedAccount {
+    account_id: unc_primitives::types::AccountId,
+    keys: Vec<AuditedKey>,
+}
+
+impl AuditedAccount {
+    fn unknown_keys(&self) -> impl Iterator<Item = &AuditedKey> {
+        self.keys.iter().filter(|key| key.storage.is_none())
+    }
+
+    /// The storage of a local full access key to sign the deletion of the unknown keys with.
+    fn signing_key_storage(&self) -> Option<KeyStorage> {
+        self.keys
+            .iter()
+            .filter(|key| key.is_full_access())
+            .filter_map(|key| key.storage)
+            .find(|storage| *storage != KeyStorage::Trusted)
+    }
+}
+
+fn audit_account_keys(
+    account_id: &unc_primitives::types::AccountId,
+    access_keys: Vec<unc_primitives::views::AccessKeyInfoView>,
+    find_key: impl Fn(&unc_crypto::PublicKey) -> Option<KeyStorage>,
+) -> AuditedAccount {
+    AuditedAccount {
+        account_id: account_id.clone(),
+        keys: access_keys
+            .into_iter()
+            .map(|access_key| AuditedKey {
+                storage: find_key(&access_key.public_key),
+                access_key,
+            })
+            .collect(),
+    }
+}
+
+fn find_local_key(
+    network_config: &crate::config::NetworkConfig,
+    credentials_home_dir: &std::path::Path,
+    account_id: &unc_primitives::types::AccountId,
+    public_key: &unc_crypto::PublicKey,
+) -> Option<KeyStorage> {
+    let account_dir = credentials_home_dir.join(&network_config.network_name);
+    let key_file = account_dir
+        .join(account_id.as_str())
+        .join(format!("{}.json", public_key.to_string().replace(':', "_")));
+    let account_file_has_key = || {
+        std::fs::read_to_string(account_dir.join(format!("{account_id}.json")))
+            .ok()
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+            .is_some_and(|data| data["public_key"] == public_key.to_string())
+    };
+    if key_file.is_file() || account_file_has_key() {
+        return Some(KeyStorage::LegacyKeychain);
+    }
+
+    let service_name = format!("unc-{}-{}", network_config.network_name, account_id);
+    keyring::Entry::new(&service_name, &format!("{account_id}:{public_key}"))
+        .and_then(|entry| entry.get_password())
+        .ok()
+        .map(|_| KeyStorage::Keychain)
+}
+
+fn display_audit(audited_accounts: &[AuditedAccount]) {
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"Account", "Public Key", "Permissions", "Allowance left", "Receiver and methods", "Stored locally"]);
+    for account in audited_accounts {
+        if account.keys.is_empty() {
+            table.add_row(prettytable::row![Fg->account.account_id, "no access keys (locked)", "", "", "", ""]);
+        }
+        for key in &account.keys {
+            let (permissions, allowance, receiver) = match &key.access_key.access_key.permission {
+                AccessKeyPermissionView::FullAccess => {
+                    ("full access".to_string(), String::new(), String::new())
+                }
+                AccessKeyPermissionView::FunctionCall {
+                    allowance,
+                    receiver_id,
+                    method_names,
+                } => (
+                    "function call".to_string(),
+                    allowance.map_or_else(
+                        || "unlimited".to_string(),
+                        |allowance| unc_token::UncToken::from_attounc(allowance).to_string(),
+                    ),
+                    if method_names.is_empty() {
+                        format!("{receiver_id} (any method)")
+                    } else {
+                        format!("{receiver_id} ({})", method_names.join(", "))
+                    },
+                ),
+            };
+            let storage = match key.storage {
+                Some(storage) => storage.to_string(),
+                None if key.is_full_access() => "UNKNOWN".red().bold().to_string(),
+                None => "no".to_string(),
+            };
+            table.add_row(prettytable::row![
+                Fg->account.account_id,
+                key.access_key.public_key,
+                permissions,
+                allowance,
+                receiver,
+                storage
+            ]);
+        }
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.printstd();
+
+    let keys_count: usize = audited_accounts
+        .iter()
+        .map(|account| account.keys.len())
+        .sum();
+    let unknown_full_access_keys: Vec<_> = audited_accounts
+        .iter()
+        .flat_map(|account| {
+            account
+                .unknown_keys()
+                .filter(|key| key.is_full_access())
+                .map(move |key| (&account.account_id, &key.access_key.public_key))
+        })
+        .collect();
+    eprintln!(
+        "\n{} accounts, {} access keys, {} unknown full access keys",
+        audited_accounts.len(),
+        keys_count,
+        unknown_full_access_keys.len()
+    );
+    for (account_id, public_key) in unknown_full_access_keys {
+        eprintln!(
+            " {} <{account_id}> has a full access key that is not stored locally: {public_key}",
+            "!".red().bold()
+        );
+    }
+}
+
+fn print_delete_commands(audited_accounts: &[AuditedAccount], network_name: &str) {
+    let commands = delete_commands(audited_accounts, network_name);
+    if commands.is_empty() {
+        eprintln!("\nThere are no unrecognized keys to delete.");
+        return;
+    }
+    eprintln!(
+        "\nRun these commands to delete the unrecognized keys (one transaction per account):"
+    );
+    for command in commands {
+        match command {
+            Ok(command) => println!("{command}"),
+            Err(account_id) => eprintln!(
+                " {} <{account_id}> has no full access key stored locally to sign the deletion with",
+                "!".red().bold()
+            ),
+        }
+    }
+}
+
+/// The `delete-keys` command for every account with unrecognized keys,
+/// or the account ID if there is no local full access key to sign the transaction with.
+fn delete_commands(
+    audited_accounts: &[AuditedAccount],
+    network_name: &str,
+) -> Vec<Result<String, unc_primitives::types::AccountId>> {
+    audited_accounts
+        .iter()
+        .filter_map(|account| {
+            let unknown_keys: Vec<String> = account
+                .unknown_keys()
+                .map(|key| key.access_key.public_key.to_string())
+                .collect();
+            if unknown_keys.is_empty() {
+                return None;
+            }
+            let Some(signing_key_storage) = account.signing_key_storage() else {
+                return Some(Err(account.account_id.clone()));
+            };
+            let sign_with = match signing_key_storage {
+                KeyStorage::Keychain => "sign-with-keychain",
+                _ => "sign-with-legacy-keychain",
+            };
+            Some(Ok(shell_words::join([
+                crate::common::get_unc_exec_path().as_str(),
+                "account",
+                "delete-keys",
+                account.account_id.as_str(),
+                "public-keys",
+                &unknown_keys.join(","),
+                "network-config",
+                network_name,
+                sign_with,
+                "send",
+            ])))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_key(public_key: &str, full_access: bool) -> unc_primitives::views::AccessKeyInfoView {
+        unc_primitives::views::AccessKeyInfoView {
+            public_key: public_key.parse().unwrap(),
+            access_key: unc_primitives::views::AccessKeyView {
+                nonce: 0,
+                permission: if full_access {
+                    AccessKeyPermissionView::FullAccess
+                } else {
+                    AccessKeyPermissionView::FunctionCall {
+                        allowance: None,
+                        receiver_id: "app.unc".to_string(),
+                        method_names: vec![],
+                    }
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn delete_unknown_keys() {
+        let local_key = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp";
+        let unknown_key = "ed25519:GyvuXfWDsvcXRo2j7g6XjnD8oHPawWFpVwdQp2xTLNy2";
+        let unknown_app_key = "ed25519:3wFGGqKGDsWBo6WoD2ePd1BqaD1Kf9N4tTHT1orvXLmU";
+        let find_key = |public_key: &unc_crypto::PublicKey| {
+            (public_key.to_string() == local_key).then_some(KeyStorage::LegacyKeychain)
+        };
+        let audited_accounts = [
+            audit_account_keys(
+                &"alice.unc".parse().unwrap(),
+                vec![
+                    access_key(local_key, true),
+                    access_key(unknown_key, true),
+                    access_key(unknown_app_key, false),
+                ],
+                find_key,
+            ),
+            audit_account_keys(
+                &"bob.unc".parse().unwrap(),
+                vec![access_key(unknown_key, true)],
+                find_key,
+            ),
+            audit_account_keys(
+                &"carol.unc".parse().unwrap(),
+                vec![access_key(local_key, true)],
+                find_key,
+            ),
+        ];
+
+        let commands = delete_commands(&audited_accounts, "testnet");
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0].as_ref().unwrap().ends_with(&format!(
+            "account delete-keys alice.unc public-keys {unknown_key},{unknown_app_key} network-config testnet sign-with-legacy-keychain send"
+        )));
+        assert_eq!(commands[1], Err("bob.unc".parse().unwrap()));
+    }
+}
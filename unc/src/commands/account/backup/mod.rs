@@ -0,0 +1,299 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use crate::common::RpcQueryResponseExt;
+
+/// The format name stored in the backup file to recognize it on restore.
+pub(super) const BACKUP_FORMAT: &str = "unc-cli-backup";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = BackupCommandContext)]
+pub struct BackupCommand {
+    /// Where do you want to save the backup file?
+    file_path: crate::types::path_buf::PathBuf,
+    /// Do not look up the keys in the keychain (then no network access is needed)
+    #[interactive_clap(long)]
+    skip_keychain: bool,
+}
+
+/// The backup file: the encrypted JSON of [`Backup`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(super) struct BackupFile {
+    pub format: String,
+    pub encrypted_backup: crate::util::encryption::EncryptedData,
+}
+
+/// Everything needed to set up the CLI with the same accounts on another machine.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub(super) struct Backup {
+    /// The contents of `config.toml`.
+    pub config_toml: Option<String>,
+    /// The files of `credentials_home_dir` (the legacy keychain and `accounts.json`) by their relative paths.
+    pub files: std::collections::BTreeMap<String, String>,
+    pub keychain: Vec<KeychainEntry>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(super) struct KeychainEntry {
+    pub network_name: String,
+    pub account_id: unc_primitives::types::AccountId,
+    pub public_key: unc_crypto::PublicKey,
+    /// The JSON of the key pair, the same as in the legacy keychain.
+    pub key_pair: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupCommandContext;
+
+impl BackupCommandContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<BackupCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let file_path = &scope.file_path.0;
+        if file_path.exists() {
+            color_eyre::eyre::bail!(
+                "The file {} already exists! Choose another file for the backup.",
+                file_path.display()
+            );
+        }
+        let config = &previous_context.config;
+
+        let mut backup = Backup {
+            config_toml: read_config_toml()?,
+            ..Default::default()
+        };
+        collect_files(
+            &config.credentials_home_dir,
+            std::path::Path::new(""),
+            &mut backup.files,
+        )?;
+        if !scope.skip_keychain {
+            // The keychain cannot be listed, so the keys to look up in it are listed on the networks.
+            let account_keys = if previous_context.offline {
+                match &previous_context.online_context {
+                    Some(online_context_path) => online_context_account_keys(
+                        crate::transaction_signature_options::online_context::OnlineContext::read(
+                            online_context_path,
+                        )?,
+                    ),
+                    None => {
+                        eprintln!(
+                            "Offline mode: the keys in the keychain are not backed up, as they are listed on the network \
+                            (pass --online-context to take them from an online context file)."
+                        );
+                        AccountKeys::new()
+                    }
+                }
+            } else {
+                crate::util::handle_step(
+                    "Looking up the keys of your accounts on the networks...",
+                    || list_account_keys(config),
+                )?
+            };
+            backup.keychain = read_keychain_entries(account_keys)?;
+        }
+
+        let password = crate::util::encryption::input_new_password(
+            "Enter a password to encrypt the backup with:",
+        )?;
+        let backup_file = BackupFile {
+            format: BACKUP_FORMAT.to_string(),
+            encrypted_backup: crate::util::encryption::EncryptedData::encrypt(
+                &password,
+                &serde_json::to_vec(&backup)?,
+            )?,
+        };
+        if let Some(dir) = file_path.parent() {
+            std::fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Failed to create directory: {dir:?}"))?;
+        }
+        std::fs::write(file_path, serde_json::to_vec_pretty(&backup_file)?)
+            .wrap_err_with(|| format!("Failed to write to file: {file_path:?}"))?;
+
+        crate::util::print_success(&format!(
+            "The backup of {} files and {} keychain entries is saved to {}",
+            backup.files.len(),
+            backup.keychain.len(),
+            file_path.display()
+        ));
+        Ok(Self)
+    }
+}
+
+pub(super) fn config_toml_path() -> color_eyre::eyre::Result<std::path::PathBuf> {
+    let mut path_config_toml = dirs::config_dir().wrap_err("Impossible to get your config dir!")?;
+    path_config_toml.extend(&["unc-cli", "config.toml"]);
+    Ok(path_config_toml)
+}
+
+fn read_config_toml() -> color_eyre::eyre::Result<Option<String>> {
+    let path_config_toml = config_toml_path()?;
+    if !path_config_toml.is_file() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(&path_config_toml)
+        .map(Some)
+        .wrap_err_with(|| format!("Failed to read file: {path_config_toml:?}"))
+}
+
+fn collect_files(
+    credentials_home_dir: &std::path::Path,
+    relative_dir: &std::path::Path,
+    files: &mut std::collections::BTreeMap<String, String>,
+) -> crate::CliResult {
+    let dir = credentials_home_dir.join(relative_dir);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let entry = entry.wrap_err_with(|| format!("Failed to read directory: {dir:?}"))?;
+        let relative_path = relative_dir.join(entry.file_name());
+        if entry.path().is_dir() {
+            collect_files(credentials_home_dir, &relative_path, files)?;
+            continue;
+        }
+        match std::fs::read_to_string(entry.path()) {
+            Ok(content) => {
+                let relative_path = relative_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.insert(relative_path, content);
+            }
+            Err(err) => eprintln!(
+                "Warning: the file {} is not included in the backup: {err}",
+                entry.path().display()
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// The public keys of the accounts by network name and account.
+type AccountKeys = std::collections::BTreeMap<
+    String,
+    std::collections::BTreeMap<unc_primitives::types::AccountId, Vec<unc_crypto::PublicKey>>,
+>;
+
+/// Looks up the access keys of the used accounts on every network.
+fn list_account_keys(config: &crate::config::Config) -> color_eyre::eyre::Result<AccountKeys> {
+    let account_ids: Vec<unc_primitives::types::AccountId> =
+        crate::common::get_used_account_list(&config.credentials_home_dir)
+            .into_iter()
+            .map(|used_account| used_account.account_id)
+            .collect();
+    let mut network_configs: Vec<&crate::config::NetworkConfig> = vec![];
+    for network_config in config.network_connection.values() {
+        if !network_configs
+            .iter()
+            .any(|known| known.network_name == network_config.network_name)
+        {
+            network_configs.push(network_config);
+        }
+    }
+
+    let access_key_lists = crate::common::block_on_concurrently(
+        network_configs
+            .iter()
+            .flat_map(|network_config| {
+                account_ids
+                    .iter()
+                    .map(move |account_id| (*network_config, account_id))
+            })
+            .map(|(network_config, account_id)| async move {
+                let public_keys = match network_config
+                    .json_rpc_client()
+                    .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+                        block_reference: unc_primitives::types::Finality::Final.into(),
+                        request: unc_primitives::views::QueryRequest::ViewAccessKeyList {
+                            account_id: account_id.clone(),
+                        },
+                    })
+                    .await
+                {
+                    Ok(response) => response
+                        .access_key_list_view()?
+                        .keys
+                        .into_iter()
+                        .map(|access_key| access_key.public_key)
+                        .collect(),
+                    Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+                        unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                            unc_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount {
+                                ..
+                            },
+                        ),
+                    )) => vec![],
+                    Err(err) => {
+                        eprintln!(
+                            "\nWarning: the keys of <{account_id}> on network <{}> are not looked up: {err}",
+                            network_config.network_name
+                        );
+                        vec![]
+                    }
+                };
+                color_eyre::eyre::Ok((network_config, account_id, public_keys))
+            }),
+    )?;
+
+    let mut account_keys = AccountKeys::new();
+    for (network_config, account_id, public_keys) in access_key_lists {
+        account_keys
+            .entry(network_config.network_name.clone())
+            .or_default()
+            .insert(account_id.clone(), public_keys);
+    }
+    Ok(account_keys)
+}
+
+/// In offline mode, the access keys listed in the online context file are looked up in the keychain.
+fn online_context_account_keys(
+    online_context: crate::transaction_signature_options::online_context::OnlineContext,
+) -> AccountKeys {
+    let mut network_keys = std::collections::BTreeMap::<_, Vec<_>>::new();
+    for access_key in online_context.access_keys {
+        network_keys
+            .entry(access_key.account_id)
+            .or_default()
+            .push(access_key.public_key);
+    }
+    AccountKeys::from([(online_context.network_name, network_keys)])
+}
+
+fn read_keychain_entries(
+    account_keys: AccountKeys,
+) -> color_eyre::eyre::Result<Vec<KeychainEntry>> {
+    let mut keychain_entries = vec![];
+    for (network_name, network_keys) in account_keys {
+        for (account_id, public_keys) in network_keys {
+            let service_name = format!("unc-{network_name}-{account_id}");
+            for public_key in public_keys {
+                let password =
+                    keyring::Entry::new(&service_name, &format!("{account_id}:{public_key}"))
+                        .and_then(|entry| entry.get_password());
+                match password {
+                    Ok(key_pair) => keychain_entries.push(KeychainEntry {
+                        network_name: network_name.clone(),
+                        account_id: account_id.clone(),
+                        public_key,
+                        key_pair,
+                    }),
+                    Err(keyring::Error::NoEntry) => {}
+                    Err(err) => color_eyre::eyre::bail!(
+                        "The keychain was not able to be read (pass `--skip-keychain` to back up only the files): {err}"
+                    ),
+                }
+            }
+        }
+    }
+    keychain_entries.sort_by(|a, b| {
+        (&a.network_name, &a.account_id, a.public_key.to_string()).cmp(&(
+            &b.network_name,
+            &b.account_id,
+            b.public_key.to_string(),
+        ))
+    });
+    Ok(keychain_entries)
+}
@@ -31,9 +31,12 @@ impl NewAccountContext {
         previous_context: crate::GlobalContext,
         scope: &<NewAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let new_account_id = scope
+            .new_account_id
+            .resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            new_account_id: scope.new_account_id.clone().into(),
+            new_account_id,
             initial_balance: scope.initial_balance,
         })
     }
@@ -46,9 +49,11 @@ impl NewAccount {
         loop {
             let new_account_id: crate::types::account_id::AccountId =
                 CustomType::new("What is the new account ID?").prompt()?;
+            let new_account_id = new_account_id.resolve(&context.config, None)?;
 
             if context.offline {
-                return Ok(Some(new_account_id));
+                crate::common::warn_account_check_skipped_offline(&new_account_id);
+                return Ok(Some(new_account_id.into()));
             }
 
             #[derive(derive_more::Display)]
@@ -58,7 +63,7 @@ impl NewAccount {
                     account_id
                 )]
                 Yes {
-                    account_id: crate::types::account_id::AccountId,
+                    account_id: unc_primitives::types::AccountId,
                 },
                 #[display(
                     fmt = "No, I know that this account does not exist and I want to proceed."
@@ -71,37 +76,36 @@ impl NewAccount {
                 )
                 .prompt()?;
             if let ConfirmOptions::Yes { account_id } = select_choose_input {
-                let network = crate::common::find_network_where_account_exist(
-                    context,
-                    account_id.clone().into(),
-                );
+                let network =
+                    crate::common::find_network_where_account_exist(context, account_id.clone());
                 if let Some(network_config) = network {
                     eprintln!(
                         "\nHeads up! You will only waste tokens if you proceed creating <{}> account on <{}> as the account already exists.",
                         &account_id, network_config.network_name
                     );
                     if !crate::common::ask_if_different_account_id_wanted()? {
-                        return Ok(Some(account_id));
+                        return Ok(Some(account_id.into()));
                     };
-                } else if account_id.0.as_str().chars().count()
-                    < MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LENGTH
+                } else if account_id.as_str().chars().count() < MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LENGTH
                 {
                     eprintln!(
                         "\nAccount <{}> has <{}> character count. Only the registrar account can create new top level accounts that are shorter than {} characters. Read more about it in nomicon: https://nomicon.io/DataStructures/Account#top-level-accounts",
                         &account_id,
-                        &account_id.0.as_str().chars().count(),
+                        &account_id.as_str().chars().count(),
                         MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LENGTH,
                     );
                     if !crate::common::ask_if_different_account_id_wanted()? {
-                        return Ok(Some(account_id));
+                        return Ok(Some(account_id.into()));
                     };
                 } else {
                     let parent_account_id =
-                        account_id.clone().get_parent_account_id_from_sub_account();
+                        crate::types::account_id::AccountId::from(account_id.clone())
+                            .get_parent_account_id_from_sub_account()
+                            .resolve(&context.config, None)?;
 
                     if crate::common::find_network_where_account_exist(
                         context,
-                        parent_account_id.clone().into(),
+                        parent_account_id.clone(),
                     )
                     .is_none()
                     {
@@ -111,14 +115,14 @@ impl NewAccount {
                             account_id
                         );
                         if !crate::common::ask_if_different_account_id_wanted()? {
-                            return Ok(Some(account_id));
+                            return Ok(Some(account_id.into()));
                         };
                     } else {
-                        return Ok(Some(account_id));
+                        return Ok(Some(account_id.into()));
                     }
                 };
             } else {
-                return Ok(Some(new_account_id));
+                return Ok(Some(new_account_id.into()));
             };
         }
     }
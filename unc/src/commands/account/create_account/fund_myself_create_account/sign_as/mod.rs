@@ -1,5 +1,3 @@
-use unc_primitives::account::id::AccountType;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::AccountPropertiesContext)]
 #[interactive_clap(output_context = SignerAccountIdContext)]
@@ -7,6 +5,10 @@ pub struct SignerAccountId {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the signer account ID?
     signer_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Create the account with this factory contract from the config (see `config add-account-factory`)
+    factory: Option<String>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_for_transaction::NetworkForTransactionArgs,
@@ -17,6 +19,7 @@ pub struct SignerAccountIdContext {
     global_context: crate::GlobalContext,
     account_properties: super::AccountProperties,
     signer_account_id: unc_primitives::types::AccountId,
+    factory: Option<crate::config::AccountFactory>,
     on_before_sending_transaction_callback:
         crate::transaction_signature_options::OnBeforeSendingTransactionCallback,
 }
@@ -26,10 +29,31 @@ impl SignerAccountIdContext {
         previous_context: super::AccountPropertiesContext,
         scope: &<SignerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id = scope
+            .signer_account_id
+            .resolve(&previous_context.global_context.config, None)?;
+        let factory = scope
+            .factory
+            .as_ref()
+            .map(|factory_name| {
+                previous_context
+                    .global_context
+                    .config
+                    .account_factories
+                    .get(factory_name)
+                    .cloned()
+                    .ok_or_else(|| {
+                        color_eyre::eyre::eyre!(
+                            "There is no account factory \"{factory_name}\" in the config (add it with `config add-account-factory`)"
+                        )
+                    })
+            })
+            .transpose()?;
         Ok(Self {
             global_context: previous_context.global_context,
             account_properties: previous_context.account_properties,
-            signer_account_id: scope.signer_account_id.clone().into(),
+            signer_account_id,
+            factory,
             on_before_sending_transaction_callback: previous_context
                 .on_before_sending_transaction_callback,
         })
@@ -46,58 +70,47 @@ impl From<SignerAccountIdContext> for crate::commands::ActionContext {
                 let signer_id = item.signer_account_id.clone();
 
                 move |network_config| {
-                    if !item.global_context.offline {
+                    if item.global_context.offline {
+                        crate::common::warn_account_check_skipped_offline(&new_account_id);
+                    } else {
                         validate_new_account_id(network_config, &new_account_id)?;
                     }
-                    let (actions, receiver_id) = if AccountType::UtilityAccount
-                        == new_account_id.get_account_type()
-                    {
-                        (vec![
-                                unc_primitives::transaction::Action::CreateAccount(
-                                    unc_primitives::transaction::CreateAccountAction {},
-                                ),
-                                unc_primitives::transaction::Action::Transfer(
-                                    unc_primitives::transaction::TransferAction {
-                                        deposit: item.account_properties.initial_balance.as_attounc(),
-                                    },
-                                ),
-                                unc_primitives::transaction::Action::AddKey(
-                                    Box::new(unc_primitives::transaction::AddKeyAction {
-                                        public_key: item.account_properties.public_key.clone(),
-                                        access_key: unc_primitives::account::AccessKey {
-                                            nonce: 0,
-                                            permission:
-                                                unc_primitives::account::AccessKeyPermission::FullAccess,
-                                        },
-                                    }),
-                                ),
-                            ],
-                        new_account_id.clone())
-                    } else {
-                            return color_eyre::eyre::Result::Err(color_eyre::eyre::eyre!(
-                            "\nAccount <{}> cannot be created on network <{}> because a <reserved_account_id> specified in the configuration file.\n",
-                            new_account_id,
-                            network_config.network_name
-                        ));
-                    };
-
-                    Ok(crate::commands::PrepopulatedTransaction {
-                        signer_id: signer_id.clone(),
-                        receiver_id,
-                        actions,
-                    })
+                    let prepopulated_transaction = super::super::account_creation_transaction(
+                        network_config,
+                        &signer_id,
+                        &new_account_id,
+                        &item.account_properties.public_key,
+                        item.account_properties.initial_balance,
+                        item.factory.as_ref(),
+                    )?;
+                    if prepopulated_transaction.receiver_id != new_account_id {
+                        eprintln!(
+                            "\nThe account <{new_account_id}> will be created by the contract <{}>.",
+                            prepopulated_transaction.receiver_id
+                        );
+                    }
+                    Ok(prepopulated_transaction)
                 }
             });
 
         let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback =
             std::sync::Arc::new({
                 let credentials_home_dir = global_context.config.credentials_home_dir.clone();
+                let new_account_id = item.account_properties.new_account_id.clone();
 
                 move |outcome_view, _network_config| {
-                    crate::common::update_used_account_list_as_signer(
-                        &credentials_home_dir,
-                        &outcome_view.transaction.receiver_id,
-                    );
+                    // The account factory can return a value even if it failed to create the account,
+                    // so its receipts are checked as well.
+                    if matches!(
+                        outcome_view.status,
+                        unc_primitives::views::FinalExecutionStatus::SuccessValue(_)
+                    ) && crate::common::outcome_error(outcome_view).is_none()
+                    {
+                        crate::common::update_used_account_list_as_signer(
+                            &credentials_home_dir,
+                            &new_account_id,
+                        );
+                    }
                     Ok(())
                 }
             });
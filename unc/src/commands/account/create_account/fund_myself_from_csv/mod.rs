@@ -0,0 +1,466 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CreateAccountsFromCsvContext)]
+pub struct CreateAccountsFromCsv {
+    /// What is the path to the CSV file (columns: account_id, public_key, initial_balance)?
+    file_path: crate::types::path_buf::PathBuf,
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Create the accounts with this factory contract from the config (see `config add-account-factory`)
+    factory: Option<String>,
+    #[interactive_clap(long)]
+    /// Save the generated key pairs to the legacy keychain (compatible with JS CLI) instead of the keychain
+    save_to_legacy_keychain: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Where to save the results report (by default, next to the CSV file with the `.report.csv` extension)
+    report_file: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: NetworkForAccounts,
+}
+
+/// A row of the CSV file; the key pair is generated when the public key is empty.
+#[derive(Debug, Clone)]
+struct AccountRow {
+    line: usize,
+    new_account_id: unc_primitives::types::AccountId,
+    public_key: Option<unc_crypto::PublicKey>,
+    initial_balance: crate::types::unc_token::UncToken,
+}
+
+#[derive(Debug, Clone)]
+struct RowResult {
+    new_account_id: unc_primitives::types::AccountId,
+    public_key: Option<unc_crypto::PublicKey>,
+    initial_balance: crate::types::unc_token::UncToken,
+    transaction_hash: Option<unc_primitives::hash::CryptoHash>,
+    /// `Ok(false)` if the transaction was not sent (e.g. only displayed).
+    status: Result<bool, String>,
+}
+
+#[derive(Clone)]
+pub struct CreateAccountsFromCsvContext {
+    global_context: crate::GlobalContext,
+    rows: Vec<AccountRow>,
+    signer_account_id: unc_primitives::types::AccountId,
+    factory: Option<crate::config::AccountFactory>,
+    save_to_legacy_keychain: bool,
+    report_file: std::path::PathBuf,
+}
+
+impl CreateAccountsFromCsvContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CreateAccountsFromCsv as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id = scope
+            .signer_account_id
+            .resolve(&previous_context.config, None)?;
+        let file_path = scope.file_path.0.clone();
+        let rows = parse_rows(
+            &std::fs::read_to_string(&file_path)
+                .wrap_err_with(|| format!("Failed to read file: {file_path:?}"))?,
+        )?;
+        if rows.is_empty() {
+            color_eyre::eyre::bail!("There are no accounts in {}", file_path.display());
+        }
+        let factory = scope
+            .factory
+            .as_ref()
+            .map(|factory_name| {
+                previous_context
+                    .config
+                    .account_factories
+                    .get(factory_name)
+                    .cloned()
+                    .wrap_err_with(|| {
+                        format!("There is no account factory \"{factory_name}\" in the config (add it with `config add-account-factory`)")
+                    })
+            })
+            .transpose()?;
+        let report_file = scope
+            .report_file
+            .as_ref()
+            .map(|report_file| report_file.0.clone())
+            .unwrap_or_else(|| file_path.with_extension("report.csv"));
+        Ok(Self {
+            global_context: previous_context,
+            rows,
+            signer_account_id,
+            factory,
+            save_to_legacy_keychain: scope.save_to_legacy_keychain,
+            report_file,
+        })
+    }
+}
+
+impl CreateAccountsFromCsv {
+    fn input_signer_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the signer account ID?",
+        )
+    }
+}
+
+/// The network and the signing options, which are chosen once for all the accounts.
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = CreateAccountsFromCsvContext)]
+#[interactive_clap(skip_default_from_cli)]
+pub struct NetworkForAccounts {
+    /// What is the name of the network?
+    #[interactive_clap(skip_default_input_arg)]
+    network_name: String,
+    #[interactive_clap(subcommand)]
+    transaction_signature_options: crate::transaction_signature_options::SignWith,
+}
+
+impl interactive_clap::FromCli for NetworkForAccounts {
+    type FromCliContext = CreateAccountsFromCsvContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+
+        if clap_variant.network_name.is_none() {
+            clap_variant.network_name = match Self::input_network_name(&context) {
+                Ok(Some(network_name)) => Some(network_name),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let network_name = clap_variant.network_name.clone().expect("Unexpected error");
+        let network_config = match context
+            .global_context
+            .config
+            .network_connection
+            .get(&network_name)
+            .wrap_err("Failed to get network config!")
+        {
+            Ok(network_config) => network_config.clone(),
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        };
+
+        let mut results = vec![];
+        let mut transactions = vec![];
+        let mut total_balance = 0u128;
+        for row in &context.rows {
+            let transaction_hash = std::sync::Arc::new(std::sync::Mutex::new(None));
+            match account_transaction(&context, &network_config, row, &transaction_hash) {
+                Ok((public_key, transaction)) => {
+                    results.push((row, Some(public_key), Ok(transaction_hash)));
+                    transactions.push(transaction);
+                    total_balance += row.initial_balance.as_attounc();
+                }
+                Err(err) => {
+                    eprintln!("<{}>: {err:#}", row.new_account_id);
+                    results.push((row, row.public_key.clone(), Err(format!("{err:#}"))));
+                }
+            }
+        }
+        eprintln!(
+            "\n{} accounts with a total initial balance of {} will be created by <{}> on network <{}>.",
+            transactions.len(),
+            crate::types::unc_token::UncToken::from_attounc(total_balance),
+            context.signer_account_id,
+            network_config.network_name,
+        );
+
+        let mut batch_results = match crate::transaction_signature_options::sign_batch(
+            &context.global_context,
+            &network_config,
+            &mut clap_variant.transaction_signature_options,
+            transactions,
+        ) {
+            Some(batch_results) => batch_results.into_iter(),
+            None => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+        };
+        let results = results
+            .into_iter()
+            .map(|(row, public_key, transaction_hash)| {
+                let (transaction_hash, status) = match transaction_hash {
+                    Ok(transaction_hash) => {
+                        let transaction_hash = transaction_hash.lock().unwrap().take();
+                        let status = match batch_results.next() {
+                            Some(Ok(())) => Ok(transaction_hash.is_some()),
+                            Some(Err(err)) => Err(format!("{err:#}")),
+                            None => Err("The transaction was not signed".to_string()),
+                        };
+                        (transaction_hash, status)
+                    }
+                    Err(err) => (None, Err(err)),
+                };
+                RowResult {
+                    new_account_id: row.new_account_id.clone(),
+                    public_key,
+                    initial_balance: row.initial_balance,
+                    transaction_hash,
+                    status,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match finish(&context.report_file, &network_config, &results) {
+            Ok(()) => interactive_clap::ResultFromCli::Ok(clap_variant),
+            Err(err) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        }
+    }
+}
+
+impl NetworkForAccounts {
+    fn input_network_name(
+        context: &CreateAccountsFromCsvContext,
+    ) -> color_eyre::eyre::Result<Option<String>> {
+        crate::common::input_network_name(
+            &context.global_context.config,
+            &[context.signer_account_id.clone()],
+        )
+    }
+}
+
+/// Parses the rows of the CSV file: a header line and lines starting with `#` are skipped.
+fn parse_rows(data: &str) -> color_eyre::eyre::Result<Vec<AccountRow>> {
+    let mut rows: Vec<AccountRow> = vec![];
+    for (index, line) in data.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        if index == 0 && fields.first() == Some(&"account_id") {
+            continue;
+        }
+        let [new_account_id, public_key, initial_balance] = fields.as_slice() else {
+            color_eyre::eyre::bail!(
+                "Line {line_number}: expected 3 columns (account_id, public_key, initial_balance), found {}",
+                fields.len()
+            );
+        };
+        let new_account_id: unc_primitives::types::AccountId = new_account_id
+            .parse()
+            .wrap_err_with(|| format!("Line {line_number}: invalid account ID"))?;
+        if let Some(row) = rows.iter().find(|row| row.new_account_id == new_account_id) {
+            color_eyre::eyre::bail!(
+                "Line {line_number}: <{new_account_id}> is already on line {}",
+                row.line
+            );
+        }
+        rows.push(AccountRow {
+            line: line_number,
+            new_account_id,
+            public_key: if public_key.is_empty() {
+                None
+            } else {
+                Some(
+                    unc_crypto::PublicKey::from_str(public_key)
+                        .wrap_err_with(|| format!("Line {line_number}: invalid public key"))?,
+                )
+            },
+            initial_balance: crate::types::unc_token::UncToken::from_str(initial_balance).map_err(
+                |err| color_eyre::eyre::eyre!("Line {line_number}: invalid initial balance: {err}"),
+            )?,
+        });
+    }
+    Ok(rows)
+}
+
+/// The account creation transaction of the row. The generated key pair is saved right before the
+/// transaction is sent, and the hash of the sent transaction is stored in `transaction_hash`.
+fn account_transaction(
+    context: &CreateAccountsFromCsvContext,
+    network_config: &crate::config::NetworkConfig,
+    row: &AccountRow,
+    transaction_hash: &std::sync::Arc<std::sync::Mutex<Option<unc_primitives::hash::CryptoHash>>>,
+) -> color_eyre::eyre::Result<(
+    unc_crypto::PublicKey,
+    crate::transaction_signature_options::BatchTransaction,
+)> {
+    let (public_key, key_pair_properties) = match &row.public_key {
+        Some(public_key) => (public_key.clone(), None),
+        None => {
+            let key_pair_properties = crate::common::generate_ed25519_keypair()?;
+            (
+                unc_crypto::PublicKey::from_str(&key_pair_properties.public_key_str)?,
+                Some(key_pair_properties),
+            )
+        }
+    };
+    let prepopulated_transaction = super::account_creation_transaction(
+        network_config,
+        &context.signer_account_id,
+        &row.new_account_id,
+        &public_key,
+        row.initial_balance,
+        context.factory.as_ref(),
+    )?;
+
+    let on_before_sending_transaction_callback: crate::transaction_signature_options::OnBeforeSendingTransactionCallback =
+        std::sync::Arc::new({
+            let new_account_id = row.new_account_id.clone();
+            let credentials_home_dir = context.global_context.config.credentials_home_dir.clone();
+            let save_to_legacy_keychain = context.save_to_legacy_keychain;
+
+            move |_signed_transaction, network_config, storage_message| {
+                let Some(key_pair_properties) = &key_pair_properties else {
+                    return Ok(());
+                };
+                let key_pair_properties_buf = serde_json::to_string(key_pair_properties)?;
+                *storage_message = if save_to_legacy_keychain {
+                    crate::common::save_access_key_to_legacy_keychain(
+                        network_config.clone(),
+                        credentials_home_dir.clone(),
+                        &key_pair_properties_buf,
+                        &key_pair_properties.public_key_str,
+                        new_account_id.as_str(),
+                    )?
+                } else {
+                    crate::common::save_access_key_to_keychain(
+                        network_config.clone(),
+                        &key_pair_properties_buf,
+                        &key_pair_properties.public_key_str,
+                        new_account_id.as_str(),
+                    )?
+                };
+                Ok(())
+            }
+        });
+
+    let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback =
+        std::sync::Arc::new({
+            let new_account_id = row.new_account_id.clone();
+            let credentials_home_dir = context.global_context.config.credentials_home_dir.clone();
+            let transaction_hash = transaction_hash.clone();
+
+            move |outcome_view, _network_config| {
+                *transaction_hash.lock().unwrap() = Some(outcome_view.transaction_outcome.id);
+                if let Some(err) = crate::common::outcome_error(outcome_view) {
+                    color_eyre::eyre::bail!(err);
+                }
+                crate::common::update_used_account_list_as_signer(
+                    &credentials_home_dir,
+                    &new_account_id,
+                );
+                Ok(())
+            }
+        });
+
+    Ok((
+        public_key,
+        crate::transaction_signature_options::BatchTransaction {
+            prepopulated_transaction,
+            on_before_sending_transaction_callback,
+            on_after_sending_transaction_callback,
+        },
+    ))
+}
+
+/// Saves the report and fails if any account was not created.
+fn finish(
+    report_file: &std::path::Path,
+    network_config: &crate::config::NetworkConfig,
+    results: &[RowResult],
+) -> crate::CliResult {
+    write_report(report_file, results)?;
+
+    let created = results
+        .iter()
+        .filter(|result| result.status == Ok(true))
+        .count();
+    let failed = results
+        .iter()
+        .filter(|result| result.status.is_err())
+        .count();
+    let message = format!(
+        "{created} of {} accounts are created on network <{}>. The report is saved to {}",
+        results.len(),
+        network_config.network_name,
+        report_file.display()
+    );
+    if failed == 0 {
+        crate::util::print_success(&message);
+        Ok(())
+    } else {
+        color_eyre::eyre::bail!(message)
+    }
+}
+
+fn write_report(report_file: &std::path::Path, results: &[RowResult]) -> crate::CliResult {
+    let mut report =
+        "account_id,public_key,initial_balance,status,transaction_hash,error\n".to_string();
+    for result in results {
+        report.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            result.new_account_id,
+            result
+                .public_key
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            result.initial_balance,
+            match result.status {
+                Ok(true) => "created",
+                Ok(false) => "not sent",
+                Err(_) => "failed",
+            },
+            result
+                .transaction_hash
+                .map(|hash| hash.to_string())
+                .unwrap_or_default(),
+            result
+                .status
+                .as_ref()
+                .err()
+                .map(String::as_str)
+                .unwrap_or_default()
+                .replace([',', '\n'], " "),
+        ));
+    }
+    std::fs::write(report_file, report)
+        .wrap_err_with(|| format!("Failed to write to file: {report_file:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_csv_rows() {
+        let rows = parse_rows(
+            "account_id,public_key,initial_balance\n\
+             # team accounts\n\
+             bob.alice.testnet,ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp,1 unc\n\
+             \n\
+             carol.alice.testnet,,0.5 unc\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 3);
+        assert!(rows[0].public_key.is_some());
+        assert_eq!(rows[1].new_account_id.as_str(), "carol.alice.testnet");
+        assert!(rows[1].public_key.is_none());
+        assert_eq!(rows[1].initial_balance.as_attounc(), 5 * 10u128.pow(23));
+
+        assert!(parse_rows("bob.alice.testnet,,1 unc\nbob.alice.testnet,,1 unc").is_err());
+        assert!(parse_rows("bob.alice.testnet,1 unc").is_err());
+    }
+}
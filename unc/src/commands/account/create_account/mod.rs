@@ -3,6 +3,7 @@ use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 mod create_implicit_account;
 mod fund_myself_create_account;
+mod fund_myself_from_csv;
 pub mod sponsor_by_faucet_service;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -28,6 +29,11 @@ pub enum CoverCostsCreateAccount {
     ))]
     /// I would like fund myself to cover the cost of creating an account
     FundMyself(self::fund_myself_create_account::NewAccount),
+    #[strum_discriminants(strum(
+        message = "fund-myself-from-csv         - I would like fund myself to create the accounts listed in a CSV file"
+    ))]
+    /// I would like fund myself to create the accounts listed in a CSV file
+    FundMyselfFromCsv(self::fund_myself_from_csv::CreateAccountsFromCsv),
     #[strum_discriminants(strum(
         message = "fund-later                   - Create an implicit-account"
     ))]
@@ -44,6 +50,11 @@ impl CoverCostsCreateAccountContext {
         scope: &<CoverCostsCreateAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         match scope {
+            CoverCostsCreateAccountDiscriminants::FundMyselfFromCsv if previous_context.offline => {
+                Err(color_eyre::Report::msg(
+                    "Error: Creating accounts from a CSV file is not possible offline.",
+                ))
+            }
             CoverCostsCreateAccountDiscriminants::SponsorByFaucetService => {
                 if previous_context.offline {
                     Err(color_eyre::Report::msg(
@@ -63,3 +74,173 @@ impl From<CoverCostsCreateAccountContext> for crate::GlobalContext {
         item.0
     }
 }
+
+/// The gas attached to `create_account` of the linkdrop contract.
+const LINKDROP_CREATE_ACCOUNT_TGAS: u64 = 30;
+
+/// Builds the transaction that creates the new account:
+/// - with the factory contract, if one is given;
+/// - directly, for a 64-character hexadecimal account ID;
+/// - with `create_account` of the linkdrop contract of the network, for a top-level account
+///   or a sub-account of the linkdrop account (the other named accounts are reserved).
+pub(crate) fn account_creation_transaction(
+    network_config: &crate::config::NetworkConfig,
+    signer_id: &unc_primitives::types::AccountId,
+    new_account_id: &unc_primitives::types::AccountId,
+    public_key: &unc_crypto::PublicKey,
+    initial_balance: crate::types::unc_token::UncToken,
+    factory: Option<&crate::config::AccountFactory>,
+) -> color_eyre::eyre::Result<crate::commands::PrepopulatedTransaction> {
+    let function_call = |receiver_id: &unc_primitives::types::AccountId,
+                         method_name: &str,
+                         args: Vec<u8>,
+                         tgas: u64| {
+        crate::commands::PrepopulatedTransaction {
+            signer_id: signer_id.clone(),
+            receiver_id: receiver_id.clone(),
+            actions: vec![unc_primitives::transaction::Action::FunctionCall(Box::new(
+                unc_primitives::transaction::FunctionCallAction {
+                    method_name: method_name.to_string(),
+                    args,
+                    gas: crate::common::UncGas::from_tgas(tgas).as_gas(),
+                    deposit: initial_balance.as_attounc(),
+                },
+            ))],
+        }
+    };
+
+    if let Some(factory) = factory {
+        return Ok(function_call(
+            &factory.factory_account_id,
+            &factory.method_name,
+            factory.args_for(new_account_id, public_key)?,
+            factory.tgas,
+        ));
+    }
+    match new_account_id.get_account_type() {
+        unc_primitives::account::id::AccountType::UtilityAccount => {
+            Ok(crate::commands::PrepopulatedTransaction {
+                signer_id: signer_id.clone(),
+                receiver_id: new_account_id.clone(),
+                actions: vec![
+                    unc_primitives::transaction::Action::CreateAccount(
+                        unc_primitives::transaction::CreateAccountAction {},
+                    ),
+                    unc_primitives::transaction::Action::Transfer(
+                        unc_primitives::transaction::TransferAction {
+                            deposit: initial_balance.as_attounc(),
+                        },
+                    ),
+                    unc_primitives::transaction::Action::AddKey(Box::new(
+                        unc_primitives::transaction::AddKeyAction {
+                            public_key: public_key.clone(),
+                            access_key: unc_primitives::account::AccessKey {
+                                nonce: 0,
+                                permission:
+                                    unc_primitives::account::AccessKeyPermission::FullAccess,
+                            },
+                        },
+                    )),
+                ],
+            })
+        }
+        unc_primitives::account::id::AccountType::Reserved => {
+            let parent_account_id = new_account_id
+                .get_parent_account_id()
+                .map(|parent_account_id| parent_account_id.as_str());
+            match &network_config.linkdrop_account_id {
+                Some(linkdrop_account_id)
+                    if parent_account_id
+                        .map_or(true, |parent| parent == linkdrop_account_id.as_str()) =>
+                {
+                    Ok(function_call(
+                        linkdrop_account_id,
+                        "create_account",
+                        serde_json::to_vec(&serde_json::json!({
+                            "new_account_id": new_account_id,
+                            "new_public_key": public_key,
+                        }))?,
+                        LINKDROP_CREATE_ACCOUNT_TGAS,
+                    ))
+                }
+                _ => color_eyre::eyre::bail!(
+                    "\nAccount <{}> cannot be created on network <{}> because a <reserved_account_id> specified in the configuration file (create it with an account factory: `--factory <name>`).\n",
+                    new_account_id,
+                    network_config.network_name
+                ),
+            }
+        }
+        unc_primitives::account::id::AccountType::EthAccount => color_eyre::eyre::bail!(
+            "\nAccount <{}> cannot be created on network <{}> because a <reserved_account_id> specified in the configuration file.\n",
+            new_account_id,
+            network_config.network_name
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_creation_routes() {
+        let network_config = crate::config::Config::default()
+            .network_connection
+            .remove("testnet")
+            .unwrap();
+        let signer_id: unc_primitives::types::AccountId = "alice.testnet".parse().unwrap();
+        let public_key: unc_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        let receiver_of =
+            |new_account_id: &str, factory: Option<&crate::config::AccountFactory>| {
+                account_creation_transaction(
+                    &network_config,
+                    &signer_id,
+                    &new_account_id.parse().unwrap(),
+                    &public_key,
+                    crate::types::unc_token::UncToken::from_attounc(1),
+                    factory,
+                )
+                .unwrap()
+                .receiver_id
+                .to_string()
+            };
+        let factory = crate::config::AccountFactory {
+            factory_account_id: "dao.testnet".parse().unwrap(),
+            method_name: "create".to_string(),
+            args: r#"{"name": "{name}", "public_key": "{new_public_key}"}"#.to_string(),
+            tgas: 100,
+        };
+
+        let implicit_account_id =
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+        assert_eq!(receiver_of(implicit_account_id, None), implicit_account_id);
+        assert_eq!(receiver_of("bob.testnet", None), "testnet");
+        assert_eq!(receiver_of("bob", None), "testnet");
+        assert!(account_creation_transaction(
+            &network_config,
+            &signer_id,
+            &"app.bob.testnet".parse().unwrap(),
+            &public_key,
+            crate::types::unc_token::UncToken::from_attounc(1),
+            None,
+        )
+        .is_err());
+        assert_eq!(
+            receiver_of("club.dao.testnet", Some(&factory)),
+            "dao.testnet"
+        );
+        assert_eq!(
+            factory
+                .args_for(&"club.dao.testnet".parse().unwrap(), &public_key)
+                .unwrap(),
+            serde_json::to_vec(&serde_json::json!({
+                "name": "club",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+            }))
+            .unwrap()
+        );
+    }
+}
@@ -13,7 +13,7 @@ pub struct GenerateKeypair {
 #[derive(Clone)]
 pub struct GenerateKeypairContext {
     config: crate::config::Config,
-    new_account_id: crate::types::account_id::AccountId,
+    new_account_id: unc_primitives::types::AccountId,
     public_key: unc_crypto::PublicKey,
     key_pair_properties: crate::common::KeyPairProperties,
     on_before_creating_account_callback: super::super::network::OnBeforeCreatingAccountCallback,
@@ -6,7 +6,7 @@ pub mod network;
 #[derive(Clone)]
 pub struct SponsorServiceContext {
     pub config: crate::config::Config,
-    pub new_account_id: crate::types::account_id::AccountId,
+    pub new_account_id: unc_primitives::types::AccountId,
     pub public_key: unc_crypto::PublicKey,
     pub on_after_getting_network_callback: self::network::OnAfterGettingNetworkCallback,
     pub on_before_creating_account_callback: self::network::OnBeforeCreatingAccountCallback,
@@ -26,7 +26,7 @@ pub struct NewAccount {
 #[derive(Clone)]
 pub struct NewAccountContext {
     pub config: crate::config::Config,
-    pub new_account_id: crate::types::account_id::AccountId,
+    pub new_account_id: unc_primitives::types::AccountId,
     pub on_before_creating_account_callback: self::network::OnBeforeCreatingAccountCallback,
 }
 
@@ -35,6 +35,9 @@ impl NewAccountContext {
         previous_context: crate::GlobalContext,
         scope: &<NewAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let new_account_id = scope
+            .new_account_id
+            .resolve(&previous_context.config, None)?;
         let credentials_home_dir = previous_context.config.credentials_home_dir.clone();
         let on_before_creating_account_callback: self::network::OnBeforeCreatingAccountCallback =
             std::sync::Arc::new({
@@ -50,7 +53,7 @@ impl NewAccountContext {
 
         Ok(Self {
             config: previous_context.config,
-            new_account_id: scope.new_account_id.clone(),
+            new_account_id,
             on_before_creating_account_callback,
         })
     }
@@ -66,7 +69,7 @@ impl NewAccount {
 
 pub fn before_creating_account(
     network_config: &crate::config::NetworkConfig,
-    new_account_id: &crate::types::account_id::AccountId,
+    new_account_id: &unc_primitives::types::AccountId,
     _public_key: &unc_crypto::PublicKey,
     credentials_home_dir: &std::path::Path,
 ) -> crate::CliResult {
@@ -95,10 +98,7 @@ pub fn before_creating_account(
             let account_creation_transaction =
                 response.json::<Transaction>()?;
 
-            crate::common::update_used_account_list_as_signer(
-                credentials_home_dir,
-                new_account_id.as_ref(),
-            );
+            crate::common::update_used_account_list_as_signer(credentials_home_dir, new_account_id);
             eprintln!("New account <{}> created successfully.", &new_account_id);
             eprintln!("Processing transaction...\nPlease wait for 6 blocks to confirm, use command: unc transaction view-status <tx_hash>");
             eprintln!("Transaction ID: {id}\nTo see the transaction in the transaction explorer, please open this url in your browser:\n{path}{id}\n",
@@ -14,7 +14,7 @@ pub struct Network {
 
 #[derive(Clone)]
 pub struct NetworkContext {
-    new_account_id: crate::types::account_id::AccountId,
+    new_account_id: unc_primitives::types::AccountId,
     public_key: unc_crypto::PublicKey,
     network_config: crate::config::NetworkConfig,
     on_after_getting_network_callback: OnAfterGettingNetworkCallback,
@@ -62,7 +62,7 @@ impl Network {
     fn input_network_name(
         context: &super::SponsorServiceContext,
     ) -> color_eyre::eyre::Result<Option<String>> {
-        crate::common::input_network_name(&context.config, &[context.new_account_id.clone().into()])
+        crate::common::input_network_name(&context.config, &[context.new_account_id.clone()])
     }
 }
 
@@ -105,7 +105,7 @@ pub type OnAfterGettingNetworkCallback =
 pub type OnBeforeCreatingAccountCallback = std::sync::Arc<
     dyn Fn(
         &crate::config::NetworkConfig,
-        &crate::types::account_id::AccountId,
+        &unc_primitives::types::AccountId,
         &unc_crypto::PublicKey,
     ) -> crate::CliResult,
 >;
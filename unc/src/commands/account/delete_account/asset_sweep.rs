@@ -0,0 +1,689 @@
+use color_eyre::eyre::WrapErr;
+use inquire::Confirm;
+
+use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
+
+const ONE_ATTOUNC: u128 = 1;
+const NFT_TOKENS_PAGE_LIMIT: u64 = 100;
+
+/// Something of value the account still holds outside of its own balance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Holding {
+    FungibleToken {
+        contract_account_id: unc_primitives::types::AccountId,
+        balance: u128,
+        beneficiary_registration: BeneficiaryRegistration,
+    },
+    NonFungibleToken {
+        contract_account_id: unc_primitives::types::AccountId,
+        token_id: String,
+    },
+    StorageDeposit {
+        contract_account_id: unc_primitives::types::AccountId,
+        total: u128,
+    },
+    Delegation {
+        pool_account_id: unc_primitives::types::AccountId,
+        pledged: u128,
+        unpledged: u128,
+        withdrawable: bool,
+    },
+}
+
+/// Whether the beneficiary can receive the tokens of a fungible token contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BeneficiaryRegistration {
+    Registered,
+    /// The storage deposit that registers the beneficiary (see `storage_balance_bounds`).
+    StorageDeposit(u128),
+    /// The beneficiary is not registered and the contract does not tell the deposit to register it.
+    Unavailable,
+}
+
+impl std::fmt::Display for Holding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FungibleToken {
+                contract_account_id,
+                balance,
+                beneficiary_registration,
+            } => write!(
+                f,
+                "FT balance {balance} on <{contract_account_id}>{}",
+                if *beneficiary_registration == BeneficiaryRegistration::Unavailable {
+                    " (unsweepable: the beneficiary is not registered on the contract, which has no `storage_balance_bounds`)"
+                } else {
+                    ""
+                }
+            ),
+            Self::NonFungibleToken {
+                contract_account_id,
+                token_id,
+            } => write!(f, "NFT \"{token_id}\" on <{contract_account_id}>"),
+            Self::StorageDeposit {
+                contract_account_id,
+                total,
+            } => write!(
+                f,
+                "storage deposit of {} on <{contract_account_id}>",
+                unc_token::UncToken::from_attounc(*total)
+            ),
+            Self::Delegation {
+                pool_account_id,
+                pledged,
+                unpledged,
+                withdrawable,
+            } => write!(
+                f,
+                "delegation to <{pool_account_id}>: {} pledged, {} unpledged{}",
+                unc_token::UncToken::from_attounc(*pledged),
+                unc_token::UncToken::from_attounc(*unpledged),
+                match (*unpledged > 0, withdrawable) {
+                    (true, true) => " (available for withdrawal)",
+                    (true, false) => " (not available for withdrawal in the current epoch)",
+                    _ => "",
+                }
+            ),
+        }
+    }
+}
+
+impl Holding {
+    /// Tokens have to leave a contract before the storage deposit on it can be released,
+    /// so they are swept first.
+    fn sort_key(&self) -> (u8, &unc_primitives::types::AccountId, &str) {
+        match self {
+            Self::FungibleToken {
+                contract_account_id,
+                ..
+            } => (0, contract_account_id, ""),
+            Self::NonFungibleToken {
+                contract_account_id,
+                token_id,
+            } => (1, contract_account_id, token_id),
+            Self::StorageDeposit {
+                contract_account_id,
+                ..
+            } => (2, contract_account_id, ""),
+            Self::Delegation {
+                pool_account_id, ..
+            } => (3, pool_account_id, ""),
+        }
+    }
+
+    /// The transaction that moves this holding to the beneficiary (or back to the account
+    /// balance, which goes to the beneficiary on deletion), if one can be sent right now.
+    pub(super) fn sweep_transaction(
+        &self,
+        account_id: &unc_primitives::types::AccountId,
+        beneficiary_account_id: &unc_primitives::types::AccountId,
+    ) -> color_eyre::eyre::Result<Option<crate::commands::PrepopulatedTransaction>> {
+        let (receiver_id, calls) = match self {
+            Self::FungibleToken {
+                beneficiary_registration: BeneficiaryRegistration::Unavailable,
+                ..
+            } => return Ok(None),
+            Self::FungibleToken {
+                contract_account_id,
+                balance,
+                beneficiary_registration,
+            } => {
+                let mut calls = Vec::new();
+                if let BeneficiaryRegistration::StorageDeposit(storage_deposit) =
+                    beneficiary_registration
+                {
+                    calls.push((
+                        "storage_deposit",
+                        serde_json::json!({
+                            "account_id": beneficiary_account_id,
+                            "registration_only": true,
+                        }),
+                        50,
+                        *storage_deposit,
+                    ));
+                }
+                calls.push((
+                    "ft_transfer",
+                    serde_json::json!({
+                        "receiver_id": beneficiary_account_id,
+                        "amount": balance.to_string(),
+                    }),
+                    100,
+                    ONE_ATTOUNC,
+                ));
+                (contract_account_id, calls)
+            }
+            Self::NonFungibleToken {
+                contract_account_id,
+                token_id,
+            } => (
+                contract_account_id,
+                vec![(
+                    "nft_transfer",
+                    serde_json::json!({
+                        "receiver_id": beneficiary_account_id,
+                        "token_id": token_id,
+                    }),
+                    100,
+                    ONE_ATTOUNC,
+                )],
+            ),
+            Self::StorageDeposit {
+                contract_account_id,
+                ..
+            } => (
+                contract_account_id,
+                vec![("storage_unregister", serde_json::json!({}), 50, ONE_ATTOUNC)],
+            ),
+            Self::Delegation {
+                pool_account_id,
+                pledged,
+                unpledged,
+                withdrawable,
+            } => {
+                let mut calls = Vec::new();
+                if *unpledged > 0 && *withdrawable {
+                    calls.push(("withdraw_all", serde_json::json!({}), 50, 0));
+                }
+                if *pledged > 0 {
+                    calls.push(("unpledge_all", serde_json::json!({}), 50, 0));
+                }
+                (pool_account_id, calls)
+            }
+        };
+        if calls.is_empty() {
+            return Ok(None);
+        }
+        let actions = calls
+            .into_iter()
+            .map(|(method_name, args, tgas, deposit)| {
+                Ok(unc_primitives::transaction::Action::FunctionCall(Box::new(
+                    unc_primitives::transaction::FunctionCallAction {
+                        method_name: method_name.to_string(),
+                        args: serde_json::to_vec(&args)?,
+                        gas: crate::common::UncGas::from_tgas(tgas).as_gas(),
+                        deposit,
+                    },
+                )))
+            })
+            .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
+        Ok(Some(crate::commands::PrepopulatedTransaction {
+            signer_id: account_id.clone(),
+            receiver_id: receiver_id.clone(),
+            actions,
+        }))
+    }
+}
+
+/// Looks for what the account still holds and returns the transactions that sweep it to the
+/// beneficiary: all of them with `sweep`, otherwise the ones confirmed one by one. Returns `None`
+/// if nothing is held, or if the holdings are to be lost with the account (`force` without `sweep`).
+pub(super) fn sweep_transactions(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &unc_primitives::types::AccountId,
+    beneficiary_account_id: &unc_primitives::types::AccountId,
+    contract_account_ids: &[unc_primitives::types::AccountId],
+    pool_account_ids: &[unc_primitives::types::AccountId],
+    sweep: bool,
+    force: bool,
+) -> color_eyre::eyre::Result<Option<Vec<crate::transaction_signature_options::BatchTransaction>>> {
+    let holdings = scan_holdings(
+        network_config,
+        account_id,
+        beneficiary_account_id,
+        contract_account_ids,
+        pool_account_ids,
+    )?;
+    if holdings.is_empty() {
+        return Ok(None);
+    }
+    eprintln!(
+        "\n<{account_id}> still holds:\n{}",
+        format_holdings(&holdings)
+    );
+    if force && !sweep {
+        eprintln!("\nThese holdings are not swept and will be lost with the account.");
+        return Ok(None);
+    }
+    if !force {
+        let unsweepable = holdings
+            .iter()
+            .filter(|holding| {
+                matches!(
+                    holding,
+                    Holding::FungibleToken {
+                        beneficiary_registration: BeneficiaryRegistration::Unavailable,
+                        ..
+                    }
+                )
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unsweepable.is_empty() {
+            color_eyre::eyre::bail!(
+                "These tokens cannot be swept to <{beneficiary_account_id}>:\n{}\nRegister <{beneficiary_account_id}> on the token contracts first, or use --force to delete the account anyway and lose them.",
+                format_holdings(&unsweepable)
+            );
+        }
+    }
+
+    let mut transactions = Vec::new();
+    for holding in holdings {
+        let Some(prepopulated_transaction) =
+            holding.sweep_transaction(account_id, beneficiary_account_id)?
+        else {
+            continue;
+        };
+        if !sweep
+            && !Confirm::new(&format!(
+                "Do you want to sweep the {holding} to <{beneficiary_account_id}>?"
+            ))
+            .with_default(true)
+            .prompt()?
+        {
+            continue;
+        }
+        transactions.push(crate::transaction_signature_options::BatchTransaction {
+            prepopulated_transaction,
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config, _message| Ok(()),
+            ),
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                move |outcome_view, _network_config| {
+                    if let Some(err) = crate::common::outcome_error(outcome_view) {
+                        color_eyre::eyre::bail!("Failed to sweep the {holding}: {err}");
+                    }
+                    eprintln!(
+                        "Swept the {holding} (transaction {})",
+                        outcome_view.transaction_outcome.id
+                    );
+                    Ok(())
+                },
+            ),
+        });
+    }
+    Ok(Some(transactions))
+}
+
+/// Refuses to go on with the deletion while something of value is left, unless `force` is set.
+pub(super) fn check_nothing_left(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &unc_primitives::types::AccountId,
+    beneficiary_account_id: &unc_primitives::types::AccountId,
+    contract_account_ids: &[unc_primitives::types::AccountId],
+    pool_account_ids: &[unc_primitives::types::AccountId],
+    force: bool,
+) -> crate::CliResult {
+    let remaining = scan_holdings(
+        network_config,
+        account_id,
+        beneficiary_account_id,
+        contract_account_ids,
+        pool_account_ids,
+    )?;
+    if remaining.is_empty() {
+        return Ok(());
+    }
+    if force {
+        eprintln!(
+            "\n<{account_id}> is deleted while it still holds:\n{}",
+            format_holdings(&remaining)
+        );
+        return Ok(());
+    }
+    color_eyre::eyre::bail!(
+        "<{account_id}> still holds:\n{}\nUnpledged balances can only be withdrawn a few epochs after `unpledge_all`. Delete the account once everything is swept, or use --force to delete it anyway and lose these holdings.",
+        format_holdings(&remaining)
+    )
+}
+
+fn format_holdings(holdings: &[Holding]) -> String {
+    holdings
+        .iter()
+        .map(|holding| format!("  - {holding}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Looks for FT, NFT and storage holdings of the account on the given contracts and for its
+/// delegations. Besides the given pools, the pools are the current and next validators, the
+/// proposals and the validators kicked out in the previous epoch.
+pub(super) fn scan_holdings(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &unc_primitives::types::AccountId,
+    beneficiary_account_id: &unc_primitives::types::AccountId,
+    contract_account_ids: &[unc_primitives::types::AccountId],
+    pool_account_ids: &[unc_primitives::types::AccountId],
+) -> color_eyre::eyre::Result<Vec<Holding>> {
+    let json_rpc_client = network_config.json_rpc_client();
+
+    let mut holdings: Vec<Holding> = crate::common::block_on_concurrently(
+        contract_account_ids.iter().map(|contract_account_id| {
+            scan_contract(
+                &json_rpc_client,
+                contract_account_id,
+                account_id,
+                beneficiary_account_id,
+            )
+        }),
+    )?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let epoch_validator_info = json_rpc_client
+        .blocking_call(
+            &unc_jsonrpc_client::methods::validators::RpcValidatorRequest {
+                epoch_reference: unc_primitives::types::EpochReference::Latest,
+            },
+        )
+        .wrap_err("Failed to get epoch validators information request.")?;
+    let pool_account_ids = epoch_validator_info
+        .current_pledge_proposals
+        .into_iter()
+        .map(|proposal| proposal.take_account_id())
+        .chain(
+            epoch_validator_info
+                .current_validators
+                .into_iter()
+                .map(|validator| validator.account_id),
+        )
+        .chain(
+            epoch_validator_info
+                .next_validators
+                .into_iter()
+                .map(|validator| validator.account_id),
+        )
+        .chain(
+            epoch_validator_info
+                .prev_epoch_kickout
+                .into_iter()
+                .map(|kickout| kickout.account_id),
+        )
+        .chain(pool_account_ids.iter().cloned())
+        .filter(|pool_account_id| pool_account_id != account_id)
+        .collect::<std::collections::BTreeSet<_>>();
+    holdings.extend(
+        crate::common::block_on_concurrently(
+            pool_account_ids
+                .into_iter()
+                .map(|pool_account_id| scan_pool(&json_rpc_client, pool_account_id, account_id)),
+        )?
+        .into_iter()
+        .flatten(),
+    );
+
+    holdings.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    Ok(holdings)
+}
+
+async fn scan_contract(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    contract_account_id: &unc_primitives::types::AccountId,
+    account_id: &unc_primitives::types::AccountId,
+    beneficiary_account_id: &unc_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Vec<Holding>> {
+    let mut holdings = Vec::new();
+    let args = serde_json::json!({ "account_id": account_id });
+
+    let balance =
+        view_balance(json_rpc_client, contract_account_id, "ft_balance_of", &args).await?;
+    if balance > 0 {
+        let beneficiary_registered = view_function::<serde_json::Value>(
+            json_rpc_client,
+            contract_account_id,
+            "storage_balance_of",
+            &serde_json::json!({ "account_id": beneficiary_account_id }),
+        )
+        .await?
+        .map_or(true, |storage_balance| !storage_balance.is_null());
+        let beneficiary_registration = if beneficiary_registered {
+            BeneficiaryRegistration::Registered
+        } else {
+            #[derive(serde::Deserialize)]
+            struct StorageBalanceBounds {
+                min: String,
+            }
+            view_function::<StorageBalanceBounds>(
+                json_rpc_client,
+                contract_account_id,
+                "storage_balance_bounds",
+                &serde_json::json!({}),
+            )
+            .await?
+            .and_then(|bounds| bounds.min.parse::<u128>().ok())
+            .map_or(
+                BeneficiaryRegistration::Unavailable,
+                BeneficiaryRegistration::StorageDeposit,
+            )
+        };
+        holdings.push(Holding::FungibleToken {
+            contract_account_id: contract_account_id.clone(),
+            balance,
+            beneficiary_registration,
+        });
+    }
+
+    // Pages may be shorter than the limit, so the listing ends with an empty page (or with one
+    // without new tokens, for the contracts that ignore `from_index`).
+    let mut token_ids: Vec<String> = Vec::new();
+    while let Some(tokens) = view_function::<Vec<serde_json::Value>>(
+        json_rpc_client,
+        contract_account_id,
+        "nft_tokens_for_owner",
+        &serde_json::json!({
+            "account_id": account_id,
+            "from_index": token_ids.len().to_string(),
+            "limit": NFT_TOKENS_PAGE_LIMIT,
+        }),
+    )
+    .await?
+    {
+        let new_token_ids = tokens
+            .iter()
+            .filter_map(|token| Some(token.get("token_id")?.as_str()?.to_string()))
+            .filter(|token_id| !token_ids.contains(token_id))
+            .collect::<Vec<_>>();
+        if new_token_ids.is_empty() {
+            break;
+        }
+        token_ids.extend(new_token_ids);
+    }
+    holdings.extend(
+        token_ids
+            .into_iter()
+            .map(|token_id| Holding::NonFungibleToken {
+                contract_account_id: contract_account_id.clone(),
+                token_id,
+            }),
+    );
+
+    #[derive(serde::Deserialize)]
+    struct StorageBalance {
+        total: String,
+    }
+    if let Some(total) = view_function::<Option<StorageBalance>>(
+        json_rpc_client,
+        contract_account_id,
+        "storage_balance_of",
+        &args,
+    )
+    .await?
+    .flatten()
+    .and_then(|storage_balance| storage_balance.total.parse::<u128>().ok())
+    .filter(|total| *total > 0)
+    {
+        holdings.push(Holding::StorageDeposit {
+            contract_account_id: contract_account_id.clone(),
+            total,
+        });
+    }
+
+    Ok(holdings)
+}
+
+async fn scan_pool(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    pool_account_id: unc_primitives::types::AccountId,
+    account_id: &unc_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Option<Holding>> {
+    let args = serde_json::json!({ "account_id": account_id });
+    let pledged = view_balance(
+        json_rpc_client,
+        &pool_account_id,
+        "get_account_pledged_balance",
+        &args,
+    )
+    .await?;
+    let unpledged = view_balance(
+        json_rpc_client,
+        &pool_account_id,
+        "get_account_unpledged_balance",
+        &args,
+    )
+    .await?;
+    if pledged == 0 && unpledged == 0 {
+        return Ok(None);
+    }
+    let withdrawable = unpledged > 0
+        && view_function::<bool>(
+            json_rpc_client,
+            &pool_account_id,
+            "is_account_unpledged_balance_available",
+            &args,
+        )
+        .await?
+        .unwrap_or_default();
+    Ok(Some(Holding::Delegation {
+        pool_account_id,
+        pledged,
+        unpledged,
+        withdrawable,
+    }))
+}
+
+/// Balances are returned by the contracts as strings; a missing one counts as zero.
+async fn view_balance(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    contract_account_id: &unc_primitives::types::AccountId,
+    method_name: &str,
+    args: &serde_json::Value,
+) -> color_eyre::eyre::Result<u128> {
+    Ok(
+        view_function::<String>(json_rpc_client, contract_account_id, method_name, args)
+            .await?
+            .and_then(|balance| balance.parse::<u128>().ok())
+            .unwrap_or_default(),
+    )
+}
+
+/// Calls a view function, returning `None` when the contract does not implement it
+/// (or returns something other than the standard response).
+async fn view_function<T: serde::de::DeserializeOwned>(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    contract_account_id: &unc_primitives::types::AccountId,
+    method_name: &str,
+    args: &serde_json::Value,
+) -> color_eyre::eyre::Result<Option<T>> {
+    let response = json_rpc_client
+        .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: unc_primitives::types::Finality::Final.into(),
+            request: unc_primitives::views::QueryRequest::CallFunction {
+                account_id: contract_account_id.clone(),
+                method_name: method_name.to_string(),
+                args: unc_primitives::types::FunctionArgs::from(serde_json::to_vec(args)?),
+            },
+        })
+        .await;
+    match response {
+        Ok(response) => Ok(response.call_result()?.parse_result_from_json::<T>().ok()),
+        Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+            unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                unc_jsonrpc_client::methods::query::RpcQueryError::NoContractCode { .. }
+                | unc_jsonrpc_client::methods::query::RpcQueryError::ContractExecutionError {
+                    ..
+                }
+                | unc_jsonrpc_client::methods::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => Ok(None),
+        Err(err) => Err(err).wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: '{method_name}' (contract <{contract_account_id}>)"
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sweep_transactions_for_holdings() {
+        let account_id: unc_primitives::types::AccountId = "alice.unc".parse().unwrap();
+        let beneficiary: unc_primitives::types::AccountId = "bob.unc".parse().unwrap();
+        let token: unc_primitives::types::AccountId = "token.unc".parse().unwrap();
+        let method_names = |holding: Holding| {
+            holding
+                .sweep_transaction(&account_id, &beneficiary)
+                .unwrap()
+                .map(|transaction| {
+                    transaction
+                        .actions
+                        .into_iter()
+                        .map(|action| match action {
+                            unc_primitives::transaction::Action::FunctionCall(call) => {
+                                call.method_name
+                            }
+                            _ => unreachable!(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        assert_eq!(
+            method_names(Holding::FungibleToken {
+                contract_account_id: token.clone(),
+                balance: 10,
+                beneficiary_registration: BeneficiaryRegistration::StorageDeposit(1250),
+            }),
+            Some(vec![
+                "storage_deposit".to_string(),
+                "ft_transfer".to_string()
+            ])
+        );
+        assert_eq!(
+            method_names(Holding::FungibleToken {
+                contract_account_id: token.clone(),
+                balance: 10,
+                beneficiary_registration: BeneficiaryRegistration::Unavailable,
+            }),
+            None
+        );
+        assert_eq!(
+            method_names(Holding::StorageDeposit {
+                contract_account_id: token,
+                total: 10,
+            }),
+            Some(vec!["storage_unregister".to_string()])
+        );
+        assert_eq!(
+            method_names(Holding::Delegation {
+                pool_account_id: "pool.unc".parse().unwrap(),
+                pledged: 5,
+                unpledged: 5,
+                withdrawable: false,
+            }),
+            Some(vec!["unpledge_all".to_string()])
+        );
+        assert_eq!(
+            method_names(Holding::Delegation {
+                pool_account_id: "pool.unc".parse().unwrap(),
+                pledged: 0,
+                unpledged: 5,
+                withdrawable: false,
+            }),
+            None
+        );
+    }
+}
@@ -1,5 +1,10 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
 use inquire::Select;
 
+mod asset_sweep;
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = DeleteAccountContext)]
@@ -23,9 +28,10 @@ impl DeleteAccountContext {
         previous_context: crate::GlobalContext,
         scope: &<DeleteAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            account_id: scope.account_id.clone().into(),
+            account_id,
         })
     }
 }
@@ -48,9 +54,23 @@ pub struct BeneficiaryAccount {
     #[interactive_clap(skip_default_input_arg)]
     /// Specify a beneficiary:
     beneficiary_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Look for FT, NFT and storage holdings on these contracts too (comma-separated), besides the used accounts
+    token_contracts: Option<crate::types::vec_string::VecString>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Look for delegations to these pledging pools too (comma-separated), e.g. to the pools that are no longer validators
+    pledging_pools: Option<crate::types::vec_string::VecString>,
+    #[interactive_clap(long)]
+    /// Send all the transactions that sweep the holdings to the beneficiary without asking
+    sweep: bool,
+    #[interactive_clap(long)]
+    /// Delete the account even if it still holds tokens, storage deposits or delegations
+    force: bool,
     #[interactive_clap(named_arg)]
     /// Select network
-    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+    network_config: NetworkForDeletion,
 }
 
 #[derive(Debug, Clone)]
@@ -58,6 +78,10 @@ pub struct BeneficiaryAccountContext {
     global_context: crate::GlobalContext,
     account_id: unc_primitives::types::AccountId,
     beneficiary_account_id: unc_primitives::types::AccountId,
+    contract_account_ids: Vec<unc_primitives::types::AccountId>,
+    pool_account_ids: Vec<unc_primitives::types::AccountId>,
+    sweep: bool,
+    force: bool,
 }
 
 impl BeneficiaryAccountContext {
@@ -65,50 +89,67 @@ impl BeneficiaryAccountContext {
         previous_context: DeleteAccountContext,
         scope: &<BeneficiaryAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.global_context.offline && !scope.force {
+            color_eyre::eyre::bail!(
+                "Holdings of the account cannot be checked in offline mode. Use --force to delete it without the check."
+            );
+        }
+        let beneficiary_account_id: unc_primitives::types::AccountId = scope
+            .beneficiary_account_id
+            .resolve(&previous_context.global_context.config, None)?;
+
+        let mut contract_account_ids = Vec::new();
+        for contract in scope
+            .token_contracts
+            .clone()
+            .map(Vec::<String>::from)
+            .unwrap_or_default()
+        {
+            contract_account_ids.push(
+                crate::types::account_id::AccountId::from_str(&contract)
+                    .wrap_err_with(|| format!("Invalid token contract <{contract}>"))?
+                    .resolve(&previous_context.global_context.config, None)?,
+            );
+        }
+        contract_account_ids.extend(
+            crate::common::get_used_account_list(
+                &previous_context.global_context.config.credentials_home_dir,
+            )
+            .into_iter()
+            .map(|used_account| used_account.account_id),
+        );
+        let mut pool_account_ids = Vec::new();
+        for pool in scope
+            .pledging_pools
+            .clone()
+            .map(Vec::<String>::from)
+            .unwrap_or_default()
+        {
+            pool_account_ids.push(
+                crate::types::account_id::AccountId::from_str(&pool)
+                    .wrap_err_with(|| format!("Invalid pledging pool <{pool}>"))?
+                    .resolve(&previous_context.global_context.config, None)?,
+            );
+        }
+        let mut seen = std::collections::HashSet::new();
+        contract_account_ids.retain(|contract_account_id| {
+            contract_account_id != &previous_context.account_id
+                && contract_account_id != &beneficiary_account_id
+                && seen.insert(contract_account_id.clone())
+        });
+
         Ok(Self {
             global_context: previous_context.global_context,
             account_id: previous_context.account_id,
-            beneficiary_account_id: scope.beneficiary_account_id.clone().into(),
+            beneficiary_account_id,
+            contract_account_ids,
+            pool_account_ids,
+            sweep: scope.sweep,
+            force: scope.force,
         })
     }
 }
 
-impl From<BeneficiaryAccountContext> for crate::commands::ActionContext {
-    fn from(item: BeneficiaryAccountContext) -> Self {
-        let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
-            std::sync::Arc::new({
-                let account_id = item.account_id.clone();
-
-                move |_network_config| {
-                    Ok(crate::commands::PrepopulatedTransaction {
-                        signer_id: account_id.clone(),
-                        receiver_id: account_id.clone(),
-                        actions: vec![unc_primitives::transaction::Action::DeleteAccount(
-                            unc_primitives::transaction::DeleteAccountAction {
-                                beneficiary_id: item.beneficiary_account_id.clone(),
-                            },
-                        )],
-                    })
-                }
-            });
-
-        Self {
-            global_context: item.global_context,
-            interacting_with_account_ids: vec![item.account_id],
-            on_after_getting_network_callback,
-            on_before_signing_callback: std::sync::Arc::new(
-                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
-            ),
-            on_before_sending_transaction_callback: std::sync::Arc::new(
-                |_signed_transaction, _network_config, _message| Ok(()),
-            ),
-            on_after_sending_transaction_callback: std::sync::Arc::new(
-                |_outcome_view, _network_config| Ok(()),
-            ),
-        }
-    }
-}
-
 impl BeneficiaryAccount {
     pub fn input_beneficiary_account_id(
         context: &DeleteAccountContext,
@@ -125,6 +166,7 @@ impl BeneficiaryAccount {
             };
 
             if context.global_context.offline {
+                crate::common::warn_account_check_skipped_offline(&beneficiary_account_id);
                 return Ok(Some(beneficiary_account_id));
             }
 
@@ -148,7 +190,7 @@ impl BeneficiaryAccount {
             if let ConfirmOptions::Yes { account_id } = select_choose_input {
                 if crate::common::find_network_where_account_exist(
                     &context.global_context,
-                    account_id.clone().into(),
+                    account_id.resolve(&context.global_context.config, None)?,
                 )
                 .is_none()
                 {
@@ -168,3 +210,146 @@ impl BeneficiaryAccount {
         }
     }
 }
+
+/// The network and the signing options of the deletion, which are also offered for the
+/// transactions that sweep the holdings of the account before it.
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = BeneficiaryAccountContext)]
+#[interactive_clap(skip_default_from_cli)]
+pub struct NetworkForDeletion {
+    /// What is the name of the network?
+    #[interactive_clap(skip_default_input_arg)]
+    network_name: String,
+    #[interactive_clap(subcommand)]
+    transaction_signature_options: crate::transaction_signature_options::SignWith,
+}
+
+impl interactive_clap::FromCli for NetworkForDeletion {
+    type FromCliContext = BeneficiaryAccountContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+
+        if clap_variant.network_name.is_none() {
+            clap_variant.network_name = match Self::input_network_name(&context) {
+                Ok(Some(network_name)) => Some(network_name),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let network_name = clap_variant.network_name.clone().expect("Unexpected error");
+        let network_config = match context
+            .global_context
+            .config
+            .network_connection
+            .get(&network_name)
+            .wrap_err("Failed to get network config!")
+        {
+            Ok(network_config) => network_config.clone(),
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        };
+
+        if !context.global_context.offline {
+            if let Err(err) = Self::sweep(&context, &network_config, &clap_variant) {
+                return match err {
+                    Some(err) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+                    None => interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                };
+            }
+        }
+
+        let delete_transaction = crate::transaction_signature_options::BatchTransaction {
+            prepopulated_transaction: crate::commands::PrepopulatedTransaction {
+                signer_id: context.account_id.clone(),
+                receiver_id: context.account_id.clone(),
+                actions: vec![unc_primitives::transaction::Action::DeleteAccount(
+                    unc_primitives::transaction::DeleteAccountAction {
+                        beneficiary_id: context.beneficiary_account_id.clone(),
+                    },
+                )],
+            },
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config, _message| Ok(()),
+            ),
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                |_outcome_view, _network_config| Ok(()),
+            ),
+        };
+        match crate::transaction_signature_options::sign_batch(
+            &context.global_context,
+            &network_config,
+            &mut clap_variant.transaction_signature_options,
+            vec![delete_transaction],
+        )
+        .map(|results| results.into_iter().next())
+        {
+            Some(Some(Err(err))) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            Some(_) => interactive_clap::ResultFromCli::Ok(clap_variant),
+            None => interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+        }
+    }
+}
+
+impl NetworkForDeletion {
+    fn input_network_name(
+        context: &BeneficiaryAccountContext,
+    ) -> color_eyre::eyre::Result<Option<String>> {
+        crate::common::input_network_name(
+            &context.global_context.config,
+            &[context.account_id.clone()],
+        )
+    }
+
+    /// Sends the transactions that sweep the holdings with the signing options of the deletion.
+    /// The options chosen interactively for the sweep are asked again for the deletion, so that
+    /// it is confirmed on its own. Fails with `None` if the sweep was cancelled.
+    fn sweep(
+        context: &BeneficiaryAccountContext,
+        network_config: &crate::config::NetworkConfig,
+        clap_variant: &<Self as interactive_clap::ToCli>::CliVariant,
+    ) -> Result<(), Option<color_eyre::eyre::Error>> {
+        let Some(transactions) = asset_sweep::sweep_transactions(
+            network_config,
+            &context.account_id,
+            &context.beneficiary_account_id,
+            &context.contract_account_ids,
+            &context.pool_account_ids,
+            context.sweep,
+            context.force,
+        )?
+        else {
+            return Ok(());
+        };
+        if !transactions.is_empty() {
+            let results = crate::transaction_signature_options::sign_batch(
+                &context.global_context,
+                network_config,
+                &mut clap_variant.transaction_signature_options.clone(),
+                transactions,
+            )
+            .ok_or(None)?;
+            for err in results.into_iter().filter_map(Result::err) {
+                eprintln!("{err:#}");
+            }
+        }
+        asset_sweep::check_nothing_left(
+            network_config,
+            &context.account_id,
+            &context.beneficiary_account_id,
+            &context.contract_account_ids,
+            &context.pool_account_ids,
+            context.force,
+        )?;
+        Ok(())
+    }
+}
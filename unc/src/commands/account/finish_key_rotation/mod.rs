@@ -0,0 +1,302 @@
+use color_eyre::eyre::Context;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = FinishKeyRotationContext)]
+pub struct FinishKeyRotation {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account did you rotate an access key of?
+    owner_account_id: crate::types::account_id::AccountId,
+    /// Enter the old public key (the one that was replaced):
+    old_public_key: crate::types::public_key::PublicKey,
+    /// Enter the new public key:
+    new_public_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct FinishKeyRotationContext(crate::network::NetworkContext);
+
+impl FinishKeyRotationContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<FinishKeyRotation as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id: unc_primitives::types::AccountId = scope
+            .owner_account_id
+            .resolve(&previous_context.config, None)?;
+        let old_public_key: unc_crypto::PublicKey = scope.old_public_key.clone().into();
+        let new_public_key: unc_crypto::PublicKey = scope.new_public_key.clone().into();
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let owner_account_id = owner_account_id.clone();
+                let credentials_home_dir = previous_context.config.credentials_home_dir.clone();
+
+                move |network_config| {
+                    finish_rotation(
+                        &credentials_home_dir,
+                        network_config,
+                        &owner_account_id,
+                        &old_public_key,
+                        &new_public_key,
+                        std::time::Duration::ZERO,
+                    )
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![owner_account_id],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<FinishKeyRotationContext> for crate::network::NetworkContext {
+    fn from(item: FinishKeyRotationContext) -> Self {
+        item.0
+    }
+}
+
+impl FinishKeyRotation {
+    pub fn input_owner_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account did you rotate an access key of?",
+        )
+    }
+}
+
+/// Verifies the rotation on chain and then removes the old key from this machine.
+/// The verification is retried until `timeout` passes, as the rotation may not be final yet.
+pub(super) fn finish_rotation(
+    credentials_home_dir: &std::path::Path,
+    network_config: &crate::config::NetworkConfig,
+    owner_account_id: &unc_primitives::types::AccountId,
+    old_public_key: &unc_crypto::PublicKey,
+    new_public_key: &unc_crypto::PublicKey,
+    timeout: std::time::Duration,
+) -> crate::CliResult {
+    crate::util::handle_step("Verifying the rotation on chain...", || {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            match verify_rotation(
+                network_config,
+                owner_account_id,
+                old_public_key,
+                new_public_key,
+            ) {
+                Err(_) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(std::time::Duration::from_secs(1))
+                }
+                result => return result,
+            }
+        }
+    })?;
+    let removed = remove_old_key(
+        credentials_home_dir,
+        &network_config.network_name,
+        owner_account_id,
+        old_public_key,
+        new_public_key,
+    )?;
+    if removed.is_empty() {
+        eprintln!("The old key {old_public_key} is not stored on this machine.");
+    }
+    for message in removed {
+        eprintln!("{message}");
+    }
+    crate::util::print_success(&format!(
+        "The access key of <{owner_account_id}> is rotated to {new_public_key}"
+    ));
+    Ok(())
+}
+
+/// The new key must be on chain and the old one must be gone before the old key is removed locally.
+fn verify_rotation(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &unc_primitives::types::AccountId,
+    old_public_key: &unc_crypto::PublicKey,
+    new_public_key: &unc_crypto::PublicKey,
+) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let block_reference: unc_primitives::types::BlockReference =
+        unc_primitives::types::Finality::Final.into();
+    match json_rpc_client.blocking_call_view_access_key(
+        account_id,
+        new_public_key,
+        block_reference.clone(),
+    ) {
+        Ok(_) => {}
+        Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+            unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                unc_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccessKey { .. },
+            ),
+        )) => color_eyre::eyre::bail!(
+            "The new key {new_public_key} is not on <{account_id}> yet: wait until the rotation transaction is final and try again"
+        ),
+        Err(err) => {
+            return Err(color_eyre::eyre::Report::from(err)).wrap_err_with(|| {
+                format!("Failed to fetch the access key {new_public_key} of <{account_id}>")
+            })
+        }
+    }
+    match json_rpc_client.blocking_call_view_access_key(account_id, old_public_key, block_reference)
+    {
+        Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+            unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                unc_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccessKey { .. },
+            ),
+        )) => Ok(()),
+        Ok(_) => color_eyre::eyre::bail!(
+            "The old key {old_public_key} is still on <{account_id}>, so it is kept on this machine"
+        ),
+        Err(err) => Err(color_eyre::eyre::Report::from(err)).wrap_err_with(|| {
+            format!("Failed to fetch the access key {old_public_key} of <{account_id}>")
+        }),
+    }
+}
+
+/// Removes the old key from the keychain and the legacy keychain, returning what was removed.
+fn remove_old_key(
+    credentials_home_dir: &std::path::Path,
+    network_name: &str,
+    account_id: &unc_primitives::types::AccountId,
+    old_public_key: &unc_crypto::PublicKey,
+    new_public_key: &unc_crypto::PublicKey,
+) -> color_eyre::eyre::Result<Vec<String>> {
+    let mut removed = remove_old_key_from_legacy_keychain(
+        credentials_home_dir,
+        network_name,
+        account_id,
+        old_public_key,
+        new_public_key,
+    )?;
+    let service_name = format!("unc-{network_name}-{account_id}");
+    match keyring::Entry::new(&service_name, &format!("{account_id}:{old_public_key}"))
+        .and_then(|entry| entry.delete_password())
+    {
+        Ok(()) => removed.push("The old key is removed from the keychain".to_string()),
+        Err(keyring::Error::NoEntry) => {}
+        Err(err) => eprintln!("Warning: keychain was not able to be updated, {err}"),
+    }
+    Ok(removed)
+}
+
+fn remove_old_key_from_legacy_keychain(
+    credentials_home_dir: &std::path::Path,
+    network_name: &str,
+    account_id: &unc_primitives::types::AccountId,
+    old_public_key: &unc_crypto::PublicKey,
+    new_public_key: &unc_crypto::PublicKey,
+) -> color_eyre::eyre::Result<Vec<String>> {
+    let mut removed = vec![];
+    let network_dir = credentials_home_dir.join(network_name);
+    let key_file = network_dir.join(account_id.as_str()).join(format!(
+        "{}.json",
+        old_public_key.to_string().replace(':', "_")
+    ));
+    if key_file.is_file() {
+        std::fs::remove_file(&key_file)
+            .wrap_err_with(|| format!("Failed to remove file: {key_file:?}"))?;
+        removed.push(format!(
+            "The old key file {} is removed",
+            key_file.display()
+        ));
+    }
+
+    // The account file holds the default key of the account for the JS CLI:
+    // the new key takes its place if it is in the legacy keychain.
+    let account_file = network_dir.join(format!("{account_id}.json"));
+    let account_file_has_old_key = std::fs::read_to_string(&account_file)
+        .ok()
+        .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        .is_some_and(|data| data["public_key"] == old_public_key.to_string());
+    if account_file_has_old_key {
+        let new_key_file = network_dir.join(account_id.as_str()).join(format!(
+            "{}.json",
+            new_public_key.to_string().replace(':', "_")
+        ));
+        if new_key_file.is_file() {
+            std::fs::copy(&new_key_file, &account_file)
+                .wrap_err_with(|| format!("Failed to write to file: {account_file:?}"))?;
+            removed.push(format!(
+                "The account file {} now holds the new key",
+                account_file.display()
+            ));
+        } else {
+            std::fs::remove_file(&account_file)
+                .wrap_err_with(|| format!("Failed to remove file: {account_file:?}"))?;
+            removed.push(format!(
+                "The account file {} is removed",
+                account_file.display()
+            ));
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_old_key_from_legacy_keychain_files() {
+        let credentials_home_dir =
+            std::env::temp_dir().join(format!("unc-finish-key-rotation-{}", std::process::id()));
+        let account_id: unc_primitives::types::AccountId = "alice.unc".parse().unwrap();
+        let old_public_key: unc_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        let new_public_key: unc_crypto::PublicKey =
+            "ed25519:GyvuXfWDsvcXRo2j7g6XjnD8oHPawWFpVwdQp2xTLNy2"
+                .parse()
+                .unwrap();
+        let new_key_data = format!(r#"{{"public_key":"{new_public_key}"}}"#);
+        let account_dir = credentials_home_dir.join("testnet").join("alice.unc");
+        std::fs::create_dir_all(&account_dir).unwrap();
+        let old_key_data = format!(r#"{{"public_key":"{old_public_key}"}}"#);
+        std::fs::write(
+            account_dir.join("ed25519_6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp.json"),
+            &old_key_data,
+        )
+        .unwrap();
+        std::fs::write(
+            account_dir.join("ed25519_GyvuXfWDsvcXRo2j7g6XjnD8oHPawWFpVwdQp2xTLNy2.json"),
+            &new_key_data,
+        )
+        .unwrap();
+        // Another key of the account, e.g. a function-call key, does not replace the old key.
+        std::fs::write(
+            account_dir.join("ed25519_2aGBDgvJSd3CrAnWRxhkqjoKxD1fBNBnbcHzLS4ZtgMR.json"),
+            r#"{"public_key":"ed25519:2aGBDgvJSd3CrAnWRxhkqjoKxD1fBNBnbcHzLS4ZtgMR"}"#,
+        )
+        .unwrap();
+        let account_file = credentials_home_dir.join("testnet").join("alice.unc.json");
+        std::fs::write(&account_file, &old_key_data).unwrap();
+
+        let removed = remove_old_key_from_legacy_keychain(
+            &credentials_home_dir,
+            "testnet",
+            &account_id,
+            &old_public_key,
+            &new_public_key,
+        )
+        .unwrap();
+        let remaining_files = std::fs::read_dir(&account_dir).unwrap().count();
+        let account_file_data = std::fs::read_to_string(&account_file).unwrap();
+        std::fs::remove_dir_all(&credentials_home_dir).unwrap();
+
+        assert_eq!(removed.len(), 2);
+        assert_eq!(remaining_files, 2);
+        assert_eq!(account_file_data, new_key_data);
+    }
+}
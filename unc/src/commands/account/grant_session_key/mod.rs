@@ -0,0 +1,363 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = GrantSessionKeyContext)]
+pub struct GrantSessionKey {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account do you want to grant a session key for?
+    owner_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which contract (dapp) should the session key be able to call?
+    receiver_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which session key preset do you want to use?
+    preset: String,
+    #[interactive_clap(long)]
+    /// Save the key pair to the legacy keychain (compatible with JS CLI) instead of the keychain
+    save_to_legacy_keychain: bool,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+/// A session key granted from this machine, kept to show its details in `account list-keys`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(super) struct SessionKey {
+    pub network_name: String,
+    pub account_id: unc_primitives::types::AccountId,
+    pub public_key: unc_crypto::PublicKey,
+    pub preset: String,
+    pub receiver_id: unc_primitives::types::AccountId,
+    pub method_names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowance: Option<crate::types::unc_token::UncToken>,
+    /// Unix time (in seconds) when the key was granted.
+    pub granted_at: u64,
+    /// Unix time (in seconds) when the session ends, according to the preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GrantSessionKeyContext {
+    global_context: crate::GlobalContext,
+    owner_account_id: unc_primitives::types::AccountId,
+    receiver_account_id: unc_primitives::types::AccountId,
+    preset_name: String,
+    preset: crate::config::SessionKeyPreset,
+    key_pair_properties: crate::common::KeyPairProperties,
+    public_key: unc_crypto::PublicKey,
+    save_to_legacy_keychain: bool,
+}
+
+impl GrantSessionKeyContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<GrantSessionKey as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id = scope
+            .owner_account_id
+            .resolve(&previous_context.config, None)?;
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.config, None)?;
+        let preset = previous_context
+            .config
+            .session_key_presets
+            .get(&scope.preset)
+            .cloned()
+            .wrap_err_with(|| {
+                format!(
+                    "There is no session key preset \"{}\" in the config (add it with `config add-session-key-preset`)",
+                    scope.preset
+                )
+            })?;
+        let key_pair_properties = crate::common::generate_ed25519_keypair()?;
+        let public_key = unc_crypto::PublicKey::from_str(&key_pair_properties.public_key_str)?;
+        Ok(Self {
+            global_context: previous_context,
+            owner_account_id,
+            receiver_account_id,
+            preset_name: scope.preset.clone(),
+            preset,
+            key_pair_properties,
+            public_key,
+            save_to_legacy_keychain: scope.save_to_legacy_keychain,
+        })
+    }
+}
+
+impl From<GrantSessionKeyContext> for crate::commands::ActionContext {
+    fn from(item: GrantSessionKeyContext) -> Self {
+        let global_context = item.global_context.clone();
+        let interacting_with_account_ids = vec![
+            item.owner_account_id.clone(),
+            item.receiver_account_id.clone(),
+        ];
+        // The method names are resolved from the ABI once the network is known,
+        // and kept for the record of the session key.
+        let method_names = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+
+        let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let owner_account_id = item.owner_account_id.clone();
+                let receiver_account_id = item.receiver_account_id.clone();
+                let preset = item.preset.clone();
+                let public_key = item.public_key.clone();
+                let method_names = method_names.clone();
+
+                move |network_config| {
+                    let resolved_method_names = if preset.method_names.is_empty() {
+                        let abi_root =
+                            crate::commands::contract::call_function::abi::get_contract_abi(
+                                network_config,
+                                &receiver_account_id,
+                            )
+                            .wrap_err("The preset has no method names, so they are taken from the contract ABI")?;
+                        let abi_method_names = preset.abi_method_names(&abi_root);
+                        if abi_method_names.is_empty() {
+                            color_eyre::eyre::bail!(
+                                "The contract <{receiver_account_id}> has no non-payable call methods matching the preset"
+                            );
+                        }
+                        abi_method_names
+                    } else {
+                        preset.method_names.clone()
+                    };
+                    eprintln!(
+                        "The session key will be able to call on <{receiver_account_id}>: {}",
+                        resolved_method_names.join(", ")
+                    );
+                    method_names
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .clone_from(&resolved_method_names);
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: owner_account_id.clone(),
+                        receiver_id: owner_account_id.clone(),
+                        actions: vec![unc_primitives::transaction::Action::AddKey(Box::new(
+                            unc_primitives::transaction::AddKeyAction {
+                                public_key: public_key.clone(),
+                                access_key: unc_primitives::account::AccessKey {
+                                    nonce: 0,
+                                    permission:
+                                        unc_primitives::account::AccessKeyPermission::FunctionCall(
+                                            unc_primitives::account::FunctionCallPermission {
+                                                allowance: preset
+                                                    .allowance
+                                                    .map(|allowance| allowance.as_attounc()),
+                                                receiver_id: receiver_account_id.to_string(),
+                                                method_names: resolved_method_names,
+                                            },
+                                        ),
+                                },
+                            },
+                        ))],
+                    })
+                }
+            });
+
+        let on_before_sending_transaction_callback: crate::transaction_signature_options::OnBeforeSendingTransactionCallback =
+            std::sync::Arc::new({
+                let credentials_home_dir = item.global_context.config.credentials_home_dir.clone();
+
+                move |_signed_transaction, network_config, storage_message| {
+                    let account_id = item.owner_account_id.as_str();
+                    let public_key_str = item.public_key.to_string();
+                    let key_pair = serde_json::to_string(&item.key_pair_properties)?;
+                    *storage_message = if item.save_to_legacy_keychain {
+                        crate::common::save_access_key_to_legacy_keychain(
+                            network_config.clone(),
+                            credentials_home_dir.clone(),
+                            &key_pair,
+                            &public_key_str,
+                            account_id,
+                        )
+                        .wrap_err_with(|| {
+                            format!("Failed to save a file with access key: {public_key_str}")
+                        })?
+                    } else {
+                        crate::common::save_access_key_to_keychain(
+                            network_config.clone(),
+                            &key_pair,
+                            &public_key_str,
+                            account_id,
+                        )?
+                    };
+
+                    let granted_at = crate::common::unix_time_now();
+                    save_session_key(
+                        &credentials_home_dir,
+                        SessionKey {
+                            network_name: network_config.network_name.clone(),
+                            account_id: item.owner_account_id.clone(),
+                            public_key: item.public_key.clone(),
+                            preset: item.preset_name.clone(),
+                            receiver_id: item.receiver_account_id.clone(),
+                            method_names: method_names
+                                .lock()
+                                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                                .clone(),
+                            allowance: item.preset.allowance,
+                            granted_at,
+                            expires_at: item
+                                .preset
+                                .expires_in_hours
+                                .map(|hours| granted_at + hours * 3600),
+                        },
+                    )?;
+                    Ok(())
+                }
+            });
+
+        Self {
+            global_context,
+            interacting_with_account_ids,
+            on_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback,
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                |_outcome_view, _network_config| Ok(()),
+            ),
+        }
+    }
+}
+
+impl GrantSessionKey {
+    pub fn input_owner_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account do you want to grant a session key for?",
+        )
+    }
+
+    pub fn input_receiver_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which contract (dapp) should the session key be able to call?",
+        )
+    }
+
+    pub fn input_preset(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<String>> {
+        let presets = context
+            .config
+            .session_key_presets
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        if presets.is_empty() {
+            color_eyre::eyre::bail!(
+                "There are no session key presets in the config (add one with `config add-session-key-preset`)"
+            );
+        }
+        Ok(Some(
+            inquire::Select::new("Which session key preset do you want to use?", presets)
+                .prompt()?,
+        ))
+    }
+}
+
+fn get_session_keys_path(credentials_home_dir: &std::path::Path) -> std::path::PathBuf {
+    credentials_home_dir.join("session-keys.json")
+}
+
+pub(super) fn get_session_keys(credentials_home_dir: &std::path::Path) -> Vec<SessionKey> {
+    std::fs::read_to_string(get_session_keys_path(credentials_home_dir))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_session_key(
+    credentials_home_dir: &std::path::Path,
+    session_key: SessionKey,
+) -> crate::CliResult {
+    let mut session_keys = get_session_keys(credentials_home_dir);
+    session_keys.retain(|known| {
+        (&known.network_name, &known.account_id, &known.public_key)
+            != (
+                &session_key.network_name,
+                &session_key.account_id,
+                &session_key.public_key,
+            )
+    });
+    session_keys.push(session_key);
+    std::fs::create_dir_all(credentials_home_dir)
+        .wrap_err_with(|| format!("Failed to create directory: {credentials_home_dir:?}"))?;
+    let session_keys_path = get_session_keys_path(credentials_home_dir);
+    std::fs::write(
+        &session_keys_path,
+        serde_json::to_string_pretty(&session_keys)?,
+    )
+    .wrap_err_with(|| format!("Failed to write to file: {session_keys_path:?}"))
+}
+
+/// Prints the details of the session keys among the access keys of the account.
+pub(super) fn display_session_keys(
+    credentials_home_dir: &std::path::Path,
+    network_name: &str,
+    account_id: &unc_primitives::types::AccountId,
+    access_keys: &[unc_primitives::views::AccessKeyInfoView],
+) {
+    let now = crate::common::unix_time_now();
+    let mut table = prettytable::Table::new();
+    table.set_titles(
+        prettytable::row![Fg=>"Session key", "Preset", "Allowance left", "Granted", "Session ends"],
+    );
+    for session_key in get_session_keys(credentials_home_dir) {
+        if session_key.network_name != network_name || &session_key.account_id != account_id {
+            continue;
+        }
+        let Some(access_key) = access_keys
+            .iter()
+            .find(|access_key| access_key.public_key == session_key.public_key)
+        else {
+            continue;
+        };
+        let allowance_left = match &access_key.access_key.permission {
+            unc_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance: Some(allowance),
+                ..
+            } => match session_key.allowance {
+                Some(initial_allowance) => format!(
+                    "{} of {}",
+                    crate::types::unc_token::UncToken::from_attounc(*allowance),
+                    initial_allowance
+                ),
+                None => crate::types::unc_token::UncToken::from_attounc(*allowance).to_string(),
+            },
+            _ => "not limited".to_string(),
+        };
+        let session_ends = match session_key.expires_at {
+            Some(expires_at) if expires_at <= now => {
+                format!("ended {} h ago, delete the key", (now - expires_at) / 3600)
+            }
+            Some(expires_at) => format!("in {} h", (expires_at - now).div_ceil(3600)),
+            None => "-".to_string(),
+        };
+        table.add_row(prettytable::row![
+            Fg->session_key.public_key,
+            session_key.preset,
+            allowance_left,
+            format!("{} h ago", now.saturating_sub(session_key.granted_at) / 3600),
+            session_ends,
+        ]);
+    }
+    if !table.is_empty() {
+        eprintln!("\nSession keys granted from this machine:");
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.printstd();
+    }
+}
@@ -44,12 +44,20 @@ pub fn login(
     key_pair_properties_buf: &str,
     public_key_str: &str,
     error_message: &str,
+    offline: bool,
 ) -> crate::CliResult {
     let public_key: unc_crypto::PublicKey = unc_crypto::PublicKey::from_str(public_key_str)?;
 
     let account_id = loop {
         let account_id_from_cli = input_account_id()?;
         eprintln!();
+        if offline {
+            eprintln!(
+                "Offline mode: the access key of <{account_id_from_cli}> is saved without checking it on network <{}>.",
+                network_config.network_name
+            );
+            break account_id_from_cli;
+        }
         if crate::common::verify_account_access_key(
             account_id_from_cli.clone(),
             public_key.clone(),
@@ -31,6 +31,7 @@ impl LoginFromSeedPhraseContext {
         let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
                 let config = previous_context.config.clone();
+                let offline = previous_context.offline;
 
                 move |network_config| {
                     super::login(
@@ -40,7 +41,8 @@ impl LoginFromSeedPhraseContext {
                         &key_pair_properties.public_key_str,
                         &format!("\nIt is currently not possible to verify the account access key on network <{}>.\nYou may have entered an incorrect account_id.\nYou have the option to reconfirm your account or save your access key information.\n",
                             network_config.network_name
-                        )
+                        ),
+                        offline
                     )
                 }
             });
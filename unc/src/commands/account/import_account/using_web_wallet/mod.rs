@@ -18,6 +18,7 @@ impl LoginFromWebWalletContext {
         let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
                 let config = previous_context.config.clone();
+                let offline = previous_context.offline;
 
                 move |network_config| {
                     let key_pair_properties: crate::common::KeyPairProperties =
@@ -43,6 +44,7 @@ impl LoginFromWebWalletContext {
                         &key_pair_properties_buf,
                         &key_pair_properties.public_key_str,
                         &error_message,
+                        offline,
                     )
                 }
             });
@@ -0,0 +1,104 @@
+use prettytable::Table;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ListAccountsContext)]
+pub struct ListAccounts;
+
+#[derive(Debug, Clone)]
+pub struct ListAccountsContext;
+
+impl ListAccountsContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        _scope: &<ListAccounts as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let credentials_home_dir = &previous_context.config.credentials_home_dir;
+        let address_book = crate::common::get_address_book(credentials_home_dir)?;
+        let mut used_account_list: Vec<crate::common::UsedAccount> =
+            crate::common::get_used_account_list(credentials_home_dir).into();
+        // The accounts known only from the address book are listed too.
+        for account_id in address_book
+            .aliases
+            .values()
+            .flat_map(|entry| entry.account_ids())
+        {
+            if !used_account_list
+                .iter()
+                .any(|used_account| &used_account.account_id == account_id)
+            {
+                used_account_list.push(crate::common::UsedAccount {
+                    account_id: account_id.clone(),
+                    used_as_signer: false,
+                    last_used_at: None,
+                });
+            }
+        }
+        if used_account_list.is_empty() {
+            eprintln!("You have not used any accounts yet.");
+            return Ok(Self);
+        }
+
+        let now = crate::common::unix_time_now();
+        let mut table = Table::new();
+        table.set_titles(
+            prettytable::row![Fg=>"Account", "Aliases", "Networks", "Signer", "Last used"],
+        );
+        for used_account in used_account_list {
+            let networks = account_networks(
+                credentials_home_dir,
+                &address_book,
+                &used_account.account_id,
+            );
+            table.add_row(prettytable::row![
+                Fg->used_account.account_id,
+                address_book.aliases_of(&used_account.account_id).join("\n"),
+                networks.into_iter().collect::<Vec<_>>().join(", "),
+                if used_account.used_as_signer { "yes" } else { "no" },
+                used_account
+                    .last_used_at
+                    .map_or_else(|| "-".to_string(), |last_used_at| format_time_ago(now.saturating_sub(last_used_at))),
+            ]);
+        }
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.printstd();
+        Ok(Self)
+    }
+}
+
+/// The networks the account is known on: the legacy keychain and the address book mappings.
+fn account_networks(
+    credentials_home_dir: &std::path::Path,
+    address_book: &crate::common::AddressBook,
+    account_id: &unc_primitives::types::AccountId,
+) -> std::collections::BTreeSet<String> {
+    let mut networks: std::collections::BTreeSet<String> = std::fs::read_dir(credentials_home_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.path().join(account_id.as_str()).is_dir()
+                || entry.path().join(format!("{account_id}.json")).is_file()
+        })
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    for entry in address_book.aliases.values() {
+        networks.extend(
+            entry
+                .networks
+                .iter()
+                .filter(|(_, network_account_id)| *network_account_id == account_id)
+                .map(|(network_name, _)| network_name.clone()),
+        );
+    }
+    networks
+}
+
+fn format_time_ago(seconds: u64) -> String {
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} min ago", seconds / 60),
+        3600..=86399 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} days ago", seconds / 86400),
+    }
+}
@@ -23,8 +23,10 @@ impl ViewListKeysContext {
         previous_context: crate::GlobalContext,
         scope: &<ViewListKeys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
-            let account_id: unc_primitives::types::AccountId = scope.account_id.clone().into();
+            let account_id = account_id.clone();
+            let credentials_home_dir = previous_context.config.credentials_home_dir.clone();
 
             move |network_config, block_reference| {
                 let access_key_list = network_config
@@ -42,13 +44,19 @@ impl ViewListKeysContext {
                     .access_key_list_view()?;
 
                 crate::common::display_access_key_list(&access_key_list.keys);
+                super::grant_session_key::display_session_keys(
+                    &credentials_home_dir,
+                    &network_config.network_name,
+                    &account_id,
+                    &access_key_list.keys,
+                );
                 Ok(())
             }
         });
 
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
             config: previous_context.config,
-            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            interacting_with_account_ids: vec![account_id],
             on_after_getting_block_reference_callback,
         }))
     }
@@ -2,14 +2,23 @@ use inquire::Select;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod add_key;
+mod address_book;
+mod audit_keys;
+mod backup;
 pub mod create_account;
 mod delete_account;
 mod delete_key;
 mod export_account;
+mod finish_key_rotation;
+mod grant_session_key;
 mod import_account;
+mod list_accounts;
 mod list_keys;
+mod restore;
+mod rotate_key;
 pub mod storage_management;
 mod view_account_summary;
+mod watch;
 
 pub const MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LENGTH: usize = 2;
 
@@ -31,6 +40,21 @@ pub enum AccountActions {
     ))]
     /// View properties for an account
     ViewAccountSummary(self::view_account_summary::ViewAccountSummary),
+    #[strum_discriminants(strum(
+        message = "watch                   - Watch accounts for balance, storage, code and key changes"
+    ))]
+    /// Watch accounts for balance, storage, code and key changes
+    Watch(self::watch::Watch),
+    #[strum_discriminants(strum(
+        message = "list                    - View the accounts you have used with their aliases"
+    ))]
+    /// View the accounts you have used with their aliases
+    List(self::list_accounts::ListAccounts),
+    #[strum_discriminants(strum(
+        message = "address-book            - Manage the aliases of accounts (use them as @alias)"
+    ))]
+    /// Manage the aliases of accounts (use them as @alias)
+    AddressBook(self::address_book::AddressBookCommands),
     #[strum_discriminants(strum(
         message = "import-account          - Import existing account (a.k.a. \"sign in\")"
     ))]
@@ -39,6 +63,16 @@ pub enum AccountActions {
     #[strum_discriminants(strum(message = "export-account          - Export existing account"))]
     /// Export existing account
     ExportAccount(self::export_account::ExportAccount),
+    #[strum_discriminants(strum(
+        message = "backup                  - Save all the accounts, keys and config to an encrypted file"
+    ))]
+    /// Save all the accounts, keys and config to an encrypted file
+    Backup(self::backup::BackupCommand),
+    #[strum_discriminants(strum(
+        message = "restore                 - Restore the accounts, keys and config from a backup file"
+    ))]
+    /// Restore the accounts, keys and config from a backup file
+    Restore(self::restore::RestoreCommand),
     #[strum_discriminants(strum(message = "create-account          - Create a new account"))]
     /// Create a new account
     CreateAccount(self::create_account::CreateAccount),
@@ -55,11 +89,31 @@ pub enum AccountActions {
     ))]
     /// Add an access key to an account
     AddKey(self::add_key::AddKeyCommand),
+    #[strum_discriminants(strum(
+        message = "grant-session-key       - Add a function-call access key for a dapp from a preset"
+    ))]
+    /// Add a function-call access key for a dapp from a preset
+    GrantSessionKey(self::grant_session_key::GrantSessionKey),
     #[strum_discriminants(strum(
         message = "delete-keys             - Delete access keys from an account"
     ))]
     /// Delete access keys from an account
     DeleteKeys(self::delete_key::DeleteKeysCommand),
+    #[strum_discriminants(strum(
+        message = "audit-keys              - Audit the access keys of all your accounts and find unknown ones"
+    ))]
+    /// Audit the access keys of all your accounts and find unknown ones
+    AuditKeys(self::audit_keys::AuditKeys),
+    #[strum_discriminants(strum(
+        message = "rotate-key              - Replace an access key with a new one in one transaction"
+    ))]
+    /// Replace an access key with a new one in one transaction
+    RotateKey(self::rotate_key::RotateKeyCommand),
+    #[strum_discriminants(strum(
+        message = "finish-key-rotation     - Verify a key rotation sent elsewhere on chain and remove the old key locally"
+    ))]
+    /// Verify a key rotation sent elsewhere on chain and remove the old key locally
+    FinishKeyRotation(self::finish_key_rotation::FinishKeyRotation),
     #[strum_discriminants(strum(
         message = "manage-storage-deposit  - Storage management: deposit, withdrawal, balance review"
     ))]
@@ -0,0 +1,472 @@
+use color_eyre::eyre::WrapErr;
+use colored::Colorize;
+use inquire::Select;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+use super::backup::{Backup, BackupFile, BACKUP_FORMAT};
+
+const USED_ACCOUNT_LIST_FILE: &str = "accounts.json";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RestoreCommandContext)]
+pub struct RestoreCommand {
+    /// What is the backup file to restore?
+    file_path: crate::types::path_buf::PathBuf,
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Where do you want to restore the access keys?
+    keys_to: KeysTarget,
+    /// Overwrite the files and keychain entries that differ from the backup
+    #[interactive_clap(long)]
+    overwrite: bool,
+}
+
+impl RestoreCommand {
+    fn input_keys_to(
+        _context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<KeysTarget>> {
+        let variants = KeysTargetDiscriminants::iter().collect::<Vec<_>>();
+        let selected =
+            Select::new("Where do you want to restore the access keys?", variants).prompt()?;
+        match selected {
+            KeysTargetDiscriminants::AsInBackup => Ok(Some(KeysTarget::AsInBackup)),
+            KeysTargetDiscriminants::Keychain => Ok(Some(KeysTarget::Keychain)),
+            KeysTargetDiscriminants::LegacyKeychain => Ok(Some(KeysTarget::LegacyKeychain)),
+        }
+    }
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// Where do you want to restore the access keys?
+pub enum KeysTarget {
+    #[strum_discriminants(strum(
+        message = "as-in-backup     - Restore every key where it was stored"
+    ))]
+    AsInBackup,
+    #[strum_discriminants(strum(
+        message = "keychain         - Restore all the keys to the keychain"
+    ))]
+    Keychain,
+    #[strum_discriminants(strum(
+        message = "legacy-keychain  - Restore all the keys to the legacy keychain (compatible with JS CLI)"
+    ))]
+    LegacyKeychain,
+}
+
+impl interactive_clap::ToCli for KeysTarget {
+    type CliVariant = KeysTarget;
+}
+
+impl std::str::FromStr for KeysTarget {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "as-in-backup" => Ok(Self::AsInBackup),
+            "keychain" => Ok(Self::Keychain),
+            "legacy-keychain" => Ok(Self::LegacyKeychain),
+            _ => Err("KeysTarget: incorrect value entered".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for KeysTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::AsInBackup => write!(f, "as-in-backup"),
+            Self::Keychain => write!(f, "keychain"),
+            Self::LegacyKeychain => write!(f, "legacy-keychain"),
+        }
+    }
+}
+
+impl std::fmt::Display for KeysTargetDiscriminants {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::AsInBackup => write!(f, "as-in-backup     - Restore every key where it was stored"),
+            Self::Keychain => write!(f, "keychain         - Restore all the keys to the keychain"),
+            Self::LegacyKeychain => write!(
+                f,
+                "legacy-keychain  - Restore all the keys to the legacy keychain (compatible with JS CLI)"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreCommandContext;
+
+impl RestoreCommandContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RestoreCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let file_path = &scope.file_path.0;
+        let backup_file: BackupFile = serde_json::from_str(
+            &std::fs::read_to_string(file_path)
+                .wrap_err_with(|| format!("Failed to read the backup file: {file_path:?}"))?,
+        )
+        .wrap_err_with(|| format!("Failed to parse the backup file: {file_path:?}"))?;
+        if backup_file.format != BACKUP_FORMAT {
+            color_eyre::eyre::bail!(
+                "The file {} is not a backup of unc CLI",
+                file_path.display()
+            );
+        }
+        let password =
+            crate::util::encryption::input_password("Enter the password of the backup:")?;
+        let backup: Backup =
+            serde_json::from_slice(&backup_file.encrypted_backup.decrypt(&password)?)
+                .wrap_err("Failed to parse the decrypted backup")?;
+
+        let credentials_home_dir = &previous_context.config.credentials_home_dir;
+        // The credentials directory of this machine is kept.
+        let config = backup.config_toml.as_deref().and_then(|config_toml| {
+            match toml::from_str::<crate::config::Config>(config_toml) {
+                Ok(config) => Some(crate::config::Config {
+                    credentials_home_dir: credentials_home_dir.clone(),
+                    ..config
+                }),
+                Err(err) => {
+                    eprintln!("Warning: the config of the backup is not restored, it could not be parsed: {err}");
+                    None
+                }
+            }
+        });
+        let items = plan_restore(&backup, scope.keys_to, credentials_home_dir)?;
+        let mut restored = 0;
+        let mut unchanged = 0;
+        let mut conflicts = vec![];
+        for item in items {
+            match item.destination.read()? {
+                Some(existing) if existing == item.content || item.only_if_missing => {
+                    unchanged += 1;
+                    continue;
+                }
+                Some(_) => {
+                    conflicts.push(item.destination.to_string());
+                    if !scope.overwrite {
+                        continue;
+                    }
+                }
+                None => {}
+            }
+            item.destination.write(&item.content)?;
+            restored += 1;
+        }
+
+        let added_accounts = merge_used_account_list(&backup, credentials_home_dir)?;
+        if let Some(config) = config {
+            if !restore_config_toml(config, scope.overwrite)? {
+                conflicts.push("config.toml".to_string());
+            }
+        }
+
+        eprintln!(
+            "Restored: {restored}, already up to date: {unchanged}, accounts added to the list of used accounts: {added_accounts}"
+        );
+        for conflict in &conflicts {
+            let resolution = if scope.overwrite {
+                "overwritten"
+            } else {
+                "kept"
+            };
+            eprintln!(
+                " {} {conflict} differs from the backup ({resolution})",
+                "!".red().bold()
+            );
+        }
+        if !conflicts.is_empty() && !scope.overwrite {
+            eprintln!("Re-run with `--overwrite` to replace them with the backup.");
+        }
+        crate::util::print_success(&format!("The backup {} is restored", file_path.display()));
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Destination {
+    File(std::path::PathBuf),
+    Keychain { service_name: String, user: String },
+}
+
+impl Destination {
+    fn keychain(
+        network_name: &str,
+        account_id: &unc_primitives::types::AccountId,
+        public_key: &str,
+    ) -> Self {
+        Self::Keychain {
+            service_name: format!("unc-{network_name}-{account_id}"),
+            user: format!("{account_id}:{public_key}"),
+        }
+    }
+
+    fn read(&self) -> color_eyre::eyre::Result<Option<String>> {
+        match self {
+            Self::File(path) if path.exists() => std::fs::read_to_string(path)
+                .map(Some)
+                .wrap_err_with(|| format!("Failed to read file: {path:?}")),
+            Self::File(_) => Ok(None),
+            Self::Keychain { service_name, user } => {
+                match keyring::Entry::new(service_name, user).and_then(|entry| entry.get_password())
+                {
+                    Ok(password) => Ok(Some(password)),
+                    Err(keyring::Error::NoEntry) => Ok(None),
+                    Err(err) => Err(err).wrap_err("Failed to read from keychain"),
+                }
+            }
+        }
+    }
+
+    fn write(&self, content: &str) -> crate::CliResult {
+        match self {
+            Self::File(path) => {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)
+                        .wrap_err_with(|| format!("Failed to create directory: {dir:?}"))?;
+                }
+                std::fs::write(path, content)
+                    .wrap_err_with(|| format!("Failed to write to file: {path:?}"))
+            }
+            Self::Keychain { service_name, user } => keyring::Entry::new(service_name, user)
+                .wrap_err("Failed to open keychain")?
+                .set_password(content)
+                .wrap_err("Failed to save password to keychain"),
+        }
+    }
+}
+
+impl std::fmt::Display for Destination {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Keychain { service_name, user } => {
+                write!(f, "the keychain entry {user} of {service_name}")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RestoreItem {
+    destination: Destination,
+    content: String,
+    /// The account file of the legacy keychain is only created when the account has none.
+    only_if_missing: bool,
+}
+
+/// Decides where every file and keychain entry of the backup goes.
+fn plan_restore(
+    backup: &Backup,
+    keys_to: KeysTarget,
+    credentials_home_dir: &std::path::Path,
+) -> color_eyre::eyre::Result<Vec<RestoreItem>> {
+    let mut items: Vec<RestoreItem> = vec![];
+    let mut push = |item: RestoreItem| {
+        if !items
+            .iter()
+            .any(|known| known.destination == item.destination)
+        {
+            items.push(item);
+        }
+    };
+
+    for (path, content) in &backup.files {
+        let components: Vec<&str> = path.split('/').collect();
+        if components
+            .iter()
+            .any(|component| component.is_empty() || *component == "." || *component == "..")
+        {
+            color_eyre::eyre::bail!("The backup has an invalid file path: {path}");
+        }
+        if path == USED_ACCOUNT_LIST_FILE {
+            continue;
+        }
+        if keys_to == KeysTarget::Keychain {
+            if let Some((network_name, account_id, public_key)) =
+                legacy_key_file(&components, content)
+            {
+                push(RestoreItem {
+                    destination: Destination::keychain(&network_name, &account_id, &public_key),
+                    content: content.clone(),
+                    only_if_missing: false,
+                });
+                continue;
+            }
+        }
+        push(RestoreItem {
+            destination: Destination::File(credentials_home_dir.join(path)),
+            content: content.clone(),
+            only_if_missing: false,
+        });
+    }
+
+    for entry in &backup.keychain {
+        let public_key = entry.public_key.to_string();
+        if keys_to == KeysTarget::LegacyKeychain {
+            let network_dir = credentials_home_dir.join(&entry.network_name);
+            push(RestoreItem {
+                destination: Destination::File(
+                    network_dir
+                        .join(entry.account_id.as_str())
+                        .join(format!("{}.json", public_key.replace(':', "_"))),
+                ),
+                content: entry.key_pair.clone(),
+                only_if_missing: false,
+            });
+            push(RestoreItem {
+                destination: Destination::File(
+                    network_dir.join(format!("{}.json", entry.account_id)),
+                ),
+                content: entry.key_pair.clone(),
+                only_if_missing: true,
+            });
+        } else {
+            push(RestoreItem {
+                destination: Destination::keychain(
+                    &entry.network_name,
+                    &entry.account_id,
+                    &public_key,
+                ),
+                content: entry.key_pair.clone(),
+                only_if_missing: false,
+            });
+        }
+    }
+    Ok(items)
+}
+
+/// A key file (`<network>/<account>/<key>.json`) or an account file (`<network>/<account>.json`) of the legacy keychain.
+fn legacy_key_file(
+    components: &[&str],
+    content: &str,
+) -> Option<(String, unc_primitives::types::AccountId, String)> {
+    let (network_name, account_id) = match components {
+        [network_name, account_id, file_name] if file_name.ends_with(".json") => {
+            (network_name, account_id.parse().ok()?)
+        }
+        [network_name, file_name] => (network_name, file_name.strip_suffix(".json")?.parse().ok()?),
+        _ => return None,
+    };
+    let public_key = serde_json::from_str::<serde_json::Value>(content)
+        .ok()?
+        .get("public_key")?
+        .as_str()?
+        .to_string();
+    Some((network_name.to_string(), account_id, public_key))
+}
+
+/// Adds the accounts of the backup to the list of used accounts, returning how many were added.
+fn merge_used_account_list(
+    backup: &Backup,
+    credentials_home_dir: &std::path::Path,
+) -> color_eyre::eyre::Result<usize> {
+    let Some(backup_list) = backup.files.get(USED_ACCOUNT_LIST_FILE) else {
+        return Ok(0);
+    };
+    let backup_list: Vec<crate::common::UsedAccount> = serde_json::from_str(backup_list)
+        .wrap_err("Failed to parse the list of used accounts of the backup")?;
+    let mut used_account_list = crate::common::get_used_account_list(credentials_home_dir);
+    let mut added = 0;
+    for backup_account in backup_list {
+        match used_account_list
+            .iter_mut()
+            .find(|used_account| used_account.account_id == backup_account.account_id)
+        {
+            Some(used_account) => used_account.used_as_signer |= backup_account.used_as_signer,
+            None => {
+                used_account_list.push_back(backup_account);
+                added += 1;
+            }
+        }
+    }
+    std::fs::create_dir_all(credentials_home_dir)
+        .wrap_err_with(|| format!("Failed to create directory: {credentials_home_dir:?}"))?;
+    let used_account_list_path = credentials_home_dir.join(USED_ACCOUNT_LIST_FILE);
+    std::fs::write(
+        &used_account_list_path,
+        serde_json::to_string(&used_account_list)?,
+    )
+    .wrap_err_with(|| format!("Failed to write to file: {used_account_list_path:?}"))?;
+    Ok(added)
+}
+
+/// Restores the config unless this machine has its own changes in it (returns `false` then).
+fn restore_config_toml(
+    config: crate::config::Config,
+    overwrite: bool,
+) -> color_eyre::eyre::Result<bool> {
+    let restored_config_toml = toml::to_string(&config)?;
+    let path_config_toml = super::backup::config_toml_path()?;
+    let existing_config_toml = std::fs::read_to_string(path_config_toml).ok();
+    if existing_config_toml.as_ref() == Some(&restored_config_toml) {
+        return Ok(true);
+    }
+    let is_default = existing_config_toml.is_none()
+        || existing_config_toml == Some(toml::to_string(&crate::config::Config::default())?);
+    if is_default || overwrite {
+        crate::common::write_config_toml(config)?;
+    }
+    Ok(is_default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_restore_to_each_keychain() {
+        let key_pair = r#"{"public_key":"ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"}"#;
+        let backup = Backup {
+            config_toml: None,
+            files: [
+                (
+                    "testnet/alice.unc/ed25519_6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp.json",
+                    key_pair,
+                ),
+                ("testnet/alice.unc.json", key_pair),
+                ("accounts.json", "[]"),
+            ]
+            .into_iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect(),
+            keychain: vec![super::super::backup::KeychainEntry {
+                network_name: "mainnet".to_string(),
+                account_id: "bob.unc".parse().unwrap(),
+                public_key: "ed25519:GyvuXfWDsvcXRo2j7g6XjnD8oHPawWFpVwdQp2xTLNy2"
+                    .parse()
+                    .unwrap(),
+                key_pair: "{}".to_string(),
+            }],
+        };
+        let home = std::path::Path::new("/home/unc/.unc-credentials");
+
+        let to_keychain = plan_restore(&backup, KeysTarget::Keychain, home).unwrap();
+        assert_eq!(
+            to_keychain
+                .iter()
+                .map(|item| item.destination.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "the keychain entry alice.unc:ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp of unc-testnet-alice.unc",
+                "the keychain entry bob.unc:ed25519:GyvuXfWDsvcXRo2j7g6XjnD8oHPawWFpVwdQp2xTLNy2 of unc-mainnet-bob.unc",
+            ]
+        );
+
+        let to_legacy_keychain = plan_restore(&backup, KeysTarget::LegacyKeychain, home).unwrap();
+        assert_eq!(to_legacy_keychain.len(), 4);
+        assert_eq!(
+            to_legacy_keychain[3].destination,
+            Destination::File(home.join("mainnet/bob.unc.json"))
+        );
+        assert!(to_legacy_keychain[3].only_if_missing);
+
+        let invalid = Backup {
+            files: [("../outside.json".to_string(), String::new())].into(),
+            ..Default::default()
+        };
+        assert!(plan_restore(&invalid, KeysTarget::AsInBackup, home).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use std::str::FromStr;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+use super::{KeyRotationContext, NewKeyStorage};
+
+#[derive(Debug, Clone, interactive_clap_derive::InteractiveClap)]
+#[interactive_clap(input_context = super::RotateKeyCommandContext)]
+#[interactive_clap(output_context = GenerateKeypairContext)]
+pub struct GenerateKeypair {
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// How do you want to pass the keys type?
+    key_type: crate::commands::account::KeysType,
+
+    #[interactive_clap(subcommand)]
+    save_mode: SaveMode,
+}
+
+impl GenerateKeypair {
+    fn input_key_type(
+        _context: &super::RotateKeyCommandContext,
+    ) -> color_eyre::eyre::Result<Option<crate::commands::account::KeysType>> {
+        crate::commands::account::input_keys_type()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GenerateKeypairContext {
+    rotate_key_context: super::RotateKeyCommandContext,
+    key_pair_properties: crate::common::KeyPairProperties,
+    public_key: unc_crypto::PublicKey,
+}
+
+impl GenerateKeypairContext {
+    pub fn from_previous_context(
+        previous_context: super::RotateKeyCommandContext,
+        scope: &<GenerateKeypair as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let key_pair_properties = match scope.key_type {
+            crate::commands::account::KeysType::Rsa2048 => {
+                crate::common::generate_rsa2048_keypair()?
+            }
+            crate::commands::account::KeysType::Ed25519 => {
+                crate::common::generate_ed25519_keypair()?
+            }
+        };
+        let public_key = unc_crypto::PublicKey::from_str(&key_pair_properties.public_key_str)?;
+        Ok(Self {
+            rotate_key_context: previous_context,
+            key_pair_properties,
+            public_key,
+        })
+    }
+
+    fn into_key_rotation_context(self, new_key_storage: NewKeyStorage) -> KeyRotationContext {
+        KeyRotationContext {
+            global_context: self.rotate_key_context.global_context,
+            owner_account_id: self.rotate_key_context.owner_account_id,
+            old_public_key: self.rotate_key_context.old_public_key,
+            new_public_key: self.public_key,
+            new_key_storage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = GenerateKeypairContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// Save the new key:
+pub enum SaveMode {
+    #[strum_discriminants(strum(
+        message = "save-to-keychain         - Save the new key pair to keychain"
+    ))]
+    /// Save the new key pair to keychain
+    SaveToKeychain(SaveToKeychain),
+    #[strum_discriminants(strum(
+        message = "save-to-legacy-keychain  - Save the new key pair to the legacy keychain (compatible with JS CLI)"
+    ))]
+    /// Save the new key pair to the legacy keychain (compatible with JS CLI)
+    SaveToLegacyKeychain(SaveToLegacyKeychain),
+    #[strum_discriminants(strum(
+        message = "save-to-encrypted-file   - Save the new key pair to a file encrypted with a password"
+    ))]
+    /// Save the new key pair to a file encrypted with a password
+    SaveToEncryptedFile(SaveToEncryptedFile),
+}
+
+#[derive(Debug, Clone, interactive_clap_derive::InteractiveClap)]
+#[interactive_clap(input_context = GenerateKeypairContext)]
+#[interactive_clap(output_context = SaveToKeychainContext)]
+pub struct SaveToKeychain {
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Debug, Clone)]
+pub struct SaveToKeychainContext(KeyRotationContext);
+
+impl SaveToKeychainContext {
+    pub fn from_previous_context(
+        previous_context: GenerateKeypairContext,
+        _scope: &<SaveToKeychain as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let key_pair_properties = previous_context.key_pair_properties.clone();
+        Ok(Self(previous_context.into_key_rotation_context(
+            NewKeyStorage::Keychain(key_pair_properties),
+        )))
+    }
+}
+
+impl From<SaveToKeychainContext> for crate::commands::ActionContext {
+    fn from(item: SaveToKeychainContext) -> Self {
+        item.0.into()
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap_derive::InteractiveClap)]
+#[interactive_clap(input_context = GenerateKeypairContext)]
+#[interactive_clap(output_context = SaveToLegacyKeychainContext)]
+pub struct SaveToLegacyKeychain {
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Debug, Clone)]
+pub struct SaveToLegacyKeychainContext(KeyRotationContext);
+
+impl SaveToLegacyKeychainContext {
+    pub fn from_previous_context(
+        previous_context: GenerateKeypairContext,
+        _scope: &<SaveToLegacyKeychain as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let key_pair_properties = previous_context.key_pair_properties.clone();
+        Ok(Self(previous_context.into_key_rotation_context(
+            NewKeyStorage::LegacyKeychain(key_pair_properties),
+        )))
+    }
+}
+
+impl From<SaveToLegacyKeychainContext> for crate::commands::ActionContext {
+    fn from(item: SaveToLegacyKeychainContext) -> Self {
+        item.0.into()
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap_derive::InteractiveClap)]
+#[interactive_clap(input_context = GenerateKeypairContext)]
+#[interactive_clap(output_context = SaveToEncryptedFileContext)]
+pub struct SaveToEncryptedFile {
+    /// Where do you want to save the encrypted key file?
+    file_path: crate::types::path_buf::PathBuf,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Debug, Clone)]
+pub struct SaveToEncryptedFileContext(KeyRotationContext);
+
+impl SaveToEncryptedFileContext {
+    pub fn from_previous_context(
+        previous_context: GenerateKeypairContext,
+        scope: &<SaveToEncryptedFile as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let password = crate::util::encryption::input_new_password(
+            "Enter a password to encrypt the new key with:",
+        )?;
+        let encrypted_key_file = crate::transaction_signature_options::sign_with_access_key_file::EncryptedKeyFile::encrypt(
+            &password,
+            &previous_context.key_pair_properties,
+            previous_context.rotate_key_context.owner_account_id.clone(),
+            previous_context.public_key.clone(),
+        )?;
+        Ok(Self(previous_context.into_key_rotation_context(
+            NewKeyStorage::EncryptedFile {
+                file_path: scope.file_path.0.clone(),
+                encrypted_key_file,
+            },
+        )))
+    }
+}
+
+impl From<SaveToEncryptedFileContext> for crate::commands::ActionContext {
+    fn from(item: SaveToEncryptedFileContext) -> Self {
+        item.0.into()
+    }
+}
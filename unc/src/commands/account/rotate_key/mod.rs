@@ -0,0 +1,254 @@
+use color_eyre::eyre::Context;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+use crate::common::JsonRpcClientExt;
+use crate::common::RpcQueryResponseExt;
+
+mod autogenerate_new_keypair;
+#[cfg(feature = "ledger")]
+mod use_ledger;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RotateKeyCommandContext)]
+pub struct RotateKeyCommand {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account do you want to rotate an access key of?
+    owner_account_id: crate::types::account_id::AccountId,
+    /// Enter the public key you want to replace:
+    old_public_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(subcommand)]
+    new_key_mode: NewKeyMode,
+}
+
+#[derive(Debug, Clone)]
+pub struct RotateKeyCommandContext {
+    global_context: crate::GlobalContext,
+    owner_account_id: unc_primitives::types::AccountId,
+    old_public_key: unc_crypto::PublicKey,
+}
+
+impl RotateKeyCommandContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RotateKeyCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id = scope
+            .owner_account_id
+            .resolve(&previous_context.config, None)?;
+        Ok(Self {
+            global_context: previous_context,
+            owner_account_id,
+            old_public_key: scope.old_public_key.clone().into(),
+        })
+    }
+}
+
+impl RotateKeyCommand {
+    pub fn input_owner_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account do you want to rotate an access key of?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = RotateKeyCommandContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// Where should the new key come from?
+pub enum NewKeyMode {
+    #[strum_discriminants(strum(
+        message = "autogenerate-new-keypair  - Automatically generate a new key pair"
+    ))]
+    /// Automatically generate a new key pair
+    AutogenerateNewKeypair(self::autogenerate_new_keypair::GenerateKeypair),
+    #[cfg(feature = "ledger")]
+    #[strum_discriminants(strum(
+        message = "use-ledger                - Use a key of a Ledger device"
+    ))]
+    /// Use a key of a Ledger device
+    UseLedger(self::use_ledger::UseLedgerKey),
+}
+
+/// Where the private part of the new key is kept.
+#[derive(Debug, Clone)]
+pub enum NewKeyStorage {
+    Keychain(crate::common::KeyPairProperties),
+    LegacyKeychain(crate::common::KeyPairProperties),
+    EncryptedFile {
+        file_path: std::path::PathBuf,
+        encrypted_key_file:
+            crate::transaction_signature_options::sign_with_access_key_file::EncryptedKeyFile,
+    },
+    #[cfg(feature = "ledger")]
+    Ledger(crate::types::slip10::BIP32Path),
+}
+
+/// How long to wait for the rotation to be visible with the final block after the transaction is executed.
+const ROTATION_VERIFICATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Everything needed to replace the old key with the new one in one transaction.
+#[derive(Debug, Clone)]
+pub struct KeyRotationContext {
+    pub global_context: crate::GlobalContext,
+    pub owner_account_id: unc_primitives::types::AccountId,
+    pub old_public_key: unc_crypto::PublicKey,
+    pub new_public_key: unc_crypto::PublicKey,
+    pub new_key_storage: NewKeyStorage,
+}
+
+impl From<KeyRotationContext> for crate::commands::ActionContext {
+    fn from(item: KeyRotationContext) -> Self {
+        let global_context = item.global_context.clone();
+        let interacting_with_account_ids = vec![item.owner_account_id.clone()];
+        let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let owner_account_id = item.owner_account_id.clone();
+                let old_public_key = item.old_public_key.clone();
+                let new_public_key = item.new_public_key.clone();
+
+                move |network_config| {
+                    // The new key gets the same permission as the key it replaces.
+                    let old_access_key = network_config
+                        .json_rpc_client()
+                        .blocking_call_view_access_key(
+                            &owner_account_id,
+                            &old_public_key,
+                            unc_primitives::types::Finality::Final.into(),
+                        )
+                        .wrap_err_with(|| {
+                            format!(
+                                "Failed to fetch the access key {old_public_key} of <{owner_account_id}> on network <{}>",
+                                network_config.network_name
+                            )
+                        })?
+                        .access_key_view()?;
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: owner_account_id.clone(),
+                        receiver_id: owner_account_id.clone(),
+                        actions: vec![
+                            unc_primitives::transaction::Action::AddKey(Box::new(
+                                unc_primitives::transaction::AddKeyAction {
+                                    public_key: new_public_key.clone(),
+                                    access_key: unc_primitives::account::AccessKey {
+                                        nonce: 0,
+                                        permission: old_access_key.permission.into(),
+                                    },
+                                },
+                            )),
+                            unc_primitives::transaction::Action::DeleteKey(Box::new(
+                                unc_primitives::transaction::DeleteKeyAction {
+                                    public_key: old_public_key.clone(),
+                                },
+                            )),
+                        ],
+                    })
+                }
+            });
+
+        let on_before_sending_transaction_callback: crate::transaction_signature_options::OnBeforeSendingTransactionCallback =
+            std::sync::Arc::new({
+                let credentials_home_dir = item.global_context.config.credentials_home_dir.clone();
+                let item = item.clone();
+
+                move |signed_transaction, network_config, storage_message| {
+                    if signed_transaction.transaction.public_key != item.old_public_key {
+                        color_eyre::eyre::bail!(
+                            "The rotation must be signed with the old key {}, but it is signed with {} (pass the signer public key explicitly)",
+                            item.old_public_key,
+                            signed_transaction.transaction.public_key
+                        );
+                    }
+                    let account_id = item.owner_account_id.as_str();
+                    let public_key_str = item.new_public_key.to_string();
+                    *storage_message = match &item.new_key_storage {
+                        NewKeyStorage::Keychain(key_pair_properties) => {
+                            crate::common::save_access_key_to_keychain(
+                                network_config.clone(),
+                                &serde_json::to_string(key_pair_properties)?,
+                                &public_key_str,
+                                account_id,
+                            )?
+                        }
+                        NewKeyStorage::LegacyKeychain(key_pair_properties) => {
+                            crate::common::save_access_key_to_legacy_keychain(
+                                network_config.clone(),
+                                credentials_home_dir.clone(),
+                                &serde_json::to_string(key_pair_properties)?,
+                                &public_key_str,
+                                account_id,
+                            )
+                            .wrap_err_with(|| {
+                                format!("Failed to save a file with access key: {public_key_str}")
+                            })?
+                        }
+                        NewKeyStorage::EncryptedFile {
+                            file_path,
+                            encrypted_key_file,
+                        } => {
+                            if file_path.exists() {
+                                color_eyre::eyre::bail!(
+                                    "The file {} already exists! Choose another file for the new key.",
+                                    file_path.display()
+                                );
+                            }
+                            if let Some(dir) = file_path.parent() {
+                                std::fs::create_dir_all(dir).wrap_err_with(|| {
+                                    format!("Failed to create directory: {dir:?}")
+                                })?;
+                            }
+                            std::fs::write(file_path, serde_json::to_vec_pretty(encrypted_key_file)?)
+                                .wrap_err_with(|| format!("Failed to write to file: {file_path:?}"))?;
+                            format!(
+                                "The data for the access key is saved encrypted in a file {}; sign with it using `sign-with-access-key-file {}`",
+                                file_path.display(),
+                                file_path.display()
+                            )
+                        }
+                        #[cfg(feature = "ledger")]
+                        NewKeyStorage::Ledger(seed_phrase_hd_path) => format!(
+                            "The new key is kept on the Ledger device (HD Path: {seed_phrase_hd_path})"
+                        ),
+                    };
+                    Ok(())
+                }
+            });
+
+        // The old key is removed from this machine only once the new key is verified on chain.
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback =
+            std::sync::Arc::new({
+                let credentials_home_dir = item.global_context.config.credentials_home_dir.clone();
+
+                move |outcome_view, network_config| {
+                    if !matches!(
+                        outcome_view.status,
+                        unc_primitives::views::FinalExecutionStatus::SuccessValue(_)
+                    ) {
+                        return Ok(());
+                    }
+                    super::finish_key_rotation::finish_rotation(
+                        &credentials_home_dir,
+                        network_config,
+                        &item.owner_account_id,
+                        &item.old_public_key,
+                        &item.new_public_key,
+                        ROTATION_VERIFICATION_TIMEOUT,
+                    )
+                }
+            });
+
+        Self {
+            global_context,
+            interacting_with_account_ids,
+            on_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback,
+            on_after_sending_transaction_callback,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::RotateKeyCommandContext)]
+#[interactive_clap(output_context = UseLedgerKeyContext)]
+pub struct UseLedgerKey {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    seed_phrase_hd_path: crate::types::slip10::BIP32Path,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Debug, Clone)]
+pub struct UseLedgerKeyContext(super::KeyRotationContext);
+
+impl UseLedgerKeyContext {
+    pub fn from_previous_context(
+        previous_context: super::RotateKeyCommandContext,
+        scope: &<UseLedgerKey as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let seed_phrase_hd_path = scope.seed_phrase_hd_path.clone();
+        eprintln!(
+            "Please allow getting the PublicKey on Ledger device (HD Path: {})",
+            seed_phrase_hd_path
+        );
+        let public_key = unc_ledger::get_public_key(seed_phrase_hd_path.clone().into()).map_err(
+            |unc_ledger_error| {
+                color_eyre::Report::msg(format!(
+                    "An error occurred while trying to get PublicKey from Ledger device: {:?}",
+                    unc_ledger_error
+                ))
+            },
+        )?;
+        let public_key = unc_crypto::PublicKey::ED25519(unc_crypto::ED25519PublicKey::from(
+            public_key.to_bytes(),
+        ));
+
+        Ok(Self(super::KeyRotationContext {
+            global_context: previous_context.global_context,
+            owner_account_id: previous_context.owner_account_id,
+            old_public_key: previous_context.old_public_key,
+            new_public_key: public_key,
+            new_key_storage: super::NewKeyStorage::Ledger(seed_phrase_hd_path),
+        }))
+    }
+}
+
+impl From<UseLedgerKeyContext> for crate::commands::ActionContext {
+    fn from(item: UseLedgerKeyContext) -> Self {
+        item.0.into()
+    }
+}
+
+impl UseLedgerKey {
+    pub fn input_seed_phrase_hd_path(
+        _context: &super::RotateKeyCommandContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::slip10::BIP32Path>> {
+        crate::transaction_signature_options::sign_with_ledger::SignLedger::input_seed_phrase_hd_path()
+    }
+}
@@ -27,8 +27,11 @@ impl ContractContext {
         scope: &<Contract as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         let contract_account_id = scope.contract_account_id.clone();
+        let config = previous_context.config.clone();
         let get_contract_account_id: GetContractAccountId =
-            std::sync::Arc::new(move |_network_config| Ok(contract_account_id.clone().into()));
+            std::sync::Arc::new(move |network_config| {
+                contract_account_id.resolve(&config, Some(&network_config.network_name))
+            });
         Ok(Self {
             global_context: previous_context,
             get_contract_account_id,
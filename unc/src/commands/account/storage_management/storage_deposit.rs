@@ -27,10 +27,13 @@ impl DepositArgsContext {
         previous_context: super::ContractContext,
         scope: &<DepositArgs as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         Ok(Self {
             global_context: previous_context.global_context,
             get_contract_account_id: previous_context.get_contract_account_id,
-            receiver_account_id: scope.receiver_account_id.clone().into(),
+            receiver_account_id,
             deposit: scope.deposit,
         })
     }
@@ -52,12 +55,13 @@ impl DepositArgs {
             };
 
             if context.global_context.offline {
+                crate::common::warn_account_check_skipped_offline(&receiver_account_id);
                 return Ok(Some(receiver_account_id));
             }
 
             if !crate::common::is_account_exist(
                 &context.global_context.config.network_connection,
-                receiver_account_id.clone().into(),
+                receiver_account_id.resolve(&context.global_context.config, None)?,
             ) {
                 eprintln!(
                     "\nThe account <{receiver_account_id}> does not exist on [{}] networks.",
@@ -105,10 +109,12 @@ impl SignerAccountIdContext {
         previous_context: DepositArgsContext,
         scope: &<SignerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id = scope
+            .signer_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
-                let signer_account_id: unc_primitives::types::AccountId =
-                    scope.signer_account_id.clone().into();
+                let signer_account_id = signer_account_id.clone();
                 let receiver_account_id = previous_context.receiver_account_id.clone();
                 let get_contract_account_id = previous_context.get_contract_account_id.clone();
                 let deposit = previous_context.deposit;
@@ -132,7 +138,7 @@ impl SignerAccountIdContext {
             });
 
         let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
-            let signer_account_id: unc_primitives::types::AccountId = scope.signer_account_id.clone().into();
+            let signer_account_id = signer_account_id.clone();
             let receiver_account_id = previous_context.receiver_account_id.clone();
 
             move |outcome_view, network_config| {
@@ -151,7 +157,7 @@ impl SignerAccountIdContext {
         Ok(Self(crate::commands::ActionContext {
             global_context: previous_context.global_context,
             interacting_with_account_ids: vec![
-                scope.signer_account_id.clone().into(),
+                signer_account_id,
                 previous_context.receiver_account_id,
             ],
             on_after_getting_network_callback,
@@ -49,10 +49,12 @@ impl SignerAccountIdContext {
         previous_context: WithdrawArgsContext,
         scope: &<SignerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id = scope
+            .signer_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
-                let signer_account_id: unc_primitives::types::AccountId =
-                    scope.signer_account_id.clone().into();
+                let signer_account_id = signer_account_id.clone();
                 let get_contract_account_id = previous_context.get_contract_account_id.clone();
                 let amount = previous_context.amount;
 
@@ -75,7 +77,7 @@ impl SignerAccountIdContext {
             });
 
         let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
-            let signer_account_id: unc_primitives::types::AccountId = scope.signer_account_id.clone().into();
+            let signer_account_id = signer_account_id.clone();
 
             move |outcome_view, network_config| {
                 let contract_account_id = (previous_context.get_contract_account_id)(network_config)?;
@@ -91,7 +93,7 @@ impl SignerAccountIdContext {
 
         Ok(Self(crate::commands::ActionContext {
             global_context: previous_context.global_context,
-            interacting_with_account_ids: vec![scope.signer_account_id.clone().into()],
+            interacting_with_account_ids: vec![signer_account_id],
             on_after_getting_network_callback,
             on_before_signing_callback: std::sync::Arc::new(
                 |_prepolulated_unsinged_transaction, _network_config| Ok(()),
@@ -18,6 +18,9 @@ impl AccountContext {
         previous_context: super::ContractContext,
         scope: &<Account as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope
+            .account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback =
             std::sync::Arc::new({
                 let _account_id = scope.account_id.clone();
@@ -31,7 +34,7 @@ impl AccountContext {
 
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
             config: previous_context.global_context.config,
-            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            interacting_with_account_ids: vec![account_id],
             on_after_getting_block_reference_callback,
         }))
     }
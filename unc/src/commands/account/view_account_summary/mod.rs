@@ -1,5 +1,4 @@
 use color_eyre::eyre::Context;
-use futures::{StreamExt, TryStreamExt};
 
 use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
 
@@ -23,8 +22,9 @@ impl ViewAccountSummaryContext {
         previous_context: crate::GlobalContext,
         scope: &<ViewAccountSummary as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
-            let account_id: unc_primitives::types::AccountId = scope.account_id.clone().into();
+            let account_id = account_id.clone();
 
             move |network_config, block_reference| {
                 let json_rpc_client = network_config.json_rpc_client();
@@ -56,30 +56,14 @@ impl ViewAccountSummaryContext {
 
                 let validators_pledge = crate::common::get_validators_pledge(&json_rpc_client)?;
 
-                let runtime = tokio::runtime::Builder::new_multi_thread()
-                    .enable_all()
-                    .build()?;
-                let concurrency = 10;
-                let delegated_pledge: std::collections::BTreeMap<unc_primitives::types::AccountId, unc_token::UncToken> = runtime
-                    .block_on(
-                        futures::stream::iter(validators_pledge.into_keys())
-                        .map(|validator_account_id| async {
-                            let balance = get_delegated_pledged_balance(&json_rpc_client, block_reference, &validator_account_id, &account_id).await?;
-                            Ok::<_, color_eyre::eyre::Report>((
-                                validator_account_id,
-                                balance,
-                            ))
-                        })
-                        .buffer_unordered(concurrency)
-                        .filter(|balance_result| futures::future::ready(
-                            if let Ok((_, balance)) = balance_result {
-                                !balance.is_zero()
-                            } else {
-                                true
-                            }
-                        ))
-                        .try_collect(),
-                    )?;
+                let delegated_pledge: std::collections::BTreeMap<unc_primitives::types::AccountId, unc_token::UncToken> =
+                    crate::common::block_on_concurrently(validators_pledge.into_keys().map(|validator_account_id| async {
+                        let balance = get_delegated_pledged_balance(&json_rpc_client, block_reference, &validator_account_id, &account_id).await?;
+                        Ok::<_, color_eyre::eyre::Report>((validator_account_id, balance))
+                    }))?
+                    .into_iter()
+                    .filter(|(_, balance)| !balance.is_zero())
+                    .collect();
 
                 crate::common::display_account_info(
                     &rpc_query_response.block_hash,
@@ -95,7 +79,7 @@ impl ViewAccountSummaryContext {
         });
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
             config: previous_context.config,
-            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            interacting_with_account_ids: vec![account_id],
             on_after_getting_block_reference_callback,
         }))
     }
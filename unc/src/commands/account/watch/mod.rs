@@ -0,0 +1,733 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+const DEFAULT_BLOCK_INTERVAL: u64 = 10;
+const MAX_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+const ALERT_COMMAND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = WatchContext)]
+pub struct Watch {
+    /// Which accounts do you want to watch (comma-separated)?
+    account_ids: crate::types::vec_string::VecString,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Check the accounts every this many blocks (10 by default)
+    block_interval: Option<u64>,
+    #[interactive_clap(long)]
+    /// Print the events as JSON lines
+    json: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Alert when the balance of an account drops below this amount (e.g. 10 unc)
+    balance_below: Option<crate::types::unc_token::UncToken>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Alert when the storage usage of an account grows above this many bytes
+    storage_above: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Shell command to run on an alert (the event is passed as JSON in UNC_WATCH_EVENT)
+    on_alert: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// URL to POST the alert events to as JSON
+    webhook: Option<crate::types::url::Url>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct WatchContext(crate::network::NetworkContext);
+
+impl WatchContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Watch as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.offline {
+            color_eyre::eyre::bail!("Watching accounts requires access to the network, it cannot be done in offline mode");
+        }
+        let mut account_ids = scope
+            .account_ids
+            .0
+            .iter()
+            .filter(|account_id| !account_id.is_empty())
+            .map(|account_id| {
+                account_id
+                    .parse::<crate::types::account_id::AccountId>()
+                    .wrap_err_with(|| format!("Invalid account ID: {account_id}"))?
+                    .resolve(&previous_context.config, None)
+            })
+            .collect::<color_eyre::eyre::Result<Vec<unc_primitives::types::AccountId>>>()?;
+        account_ids.sort();
+        account_ids.dedup();
+        if account_ids.is_empty() {
+            color_eyre::eyre::bail!("There are no accounts to watch");
+        }
+        let block_interval = scope
+            .block_interval
+            .unwrap_or(DEFAULT_BLOCK_INTERVAL)
+            .max(1);
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_ids = account_ids.clone();
+                let thresholds = Thresholds {
+                    balance_below: scope
+                        .balance_below
+                        .as_ref()
+                        .map(crate::types::unc_token::UncToken::as_attounc),
+                    storage_above: scope.storage_above,
+                };
+                let hooks = Hooks {
+                    on_alert: scope.on_alert.clone(),
+                    webhook: scope.webhook.clone().map(Into::into),
+                };
+                let json = scope.json;
+
+                move |network_config| {
+                    watch(
+                        network_config,
+                        &account_ids,
+                        block_interval,
+                        &thresholds,
+                        &hooks,
+                        json,
+                    )
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: account_ids,
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<WatchContext> for crate::network::NetworkContext {
+    fn from(item: WatchContext) -> Self {
+        item.0
+    }
+}
+
+/// The observed state of an account; `None` stands for an account that does not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AccountSnapshot {
+    amount: u128,
+    pledging: u128,
+    storage_usage: u64,
+    code_hash: unc_primitives::hash::CryptoHash,
+    access_keys: std::collections::BTreeMap<String, String>,
+}
+
+/// The amounts are serialized as strings of attounc, like in the RPC responses.
+impl serde::Serialize for AccountSnapshot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(
+            &serde_json::json!({
+                "amount": self.amount.to_string(),
+                "pledging": self.pledging.to_string(),
+                "storage_usage": self.storage_usage,
+                "code_hash": self.code_hash,
+                "access_keys": self.access_keys,
+            }),
+            serializer,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Thresholds {
+    balance_below: Option<u128>,
+    storage_above: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Hooks {
+    on_alert: Option<String>,
+    webhook: Option<url::Url>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct WatchEvent {
+    account_id: unc_primitives::types::AccountId,
+    block_height: unc_primitives::types::BlockHeight,
+    kind: EventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold: Option<String>,
+    /// The state of the account for the first poll and for a created account
+    /// (`null` for an account that does not exist).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot: Option<Option<AccountSnapshot>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, strum_macros::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+enum EventKind {
+    Snapshot,
+    AccountCreated,
+    AccountDeleted,
+    Balance,
+    Pledging,
+    StorageUsage,
+    CodeHash,
+    AccessKeyAdded,
+    AccessKeyRemoved,
+    AccessKeyChanged,
+    BalanceBelow,
+    StorageAbove,
+}
+
+impl EventKind {
+    fn is_alert(self) -> bool {
+        matches!(self, Self::BalanceBelow | Self::StorageAbove)
+    }
+
+    /// The values of these events are amounts in attounc.
+    fn is_token_amount(self) -> bool {
+        matches!(self, Self::Balance | Self::Pledging | Self::BalanceBelow)
+    }
+}
+
+impl std::fmt::Display for WatchEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[#{}] <{}> {}",
+            self.block_height, self.account_id, self.kind
+        )?;
+        let value = |value: &String| match value.parse::<u128>() {
+            Ok(amount) if self.kind.is_token_amount() => {
+                unc_token::UncToken::from_attounc(amount).to_string()
+            }
+            _ => value.clone(),
+        };
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, ": {} -> {}", value(old), value(new))?,
+            (None, Some(new_or_old)) | (Some(new_or_old), None) => {
+                write!(f, ": {}", value(new_or_old))?
+            }
+            (None, None) => {}
+        }
+        if let Some(snapshot) = &self.snapshot {
+            write!(f, ": {}", describe_snapshot(snapshot))?;
+        }
+        if let Some(threshold) = &self.threshold {
+            write!(f, " (threshold {})", value(threshold))?;
+        }
+        Ok(())
+    }
+}
+
+fn watch(
+    network_config: &crate::config::NetworkConfig,
+    account_ids: &[unc_primitives::types::AccountId],
+    block_interval: u64,
+    thresholds: &Thresholds,
+    hooks: &Hooks,
+    json: bool,
+) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let mut snapshots: std::collections::HashMap<
+        unc_primitives::types::AccountId,
+        Option<AccountSnapshot>,
+    > = std::collections::HashMap::new();
+    let mut last_block_height: Option<unc_primitives::types::BlockHeight> = None;
+    let mut retry_delay = std::time::Duration::from_secs(1);
+
+    eprintln!(
+        "Watching {} on network <{}> every {block_interval} blocks (press Ctrl+C to stop)",
+        account_ids
+            .iter()
+            .map(|account_id| format!("<{account_id}>"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        network_config.network_name
+    );
+    loop {
+        match poll(
+            &json_rpc_client,
+            account_ids,
+            last_block_height,
+            block_interval,
+        ) {
+            Ok(Some((block_height, current_snapshots))) => {
+                for (account_id, current) in current_snapshots {
+                    for event in events(
+                        &account_id,
+                        block_height,
+                        snapshots.get(&account_id),
+                        &current,
+                        thresholds,
+                    ) {
+                        if json {
+                            println!("{}", serde_json::to_string(&event)?);
+                        } else {
+                            println!("{event}");
+                        }
+                        if event.kind.is_alert() {
+                            run_hooks(hooks, &event);
+                        }
+                    }
+                    snapshots.insert(account_id, current);
+                }
+                last_block_height = Some(block_height);
+                retry_delay = std::time::Duration::from_secs(1);
+                std::thread::sleep(std::time::Duration::from_secs(1));
+            }
+            Ok(None) => std::thread::sleep(std::time::Duration::from_secs(1)),
+            Err(err) => {
+                eprintln!(
+                    "Failed to poll network <{}> (retrying in {}s): {err:#}",
+                    network_config.network_name,
+                    retry_delay.as_secs()
+                );
+                std::thread::sleep(retry_delay);
+                retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+            }
+        }
+    }
+}
+
+type Snapshots = Vec<(unc_primitives::types::AccountId, Option<AccountSnapshot>)>;
+
+/// Takes the snapshots of the accounts at the latest final block once it is `block_interval`
+/// blocks past the previous poll.
+fn poll(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    account_ids: &[unc_primitives::types::AccountId],
+    last_block_height: Option<unc_primitives::types::BlockHeight>,
+    block_interval: u64,
+) -> color_eyre::eyre::Result<Option<(unc_primitives::types::BlockHeight, Snapshots)>> {
+    let block_height = json_rpc_client
+        .blocking_call(unc_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: unc_primitives::types::Finality::Final.into(),
+        })
+        .wrap_err("Failed to fetch the latest final block")?
+        .header
+        .height;
+    if last_block_height
+        .is_some_and(|last_block_height| block_height < last_block_height + block_interval)
+    {
+        return Ok(None);
+    }
+    let block_reference = unc_primitives::types::BlockReference::BlockId(
+        unc_primitives::types::BlockId::Height(block_height),
+    );
+    let mut snapshots =
+        crate::common::block_on_concurrently(account_ids.iter().map(|account_id| {
+            let block_reference = &block_reference;
+            async move {
+                Ok::<_, color_eyre::eyre::Report>((
+                    account_id.clone(),
+                    snapshot(json_rpc_client, account_id, block_reference).await?,
+                ))
+            }
+        }))?;
+    snapshots.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(Some((block_height, snapshots)))
+}
+
+async fn snapshot(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    account_id: &unc_primitives::types::AccountId,
+    block_reference: &unc_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<Option<AccountSnapshot>> {
+    let account_view = match json_rpc_client
+        .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: unc_primitives::views::QueryRequest::ViewAccount {
+                account_id: account_id.clone(),
+            },
+        })
+        .await
+    {
+        Ok(response) => response.account_view()?,
+        Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+            unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                unc_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => return Ok(None),
+        Err(err) => {
+            return Err(color_eyre::eyre::Report::from(err))
+                .wrap_err_with(|| format!("Failed to fetch query ViewAccount for <{account_id}>"))
+        }
+    };
+    let access_keys = json_rpc_client
+        .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: unc_primitives::views::QueryRequest::ViewAccessKeyList {
+                account_id: account_id.clone(),
+            },
+        })
+        .await
+        .wrap_err_with(|| format!("Failed to fetch query AccessKeyList for <{account_id}>"))?
+        .access_key_list_view()?
+        .keys
+        .into_iter()
+        .map(|key| {
+            let permission = match key.access_key.permission {
+                unc_primitives::views::AccessKeyPermissionView::FullAccess => {
+                    "full access".to_string()
+                }
+                unc_primitives::views::AccessKeyPermissionView::FunctionCall {
+                    receiver_id,
+                    ..
+                } => format!("function call on <{receiver_id}>"),
+            };
+            (key.public_key.to_string(), permission)
+        })
+        .collect();
+    Ok(Some(AccountSnapshot {
+        amount: account_view.amount,
+        pledging: account_view.pledging,
+        storage_usage: account_view.storage_usage,
+        code_hash: account_view.code_hash,
+        access_keys,
+    }))
+}
+
+/// The events of the account since the previous poll (`None` for the first poll, which reports
+/// the initial state).
+fn events(
+    account_id: &unc_primitives::types::AccountId,
+    block_height: unc_primitives::types::BlockHeight,
+    previous: Option<&Option<AccountSnapshot>>,
+    current: &Option<AccountSnapshot>,
+    thresholds: &Thresholds,
+) -> Vec<WatchEvent> {
+    let mut events = match previous {
+        Some(previous) => changes(account_id, block_height, previous, current),
+        None => vec![WatchEvent {
+            account_id: account_id.clone(),
+            block_height,
+            kind: EventKind::Snapshot,
+            old: None,
+            new: None,
+            threshold: None,
+            snapshot: Some(current.clone()),
+        }],
+    };
+    events.extend(alerts(
+        account_id,
+        block_height,
+        previous.and_then(Option::as_ref),
+        current.as_ref(),
+        thresholds,
+    ));
+    events
+}
+
+fn describe_snapshot(snapshot: &Option<AccountSnapshot>) -> String {
+    match snapshot {
+        Some(snapshot) => format!(
+            "balance {}, pledged {}, storage usage {} bytes, code hash {}, {} access keys",
+            unc_token::UncToken::from_attounc(snapshot.amount),
+            unc_token::UncToken::from_attounc(snapshot.pledging),
+            snapshot.storage_usage,
+            snapshot.code_hash,
+            snapshot.access_keys.len()
+        ),
+        None => "does not exist".to_string(),
+    }
+}
+
+fn changes(
+    account_id: &unc_primitives::types::AccountId,
+    block_height: unc_primitives::types::BlockHeight,
+    previous: &Option<AccountSnapshot>,
+    current: &Option<AccountSnapshot>,
+) -> Vec<WatchEvent> {
+    let event = |kind, old: Option<String>, new: Option<String>| WatchEvent {
+        account_id: account_id.clone(),
+        block_height,
+        kind,
+        old,
+        new,
+        threshold: None,
+        snapshot: None,
+    };
+    let (previous, current) = match (previous, current) {
+        (Some(previous), Some(current)) => (previous, current),
+        (None, Some(current)) => {
+            return vec![WatchEvent {
+                snapshot: Some(Some(current.clone())),
+                ..event(EventKind::AccountCreated, None, None)
+            }]
+        }
+        (Some(_), None) => return vec![event(EventKind::AccountDeleted, None, None)],
+        (None, None) => return vec![],
+    };
+
+    let mut events = vec![];
+    if previous.amount != current.amount {
+        events.push(event(
+            EventKind::Balance,
+            Some(previous.amount.to_string()),
+            Some(current.amount.to_string()),
+        ));
+    }
+    if previous.pledging != current.pledging {
+        events.push(event(
+            EventKind::Pledging,
+            Some(previous.pledging.to_string()),
+            Some(current.pledging.to_string()),
+        ));
+    }
+    if previous.storage_usage != current.storage_usage {
+        events.push(event(
+            EventKind::StorageUsage,
+            Some(previous.storage_usage.to_string()),
+            Some(current.storage_usage.to_string()),
+        ));
+    }
+    if previous.code_hash != current.code_hash {
+        events.push(event(
+            EventKind::CodeHash,
+            Some(previous.code_hash.to_string()),
+            Some(current.code_hash.to_string()),
+        ));
+    }
+    for (public_key, permission) in &previous.access_keys {
+        match current.access_keys.get(public_key) {
+            None => events.push(event(
+                EventKind::AccessKeyRemoved,
+                Some(format!("{public_key} ({permission})")),
+                None,
+            )),
+            Some(current_permission) if current_permission != permission => events.push(event(
+                EventKind::AccessKeyChanged,
+                Some(format!("{public_key} ({permission})")),
+                Some(format!("{public_key} ({current_permission})")),
+            )),
+            Some(_) => {}
+        }
+    }
+    for (public_key, permission) in &current.access_keys {
+        if !previous.access_keys.contains_key(public_key) {
+            events.push(event(
+                EventKind::AccessKeyAdded,
+                None,
+                Some(format!("{public_key} ({permission})")),
+            ));
+        }
+    }
+    events
+}
+
+/// An alert is raised when a threshold is crossed (or already crossed at the first poll),
+/// not on every poll while it stays crossed.
+fn alerts(
+    account_id: &unc_primitives::types::AccountId,
+    block_height: unc_primitives::types::BlockHeight,
+    previous: Option<&AccountSnapshot>,
+    current: Option<&AccountSnapshot>,
+    thresholds: &Thresholds,
+) -> Vec<WatchEvent> {
+    let Some(current) = current else {
+        return vec![];
+    };
+    let mut alerts = vec![];
+    if let Some(balance_below) = thresholds.balance_below {
+        let crossed = |snapshot: &AccountSnapshot| snapshot.amount < balance_below;
+        if crossed(current) && !previous.is_some_and(crossed) {
+            alerts.push(WatchEvent {
+                account_id: account_id.clone(),
+                block_height,
+                kind: EventKind::BalanceBelow,
+                old: None,
+                new: Some(current.amount.to_string()),
+                threshold: Some(balance_below.to_string()),
+                snapshot: None,
+            });
+        }
+    }
+    if let Some(storage_above) = thresholds.storage_above {
+        let crossed = |snapshot: &AccountSnapshot| snapshot.storage_usage > storage_above;
+        if crossed(current) && !previous.is_some_and(crossed) {
+            alerts.push(WatchEvent {
+                account_id: account_id.clone(),
+                block_height,
+                kind: EventKind::StorageAbove,
+                old: None,
+                new: Some(current.storage_usage.to_string()),
+                threshold: Some(storage_above.to_string()),
+                snapshot: None,
+            });
+        }
+    }
+    alerts
+}
+
+/// Hook failures are reported but never stop the watch; the alert command runs in the background
+/// and is killed if it does not finish within `ALERT_COMMAND_TIMEOUT`.
+fn run_hooks(hooks: &Hooks, event: &WatchEvent) {
+    let event_json = match serde_json::to_string(event) {
+        Ok(event_json) => event_json,
+        Err(err) => {
+            eprintln!("Failed to serialize the event: {err}");
+            return;
+        }
+    };
+    if let Some(command) = &hooks.on_alert {
+        #[cfg(not(windows))]
+        let mut shell = std::process::Command::new("sh");
+        #[cfg(not(windows))]
+        shell.arg("-c");
+        #[cfg(windows)]
+        let mut shell = std::process::Command::new("cmd");
+        #[cfg(windows)]
+        shell.arg("/C");
+        match shell
+            .arg(command)
+            .env("UNC_WATCH_EVENT", &event_json)
+            .env("UNC_WATCH_ACCOUNT_ID", event.account_id.as_str())
+            .spawn()
+        {
+            Ok(child) => {
+                std::thread::spawn(move || wait_for_alert_command(child));
+            }
+            Err(err) => eprintln!("Failed to run the alert command: {err}"),
+        }
+    }
+    if let Some(webhook) = &hooks.webhook {
+        let response = reqwest::blocking::Client::new()
+            .post(webhook.clone())
+            .timeout(std::time::Duration::from_secs(10))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(event_json)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status);
+        if let Err(err) = response {
+            eprintln!("Failed to call the webhook <{webhook}>: {err}");
+        }
+    }
+}
+
+fn wait_for_alert_command(mut child: std::process::Child) {
+    let started_at = std::time::Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if !status.success() => {
+                eprintln!("The alert command exited with {status}");
+                return;
+            }
+            Ok(Some(_)) => return,
+            Ok(None) if started_at.elapsed() >= ALERT_COMMAND_TIMEOUT => {
+                eprintln!(
+                    "The alert command did not finish within {}s and is killed",
+                    ALERT_COMMAND_TIMEOUT.as_secs()
+                );
+                let _ = child.kill();
+                let _ = child.wait();
+                return;
+            }
+            Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+            Err(err) => {
+                eprintln!("Failed to wait for the alert command: {err}");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_changes_and_alerts() {
+        let account_id: unc_primitives::types::AccountId = "alice.unc".parse().unwrap();
+        let previous = AccountSnapshot {
+            amount: 100,
+            pledging: 0,
+            storage_usage: 200,
+            code_hash: Default::default(),
+            access_keys: [("ed25519:a".to_string(), "full access".to_string())].into(),
+        };
+        let current = AccountSnapshot {
+            amount: 40,
+            access_keys: [("ed25519:b".to_string(), "full access".to_string())].into(),
+            ..previous.clone()
+        };
+        let kinds = |events: Vec<WatchEvent>| {
+            events
+                .into_iter()
+                .map(|event| event.kind)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(changes(
+                &account_id,
+                1,
+                &Some(previous.clone()),
+                &Some(current.clone())
+            )),
+            vec![
+                EventKind::Balance,
+                EventKind::AccessKeyRemoved,
+                EventKind::AccessKeyAdded
+            ]
+        );
+        assert_eq!(
+            kinds(changes(&account_id, 1, &Some(previous.clone()), &None)),
+            vec![EventKind::AccountDeleted]
+        );
+
+        let thresholds = Thresholds {
+            balance_below: Some(50),
+            storage_above: None,
+        };
+        assert_eq!(
+            kinds(alerts(
+                &account_id,
+                1,
+                Some(&previous),
+                Some(&current),
+                &thresholds
+            )),
+            vec![EventKind::BalanceBelow]
+        );
+        // The alert is not repeated while the balance stays below the threshold.
+        assert!(alerts(&account_id, 2, Some(&current), Some(&current), &thresholds).is_empty());
+
+        // The first poll reports the initial state (and the thresholds already crossed).
+        assert_eq!(
+            kinds(events(
+                &account_id,
+                1,
+                None,
+                &Some(current.clone()),
+                &thresholds
+            )),
+            vec![EventKind::Snapshot, EventKind::BalanceBelow]
+        );
+
+        // The JSON events carry the amounts in attounc.
+        let balance_event = changes(
+            &account_id,
+            1,
+            &Some(AccountSnapshot {
+                amount: 5 * 10u128.pow(24),
+                ..previous.clone()
+            }),
+            &Some(previous.clone()),
+        )
+        .remove(0);
+        let balance_event_json = serde_json::to_value(&balance_event).unwrap();
+        assert_eq!(balance_event_json["old"], "5000000000000000000000000");
+        assert_eq!(balance_event_json["new"], "100");
+        assert!(balance_event.to_string().contains("5.00 UNC"));
+    }
+}
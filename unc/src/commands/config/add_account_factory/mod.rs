@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AddAccountFactoryContext)]
+pub struct AddAccountFactory {
+    /// What is the factory name? (e.g. dao-factory)
+    factory_name: String,
+    #[interactive_clap(long)]
+    /// What is the account ID of the factory contract?
+    factory_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the name of the method that creates an account?
+    method_name: String,
+    #[interactive_clap(long)]
+    /// Enter the JSON arguments of the method ({new_account_id}, {name} and {new_public_key} are replaced with the values of the new account):
+    args: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How much gas (in TGas) to attach to the call (100 by default)
+    tgas: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddAccountFactoryContext;
+
+impl AddAccountFactoryContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AddAccountFactory as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let factory_account_id = scope
+            .factory_account_id
+            .resolve(&previous_context.config, None)?;
+        let factory = crate::config::AccountFactory {
+            factory_account_id,
+            method_name: scope.method_name.clone(),
+            args: scope.args.clone(),
+            tgas: scope.tgas.unwrap_or(100),
+        };
+        // The template is checked once here rather than at every account creation.
+        factory.args_for(
+            &factory.factory_account_id,
+            &unc_crypto::PublicKey::empty(unc_crypto::KeyType::ED25519),
+        )?;
+        let mut config = previous_context.config;
+        config
+            .account_factories
+            .insert(scope.factory_name.clone(), factory);
+        eprintln!();
+        crate::common::write_config_toml(config)?;
+        eprintln!(
+            "Account factory \"{}\" was successfully added to config.toml",
+            &scope.factory_name
+        );
+        Ok(Self)
+    }
+}
@@ -45,6 +45,13 @@ impl AddNetworkConnectionContext {
         scope: &<AddNetworkConnection as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         let mut config = previous_context.config;
+        let linkdrop_account_id = scope
+            .linkdrop_account_id
+            .as_ref()
+            .map(|linkdrop_account_id| {
+                linkdrop_account_id.resolve(&config, Some(&scope.network_name))
+            })
+            .transpose()?;
         config.network_connection.insert(
             scope.connection_name.clone(),
             crate::config::NetworkConfig {
@@ -53,10 +60,7 @@ impl AddNetworkConnectionContext {
                 wallet_url: scope.wallet_url.clone().into(),
                 explorer_transaction_url: scope.explorer_transaction_url.0.clone(),
                 rpc_api_key: scope.rpc_api_key.clone(),
-                linkdrop_account_id: scope
-                    .linkdrop_account_id
-                    .clone()
-                    .map(|linkdrop_account_id| linkdrop_account_id.into()),
+                linkdrop_account_id,
                 faucet_url: scope.faucet_url.clone().map(|faucet_url| faucet_url.into()),
                 meta_transaction_relayer_url: scope
                     .meta_transaction_relayer_url
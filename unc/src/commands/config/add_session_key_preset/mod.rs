@@ -0,0 +1,71 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AddSessionKeyPresetContext)]
+pub struct AddSessionKeyPreset {
+    /// What is the preset name? (e.g. game-session)
+    preset_name: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// The allowance of the session keys (example: 0.25unc); the keys are not limited without it
+    allowance: Option<crate::types::unc_token::UncToken>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The methods the session keys can call (comma-separated); without them, the non-payable call methods of the contract ABI are used
+    method_names: Option<crate::types::vec_string::VecString>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Use only the ABI methods matching these patterns (comma-separated, `*` matches any characters)
+    method_filter: Option<crate::types::vec_string::VecString>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many hours a session lasts (it is shown in `account list-keys`, the keys do not expire on chain)
+    expires_in_hours: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddSessionKeyPresetContext;
+
+impl AddSessionKeyPresetContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AddSessionKeyPreset as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        config.session_key_presets.insert(
+            scope.preset_name.clone(),
+            crate::config::SessionKeyPreset {
+                allowance: scope.allowance,
+                method_names: scope
+                    .method_names
+                    .clone()
+                    .map(Into::into)
+                    .unwrap_or_default(),
+                method_filter: scope
+                    .method_filter
+                    .clone()
+                    .map(Into::into)
+                    .unwrap_or_default(),
+                expires_in_hours: scope.expires_in_hours,
+            },
+        );
+        eprintln!();
+        crate::common::write_config_toml(config)?;
+        eprintln!(
+            "Session key preset \"{}\" was successfully added to config.toml",
+            &scope.preset_name
+        );
+        Ok(Self)
+    }
+}
+
+impl AddSessionKeyPreset {
+    fn input_allowance(
+        _context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::unc_token::UncToken>> {
+        eprintln!();
+        Ok(inquire::CustomType::new(
+            "Enter an allowance of the session keys (example: 0.25unc), or press Esc for no limit:",
+        )
+        .prompt_skippable()?)
+    }
+}
@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = DeleteAccountFactoryContext)]
+pub struct DeleteAccountFactory {
+    /// What is the factory name?
+    #[interactive_clap(skip_default_input_arg)]
+    factory_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteAccountFactoryContext;
+
+impl DeleteAccountFactoryContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<DeleteAccountFactory as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        if config
+            .account_factories
+            .remove(&scope.factory_name)
+            .is_none()
+        {
+            color_eyre::eyre::bail!(
+                "There is no account factory \"{}\" in the config",
+                scope.factory_name
+            );
+        }
+        eprintln!();
+        crate::common::write_config_toml(config)?;
+        eprintln!(
+            "Account factory \"{}\" was successfully removed from config.toml",
+            &scope.factory_name
+        );
+        Ok(Self)
+    }
+}
+
+impl DeleteAccountFactory {
+    fn input_factory_name(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<String>> {
+        let factories = context
+            .config
+            .account_factories
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        if factories.is_empty() {
+            color_eyre::eyre::bail!("There are no account factories in the config");
+        }
+        Ok(Some(
+            inquire::Select::new("What is the factory name?", factories).prompt()?,
+        ))
+    }
+}
@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = DeleteSessionKeyPresetContext)]
+pub struct DeleteSessionKeyPreset {
+    /// What is the preset name?
+    #[interactive_clap(skip_default_input_arg)]
+    preset_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteSessionKeyPresetContext;
+
+impl DeleteSessionKeyPresetContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<DeleteSessionKeyPreset as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        if config
+            .session_key_presets
+            .remove(&scope.preset_name)
+            .is_none()
+        {
+            color_eyre::eyre::bail!(
+                "There is no session key preset \"{}\" in the config",
+                scope.preset_name
+            );
+        }
+        eprintln!();
+        crate::common::write_config_toml(config)?;
+        eprintln!(
+            "Session key preset \"{}\" was successfully removed from config.toml",
+            &scope.preset_name
+        );
+        Ok(Self)
+    }
+}
+
+impl DeleteSessionKeyPreset {
+    fn input_preset_name(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<String>> {
+        let presets = context
+            .config
+            .session_key_presets
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        if presets.is_empty() {
+            color_eyre::eyre::bail!("There are no session key presets in the config");
+        }
+        Ok(Some(
+            inquire::Select::new("What is the preset name?", presets).prompt()?,
+        ))
+    }
+}
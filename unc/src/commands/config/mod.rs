@@ -1,8 +1,12 @@
 use color_eyre::eyre::ContextCompat;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod add_account_factory;
 mod add_connection;
+mod add_session_key_preset;
+mod delete_account_factory;
 mod delete_connection;
+mod delete_session_key_preset;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = crate::GlobalContext)]
@@ -30,6 +34,24 @@ pub enum ConfigActions {
     ))]
     /// Delete a network connection
     DeleteConnection(self::delete_connection::DeleteNetworkConnection),
+    #[strum_discriminants(strum(
+        message = "add-session-key-preset - Add a preset of session keys for dapps"
+    ))]
+    /// Add a preset of session keys for dapps
+    AddSessionKeyPreset(self::add_session_key_preset::AddSessionKeyPreset),
+    #[strum_discriminants(strum(
+        message = "delete-session-key-preset - Delete a preset of session keys"
+    ))]
+    /// Delete a preset of session keys
+    DeleteSessionKeyPreset(self::delete_session_key_preset::DeleteSessionKeyPreset),
+    #[strum_discriminants(strum(
+        message = "add-account-factory    - Add a factory contract that creates accounts"
+    ))]
+    /// Add a factory contract that creates accounts
+    AddAccountFactory(self::add_account_factory::AddAccountFactory),
+    #[strum_discriminants(strum(message = "delete-account-factory - Delete an account factory"))]
+    /// Delete an account factory
+    DeleteAccountFactory(self::delete_account_factory::DeleteAccountFactory),
 }
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -0,0 +1,1192 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use inquire::{Confirm, CustomType, Select, Text};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+
+use borsh::schema::{BorshSchemaContainer, Declaration, Definition, Fields};
+
+pub fn get_contract_abi(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_id: &unc_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<unc_abi::AbiRoot> {
+    crate::common::block_on(crate::commands::contract::inspect::get_contract_abi(
+        &network_config.json_rpc_client(),
+        &unc_primitives::types::Finality::Final.into(),
+        contract_account_id,
+    ))
+    .wrap_err_with(|| {
+        format!(
+            "Failed to fetch the ABI of the contract <{}> on network <{}>",
+            contract_account_id, network_config.network_name
+        )
+    })
+}
+
+pub fn get_abi_function<'a>(
+    abi_root: &'a unc_abi::AbiRoot,
+    function_name: &str,
+) -> color_eyre::eyre::Result<&'a unc_abi::AbiFunction> {
+    abi_root
+        .body
+        .functions
+        .iter()
+        .find(|function| function.name == function_name)
+        .wrap_err_with(|| {
+            format!("The function <{function_name}> is not found in the contract ABI")
+        })
+}
+
+struct AbiFunctionItem<'a>(&'a unc_abi::AbiFunction);
+
+impl std::fmt::Display for AbiFunctionItem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tags = vec![match self.0.kind {
+            unc_abi::AbiFunctionKind::View => "view",
+            unc_abi::AbiFunctionKind::Call => "call",
+        }];
+        for modifier in &self.0.modifiers {
+            tags.push(match modifier {
+                unc_abi::AbiFunctionModifier::Init => "init",
+                unc_abi::AbiFunctionModifier::Private => "private",
+                unc_abi::AbiFunctionModifier::Payable => "payable",
+            });
+        }
+        write!(f, "{:<40} ({})", self.0.name, tags.join(", "))?;
+        if let Some(doc) = &self.0.doc {
+            write!(f, " {}", doc.lines().next().unwrap_or_default().trim())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn input_abi_function(
+    abi_root: &unc_abi::AbiRoot,
+) -> color_eyre::eyre::Result<Option<unc_abi::AbiFunction>> {
+    if abi_root.body.functions.is_empty() {
+        return Err(color_eyre::Report::msg(
+            "The contract ABI does not contain any functions",
+        ));
+    }
+    let variants = abi_root
+        .body
+        .functions
+        .iter()
+        .map(AbiFunctionItem)
+        .collect::<Vec<_>>();
+    let selected = Select::new("Which function do you want to call?", variants).prompt()?;
+    Ok(Some(selected.0.clone()))
+}
+
+/// Encodes the arguments given as a JSON object (`{"param_name": value, ...}`)
+/// according to the serialization type of the function parameters.
+///
+/// Returns the arguments in the form suitable for the `json-args` or `base64-args` modes.
+pub fn encode_function_args(
+    abi_function: &unc_abi::AbiFunction,
+    args: &serde_json::Value,
+) -> color_eyre::eyre::Result<(super::call_function_args_type::FunctionArgsType, String)> {
+    match &abi_function.params {
+        unc_abi::AbiParameters::Json { .. } => {
+            if !args.is_object() {
+                return Err(color_eyre::Report::msg(
+                    "The function arguments must be a JSON object",
+                ));
+            }
+            Ok((
+                super::call_function_args_type::FunctionArgsType::JsonArgs,
+                serde_json::to_string(args)?,
+            ))
+        }
+        unc_abi::AbiParameters::Borsh { args: params } => {
+            let mut bytes = Vec::new();
+            for param in params {
+                let value = args.get(&param.name).unwrap_or(&serde_json::Value::Null);
+                borsh_serialize_value(
+                    &param.type_schema,
+                    param.type_schema.declaration(),
+                    value,
+                    &mut bytes,
+                )
+                .wrap_err_with(|| format!("Invalid value of the parameter <{}>", param.name))?;
+            }
+            Ok((
+                super::call_function_args_type::FunctionArgsType::Base64Args,
+                unc_primitives::serialize::to_base64(&bytes),
+            ))
+        }
+    }
+}
+
+/// Prompts the function parameters one by one and returns them as a JSON object.
+pub fn input_function_args(
+    abi_root: &unc_abi::AbiRoot,
+    abi_function: &unc_abi::AbiFunction,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    let mut args = serde_json::Map::new();
+    match &abi_function.params {
+        unc_abi::AbiParameters::Json { args: params } => {
+            for param in params {
+                args.insert(
+                    param.name.clone(),
+                    input_json_schema_value(&param.name, &param.type_schema, abi_root)?,
+                );
+            }
+        }
+        unc_abi::AbiParameters::Borsh { args: params } => {
+            for param in params {
+                args.insert(
+                    param.name.clone(),
+                    input_borsh_schema_value(
+                        &param.name,
+                        &param.type_schema,
+                        param.type_schema.declaration(),
+                    )?,
+                );
+            }
+        }
+    }
+    Ok(serde_json::Value::Object(args))
+}
+
+fn resolve_schema<'a>(
+    schema: &'a Schema,
+    abi_root: &'a unc_abi::AbiRoot,
+) -> color_eyre::eyre::Result<&'a Schema> {
+    let mut schema = schema;
+    while let Schema::Object(SchemaObject {
+        reference: Some(reference),
+        ..
+    }) = schema
+    {
+        let definition_name = reference.trim_start_matches("#/definitions/");
+        schema = abi_root
+            .body
+            .root_schema
+            .definitions
+            .get(definition_name)
+            .wrap_err_with(|| format!("The type <{reference}> is not defined in the ABI"))?;
+    }
+    Ok(schema)
+}
+
+fn is_null_schema(schema: &Schema, abi_root: &unc_abi::AbiRoot) -> bool {
+    matches!(
+        resolve_schema(schema, abi_root),
+        Ok(Schema::Object(SchemaObject {
+            instance_type: Some(SingleOrVec::Single(instance_type)),
+            ..
+        })) if **instance_type == InstanceType::Null
+    )
+}
+
+fn input_json_schema_value(
+    name: &str,
+    schema: &Schema,
+    abi_root: &unc_abi::AbiRoot,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    let schema_object = match resolve_schema(schema, abi_root)? {
+        Schema::Object(schema_object) => schema_object,
+        Schema::Bool(_) => return input_raw_json_value(name),
+    };
+
+    if let Some(subschemas) = &schema_object.subschemas {
+        let variants = subschemas
+            .any_of
+            .as_ref()
+            .or(subschemas.one_of.as_ref())
+            .or(subschemas
+                .all_of
+                .as_ref()
+                .filter(|all_of| all_of.len() == 1));
+        if let Some(variants) = variants {
+            let (nullable, variants): (Vec<_>, Vec<_>) = variants
+                .iter()
+                .partition(|variant| is_null_schema(variant, abi_root));
+            if !nullable.is_empty()
+                && !Confirm::new(&format!("Do you want to set the optional <{name}>?"))
+                    .with_default(false)
+                    .prompt()?
+            {
+                return Ok(serde_json::Value::Null);
+            }
+            let variant = match variants.as_slice() {
+                [] => return Ok(serde_json::Value::Null),
+                [variant] => *variant,
+                _ => {
+                    let titles = variants
+                        .iter()
+                        .enumerate()
+                        .map(|(index, variant)| schema_title(variant, abi_root, index))
+                        .collect::<Vec<_>>();
+                    let selected =
+                        Select::new(&format!("Select the variant of <{name}>:"), titles.clone())
+                            .prompt()?;
+                    let index = titles
+                        .iter()
+                        .position(|title| *title == selected)
+                        .expect("Unexpected error");
+                    variants[index]
+                }
+            };
+            return input_json_schema_value(name, variant, abi_root);
+        }
+    }
+
+    if let Some(enum_values) = &schema_object.enum_values {
+        let variants = enum_values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+        let selected = Select::new(&format!("Select the value of <{name}>:"), variants).prompt()?;
+        return Ok(serde_json::from_str(&selected)?);
+    }
+    if let Some(const_value) = &schema_object.const_value {
+        return Ok(const_value.clone());
+    }
+
+    let instance_type = match &schema_object.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => **instance_type,
+        Some(SingleOrVec::Vec(instance_types)) => {
+            let non_null = instance_types
+                .iter()
+                .filter(|instance_type| **instance_type != InstanceType::Null)
+                .collect::<Vec<_>>();
+            if non_null.len() != instance_types.len()
+                && !Confirm::new(&format!("Do you want to set the optional <{name}>?"))
+                    .with_default(false)
+                    .prompt()?
+            {
+                return Ok(serde_json::Value::Null);
+            }
+            match non_null.as_slice() {
+                [instance_type] => **instance_type,
+                _ => return input_raw_json_value(name),
+            }
+        }
+        None if schema_object.object.is_some() => InstanceType::Object,
+        None => return input_raw_json_value(name),
+    };
+
+    let help = schema_object
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.description.clone())
+        .or_else(|| schema_object.format.clone())
+        .unwrap_or_default();
+
+    match instance_type {
+        InstanceType::Null => Ok(serde_json::Value::Null),
+        InstanceType::Boolean => Ok(serde_json::Value::Bool(
+            Confirm::new(&format!("<{name}>:")).prompt()?,
+        )),
+        InstanceType::Integer => {
+            let is_unsigned = schema_object
+                .format
+                .as_deref()
+                .map_or(false, |format| format.starts_with("uint"));
+            let value = Text::new(&format!("Enter <{name}> (integer):"))
+                .with_help_message(&help)
+                .with_validator(move |input: &str| {
+                    let is_valid = if is_unsigned {
+                        input.parse::<u64>().is_ok()
+                    } else {
+                        input.parse::<i64>().is_ok()
+                    };
+                    if is_valid {
+                        Ok(inquire::validator::Validation::Valid)
+                    } else {
+                        Ok(inquire::validator::Validation::Invalid(
+                            "The value is not a valid integer".into(),
+                        ))
+                    }
+                })
+                .prompt()?;
+            Ok(serde_json::from_str(&value)?)
+        }
+        InstanceType::Number => Ok(serde_json::json!(CustomType::<f64>::new(&format!(
+            "Enter <{name}> (number):"
+        ))
+        .with_help_message(&help)
+        .prompt()?)),
+        InstanceType::String => Ok(serde_json::Value::String(
+            Text::new(&format!("Enter <{name}> (string):"))
+                .with_help_message(&help)
+                .prompt()?,
+        )),
+        InstanceType::Array => {
+            let items_schema = match schema_object
+                .array
+                .as_ref()
+                .and_then(|array| array.items.as_ref())
+            {
+                Some(SingleOrVec::Single(items_schema)) => items_schema,
+                _ => return input_raw_json_value(name),
+            };
+            let length = CustomType::<usize>::new(&format!(
+                "How many elements do you want to add to <{name}>?"
+            ))
+            .prompt()?;
+            (0..length)
+                .map(|index| {
+                    input_json_schema_value(&format!("{name}[{index}]"), items_schema, abi_root)
+                })
+                .collect::<color_eyre::eyre::Result<Vec<_>>>()
+                .map(serde_json::Value::Array)
+        }
+        InstanceType::Object => {
+            let object = match &schema_object.object {
+                Some(object) if !object.properties.is_empty() => object,
+                _ => return input_raw_json_value(name),
+            };
+            let mut map = serde_json::Map::new();
+            for (property_name, property_schema) in &object.properties {
+                let field_name = format!("{name}.{property_name}");
+                if !object.required.contains(property_name)
+                    && !Confirm::new(&format!("Do you want to set the optional <{field_name}>?"))
+                        .with_default(false)
+                        .prompt()?
+                {
+                    continue;
+                }
+                map.insert(
+                    property_name.clone(),
+                    input_json_schema_value(&field_name, property_schema, abi_root)?,
+                );
+            }
+            Ok(serde_json::Value::Object(map))
+        }
+    }
+}
+
+fn schema_title(schema: &Schema, abi_root: &unc_abi::AbiRoot, index: usize) -> String {
+    let title = match resolve_schema(schema, abi_root) {
+        Ok(Schema::Object(schema_object)) => schema_object
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.title.clone())
+            .or_else(|| {
+                schema_object
+                    .enum_values
+                    .as_ref()
+                    .map(|values| serde_json::Value::Array(values.clone()).to_string())
+            })
+            .or_else(|| {
+                schema_object
+                    .object
+                    .as_ref()
+                    .and_then(|object| object.properties.keys().next().map(|key| key.to_string()))
+            }),
+        _ => None,
+    };
+    format!("#{} {}", index + 1, title.unwrap_or_default())
+}
+
+fn input_raw_json_value(name: &str) -> color_eyre::eyre::Result<serde_json::Value> {
+    let value = Text::new(&format!("Enter <{name}> as JSON:"))
+        .with_validator(
+            |input: &str| match serde_json::from_str::<serde_json::Value>(input) {
+                Ok(_) => Ok(inquire::validator::Validation::Valid),
+                Err(err) => Ok(inquire::validator::Validation::Invalid(
+                    format!("Invalid JSON: {err}").into(),
+                )),
+            },
+        )
+        .prompt()?;
+    Ok(serde_json::from_str(&value)?)
+}
+
+fn input_borsh_schema_value(
+    name: &str,
+    container: &BorshSchemaContainer,
+    declaration: &Declaration,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    match container.get_definition(declaration) {
+        None | Some(Definition::Primitive(_)) => input_borsh_primitive_value(name, declaration),
+        Some(Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        }) => {
+            if declaration == "String" {
+                return Ok(serde_json::Value::String(
+                    Text::new(&format!("Enter <{name}> (string):")).prompt()?,
+                ));
+            }
+            let length = if *length_width == 0 && length_range.start() == length_range.end() {
+                *length_range.start()
+            } else {
+                CustomType::<u64>::new(&format!(
+                    "How many elements do you want to add to <{name}>?"
+                ))
+                .prompt()?
+            };
+            (0..length)
+                .map(|index| {
+                    input_borsh_schema_value(&format!("{name}[{index}]"), container, elements)
+                })
+                .collect::<color_eyre::eyre::Result<Vec<_>>>()
+                .map(serde_json::Value::Array)
+        }
+        Some(Definition::Tuple { elements }) => elements
+            .iter()
+            .enumerate()
+            .map(|(index, element)| {
+                input_borsh_schema_value(&format!("{name}.{index}"), container, element)
+            })
+            .collect::<color_eyre::eyre::Result<Vec<_>>>()
+            .map(serde_json::Value::Array),
+        Some(Definition::Enum { variants, .. }) => {
+            if let Some(inner) = option_inner_declaration(variants) {
+                if !Confirm::new(&format!("Do you want to set the optional <{name}>?"))
+                    .with_default(false)
+                    .prompt()?
+                {
+                    return Ok(serde_json::Value::Null);
+                }
+                return input_borsh_schema_value(name, container, inner);
+            }
+            let variant_names = variants
+                .iter()
+                .map(|(_, variant_name, _)| variant_name.clone())
+                .collect::<Vec<_>>();
+            let selected =
+                Select::new(&format!("Select the variant of <{name}>:"), variant_names).prompt()?;
+            let (_, _, variant_declaration) = variants
+                .iter()
+                .find(|(_, variant_name, _)| *variant_name == selected)
+                .expect("Unexpected error");
+            if is_empty_struct(container, variant_declaration) {
+                return Ok(serde_json::Value::String(selected));
+            }
+            let value = input_borsh_schema_value(
+                &format!("{name}::{selected}"),
+                container,
+                variant_declaration,
+            )?;
+            Ok(serde_json::json!({ selected: value }))
+        }
+        Some(Definition::Struct { fields }) => match fields {
+            Fields::NamedFields(fields) => {
+                let mut map = serde_json::Map::new();
+                for (field_name, field_declaration) in fields {
+                    map.insert(
+                        field_name.clone(),
+                        input_borsh_schema_value(
+                            &format!("{name}.{field_name}"),
+                            container,
+                            field_declaration,
+                        )?,
+                    );
+                }
+                Ok(serde_json::Value::Object(map))
+            }
+            Fields::UnnamedFields(fields) if fields.len() == 1 => {
+                input_borsh_schema_value(name, container, &fields[0])
+            }
+            Fields::UnnamedFields(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field_declaration)| {
+                    input_borsh_schema_value(
+                        &format!("{name}.{index}"),
+                        container,
+                        field_declaration,
+                    )
+                })
+                .collect::<color_eyre::eyre::Result<Vec<_>>>()
+                .map(serde_json::Value::Array),
+            Fields::Empty => Ok(serde_json::Value::Null),
+        },
+    }
+}
+
+fn input_borsh_primitive_value(
+    name: &str,
+    declaration: &Declaration,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    match declaration.as_str() {
+        "bool" => Ok(serde_json::Value::Bool(
+            Confirm::new(&format!("<{name}>:")).prompt()?,
+        )),
+        "()" => Ok(serde_json::Value::Null),
+        "String" | "str" => Ok(serde_json::Value::String(
+            Text::new(&format!("Enter <{name}> (string):")).prompt()?,
+        )),
+        "f32" | "f64" => Ok(serde_json::json!(CustomType::<f64>::new(&format!(
+            "Enter <{name}> ({declaration}):"
+        ))
+        .prompt()?)),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" => {
+            let integer_declaration = declaration.clone();
+            let value = Text::new(&format!("Enter <{name}> ({declaration}):"))
+                .with_validator(move |input: &str| {
+                    match integer_to_le_bytes(&integer_declaration, input) {
+                        Ok(_) => Ok(inquire::validator::Validation::Valid),
+                        Err(err) => Ok(inquire::validator::Validation::Invalid(
+                            err.to_string().into(),
+                        )),
+                    }
+                })
+                .prompt()?;
+            Ok(if matches!(declaration.as_str(), "u128" | "i128") {
+                serde_json::Value::String(value)
+            } else {
+                serde_json::from_str(&value)?
+            })
+        }
+        _ => input_raw_json_value(&format!("{name} ({declaration})")),
+    }
+}
+
+fn option_inner_declaration(
+    variants: &[(borsh::schema::DiscriminantValue, String, Declaration)],
+) -> Option<&Declaration> {
+    match variants {
+        [(_, none, _), (_, some, inner)] if none == "None" && some == "Some" => Some(inner),
+        _ => None,
+    }
+}
+
+fn is_empty_struct(container: &BorshSchemaContainer, declaration: &Declaration) -> bool {
+    matches!(
+        container.get_definition(declaration),
+        Some(Definition::Struct {
+            fields: Fields::Empty
+        })
+    )
+}
+
+fn integer_to_le_bytes(declaration: &str, value: &str) -> color_eyre::eyre::Result<Vec<u8>> {
+    let invalid = || format!("The value <{value}> is not a valid {declaration}");
+    Ok(match declaration {
+        "u8" => value
+            .parse::<u8>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "u16" => value
+            .parse::<u16>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "u32" => value
+            .parse::<u32>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "u64" => value
+            .parse::<u64>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "u128" => value
+            .parse::<u128>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "i8" => value
+            .parse::<i8>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "i16" => value
+            .parse::<i16>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "i32" => value
+            .parse::<i32>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "i64" => value
+            .parse::<i64>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        "i128" => value
+            .parse::<i128>()
+            .wrap_err_with(invalid)?
+            .to_le_bytes()
+            .to_vec(),
+        _ => {
+            return Err(color_eyre::eyre::eyre!(
+                "<{declaration}> is not an integer type"
+            ))
+        }
+    })
+}
+
+fn write_length(
+    length_width: u8,
+    length: usize,
+    out: &mut Vec<u8>,
+) -> color_eyre::eyre::Result<()> {
+    let length_bytes = (length as u64).to_le_bytes();
+    let width = usize::from(length_width);
+    if length_bytes[width.min(8)..].iter().any(|byte| *byte != 0) {
+        return Err(color_eyre::eyre::eyre!(
+            "The length {length} does not fit into {length_width} bytes"
+        ));
+    }
+    out.extend_from_slice(&length_bytes[..width.min(8)]);
+    Ok(())
+}
+
+/// Serializes the JSON value with Borsh following the given Borsh schema.
+pub fn borsh_serialize_value(
+    container: &BorshSchemaContainer,
+    declaration: &Declaration,
+    value: &serde_json::Value,
+    out: &mut Vec<u8>,
+) -> color_eyre::eyre::Result<()> {
+    match container.get_definition(declaration) {
+        None | Some(Definition::Primitive(_)) => match (declaration.as_str(), value) {
+            ("bool", serde_json::Value::Bool(value)) => out.push(u8::from(*value)),
+            ("()", _) => {}
+            ("String" | "str", serde_json::Value::String(value)) => {
+                write_length(4, value.len(), out)?;
+                out.extend_from_slice(value.as_bytes());
+            }
+            ("f32", serde_json::Value::Number(value)) => out.extend_from_slice(
+                &(value.as_f64().wrap_err("Expected a number")? as f32).to_le_bytes(),
+            ),
+            ("f64", serde_json::Value::Number(value)) => {
+                out.extend_from_slice(&value.as_f64().wrap_err("Expected a number")?.to_le_bytes())
+            }
+            (integer, serde_json::Value::Number(value)) => {
+                out.extend(integer_to_le_bytes(integer, &value.to_string())?)
+            }
+            (integer, serde_json::Value::String(value)) => {
+                out.extend(integer_to_le_bytes(integer, value)?)
+            }
+            _ => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The value {value} does not match the type <{declaration}>"
+                ))
+            }
+        },
+        Some(Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        }) => {
+            if let serde_json::Value::String(string) = value {
+                if elements == "u8" {
+                    write_length(*length_width, string.len(), out)?;
+                    out.extend_from_slice(string.as_bytes());
+                    return Ok(());
+                }
+            }
+            let items = value.as_array().wrap_err_with(|| {
+                format!("Expected an array for the type <{declaration}>, got {value}")
+            })?;
+            if !length_range.contains(&(items.len() as u64)) {
+                return Err(color_eyre::eyre::eyre!(
+                    "The number of elements of <{declaration}> must be in the range {length_range:?}"
+                ));
+            }
+            write_length(*length_width, items.len(), out)?;
+            for item in items {
+                borsh_serialize_value(container, elements, item, out)?;
+            }
+        }
+        Some(Definition::Tuple { elements }) => {
+            let items = value.as_array().wrap_err_with(|| {
+                format!("Expected an array for the type <{declaration}>, got {value}")
+            })?;
+            if items.len() != elements.len() {
+                return Err(color_eyre::eyre::eyre!(
+                    "Expected {} elements for the type <{declaration}>",
+                    elements.len()
+                ));
+            }
+            for (element, item) in elements.iter().zip(items) {
+                borsh_serialize_value(container, element, item, out)?;
+            }
+        }
+        Some(Definition::Enum {
+            tag_width,
+            variants,
+        }) => {
+            let (variant_name, inner_value) = match value {
+                serde_json::Value::Null if option_inner_declaration(variants).is_some() => {
+                    ("None".to_string(), serde_json::Value::Null)
+                }
+                _ if option_inner_declaration(variants).is_some() => {
+                    ("Some".to_string(), value.clone())
+                }
+                serde_json::Value::String(variant_name) => {
+                    (variant_name.clone(), serde_json::Value::Null)
+                }
+                serde_json::Value::Object(map) if map.len() == 1 => {
+                    let (variant_name, inner_value) = map.iter().next().expect("Unexpected error");
+                    (variant_name.clone(), inner_value.clone())
+                }
+                _ => {
+                    return Err(color_eyre::eyre::eyre!(
+                        "Expected a variant name or {{\"Variant\": value}} for the type <{declaration}>, got {value}"
+                    ))
+                }
+            };
+            let (discriminant, _, variant_declaration) = variants
+                .iter()
+                .find(|(_, name, _)| *name == variant_name)
+                .wrap_err_with(|| {
+                    format!("<{variant_name}> is not a variant of the type <{declaration}>")
+                })?;
+            let tag_bytes = discriminant.to_le_bytes();
+            out.extend_from_slice(&tag_bytes[..usize::from(*tag_width).min(8)]);
+            borsh_serialize_value(container, variant_declaration, &inner_value, out)?;
+        }
+        Some(Definition::Struct { fields }) => match fields {
+            Fields::NamedFields(fields) => {
+                let map = value.as_object().wrap_err_with(|| {
+                    format!("Expected an object for the type <{declaration}>, got {value}")
+                })?;
+                for (field_name, field_declaration) in fields {
+                    borsh_serialize_value(
+                        container,
+                        field_declaration,
+                        map.get(field_name).unwrap_or(&serde_json::Value::Null),
+                        out,
+                    )
+                    .wrap_err_with(|| format!("Invalid value of the field <{field_name}>"))?;
+                }
+            }
+            Fields::UnnamedFields(fields) if fields.len() == 1 => {
+                borsh_serialize_value(container, &fields[0], value, out)?;
+            }
+            Fields::UnnamedFields(fields) => {
+                let items = value.as_array().wrap_err_with(|| {
+                    format!("Expected an array for the type <{declaration}>, got {value}")
+                })?;
+                if items.len() != fields.len() {
+                    return Err(color_eyre::eyre::eyre!(
+                        "Expected {} elements for the type <{declaration}>",
+                        fields.len()
+                    ));
+                }
+                for (field_declaration, item) in fields.iter().zip(items) {
+                    borsh_serialize_value(container, field_declaration, item, out)?;
+                }
+            }
+            Fields::Empty => {}
+        },
+    }
+    Ok(())
+}
+
+/// Fills in the `call-function` properties missing on the command line, and resolves the
+/// `abi-args` mode into `json-args` or `base64-args` so that the resulting command line can be
+/// re-run without the interactive prompts.
+///
+/// With `abi-args`, the function is selected from the ABI if `function_name` is not provided, and
+/// the arguments are prompted one by one if `function_args` is not provided; otherwise
+/// `function_args` is treated as a JSON object and encoded according to the ABI. The ABI is
+/// fetched from the network of the call: `network_name` comes from the command line, or it is
+/// selected here and filled in, so that it is not asked again.
+pub fn input_call_function_properties(
+    context: &crate::GlobalContext,
+    contract_account_id: &mut Option<crate::types::account_id::AccountId>,
+    function_name: &mut Option<String>,
+    function_args_type: &mut Option<super::call_function_args_type::CallFunctionArgsType>,
+    function_args: &mut Option<String>,
+    network_name: &mut Option<String>,
+) -> color_eyre::eyre::Result<Option<()>> {
+    if contract_account_id.is_none() {
+        *contract_account_id = crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )?;
+    }
+    let Some(contract_account_id) = contract_account_id.clone() else {
+        return Ok(None);
+    };
+    let contract_account_id = contract_account_id.resolve(&context.config, None)?;
+
+    if function_args_type.is_none() {
+        *function_args_type = super::call_function_args_type::input_call_function_args_type()?;
+    }
+    match function_args_type {
+        None => return Ok(None),
+        Some(super::call_function_args_type::CallFunctionArgsType::AbiArgs) => {
+            if context.offline {
+                return Err(color_eyre::Report::msg(
+                    "The abi-args mode needs the contract ABI from the network, it cannot be used in offline mode",
+                ));
+            }
+            if network_name.is_none() {
+                *network_name = crate::common::input_network_name(
+                    &context.config,
+                    &[contract_account_id.clone()],
+                )?;
+            }
+            let Some(network_name) = network_name.as_ref() else {
+                return Ok(None);
+            };
+            let network_config = context
+                .config
+                .network_connection
+                .get(network_name)
+                .wrap_err_with(|| format!("The network <{network_name}> is not configured"))?;
+            let abi_root = get_contract_abi(network_config, &contract_account_id)?;
+
+            let abi_function = match function_name {
+                Some(function_name) => get_abi_function(&abi_root, function_name)?.clone(),
+                None => match input_abi_function(&abi_root)? {
+                    Some(abi_function) => abi_function,
+                    None => return Ok(None),
+                },
+            };
+            let args = match function_args {
+                Some(function_args) => serde_json::from_str(function_args)
+                    .wrap_err("The abi-args arguments must be a JSON object")?,
+                None => input_function_args(&abi_root, &abi_function)?,
+            };
+            let (encoded_args_type, encoded_args) = encode_function_args(&abi_function, &args)?;
+            *function_name = Some(abi_function.name);
+            *function_args_type = Some(encoded_args_type.into());
+            *function_args = Some(encoded_args);
+        }
+        Some(_) => {}
+    }
+
+    if function_name.is_none() {
+        *function_name = Some(Text::new("What is the name of the function?").prompt()?);
+    }
+    if function_args.is_none() {
+        *function_args = Some(Text::new("Enter the arguments to this function:").prompt()?);
+    }
+    Ok(Some(()))
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8], length: usize) -> color_eyre::eyre::Result<&'a [u8]> {
+    if bytes.len() < length {
+        return Err(color_eyre::Report::msg(
+            "Unexpected end of the Borsh-serialized data",
+        ));
+    }
+    let (head, tail) = bytes.split_at(length);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn read_uint(bytes: &mut &[u8], width: u8) -> color_eyre::eyre::Result<u64> {
+    let width = usize::from(width).min(8);
+    let mut buffer = [0u8; 8];
+    buffer[..width].copy_from_slice(read_bytes(bytes, width)?);
+    Ok(u64::from_le_bytes(buffer))
+}
+
+/// Deserializes the Borsh-serialized data into a JSON value following the given Borsh schema.
+///
+/// The produced JSON value has the same shape which is accepted by [borsh_serialize_value].
+pub fn borsh_deserialize_value(
+    container: &BorshSchemaContainer,
+    declaration: &Declaration,
+    bytes: &mut &[u8],
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    macro_rules! read_number {
+        ($type:ty) => {{
+            let mut buffer = [0u8; std::mem::size_of::<$type>()];
+            buffer.copy_from_slice(read_bytes(bytes, std::mem::size_of::<$type>())?);
+            <$type>::from_le_bytes(buffer)
+        }};
+    }
+    Ok(match container.get_definition(declaration) {
+        None | Some(Definition::Primitive(_)) => match declaration.as_str() {
+            "bool" => serde_json::Value::Bool(read_number!(u8) != 0),
+            "()" => serde_json::Value::Null,
+            "String" | "str" => {
+                let length = read_uint(bytes, 4)? as usize;
+                serde_json::Value::String(
+                    String::from_utf8(read_bytes(bytes, length)?.to_vec())
+                        .wrap_err("The string is not a valid UTF-8")?,
+                )
+            }
+            "u8" => serde_json::json!(read_number!(u8)),
+            "u16" => serde_json::json!(read_number!(u16)),
+            "u32" => serde_json::json!(read_number!(u32)),
+            "u64" => serde_json::json!(read_number!(u64)),
+            "u128" => serde_json::Value::String(read_number!(u128).to_string()),
+            "i8" => serde_json::json!(read_number!(i8)),
+            "i16" => serde_json::json!(read_number!(i16)),
+            "i32" => serde_json::json!(read_number!(i32)),
+            "i64" => serde_json::json!(read_number!(i64)),
+            "i128" => serde_json::Value::String(read_number!(i128).to_string()),
+            "f32" => serde_json::json!(read_number!(f32)),
+            "f64" => serde_json::json!(read_number!(f64)),
+            _ => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The type <{declaration}> is not defined in the Borsh schema"
+                ))
+            }
+        },
+        Some(Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        }) => {
+            let length = if *length_width == 0 {
+                if length_range.start() != length_range.end() {
+                    return Err(color_eyre::eyre::eyre!(
+                        "The length of <{declaration}> cannot be determined from the Borsh schema"
+                    ));
+                }
+                *length_range.start()
+            } else {
+                read_uint(bytes, *length_width)?
+            };
+            if declaration == "String" && elements == "u8" {
+                serde_json::Value::String(
+                    String::from_utf8(read_bytes(bytes, length as usize)?.to_vec())
+                        .wrap_err("The string is not a valid UTF-8")?,
+                )
+            } else {
+                serde_json::Value::Array(
+                    (0..length)
+                        .map(|_| borsh_deserialize_value(container, elements, bytes))
+                        .collect::<color_eyre::eyre::Result<_>>()?,
+                )
+            }
+        }
+        Some(Definition::Tuple { elements }) => serde_json::Value::Array(
+            elements
+                .iter()
+                .map(|element| borsh_deserialize_value(container, element, bytes))
+                .collect::<color_eyre::eyre::Result<_>>()?,
+        ),
+        Some(Definition::Enum {
+            tag_width,
+            variants,
+        }) => {
+            let tag = read_uint(bytes, *tag_width)? as i64;
+            let (_, variant_name, variant_declaration) = variants
+                .iter()
+                .find(|(discriminant, _, _)| *discriminant == tag)
+                .wrap_err_with(|| {
+                    format!("Unknown variant tag {tag} of the type <{declaration}>")
+                })?;
+            let value = borsh_deserialize_value(container, variant_declaration, bytes)?;
+            if option_inner_declaration(variants).is_some() {
+                value
+            } else if is_empty_struct(container, variant_declaration) {
+                serde_json::Value::String(variant_name.clone())
+            } else {
+                serde_json::json!({ variant_name.clone(): value })
+            }
+        }
+        Some(Definition::Struct { fields }) => match fields {
+            Fields::NamedFields(fields) => {
+                let mut map = serde_json::Map::new();
+                for (field_name, field_declaration) in fields {
+                    map.insert(
+                        field_name.clone(),
+                        borsh_deserialize_value(container, field_declaration, bytes)?,
+                    );
+                }
+                serde_json::Value::Object(map)
+            }
+            Fields::UnnamedFields(fields) if fields.len() == 1 => {
+                borsh_deserialize_value(container, &fields[0], bytes)?
+            }
+            Fields::UnnamedFields(fields) => serde_json::Value::Array(
+                fields
+                    .iter()
+                    .map(|field_declaration| {
+                        borsh_deserialize_value(container, field_declaration, bytes)
+                    })
+                    .collect::<color_eyre::eyre::Result<_>>()?,
+            ),
+            Fields::Empty => serde_json::Value::Null,
+        },
+    })
+}
+
+/// Decodes the value returned by a contract function according to its ABI type.
+pub fn decode_result(
+    abi_type: &unc_abi::AbiType,
+    result: &[u8],
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    match abi_type {
+        unc_abi::AbiType::Json { .. } => {
+            serde_json::from_slice(result).wrap_err("The returned value is not a valid JSON")
+        }
+        unc_abi::AbiType::Borsh { type_schema } => {
+            let mut bytes = result;
+            let value =
+                borsh_deserialize_value(type_schema, type_schema.declaration(), &mut bytes)?;
+            if !bytes.is_empty() {
+                return Err(color_eyre::eyre::eyre!(
+                    "{} bytes left after decoding the returned value",
+                    bytes.len()
+                ));
+            }
+            Ok(value)
+        }
+    }
+}
+
+/// Decodes the value returned by the contract function using the ABI embedded into the contract.
+///
+/// Returns `None` if the contract has no embedded ABI or the function result type is not described there.
+pub fn decode_function_result(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_id: &unc_primitives::types::AccountId,
+    function_name: &str,
+    result: &[u8],
+) -> color_eyre::eyre::Result<Option<serde_json::Value>> {
+    let abi_root = match crate::common::block_on(
+        crate::commands::contract::inspect::get_contract_abi(
+            &network_config.json_rpc_client(),
+            &unc_primitives::types::Finality::Final.into(),
+            contract_account_id,
+        ),
+    ) {
+        Ok(abi_root) => abi_root,
+        Err(crate::commands::contract::inspect::FetchAbiError::AbiNotSupported) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    match get_abi_function(&abi_root, function_name)?.result.as_ref() {
+        Some(abi_type) => decode_result(abi_type, result).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Decodes the `SuccessValue` of a transaction whose last action is a function call
+/// using the ABI embedded into the receiver contract.
+pub fn decode_transaction_result(
+    network_config: &crate::config::NetworkConfig,
+    transaction_info: &unc_primitives::views::FinalExecutionOutcomeView,
+    result: &[u8],
+) -> color_eyre::eyre::Result<Option<serde_json::Value>> {
+    let function_name = transaction_info
+        .transaction
+        .actions
+        .iter()
+        .rev()
+        .find_map(|action| match action {
+            unc_primitives::views::ActionView::FunctionCall { method_name, .. } => {
+                Some(method_name)
+            }
+            _ => None,
+        });
+    match function_name {
+        Some(function_name) => decode_function_result(
+            network_config,
+            &transaction_info.transaction.receiver_id,
+            function_name,
+            result,
+        ),
+        None => Ok(None),
+    }
+}
+
+/// Same as [`decode_transaction_result`], but the failures to fetch or use the ABI are reported
+/// as a warning: the transaction itself has already been executed at this point.
+pub fn decode_transaction_result_or_warn(
+    network_config: &crate::config::NetworkConfig,
+    transaction_info: &unc_primitives::views::FinalExecutionOutcomeView,
+    result: &[u8],
+) -> Option<serde_json::Value> {
+    decode_transaction_result(network_config, transaction_info, result).unwrap_or_else(|err| {
+        eprintln!("Warning: the result cannot be decoded with the contract ABI: {err:#}");
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<T: borsh::BorshSerialize + borsh::BorshSchema>(
+        value: &T,
+        json: serde_json::Value,
+    ) {
+        let container = BorshSchemaContainer::for_type::<T>();
+        let bytes = borsh::to_vec(value).unwrap();
+
+        let mut encoded = Vec::new();
+        borsh_serialize_value(&container, container.declaration(), &json, &mut encoded).unwrap();
+        assert_eq!(encoded, bytes);
+
+        let mut slice = bytes.as_slice();
+        let decoded =
+            borsh_deserialize_value(&container, container.declaration(), &mut slice).unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(decoded, json);
+    }
+
+    #[test]
+    fn borsh_primitives_roundtrip() {
+        roundtrip(&true, serde_json::json!(true));
+        roundtrip(&42u64, serde_json::json!(42));
+        roundtrip(&-7i32, serde_json::json!(-7));
+        roundtrip(
+            &340282366920938463463374607431768211455u128,
+            serde_json::json!("340282366920938463463374607431768211455"),
+        );
+        roundtrip(&"unc".to_string(), serde_json::json!("unc"));
+    }
+
+    #[test]
+    fn borsh_composite_types_roundtrip() {
+        roundtrip(&Some(5u32), serde_json::json!(5));
+        roundtrip(&None::<u32>, serde_json::json!(null));
+        roundtrip(&vec![1u16, 2, 3], serde_json::json!([1, 2, 3]));
+        roundtrip(
+            &("alice".to_string(), 10u8),
+            serde_json::json!(["alice", 10]),
+        );
+        roundtrip(&Ok::<u8, String>(1), serde_json::json!({ "Ok": 1 }));
+    }
+
+    #[test]
+    fn transaction_result_decode_failure_is_a_warning() {
+        let mut network_config =
+            crate::config::Config::default().network_connection["custom"].clone();
+        network_config.rpc_url = "http://127.0.0.1:1/".parse().unwrap();
+        let public_key = unc_crypto::PublicKey::empty(unc_crypto::KeyType::ED25519);
+        let signature = unc_crypto::Signature::empty(unc_crypto::KeyType::ED25519);
+        let outcome = serde_json::json!({
+            "logs": [],
+            "receipt_ids": [],
+            "gas_burnt": 0,
+            "tokens_burnt": "0",
+            "executor_id": "alice.unc",
+            "status": { "SuccessValue": "" },
+        });
+        let transaction_info: unc_primitives::views::FinalExecutionOutcomeView =
+            serde_json::from_value(serde_json::json!({
+                "status": { "SuccessValue": "AQI=" },
+                "transaction": {
+                    "signer_id": "alice.unc",
+                    "public_key": public_key,
+                    "nonce": 1,
+                    "receiver_id": "contract.unc",
+                    "actions": [{ "FunctionCall": {
+                        "method_name": "get",
+                        "args": "",
+                        "gas": 0,
+                        "deposit": "0",
+                    }}],
+                    "signature": signature,
+                    "hash": unc_primitives::hash::CryptoHash::default(),
+                },
+                "transaction_outcome": {
+                    "proof": [],
+                    "block_hash": unc_primitives::hash::CryptoHash::default(),
+                    "id": unc_primitives::hash::CryptoHash::default(),
+                    "outcome": outcome,
+                },
+                "receipts_outcome": [],
+            }))
+            .unwrap();
+
+        assert!(decode_transaction_result(&network_config, &transaction_info, &[1, 2]).is_err());
+        assert_eq!(
+            decode_transaction_result_or_warn(&network_config, &transaction_info, &[1, 2]),
+            None
+        );
+    }
+}
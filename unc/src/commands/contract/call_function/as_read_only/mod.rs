@@ -6,6 +6,7 @@ use crate::common::JsonRpcClientExt;
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = CallFunctionViewContext)]
+#[interactive_clap(skip_default_from_cli)]
 pub struct CallFunctionView {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the contract account ID?
@@ -15,7 +16,7 @@ pub struct CallFunctionView {
     #[interactive_clap(value_enum)]
     #[interactive_clap(skip_default_input_arg)]
     /// How do you want to pass the function call arguments?
-    function_args_type: super::call_function_args_type::FunctionArgsType,
+    function_args_type: super::call_function_args_type::CallFunctionArgsType,
     /// Enter the arguments to this function:
     function_args: String,
     #[interactive_clap(named_arg)]
@@ -31,14 +32,17 @@ impl CallFunctionViewContext {
         previous_context: crate::GlobalContext,
         scope: &<CallFunctionView as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope
+            .contract_account_id
+            .resolve(&previous_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let function_args = scope.function_args.clone();
             let function_args_type = scope.function_args_type.clone();
-            let account_id: unc_primitives::types::AccountId = scope.contract_account_id.clone().into();
+            let account_id = account_id.clone();
             let function_name = scope.function_name.clone();
 
             move |network_config, block_reference| {
-                let args = super::call_function_args_type::function_args(
+                let args = super::call_function_args_type::call_function_args(
                     function_args.clone(),
                     function_args_type.clone(),
                 )?;
@@ -64,6 +68,17 @@ impl CallFunctionViewContext {
                     eprintln!("Empty result");
                 } else if let Ok(json_result) = call_result.parse_result_from_json::<serde_json::Value>() {
                     println!("{}", serde_json::to_string_pretty(&json_result)?);
+                } else if let Some(decoded_result) = super::abi::decode_function_result(
+                    network_config,
+                    &account_id,
+                    &function_name,
+                    &call_result.result,
+                )
+                .unwrap_or_else(|err| {
+                    eprintln!("Warning: the result cannot be decoded with the contract ABI: {err:#}");
+                    None
+                }) {
+                    println!("{}", serde_json::to_string_pretty(&decoded_result)?);
                 } else if let Ok(string_result) = String::from_utf8(call_result.result) {
                     println!("{string_result}");
                 } else {
@@ -76,7 +91,7 @@ impl CallFunctionViewContext {
 
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
             config: previous_context.config,
-            interacting_with_account_ids: vec![scope.contract_account_id.clone().into()],
+            interacting_with_account_ids: vec![account_id],
             on_after_getting_block_reference_callback,
         }))
     }
@@ -88,19 +103,97 @@ impl From<CallFunctionViewContext> for crate::network_view_at_block::ArgsForView
     }
 }
 
-impl CallFunctionView {
-    fn input_function_args_type(
-        _context: &crate::GlobalContext,
-    ) -> color_eyre::eyre::Result<Option<super::call_function_args_type::FunctionArgsType>> {
-        super::call_function_args_type::input_function_args_type()
-    }
+impl interactive_clap::FromCli for CallFunctionView {
+    type FromCliContext = crate::GlobalContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
 
-    pub fn input_contract_account_id(
-        context: &crate::GlobalContext,
-    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
-        crate::common::input_non_signer_account_id_from_used_account_list(
-            &context.config.credentials_home_dir,
-            "What is the contract account ID?",
-        )
+        let mut network_name = clap_variant.network_config.as_ref().and_then(
+            |ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig(cli_arg)| {
+                cli_arg.network_name.clone()
+            },
+        );
+        match super::abi::input_call_function_properties(
+            &context,
+            &mut clap_variant.contract_account_id,
+            &mut clap_variant.function_name,
+            &mut clap_variant.function_args_type,
+            &mut clap_variant.function_args,
+            &mut network_name,
+        ) {
+            Ok(Some(())) => {}
+            Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        }
+        if network_name.is_some() {
+            let ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig(cli_arg) =
+                clap_variant.network_config.get_or_insert_with(|| {
+                    ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig(
+                        Default::default(),
+                    )
+                });
+            cli_arg.network_name = network_name;
+        }
+        let contract_account_id = clap_variant
+            .contract_account_id
+            .clone()
+            .expect("Unexpected error");
+        let function_name = clap_variant
+            .function_name
+            .clone()
+            .expect("Unexpected error");
+        let function_args_type = clap_variant
+            .function_args_type
+            .clone()
+            .expect("Unexpected error");
+        let function_args = clap_variant
+            .function_args
+            .clone()
+            .expect("Unexpected error");
+
+        let new_context_scope = InteractiveClapContextScopeForCallFunctionView {
+            contract_account_id,
+            function_name,
+            function_args_type,
+            function_args,
+        };
+        let output_context =
+            match CallFunctionViewContext::from_previous_context(context, &new_context_scope) {
+                Ok(new_context) => new_context,
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+
+        let optional_field = clap_variant.network_config.take().map(
+            |ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig(cli_arg)| cli_arg,
+        );
+        match <crate::network_view_at_block::NetworkViewAtBlockArgs as interactive_clap::FromCli>::from_cli(
+            optional_field,
+            output_context.into(),
+        ) {
+            interactive_clap::ResultFromCli::Ok(cli_field) => {
+                clap_variant.network_config = Some(ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig(cli_field));
+                interactive_clap::ResultFromCli::Ok(clap_variant)
+            }
+            interactive_clap::ResultFromCli::Cancel(optional_cli_field) => {
+                clap_variant.network_config = optional_cli_field.map(ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig);
+                interactive_clap::ResultFromCli::Cancel(Some(clap_variant))
+            }
+            interactive_clap::ResultFromCli::Back => interactive_clap::ResultFromCli::Back,
+            interactive_clap::ResultFromCli::Err(optional_cli_field, err) => {
+                clap_variant.network_config = optional_cli_field.map(ClapNamedArgNetworkViewAtBlockArgsForCallFunctionView::NetworkConfig);
+                interactive_clap::ResultFromCli::Err(Some(clap_variant), err)
+            }
+        }
     }
 }
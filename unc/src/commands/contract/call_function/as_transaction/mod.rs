@@ -3,6 +3,7 @@ use inquire::CustomType;
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = CallFunctionPropertiesContext)]
+#[interactive_clap(skip_default_from_cli)]
 pub struct CallFunctionProperties {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the contract account ID?
@@ -12,7 +13,7 @@ pub struct CallFunctionProperties {
     #[interactive_clap(value_enum)]
     #[interactive_clap(skip_default_input_arg)]
     /// How do you want to pass the function call arguments?
-    function_args_type: super::call_function_args_type::FunctionArgsType,
+    function_args_type: super::call_function_args_type::CallFunctionArgsType,
     /// Enter the arguments to this function:
     function_args: String,
     #[interactive_clap(named_arg)]
@@ -33,33 +34,146 @@ impl CallFunctionPropertiesContext {
         previous_context: crate::GlobalContext,
         scope: &<CallFunctionProperties as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
-        let function_args = super::call_function_args_type::function_args(
+        let contract_account_id = scope
+            .contract_account_id
+            .resolve(&previous_context.config, None)?;
+        let function_args = super::call_function_args_type::call_function_args(
             scope.function_args.clone(),
             scope.function_args_type.clone(),
         )?;
         Ok(Self {
             global_context: previous_context,
-            receiver_account_id: scope.contract_account_id.clone().into(),
+            receiver_account_id: contract_account_id,
             function_name: scope.function_name.clone(),
             function_args,
         })
     }
 }
 
-impl CallFunctionProperties {
-    pub fn input_contract_account_id(
-        context: &crate::GlobalContext,
-    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
-        crate::common::input_non_signer_account_id_from_used_account_list(
-            &context.config.credentials_home_dir,
-            "What is the contract account ID?",
-        )
-    }
+impl interactive_clap::FromCli for CallFunctionProperties {
+    type FromCliContext = crate::GlobalContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
 
-    fn input_function_args_type(
-        _context: &crate::GlobalContext,
-    ) -> color_eyre::eyre::Result<Option<super::call_function_args_type::FunctionArgsType>> {
-        super::call_function_args_type::input_function_args_type()
+        let mut network_name = clap_variant
+            .prepaid_gas
+            .as_ref()
+            .and_then(
+                |ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas(prepaid_gas)| {
+                    prepaid_gas.attached_deposit.as_ref()
+                },
+            )
+            .and_then(
+                |ClapNamedArgDepositForPrepaidGas::AttachedDeposit(deposit)| {
+                    deposit.sign_as.as_ref()
+                },
+            )
+            .and_then(|ClapNamedArgSignerAccountIdForDeposit::SignAs(signer)| {
+                signer.network_config.as_ref()
+            })
+            .and_then(
+                |ClapNamedArgNetworkForTransactionArgsForSignerAccountId::NetworkConfig(
+                    network_config,
+                )| network_config.network_name.clone(),
+            );
+        match super::abi::input_call_function_properties(
+            &context,
+            &mut clap_variant.contract_account_id,
+            &mut clap_variant.function_name,
+            &mut clap_variant.function_args_type,
+            &mut clap_variant.function_args,
+            &mut network_name,
+        ) {
+            Ok(Some(())) => {}
+            Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        }
+        if network_name.is_some() {
+            let ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas(prepaid_gas) =
+                clap_variant.prepaid_gas.get_or_insert_with(|| {
+                    ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas(Default::default())
+                });
+            let ClapNamedArgDepositForPrepaidGas::AttachedDeposit(deposit) =
+                prepaid_gas.attached_deposit.get_or_insert_with(|| {
+                    ClapNamedArgDepositForPrepaidGas::AttachedDeposit(Default::default())
+                });
+            let ClapNamedArgSignerAccountIdForDeposit::SignAs(signer) =
+                deposit.sign_as.get_or_insert_with(|| {
+                    ClapNamedArgSignerAccountIdForDeposit::SignAs(Default::default())
+                });
+            let ClapNamedArgNetworkForTransactionArgsForSignerAccountId::NetworkConfig(
+                network_config,
+            ) = signer.network_config.get_or_insert_with(|| {
+                ClapNamedArgNetworkForTransactionArgsForSignerAccountId::NetworkConfig(
+                    Default::default(),
+                )
+            });
+            network_config.network_name = network_name;
+        }
+        let contract_account_id = clap_variant
+            .contract_account_id
+            .clone()
+            .expect("Unexpected error");
+        let function_name = clap_variant
+            .function_name
+            .clone()
+            .expect("Unexpected error");
+        let function_args_type = clap_variant
+            .function_args_type
+            .clone()
+            .expect("Unexpected error");
+        let function_args = clap_variant
+            .function_args
+            .clone()
+            .expect("Unexpected error");
+
+        let new_context_scope = InteractiveClapContextScopeForCallFunctionProperties {
+            contract_account_id,
+            function_name,
+            function_args_type,
+            function_args,
+        };
+        let output_context =
+            match CallFunctionPropertiesContext::from_previous_context(context, &new_context_scope)
+            {
+                Ok(new_context) => new_context,
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+
+        let optional_field = clap_variant
+            .prepaid_gas
+            .take()
+            .map(|ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas(cli_arg)| cli_arg);
+        match <PrepaidGas as interactive_clap::FromCli>::from_cli(optional_field, output_context) {
+            interactive_clap::ResultFromCli::Ok(cli_field) => {
+                clap_variant.prepaid_gas = Some(
+                    ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas(cli_field),
+                );
+                interactive_clap::ResultFromCli::Ok(clap_variant)
+            }
+            interactive_clap::ResultFromCli::Cancel(optional_cli_field) => {
+                clap_variant.prepaid_gas = optional_cli_field
+                    .map(ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas);
+                interactive_clap::ResultFromCli::Cancel(Some(clap_variant))
+            }
+            interactive_clap::ResultFromCli::Back => interactive_clap::ResultFromCli::Back,
+            interactive_clap::ResultFromCli::Err(optional_cli_field, err) => {
+                clap_variant.prepaid_gas = optional_cli_field
+                    .map(ClapNamedArgPrepaidGasForCallFunctionProperties::PrepaidGas);
+                interactive_clap::ResultFromCli::Err(Some(clap_variant), err)
+            }
+        }
     }
 }
 
@@ -204,6 +318,9 @@ impl SignerAccountIdContext {
         previous_context: DepositContext,
         scope: &<SignerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id = scope
+            .signer_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         Ok(Self {
             global_context: previous_context.global_context,
             receiver_account_id: previous_context.receiver_account_id,
@@ -211,7 +328,7 @@ impl SignerAccountIdContext {
             function_args: previous_context.function_args,
             gas: previous_context.gas,
             deposit: previous_context.deposit,
-            signer_account_id: scope.signer_account_id.clone().into(),
+            signer_account_id,
         })
     }
 }
@@ -103,3 +103,143 @@ pub fn function_args(
         }
     }
 }
+
+#[derive(Debug, EnumDiscriminants, Clone, clap::ValueEnum)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// How do you want to pass the function call arguments?
+pub enum CallFunctionArgsType {
+    #[strum_discriminants(strum(
+        message = "json-args    - Valid JSON arguments (e.g. {\"token_id\": \"42\"})"
+    ))]
+    /// Valid JSON arguments (e.g. {"token_id": "42"})
+    JsonArgs,
+    #[strum_discriminants(strum(message = "text-args    - Arbitrary text arguments"))]
+    /// Arbitrary text arguments
+    TextArgs,
+    #[strum_discriminants(strum(message = "base64-args  - Base64-encoded string (e.g. e30=)"))]
+    /// Base64-encoded string (e.g. e30=)
+    Base64Args,
+    #[strum_discriminants(strum(
+        message = "file-args    - Read from file (e.g. reusable JSON or binary data)"
+    ))]
+    /// Read from file (e.g. reusable JSON or binary data)
+    FileArgs,
+    #[strum_discriminants(strum(
+        message = "abi-args     - Build the arguments interactively from the contract ABI"
+    ))]
+    /// Build the arguments from the contract ABI (a JSON object, or prompted parameter by parameter)
+    AbiArgs,
+}
+
+impl interactive_clap::ToCli for CallFunctionArgsType {
+    type CliVariant = CallFunctionArgsType;
+}
+
+impl CallFunctionArgsType {
+    /// `None` for `abi-args`, which has to be resolved with the contract ABI first.
+    pub fn function_args_type(&self) -> Option<FunctionArgsType> {
+        match self {
+            Self::JsonArgs => Some(FunctionArgsType::JsonArgs),
+            Self::TextArgs => Some(FunctionArgsType::TextArgs),
+            Self::Base64Args => Some(FunctionArgsType::Base64Args),
+            Self::FileArgs => Some(FunctionArgsType::FileArgs),
+            Self::AbiArgs => None,
+        }
+    }
+}
+
+impl From<FunctionArgsType> for CallFunctionArgsType {
+    fn from(function_args_type: FunctionArgsType) -> Self {
+        match function_args_type {
+            FunctionArgsType::JsonArgs => Self::JsonArgs,
+            FunctionArgsType::TextArgs => Self::TextArgs,
+            FunctionArgsType::Base64Args => Self::Base64Args,
+            FunctionArgsType::FileArgs => Self::FileArgs,
+        }
+    }
+}
+
+impl std::str::FromStr for CallFunctionArgsType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "abi-args" => Ok(Self::AbiArgs),
+            _ => FunctionArgsType::from_str(s).map(Into::into),
+        }
+    }
+}
+
+impl std::fmt::Display for CallFunctionArgsType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.function_args_type() {
+            Some(function_args_type) => function_args_type.fmt(f),
+            None => write!(f, "abi-args"),
+        }
+    }
+}
+
+impl std::fmt::Display for CallFunctionArgsTypeDiscriminants {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::JsonArgs => write!(f, "json-args    - Valid JSON arguments (e.g. {{\"token_id\": \"42\"}} or {{}} if no arguments)"),
+            Self::TextArgs => write!(f, "text-args    - Arbitrary text arguments"),
+            Self::Base64Args => write!(f, "base64-args  - Base64-encoded string (e.g. e30=)"),
+            Self::FileArgs => write!(f, "file-args    - Read from file reusable JSON or binary data (e.g. ./args.json)"),
+            Self::AbiArgs => write!(f, "abi-args     - Build the arguments interactively from the contract ABI"),
+        }
+    }
+}
+
+pub fn input_call_function_args_type() -> color_eyre::eyre::Result<Option<CallFunctionArgsType>> {
+    let variants = CallFunctionArgsTypeDiscriminants::iter().collect::<Vec<_>>();
+    let selected = Select::new(
+        "How would you like to pass the function arguments?",
+        variants,
+    )
+    .prompt()?;
+    match selected {
+        CallFunctionArgsTypeDiscriminants::JsonArgs => Ok(Some(CallFunctionArgsType::JsonArgs)),
+        CallFunctionArgsTypeDiscriminants::TextArgs => Ok(Some(CallFunctionArgsType::TextArgs)),
+        CallFunctionArgsTypeDiscriminants::Base64Args => Ok(Some(CallFunctionArgsType::Base64Args)),
+        CallFunctionArgsTypeDiscriminants::FileArgs => Ok(Some(CallFunctionArgsType::FileArgs)),
+        CallFunctionArgsTypeDiscriminants::AbiArgs => Ok(Some(CallFunctionArgsType::AbiArgs)),
+    }
+}
+
+pub fn call_function_args(
+    args: String,
+    function_args_type: CallFunctionArgsType,
+) -> color_eyre::eyre::Result<Vec<u8>> {
+    match function_args_type.function_args_type() {
+        Some(function_args_type) => function_args(args, function_args_type),
+        None => Err(color_eyre::Report::msg(
+            "The abi-args arguments must be resolved with the contract ABI before the call",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn abi_args_are_only_accepted_by_call_function() {
+        assert!(matches!(
+            CallFunctionArgsType::from_str("abi-args"),
+            Ok(CallFunctionArgsType::AbiArgs)
+        ));
+        assert!(matches!(
+            CallFunctionArgsType::from_str("json-args"),
+            Ok(CallFunctionArgsType::JsonArgs)
+        ));
+        assert_eq!(CallFunctionArgsType::AbiArgs.to_string(), "abi-args");
+        assert_eq!(CallFunctionArgsType::Base64Args.to_string(), "base64-args");
+        assert!(FunctionArgsType::from_str("abi-args").is_err());
+        assert!(call_function_args("{}".to_string(), CallFunctionArgsType::AbiArgs).is_err());
+        assert_eq!(
+            call_function_args("{}".to_string(), CallFunctionArgsType::JsonArgs).unwrap(),
+            b"{}".to_vec()
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+pub mod abi;
 mod as_read_only;
 mod as_transaction;
 pub mod call_function_args_type;
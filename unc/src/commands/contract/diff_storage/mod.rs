@@ -0,0 +1,79 @@
+use color_eyre::owo_colors::OwoColorize;
+
+use super::view_storage::contract_state::{
+    get_snapshot_description, ContractStateDiff, ContractStateSnapshot, StorageDecodeHints,
+};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = DiffStorageContext)]
+pub struct DiffStorage {
+    /// Enter the path to the old contract storage state snapshot (created with "view-storage ... save-to-file"):
+    old_snapshot_file: crate::types::path_buf::PathBuf,
+    /// Enter the path to the new contract storage state snapshot:
+    new_snapshot_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Path to the JSON file with the hints for decoding the storage keys and values
+    decode_hints: Option<crate::types::path_buf::PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffStorageContext;
+
+impl DiffStorageContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<DiffStorage as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let old_snapshot = ContractStateSnapshot::read_from_file(&scope.old_snapshot_file.0)?;
+        let new_snapshot = ContractStateSnapshot::read_from_file(&scope.new_snapshot_file.0)?;
+        let decode_hints = StorageDecodeHints::read_optional(scope.decode_hints.as_ref())?;
+
+        eprintln!("Old: {}", get_snapshot_description(&old_snapshot));
+        eprintln!("New: {}\n", get_snapshot_description(&new_snapshot));
+        old_snapshot.warn_about_unfetched_keys();
+        new_snapshot.warn_about_unfetched_keys();
+
+        let diff = ContractStateDiff::new(&old_snapshot, &new_snapshot);
+        if diff.is_empty() {
+            eprintln!(
+                "The contract storage states are identical ({} keys)",
+                old_snapshot.values.len()
+            );
+            return Ok(Self);
+        }
+
+        for item in &diff.removed {
+            eprintln!("{}", "- removed".red());
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&decode_hints.decode(item))?
+            );
+        }
+        for item in &diff.added {
+            eprintln!("{}", "+ added".green());
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&decode_hints.decode(item))?
+            );
+        }
+        for (old_item, new_item) in &diff.changed {
+            eprintln!("{}", "~ changed".yellow());
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "old": decode_hints.decode(old_item),
+                    "new": decode_hints.decode(new_item),
+                }))?
+            );
+        }
+        eprintln!(
+            "\nSummary: {} added, {} removed, {} changed",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len()
+        );
+        Ok(Self)
+    }
+}
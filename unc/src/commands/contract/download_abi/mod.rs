@@ -26,9 +26,10 @@ impl ContractContext {
         previous_context: crate::GlobalContext,
         scope: &<Contract as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            account_id: scope.account_id.clone().into(),
+            account_id,
         })
     }
 }
@@ -69,9 +70,7 @@ impl DownloadContractContext {
             let file_path: std::path::PathBuf = scope.file_path.clone().into();
 
             move |network_config, block_reference| {
-                let abi_root = tokio::runtime::Runtime::new()
-                    .unwrap()
-                    .block_on(super::inspect::get_contract_abi(&network_config.json_rpc_client(), block_reference, &account_id))?;
+                let abi_root = crate::common::block_on(super::inspect::get_contract_abi(&network_config.json_rpc_client(), block_reference, &account_id))?;
                 std::fs::File::create(&file_path)
                     .wrap_err_with(|| format!("Failed to create file: {:?}", &file_path))?
                     .write(&serde_json::to_vec_pretty(&abi_root)?)
@@ -28,9 +28,10 @@ impl ContractContext {
         previous_context: crate::GlobalContext,
         scope: &<Contract as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            account_id: scope.account_id.clone().into(),
+            account_id,
         })
     }
 }
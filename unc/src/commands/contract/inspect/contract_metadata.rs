@@ -7,6 +7,8 @@ pub struct ContractSourceMetadata {
     pub version: Option<String>,
     pub link: Option<String>,
     pub standards: Vec<Standard>,
+    /// Present for contracts built reproducibly (NEP-0330 1.2.0).
+    pub build_info: Option<BuildInfo>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -14,3 +16,11 @@ pub struct Standard {
     pub standard: String,
     pub version: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct BuildInfo {
+    pub build_environment: String,
+    pub build_command: Vec<String>,
+    pub contract_path: String,
+    pub source_code_snapshot: String,
+}
@@ -43,8 +43,11 @@ impl ContractContext {
         previous_context: crate::GlobalContext,
         scope: &<Contract as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope
+            .contract_account_id
+            .resolve(&previous_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
-            let account_id: unc_primitives::types::AccountId = scope.contract_account_id.clone().into();
+            let account_id = account_id.clone();
 
             move |network_config, block_reference| {
                 let view_code_response = network_config
@@ -57,15 +60,13 @@ impl ContractContext {
                     })
                     .wrap_err_with(|| format!("Failed to fetch query ViewCode for <{}> on network <{}>", &account_id, network_config.network_name))?;
 
-                tokio::runtime::Runtime::new()
-                    .unwrap()
-                    .block_on(display_inspect_contract(&account_id, network_config, view_code_response))
+                crate::common::block_on(display_inspect_contract(&account_id, network_config, view_code_response))
             }
         });
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
             config: previous_context.config,
             on_after_getting_block_reference_callback,
-            interacting_with_account_ids: vec![scope.contract_account_id.clone().into()],
+            interacting_with_account_ids: vec![account_id],
         }))
     }
 }
@@ -92,21 +93,25 @@ async fn display_inspect_contract(
             return Err(color_eyre::Report::msg("Error call result".to_string()));
         };
 
-    let account_view = get_account_view(
-        &network_config.network_name,
-        &json_rpc_client,
-        &block_reference,
-        account_id,
-    )
-    .await?;
-
-    let access_keys = get_access_keys(
-        &network_config.network_name,
-        &json_rpc_client,
-        &block_reference,
-        account_id,
-    )
-    .await?;
+    // The queries are independent, so they are sent at once.
+    let (account_view, access_keys, contract_source_metadata, contract_abi) = futures::join!(
+        get_account_view(
+            &network_config.network_name,
+            &json_rpc_client,
+            &block_reference,
+            account_id,
+        ),
+        get_access_keys(
+            &network_config.network_name,
+            &json_rpc_client,
+            &block_reference,
+            account_id,
+        ),
+        get_contract_source_metadata(&json_rpc_client, &block_reference, account_id),
+        get_contract_abi(&json_rpc_client, &block_reference, account_id),
+    );
+    let account_view = account_view?;
+    let access_keys = access_keys?;
 
     let mut table = prettytable::Table::new();
     table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
@@ -162,7 +167,7 @@ async fn display_inspect_contract(
         access_keys_summary
     ]);
 
-    match get_contract_source_metadata(&json_rpc_client, &block_reference, account_id).await {
+    match contract_source_metadata {
         Ok(contract_source_metadata) => {
             table.add_row(prettytable::row![
                 Fy->"Contract version",
@@ -181,6 +186,24 @@ async fn display_inspect_contract(
                         output
                     })
             ]);
+            if let Some(build_info) = contract_source_metadata.build_info {
+                table.add_row(prettytable::row![
+                    Fy->"Build environment",
+                    build_info.build_environment
+                ]);
+                table.add_row(prettytable::row![
+                    Fy->"Build command",
+                    shell_words::join(&build_info.build_command)
+                ]);
+                table.add_row(prettytable::row![
+                    Fy->"Contract path",
+                    build_info.contract_path
+                ]);
+                table.add_row(prettytable::row![
+                    Fy->"Source code snapshot",
+                    build_info.source_code_snapshot
+                ]);
+            }
         }
         Err(err) => {
             table.add_row(prettytable::row![
@@ -214,7 +237,7 @@ async fn display_inspect_contract(
         }
     }
 
-    match get_contract_abi(&json_rpc_client, &block_reference, account_id).await {
+    match contract_abi {
         Ok(abi_root) => {
             table.add_row(prettytable::row![
                 Fy->"unc ABI version",
@@ -2,10 +2,12 @@ use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 pub mod call_function;
 pub mod deploy;
+mod diff_storage;
 mod download_abi;
 mod download_wasm;
 mod inspect;
-mod view_storage;
+mod verify;
+pub mod view_storage;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = crate::GlobalContext)]
@@ -42,4 +44,14 @@ pub enum ContractActions {
     #[strum_discriminants(strum(message = "view-storage    - View contract storage state"))]
     /// View contract storage state
     ViewStorage(self::view_storage::ViewStorage),
+    #[strum_discriminants(strum(
+        message = "diff-storage    - Compare two contract storage state snapshots"
+    ))]
+    /// Compare two contract storage state snapshots
+    DiffStorage(self::diff_storage::DiffStorage),
+    #[strum_discriminants(strum(
+        message = "verify          - Verify that the deployed contract code matches a local build"
+    ))]
+    /// Verify that the deployed contract code matches a local build
+    Verify(self::verify::Contract),
 }
@@ -0,0 +1,491 @@
+use color_eyre::{
+    eyre::{Context, ContextCompat},
+    owo_colors::OwoColorize,
+};
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ContractContext)]
+pub struct Contract {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_account_id: crate::types::account_id::AccountId,
+    /// Path to the `Cargo.toml` of the contract to build (relative to the repository root when `--git-rev` is used)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    manifest_path: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Git repository (URL or local path) to build the contract from (defaults to the current directory when `--git-rev` is set)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    git_url: Option<String>,
+    /// Git revision (commit, tag or branch) to build the contract from
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    git_rev: Option<String>,
+    /// Set compile-time feature flags.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    features: Option<String>,
+    /// Disables default feature flags.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    no_default_features: bool,
+    /// Do not include rustdocs in the embedded ABI
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    no_doc: bool,
+    /// Build with the currently active Rust toolchain instead of the one recorded in the contract ABI
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    use_current_toolchain: bool,
+    /// Save the verification report to this JSON file
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    report_file: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+impl Contract {
+    pub fn input_contract_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct ContractContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ContractContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Contract as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope
+            .contract_account_id
+            .resolve(&previous_context.config, None)?;
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let account_id = account_id.clone();
+            let build_source = BuildSource {
+                manifest_path: scope.manifest_path.clone().map(Into::into),
+                git_url: scope.git_url.clone(),
+                git_rev: scope.git_rev.clone(),
+                features: scope.features.clone(),
+                no_default_features: scope.no_default_features,
+                no_doc: scope.no_doc,
+                use_current_toolchain: scope.use_current_toolchain,
+            };
+            let report_file: Option<std::path::PathBuf> = scope.report_file.clone().map(Into::into);
+
+            move |network_config, block_reference| {
+                let report = verify_contract(network_config, block_reference, &account_id, &build_source)?;
+                report.print();
+                if let Some(report_file) = &report_file {
+                    std::fs::write(report_file, serde_json::to_vec_pretty(&report)?)
+                        .wrap_err_with(|| format!("Failed to write to file: {:?}", report_file))?;
+                    eprintln!("\nThe verification report was saved to the file {:?}", report_file);
+                }
+                if !report.verified {
+                    return Err(color_eyre::Report::msg(format!(
+                        "The code deployed on <{account_id}> does not match the local build"
+                    )));
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            on_after_getting_block_reference_callback,
+            interacting_with_account_ids: vec![account_id],
+        }))
+    }
+}
+
+impl From<ContractContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ContractContext) -> Self {
+        item.0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BuildSource {
+    manifest_path: Option<camino::Utf8PathBuf>,
+    git_url: Option<String>,
+    git_rev: Option<String>,
+    features: Option<String>,
+    no_default_features: bool,
+    no_doc: bool,
+    use_current_toolchain: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VerificationReport {
+    pub contract_account_id: unc_primitives::types::AccountId,
+    pub network_name: String,
+    pub block_height: unc_primitives::types::BlockHeight,
+    pub block_hash: unc_primitives::hash::CryptoHash,
+    pub on_chain_code_hash: unc_primitives::hash::CryptoHash,
+    pub local_code_hash: unc_primitives::hash::CryptoHash,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_rev: Option<String>,
+    pub build_settings_differences: Vec<BuildSettingDifference>,
+    pub verifier: String,
+    /// The git user configured on the machine which ran the verification (self-reported, not a signature)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_user: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BuildSettingDifference {
+    pub setting: String,
+    pub on_chain: String,
+    pub local: String,
+}
+
+impl VerificationReport {
+    fn print(&self) {
+        let mut table = prettytable::Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+        table.add_row(prettytable::row![
+            Fg->self.contract_account_id,
+            format!("At block #{}\n({})", self.block_height, self.block_hash)
+        ]);
+        table.add_row(prettytable::row![Fy->"On-chain code hash", self.on_chain_code_hash]);
+        table.add_row(prettytable::row![Fy->"Local build code hash", self.local_code_hash]);
+        if let Some(git_url) = &self.git_url {
+            table.add_row(prettytable::row![Fy->"Git repository", git_url]);
+        }
+        if let Some(git_rev) = &self.git_rev {
+            table.add_row(prettytable::row![Fy->"Git revision", git_rev]);
+        }
+        table.add_row(prettytable::row![Fy->"Verifier", self.verifier]);
+        if let Some(git_user) = &self.git_user {
+            table.add_row(prettytable::row![Fy->"Run by (git user)", git_user]);
+        }
+        table.add_row(prettytable::row![
+            Fy->"Result",
+            if self.verified {
+                "Verified: the deployed code matches the local build".green().to_string()
+            } else {
+                "Mismatch: the deployed code differs from the local build".red().to_string()
+            }
+        ]);
+        table.printstd();
+
+        if !self.build_settings_differences.is_empty() {
+            eprintln!("\nBuild settings that differ:");
+            let mut table = prettytable::Table::new();
+            table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+            table.set_titles(prettytable::row![Fg->"Setting", Fg->"On-chain", Fg->"Local"]);
+            for difference in &self.build_settings_differences {
+                table.add_row(prettytable::row![
+                    Fy->difference.setting,
+                    difference.on_chain,
+                    difference.local
+                ]);
+            }
+            table.printstd();
+        }
+    }
+}
+
+fn verify_contract(
+    network_config: &crate::config::NetworkConfig,
+    block_reference: &unc_primitives::types::BlockReference,
+    account_id: &unc_primitives::types::AccountId,
+    build_source: &BuildSource,
+) -> color_eyre::eyre::Result<VerificationReport> {
+    let view_code_response = network_config
+        .json_rpc_client()
+        .blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: unc_primitives::views::QueryRequest::ViewCode {
+                account_id: account_id.clone(),
+            },
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewCode for <{}> on network <{}>",
+                account_id, network_config.network_name
+            )
+        })?;
+    let on_chain_code_hash =
+        if let unc_jsonrpc_primitives::types::query::QueryResponseKind::ViewCode(result) =
+            view_code_response.kind
+        {
+            result.hash
+        } else {
+            return Err(color_eyre::Report::msg("Error call result".to_string()));
+        };
+
+    let block_reference = unc_primitives::types::BlockReference::from(
+        unc_primitives::types::BlockId::Hash(view_code_response.block_hash),
+    );
+    let on_chain_abi = match crate::common::block_on(super::inspect::get_contract_abi(
+        &network_config.json_rpc_client(),
+        &block_reference,
+        account_id,
+    )) {
+        Ok(abi_root) => Some(abi_root),
+        Err(super::inspect::FetchAbiError::AbiNotSupported) => {
+            eprintln!("The contract does not embed an ABI, so the build settings cannot be reproduced exactly; building with `--no-abi`.");
+            None
+        }
+        Err(err) => return Err(color_eyre::Report::msg(err.to_string())),
+    };
+
+    // Contracts built with `--reproducible` record the container image, which pins the toolchain as well
+    let image = on_chain_abi
+        .as_ref()
+        .and_then(|abi_root| abi_root.metadata.build.as_ref())
+        .and_then(|build_info| build_info.image.clone());
+    let toolchain = match (
+        &on_chain_abi,
+        build_source.use_current_toolchain || image.is_some(),
+    ) {
+        (Some(abi_root), false) => abi_root
+            .metadata
+            .build
+            .as_ref()
+            .and_then(|build_info| get_toolchain_from_compiler(&build_info.compiler)),
+        _ => None,
+    };
+
+    let work_dir =
+        std::env::temp_dir().join(format!("unc-verify-{}-{}", account_id, std::process::id()));
+    let result = build_contract(
+        &work_dir,
+        build_source,
+        on_chain_abi.is_some(),
+        toolchain.as_deref(),
+        image,
+    );
+    let _ = std::fs::remove_dir_all(&work_dir);
+    let (wasm, local_abi) = result?;
+
+    let local_code_hash = unc_primitives::hash::CryptoHash::hash_bytes(&wasm);
+    let build_settings_differences = match (&on_chain_abi, &local_abi) {
+        (Some(on_chain_abi), Some(local_abi)) => {
+            get_build_settings_differences(&on_chain_abi.metadata, &local_abi.metadata)
+        }
+        _ => vec![],
+    };
+
+    Ok(VerificationReport {
+        contract_account_id: account_id.clone(),
+        network_name: network_config.network_name.clone(),
+        block_height: view_code_response.block_height,
+        block_hash: view_code_response.block_hash,
+        on_chain_code_hash,
+        local_code_hash,
+        verified: on_chain_code_hash == local_code_hash,
+        git_url: build_source.git_rev.as_ref().map(|_| {
+            build_source
+                .git_url
+                .clone()
+                .unwrap_or_else(|| ".".to_string())
+        }),
+        git_rev: build_source.git_rev.clone(),
+        build_settings_differences,
+        verifier: format!("unc {}", env!("CARGO_PKG_VERSION")),
+        git_user: get_git_user(),
+    })
+}
+
+/// Builds the contract in a scratch directory and returns the wasm code together with the generated ABI (if any).
+fn build_contract(
+    work_dir: &std::path::Path,
+    build_source: &BuildSource,
+    with_abi: bool,
+    toolchain: Option<&str>,
+    image: Option<String>,
+) -> color_eyre::eyre::Result<(Vec<u8>, Option<unc_abi::AbiRoot>)> {
+    let out_dir = camino::Utf8PathBuf::from_path_buf(work_dir.join("out"))
+        .map_err(|path| color_eyre::Report::msg(format!("Non UTF-8 path: {path:?}")))?;
+    std::fs::create_dir_all(&out_dir)?;
+
+    let manifest_path = if let Some(git_rev) = &build_source.git_rev {
+        let checkout_dir = work_dir.join("source");
+        let git_url = build_source.git_url.as_deref().unwrap_or(".");
+        run_git(
+            None,
+            &["clone", "--quiet", git_url, &checkout_dir.to_string_lossy()],
+        )?;
+        run_git(Some(&checkout_dir), &["checkout", "--quiet", git_rev])?;
+        let checkout_dir = camino::Utf8PathBuf::from_path_buf(checkout_dir)
+            .map_err(|path| color_eyre::Report::msg(format!("Non UTF-8 path: {path:?}")))?;
+        Some(
+            checkout_dir.join(
+                build_source
+                    .manifest_path
+                    .clone()
+                    .unwrap_or_else(|| "Cargo.toml".into()),
+            ),
+        )
+    } else {
+        build_source.manifest_path.clone()
+    };
+
+    if let Some(toolchain) = toolchain {
+        eprintln!("Building with the Rust toolchain <{toolchain}> recorded in the contract ABI");
+    }
+    if let Some(image) = &image {
+        eprintln!(
+            "Building reproducibly in the container image <{image}> recorded in the contract ABI"
+        );
+    }
+    let wasm_artifact = crate::commands::devtool::build_command::build::run(
+        crate::commands::devtool::build_command::BuildCommand {
+            no_abi: !with_abi,
+            no_embed_abi: !with_abi,
+            no_doc: build_source.no_doc,
+            out_dir: Some(out_dir.clone().into()),
+            manifest_path: manifest_path.map(Into::into),
+            features: build_source.features.clone(),
+            no_default_features: build_source.no_default_features,
+            toolchain: toolchain.map(str::to_string),
+            reproducible: image.is_some(),
+            image,
+            ..Default::default()
+        },
+    )?;
+
+    let wasm = std::fs::read(&wasm_artifact.path)
+        .wrap_err_with(|| format!("Failed to read file: {}", wasm_artifact.path))?;
+    let local_abi = if with_abi {
+        let abi_path = std::fs::read_dir(&out_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|path| {
+                path.file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .is_some_and(|file_name| file_name.ends_with("_abi.json"))
+            })
+            .wrap_err("The ABI file of the local build was not found")?;
+        Some(
+            serde_json::from_slice::<unc_abi::AbiRoot>(&std::fs::read(&abi_path)?)
+                .wrap_err_with(|| format!("Failed to parse the ABI file: {abi_path:?}"))?,
+        )
+    } else {
+        None
+    };
+    Ok((wasm, local_abi))
+}
+
+fn run_git(current_dir: Option<&std::path::Path>, args: &[&str]) -> color_eyre::eyre::Result<()> {
+    let mut command = std::process::Command::new("git");
+    command.args(args);
+    if let Some(current_dir) = current_dir {
+        command.current_dir(current_dir);
+    }
+    let output = command
+        .output()
+        .wrap_err_with(|| format!("Failed to execute process: `git {}`", args.join(" ")))?;
+    if !output.status.success() {
+        return Err(color_eyre::Report::msg(format!(
+            "`git {}` failed:\n{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(())
+}
+
+/// Returns "Name <email>" from the git configuration to show who ran the verification.
+fn get_git_user() -> Option<String> {
+    let get_config = |key: &str| {
+        std::process::Command::new("git")
+            .args(["config", key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    match (get_config("user.name"), get_config("user.email")) {
+        (Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+        (Some(name), None) => Some(name),
+        (None, Some(email)) => Some(format!("<{email}>")),
+        (None, None) => None,
+    }
+}
+
+/// Extracts the toolchain version from the `BuildInfo.compiler` string (e.g. "rustc 1.78.0" -> "1.78.0").
+///
+/// Pre-release compilers (nightly, beta) do not map to a single rustup toolchain, so they are ignored.
+fn get_toolchain_from_compiler(compiler: &str) -> Option<String> {
+    compiler
+        .strip_prefix("rustc ")
+        .and_then(|version| version.split_whitespace().next())
+        .filter(|version| !version.contains('-'))
+        .map(str::to_string)
+}
+
+fn get_build_settings_differences(
+    on_chain: &unc_abi::AbiMetadata,
+    local: &unc_abi::AbiMetadata,
+) -> Vec<BuildSettingDifference> {
+    let display = |value: Option<&String>| value.cloned().unwrap_or_else(|| "-".to_string());
+    let on_chain_build = on_chain.build.as_ref();
+    let local_build = local.build.as_ref();
+    [
+        (
+            "Contract name",
+            display(on_chain.name.as_ref()),
+            display(local.name.as_ref()),
+        ),
+        (
+            "Contract version",
+            display(on_chain.version.as_ref()),
+            display(local.version.as_ref()),
+        ),
+        (
+            "Compiler",
+            display(on_chain_build.map(|build| &build.compiler)),
+            display(local_build.map(|build| &build.compiler)),
+        ),
+        (
+            "Builder",
+            display(on_chain_build.map(|build| &build.builder)),
+            display(local_build.map(|build| &build.builder)),
+        ),
+        (
+            "Docker image",
+            display(on_chain_build.and_then(|build| build.image.as_ref())),
+            display(local_build.and_then(|build| build.image.as_ref())),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, on_chain, local)| on_chain != local)
+    .map(|(setting, on_chain, local)| BuildSettingDifference {
+        setting: setting.to_string(),
+        on_chain,
+        local,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toolchain_from_compiler() {
+        assert_eq!(
+            get_toolchain_from_compiler("rustc 1.78.0"),
+            Some("1.78.0".to_string())
+        );
+        assert_eq!(get_toolchain_from_compiler("rustc 1.79.0-nightly"), None);
+        assert_eq!(get_toolchain_from_compiler("gcc 13"), None);
+    }
+}
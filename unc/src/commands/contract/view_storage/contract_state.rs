@@ -0,0 +1,563 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+/// The contract state at a specific block, which can be saved to a file and compared later.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContractStateSnapshot {
+    pub contract_account_id: unc_primitives::types::AccountId,
+    pub network_name: String,
+    pub block_height: unc_primitives::types::BlockHeight,
+    pub block_hash: unc_primitives::hash::CryptoHash,
+    pub values: Vec<unc_primitives::views::StateItem>,
+    /// The keys which may hold a value missing from `values`.
+    ///
+    /// The RPC has no query for a single key, so when the state is fetched page by page,
+    /// the value stored under the exact key at which the key space was split (if any) is not
+    /// returned by any page.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unfetched_keys: Vec<unc_primitives::types::StoreKey>,
+    /// The proof returned by the RPC (only when the state is fetched at once)
+    #[serde(skip)]
+    pub proof: Vec<std::sync::Arc<[u8]>>,
+}
+
+impl ContractStateSnapshot {
+    pub fn read_from_file(file_path: &std::path::Path) -> color_eyre::eyre::Result<Self> {
+        let data = std::fs::read_to_string(file_path)
+            .wrap_err_with(|| format!("Failed to read the snapshot file: {:?}", file_path))?;
+        serde_json::from_str(&data)
+            .wrap_err_with(|| format!("Failed to parse the snapshot file: {:?}", file_path))
+    }
+
+    pub fn write_to_file(&self, file_path: &std::path::Path) -> crate::CliResult {
+        std::fs::write(file_path, serde_json::to_vec_pretty(self)?)
+            .wrap_err_with(|| format!("Failed to write the snapshot file: {:?}", file_path))
+    }
+
+    /// Warns about the keys which could not be fetched, see [`Self::unfetched_keys`].
+    pub fn warn_about_unfetched_keys(&self) {
+        if self.unfetched_keys.is_empty() {
+            return;
+        }
+        eprintln!(
+            "Warning: the state of {} was fetched page by page, and the values stored under these exact keys (if any) could not be fetched:",
+            get_snapshot_description(self)
+        );
+        for key in &self.unfetched_keys {
+            eprintln!(
+                "  {:?} (base64: {})",
+                String::from_utf8_lossy(key.as_ref()),
+                unc_primitives::serialize::to_base64(key.as_ref())
+            );
+        }
+    }
+}
+
+/// Fetches the contract state with the keys starting with `prefix`.
+///
+/// The RPC refuses to return the state which exceeds its size limit, so in that case the state
+/// is fetched page by page, splitting the key space by the next byte of the prefix.
+/// All the pages are fetched at the same block.
+pub fn fetch_contract_state(
+    network_config: &crate::config::NetworkConfig,
+    block_reference: &unc_primitives::types::BlockReference,
+    contract_account_id: &unc_primitives::types::AccountId,
+    prefix: &unc_primitives::types::StoreKey,
+) -> color_eyre::eyre::Result<ContractStateSnapshot> {
+    let json_rpc_client = network_config.json_rpc_client();
+    match view_state(
+        &json_rpc_client,
+        block_reference,
+        contract_account_id,
+        prefix,
+    ) {
+        Ok((values, proof, block_height, block_hash)) => Ok(ContractStateSnapshot {
+            contract_account_id: contract_account_id.clone(),
+            network_name: network_config.network_name.clone(),
+            block_height,
+            block_hash,
+            values,
+            unfetched_keys: Vec::new(),
+            proof,
+        }),
+        Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+            unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                unc_jsonrpc_primitives::types::query::RpcQueryError::TooLargeContractState {
+                    block_height,
+                    block_hash,
+                    ..
+                },
+            ),
+        )) => {
+            eprintln!("The contract state is too large to be fetched at once, fetching it page by page at block #{block_height} ({block_hash}) ...");
+            let block_reference = unc_primitives::types::BlockReference::BlockId(
+                unc_primitives::types::BlockId::Hash(block_hash),
+            );
+            let mut view_page = |page_prefix: &[u8]| {
+                match view_state(
+                &json_rpc_client,
+                &block_reference,
+                contract_account_id,
+                &page_prefix.to_vec().into(),
+            ) {
+                Ok((page_values, _, _, _)) => Ok(Some(page_values)),
+                Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+                    unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                        unc_jsonrpc_primitives::types::query::RpcQueryError::TooLargeContractState {
+                            ..
+                        },
+                    ),
+                )) => Ok(None),
+                Err(err) => Err(color_eyre::Report::new(err)),
+            }
+            };
+            let (values, unfetched_keys) =
+                fetch_contract_state_pages(&mut view_page, <[u8]>::to_vec(prefix.as_ref()))
+                    .wrap_err_with(|| {
+                        format!(
+                    "Failed to fetch query ViewState for <{contract_account_id}> on network <{}>",
+                    network_config.network_name
+                )
+                    })?;
+            Ok(ContractStateSnapshot {
+                contract_account_id: contract_account_id.clone(),
+                network_name: network_config.network_name.clone(),
+                block_height,
+                block_hash,
+                values,
+                unfetched_keys,
+                proof: Vec::new(),
+            })
+        }
+        Err(err) => Err(color_eyre::Report::new(err)).wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewState for <{contract_account_id}> on network <{}>",
+                network_config.network_name
+            )
+        }),
+    }
+}
+
+/// A function which returns the state items with the keys starting with the given prefix,
+/// or `None` if the state under the prefix is too large to be returned at once.
+type ViewPage<'a> = dyn FnMut(&[u8]) -> color_eyre::eyre::Result<Option<Vec<unc_primitives::views::StateItem>>>
+    + 'a;
+
+/// Fetches the state under a prefix which is too large to be returned at once.
+///
+/// Returns the state items sorted by key and the keys equal to the prefixes which were split
+/// into pages: a page `prefix + byte` never includes the key equal to `prefix` itself.
+fn fetch_contract_state_pages(
+    view_page: &mut ViewPage,
+    prefix: Vec<u8>,
+) -> color_eyre::eyre::Result<(
+    Vec<unc_primitives::views::StateItem>,
+    Vec<unc_primitives::types::StoreKey>,
+)> {
+    let mut values = Vec::new();
+    let mut unfetched_keys = Vec::new();
+    let mut too_large_prefixes = vec![prefix];
+    while let Some(prefix) = too_large_prefixes.pop() {
+        let mut too_large_pages = Vec::new();
+        for next_byte in 0..=u8::MAX {
+            let mut page_prefix = prefix.clone();
+            page_prefix.push(next_byte);
+            match view_page(&page_prefix)? {
+                Some(page_values) => values.extend(page_values),
+                None => too_large_pages.push(page_prefix),
+            }
+        }
+        if too_large_pages.len() == usize::from(u8::MAX) + 1 {
+            return Err(color_eyre::eyre::eyre!(
+                "The RPC server refuses to return the state under any key prefix, so its state size limit applies to the whole account and the state cannot be fetched page by page. Use an RPC server with a larger state size limit."
+            ));
+        }
+        unfetched_keys.push(prefix);
+        too_large_prefixes.extend(too_large_pages.into_iter().rev());
+    }
+    values.sort_by(|a, b| (*a.key).cmp(&*b.key));
+    unfetched_keys.sort();
+    Ok((values, unfetched_keys.into_iter().map(Into::into).collect()))
+}
+
+type ViewStateResponse = (
+    Vec<unc_primitives::views::StateItem>,
+    Vec<std::sync::Arc<[u8]>>,
+    unc_primitives::types::BlockHeight,
+    unc_primitives::hash::CryptoHash,
+);
+
+fn view_state(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    block_reference: &unc_primitives::types::BlockReference,
+    contract_account_id: &unc_primitives::types::AccountId,
+    prefix: &unc_primitives::types::StoreKey,
+) -> Result<
+    ViewStateResponse,
+    unc_jsonrpc_client::errors::JsonRpcError<unc_jsonrpc_primitives::types::query::RpcQueryError>,
+> {
+    let query_view_method_response =
+        json_rpc_client.blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: unc_primitives::views::QueryRequest::ViewState {
+                account_id: contract_account_id.clone(),
+                prefix: prefix.clone(),
+                include_proof: false,
+            },
+        })?;
+    match query_view_method_response.kind {
+        unc_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result) => Ok((
+            result.values,
+            result.proof,
+            query_view_method_response.block_height,
+            query_view_method_response.block_hash,
+        )),
+        _ => Ok((
+            Vec::new(),
+            Vec::new(),
+            query_view_method_response.block_height,
+            query_view_method_response.block_hash,
+        )),
+    }
+}
+
+/// User-provided hints describing how to decode the storage keys and values.
+///
+/// Collections from `unc-sdk` (`LookupMap`, `UnorderedMap`, `Vector`, ...) store their entries
+/// under the collection prefix followed by the Borsh-serialized key, e.g.:
+///
+/// ```json
+/// {
+///   "collections": [
+///     { "name": "accounts", "prefix": "a", "key": "string", "value": "u128" },
+///     { "name": "owner", "prefix": "STATE", "key": "utf8", "value": "base64" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct StorageDecodeHints {
+    pub collections: Vec<CollectionHint>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CollectionHint {
+    pub name: String,
+    /// The collection prefix as a UTF-8 string
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// The collection prefix as Base64 bytes (for non UTF-8 prefixes)
+    #[serde(default)]
+    pub prefix_base64: Option<String>,
+    /// The encoding of the key bytes following the prefix
+    #[serde(default)]
+    pub key: ValueEncoding,
+    /// The encoding of the value
+    #[serde(default)]
+    pub value: ValueEncoding,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValueEncoding {
+    /// Raw UTF-8 string
+    Utf8,
+    /// JSON value
+    Json,
+    /// Borsh-serialized string (also used for account IDs)
+    String,
+    /// Borsh-serialized integers
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    /// Borsh-serialized boolean
+    Bool,
+    /// Raw bytes as Hex
+    Hex,
+    /// Raw bytes as Base64
+    #[default]
+    Base64,
+}
+
+impl StorageDecodeHints {
+    pub fn read_from_file(file_path: &std::path::Path) -> color_eyre::eyre::Result<Self> {
+        let data = std::fs::read_to_string(file_path)
+            .wrap_err_with(|| format!("Failed to read the decode hints file: {:?}", file_path))?;
+        serde_json::from_str(&data)
+            .wrap_err_with(|| format!("Failed to parse the decode hints file: {:?}", file_path))
+    }
+
+    pub fn read_optional(
+        file_path: Option<&crate::types::path_buf::PathBuf>,
+    ) -> color_eyre::eyre::Result<Self> {
+        match file_path {
+            Some(file_path) => Self::read_from_file(&file_path.0),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Decodes the state item with the collection which has the longest matching prefix.
+    pub fn decode(&self, item: &unc_primitives::views::StateItem) -> serde_json::Value {
+        let key: &[u8] = item.key.as_ref();
+        let value: &[u8] = item.value.as_ref();
+        let mut decoded = serde_json::json!({
+            "key": unc_primitives::serialize::to_base64(key),
+            "value": unc_primitives::serialize::to_base64(value),
+        });
+        let matched = self
+            .collections
+            .iter()
+            .filter_map(|collection| {
+                let prefix = collection.prefix_bytes().ok()?;
+                key.starts_with(&prefix).then_some((collection, prefix))
+            })
+            .max_by_key(|(_, prefix)| prefix.len());
+        if let Some((collection, prefix)) = matched {
+            decoded["collection"] = serde_json::Value::String(collection.name.clone());
+            decoded["decoded_key"] = collection.key.decode(&key[prefix.len()..]);
+            decoded["decoded_value"] = collection.value.decode(value);
+        }
+        decoded
+    }
+}
+
+impl CollectionHint {
+    fn prefix_bytes(&self) -> color_eyre::eyre::Result<Vec<u8>> {
+        match (&self.prefix, &self.prefix_base64) {
+            (Some(prefix), _) => Ok(prefix.clone().into_bytes()),
+            (None, Some(prefix_base64)) => unc_primitives::serialize::from_base64(prefix_base64)
+                .map_err(|_| color_eyre::eyre::eyre!("The prefix cannot be decoded with base64")),
+            (None, None) => Ok(Vec::new()),
+        }
+    }
+}
+
+impl ValueEncoding {
+    /// Decodes the bytes, falling back to Base64 if the bytes do not match the encoding.
+    pub fn decode(&self, bytes: &[u8]) -> serde_json::Value {
+        self.try_decode(bytes).unwrap_or_else(|_| {
+            serde_json::Value::String(unc_primitives::serialize::to_base64(bytes))
+        })
+    }
+
+    fn try_decode(&self, bytes: &[u8]) -> color_eyre::eyre::Result<serde_json::Value> {
+        use unc_primitives::borsh::BorshDeserialize;
+
+        Ok(match self {
+            Self::Utf8 => serde_json::Value::String(String::from_utf8(bytes.to_vec())?),
+            Self::Json => serde_json::from_slice(bytes)?,
+            Self::String => serde_json::Value::String(String::try_from_slice(bytes)?),
+            Self::U8 => serde_json::json!(u8::try_from_slice(bytes)?),
+            Self::U16 => serde_json::json!(u16::try_from_slice(bytes)?),
+            Self::U32 => serde_json::json!(u32::try_from_slice(bytes)?),
+            Self::U64 => serde_json::json!(u64::try_from_slice(bytes)?),
+            Self::U128 => serde_json::Value::String(u128::try_from_slice(bytes)?.to_string()),
+            Self::Bool => serde_json::json!(bool::try_from_slice(bytes)?),
+            Self::Hex => serde_json::Value::String(hex::encode(bytes)),
+            Self::Base64 => serde_json::Value::String(unc_primitives::serialize::to_base64(bytes)),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ContractStateDiff {
+    pub added: Vec<unc_primitives::views::StateItem>,
+    pub removed: Vec<unc_primitives::views::StateItem>,
+    /// Items with the same key: (old, new)
+    pub changed: Vec<(
+        unc_primitives::views::StateItem,
+        unc_primitives::views::StateItem,
+    )>,
+}
+
+impl ContractStateDiff {
+    pub fn new(old: &ContractStateSnapshot, new: &ContractStateSnapshot) -> Self {
+        let old_values = old
+            .values
+            .iter()
+            .map(|item| (item.key.to_vec(), item))
+            .collect::<std::collections::BTreeMap<_, _>>();
+        let new_values = new
+            .values
+            .iter()
+            .map(|item| (item.key.to_vec(), item))
+            .collect::<std::collections::BTreeMap<_, _>>();
+
+        let mut diff = Self::default();
+        for (key, old_item) in &old_values {
+            match new_values.get(key) {
+                None => diff.removed.push((*old_item).clone()),
+                Some(new_item) if new_item.value != old_item.value => diff
+                    .changed
+                    .push(((*old_item).clone(), (*new_item).clone())),
+                Some(_) => {}
+            }
+        }
+        for (key, new_item) in &new_values {
+            if !old_values.contains_key(key) {
+                diff.added.push((*new_item).clone());
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+pub fn get_snapshot_description(snapshot: &ContractStateSnapshot) -> String {
+    format!(
+        "<{}> on network <{}> at block #{} ({})",
+        snapshot.contract_account_id,
+        snapshot.network_name,
+        snapshot.block_height,
+        snapshot.block_hash
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(key: &[u8], value: &[u8]) -> unc_primitives::views::StateItem {
+        unc_primitives::views::StateItem {
+            key: key.to_vec().into(),
+            value: value.to_vec().into(),
+        }
+    }
+
+    fn snapshot(values: Vec<unc_primitives::views::StateItem>) -> ContractStateSnapshot {
+        ContractStateSnapshot {
+            contract_account_id: "contract.unc".parse().unwrap(),
+            network_name: "testnet".to_string(),
+            block_height: 1,
+            block_hash: Default::default(),
+            values,
+            unfetched_keys: Vec::new(),
+            proof: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn large_state_is_fetched_page_by_page() {
+        let state = vec![
+            item(b"a", b"split prefix"),
+            item(b"a\x00", b"0"),
+            item(b"a\x01", b"1"),
+            item(b"ab", b"2"),
+            item(b"abc", b"3"),
+            item(b"b", b"4"),
+            item(b"\xff", b"5"),
+        ];
+        // The "RPC server" refuses to return more than two items at once
+        let mut requested_prefixes = Vec::new();
+        let mut view_page = |prefix: &[u8]| {
+            requested_prefixes.push(prefix.to_vec());
+            let page = state
+                .iter()
+                .filter(|item| item.key.starts_with(prefix))
+                .cloned()
+                .collect::<Vec<_>>();
+            Ok((page.len() <= 2).then_some(page))
+        };
+
+        let (values, unfetched_keys) =
+            fetch_contract_state_pages(&mut view_page, Vec::new()).unwrap();
+
+        let mut expected_values = state
+            .iter()
+            .filter(|item| *item.key != b"a")
+            .map(|item| item.key.to_vec())
+            .collect::<Vec<_>>();
+        expected_values.sort();
+        assert_eq!(
+            values
+                .iter()
+                .map(|item| item.key.to_vec())
+                .collect::<Vec<_>>(),
+            expected_values
+        );
+        // The value under the exact key "a" cannot be fetched by any page, so it is reported
+        assert_eq!(
+            unfetched_keys
+                .iter()
+                .map(|key| key.to_vec())
+                .collect::<Vec<_>>(),
+            vec![b"".to_vec(), b"a".to_vec()]
+        );
+        assert_eq!(requested_prefixes.len(), 2 * 256);
+    }
+
+    #[test]
+    fn paging_fails_if_the_limit_applies_to_the_whole_account() {
+        let mut view_page = |_: &[u8]| Ok(None);
+        assert!(fetch_contract_state_pages(&mut view_page, b"a".to_vec()).is_err());
+    }
+
+    #[test]
+    fn state_items_are_decoded_with_the_longest_matching_prefix() {
+        let hints: StorageDecodeHints = serde_json::from_value(serde_json::json!({
+            "collections": [
+                { "name": "all", "prefix": "", "key": "hex", "value": "base64" },
+                { "name": "accounts", "prefix": "a", "key": "string", "value": "u128" },
+                { "name": "flags", "prefix_base64": "AQ==", "key": "u8", "value": "bool" },
+            ]
+        }))
+        .unwrap();
+
+        let key = [b"a".to_vec(), borsh::to_vec("alice.unc").unwrap()].concat();
+        let decoded = hints.decode(&item(&key, &borsh::to_vec(&42u128).unwrap()));
+        assert_eq!(decoded["collection"], "accounts");
+        assert_eq!(decoded["decoded_key"], "alice.unc");
+        assert_eq!(decoded["decoded_value"], "42");
+
+        let decoded = hints.decode(&item(&[1, 7], &[1]));
+        assert_eq!(decoded["collection"], "flags");
+        assert_eq!(decoded["decoded_key"], 7);
+        assert_eq!(decoded["decoded_value"], true);
+
+        // The value which does not match the encoding falls back to base64
+        let decoded = hints.decode(&item(b"STATE", &[1, 2]));
+        assert_eq!(decoded["collection"], "all");
+        assert_eq!(decoded["decoded_key"], hex::encode(b"STATE"));
+        assert_eq!(decoded["decoded_value"], "AQI=");
+        let decoded = hints.decode(&item(b"a", &[1, 2]));
+        assert_eq!(decoded["decoded_value"], "AQI=");
+
+        let decoded = StorageDecodeHints::default().decode(&item(b"k", b"v"));
+        assert_eq!(
+            decoded,
+            serde_json::json!({ "key": "aw==", "value": "dg==" })
+        );
+    }
+
+    #[test]
+    fn contract_state_diff() {
+        let old = snapshot(vec![item(b"a", b"1"), item(b"b", b"2"), item(b"c", b"3")]);
+        let new = snapshot(vec![item(b"a", b"1"), item(b"b", b"20"), item(b"d", b"4")]);
+
+        let diff = ContractStateDiff::new(&old, &new);
+        assert_eq!(diff.added, vec![item(b"d", b"4")]);
+        assert_eq!(diff.removed, vec![item(b"c", b"3")]);
+        assert_eq!(diff.changed, vec![(item(b"b", b"2"), item(b"b", b"20"))]);
+        assert!(!diff.is_empty());
+        assert!(ContractStateDiff::new(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn snapshot_file_roundtrip() {
+        let mut snapshot = snapshot(vec![item(b"a", b"1")]);
+        snapshot.unfetched_keys = vec![b"x".to_vec().into()];
+        let file_path =
+            std::env::temp_dir().join(format!("unc-contract-state-{}.json", std::process::id()));
+        snapshot.write_to_file(&file_path).unwrap();
+        let read = ContractStateSnapshot::read_from_file(&file_path).unwrap();
+        std::fs::remove_file(&file_path).unwrap();
+        assert_eq!(read.values, snapshot.values);
+        assert_eq!(read.unfetched_keys, snapshot.unfetched_keys);
+        assert_eq!(read.block_height, snapshot.block_height);
+    }
+}
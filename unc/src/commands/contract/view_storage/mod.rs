@@ -1,3 +1,4 @@
+pub mod contract_state;
 mod keys_to_view;
 mod output_format;
 
@@ -23,9 +24,12 @@ impl ViewStorageContext {
         previous_context: crate::GlobalContext,
         scope: &<ViewStorage as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let contract_account_id = scope
+            .contract_account_id
+            .resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            contract_account_id: scope.contract_account_id.clone().into(),
+            contract_account_id,
         })
     }
 }
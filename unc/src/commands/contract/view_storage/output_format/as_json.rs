@@ -1,10 +1,11 @@
-use crate::common::JsonRpcClientExt;
-use color_eyre::eyre::Context;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::super::keys_to_view::KeysContext)]
 #[interactive_clap(output_context = AsJsonContext)]
 pub struct AsJson {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Path to the JSON file with the hints for decoding the storage keys and values
+    decode_hints: Option<crate::types::path_buf::PathBuf>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
@@ -16,39 +17,42 @@ pub struct AsJsonContext(crate::network_view_at_block::ArgsForViewContext);
 impl AsJsonContext {
     pub fn from_previous_context(
         previous_context: super::super::keys_to_view::KeysContext,
-        _scope: &<AsJson as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+        scope: &<AsJson as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let decode_hints = super::super::contract_state::StorageDecodeHints::read_optional(
+            scope.decode_hints.as_ref(),
+        )?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let contract_account_id = previous_context.contract_account_id.clone();
             let prefix = previous_context.prefix;
 
             move |network_config, block_reference| {
-                let query_view_method_response = network_config
-                    .json_rpc_client()
-                    .blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
-                        block_reference: block_reference.clone(),
-                        request: unc_primitives::views::QueryRequest::ViewState {
-                            account_id: contract_account_id.clone(),
-                            prefix: prefix.clone(),
-                            include_proof: false,
-                        },
-                    })
-                    .wrap_err_with(|| format!("Failed to fetch query ViewState for <{contract_account_id}> on network <{}>", network_config.network_name))?;
-                    if let unc_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result) =
-                        query_view_method_response.kind
-                    {
-                        eprintln!("Contract state (values):");
-                        println!(
-                            "{}",
-                            serde_json::to_string_pretty(&result.values)?
-                        );
-                        eprintln!(
-                            "\nContract state (proof):\n{:#?}\n",
-                            &result.proof
-                        );
-                    } else {
-                        return Err(color_eyre::Report::msg("Error call result".to_string()));
-                    };
+                let contract_state = super::super::contract_state::fetch_contract_state(
+                    network_config,
+                    block_reference,
+                    &contract_account_id,
+                    &prefix,
+                )?;
+                eprintln!(
+                    "Contract state (values) at block #{} ({}):",
+                    contract_state.block_height, contract_state.block_hash
+                );
+                if decode_hints.collections.is_empty() {
+                    println!("{}", serde_json::to_string_pretty(&contract_state.values)?);
+                } else {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(
+                            &contract_state
+                                .values
+                                .iter()
+                                .map(|item| decode_hints.decode(item))
+                                .collect::<Vec<_>>()
+                        )?
+                    );
+                }
+                eprintln!("\nContract state (proof):\n{:#?}\n", &contract_state.proof);
+                contract_state.warn_about_unfetched_keys();
                 Ok(())
             }
         });
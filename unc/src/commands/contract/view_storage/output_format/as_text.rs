@@ -1,11 +1,13 @@
 use color_eyre::{eyre::Context, owo_colors::OwoColorize};
 
-use crate::common::JsonRpcClientExt;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::super::keys_to_view::KeysContext)]
 #[interactive_clap(output_context = AsTextContext)]
 pub struct AsText {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Path to the JSON file with the hints for decoding the storage keys and values
+    decode_hints: Option<crate::types::path_buf::PathBuf>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
@@ -17,40 +19,42 @@ pub struct AsTextContext(crate::network_view_at_block::ArgsForViewContext);
 impl AsTextContext {
     pub fn from_previous_context(
         previous_context: super::super::keys_to_view::KeysContext,
-        _scope: &<AsText as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+        scope: &<AsText as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let decode_hints = super::super::contract_state::StorageDecodeHints::read_optional(
+            scope.decode_hints.as_ref(),
+        )?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let contract_account_id = previous_context.contract_account_id.clone();
             let prefix = previous_context.prefix;
 
             move |network_config, block_reference| {
-                let query_view_method_response = network_config
-                    .json_rpc_client()
-                    .blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
-                        block_reference: block_reference.clone(),
-                        request: unc_primitives::views::QueryRequest::ViewState {
-                            account_id: contract_account_id.clone(),
-                            prefix: prefix.clone(),
-                            include_proof: false,
-                        },
-                    })
-                    .wrap_err_with(|| format!("Failed to fetch query ViewState for <{contract_account_id}> on network <{}>", network_config.network_name))?;
-                    if let unc_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result) =
-                        query_view_method_response.kind
-                    {
-                        eprintln!("Contract state (values):");
-                        for value in &result.values {
-                            eprintln!("key:\n{}", key_value_to_string(&value.key)?.green());
-                            eprintln!("value:\n{}", key_value_to_string(&value.value)?.yellow());
-                            eprintln!("--------------------------------");
-                        }
+                let contract_state = super::super::contract_state::fetch_contract_state(
+                    network_config,
+                    block_reference,
+                    &contract_account_id,
+                    &prefix,
+                )?;
+                eprintln!(
+                    "Contract state (values) at block #{} ({}):",
+                    contract_state.block_height, contract_state.block_hash
+                );
+                for item in &contract_state.values {
+                    eprintln!("key:\n{}", key_value_to_string(&item.key)?.green());
+                    eprintln!("value:\n{}", key_value_to_string(&item.value)?.yellow());
+                    let decoded = decode_hints.decode(item);
+                    if let Some(collection) = decoded.get("collection") {
                         eprintln!(
-                            "\nContract state (proof):\n{:#?}\n",
-                            &result.proof
+                            "decoded ({}):\n{} => {}",
+                            collection.as_str().unwrap_or_default(),
+                            decoded["decoded_key"],
+                            decoded["decoded_value"]
                         );
-                    } else {
-                        return Err(color_eyre::Report::msg("Error call result".to_string()));
-                    };
+                    }
+                    eprintln!("--------------------------------");
+                }
+                eprintln!("\nContract state (proof):\n{:#?}\n", &contract_state.proof);
+                contract_state.warn_about_unfetched_keys();
                 Ok(())
             }
         });
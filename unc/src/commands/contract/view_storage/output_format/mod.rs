@@ -2,6 +2,7 @@ use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 mod as_json;
 mod as_text;
+mod save_to_file;
 
 #[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = super::keys_to_view::KeysContext)]
@@ -9,13 +10,18 @@ mod as_text;
 /// Choose a format to view contract storage state:
 pub enum OutputFormat {
     #[strum_discriminants(strum(
-        message = "as-json    - View contract storage state in JSON format"
+        message = "as-json       - View contract storage state in JSON format"
     ))]
     /// View contract storage state in JSON format
     AsJson(self::as_json::AsJson),
     #[strum_discriminants(strum(
-        message = "as-text    - View contract storage state in the text"
+        message = "as-text       - View contract storage state in the text"
     ))]
     /// View contract storage state in the text
     AsText(self::as_text::AsText),
+    #[strum_discriminants(strum(
+        message = "save-to-file  - Save contract storage state snapshot to a file"
+    ))]
+    /// Save contract storage state snapshot to a file
+    SaveToFile(self::save_to_file::SaveToFile),
 }
@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::super::keys_to_view::KeysContext)]
+#[interactive_clap(output_context = SaveToFileContext)]
+pub struct SaveToFile {
+    /// Enter the file path where to save the contract storage state snapshot:
+    file_path: crate::types::path_buf::PathBuf,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct SaveToFileContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl SaveToFileContext {
+    pub fn from_previous_context(
+        previous_context: super::super::keys_to_view::KeysContext,
+        scope: &<SaveToFile as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let contract_account_id = previous_context.contract_account_id.clone();
+            let prefix = previous_context.prefix;
+            let file_path: std::path::PathBuf = scope.file_path.clone().into();
+
+            move |network_config, block_reference| {
+                let contract_state = super::super::contract_state::fetch_contract_state(
+                    network_config,
+                    block_reference,
+                    &contract_account_id,
+                    &prefix,
+                )?;
+                contract_state.write_to_file(&file_path)?;
+                eprintln!(
+                    "\nThe contract storage state of {} ({} keys) was saved to the file {:?}",
+                    super::super::contract_state::get_snapshot_description(&contract_state),
+                    contract_state.values.len(),
+                    &file_path
+                );
+                contract_state.warn_about_unfetched_keys();
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.global_context.config,
+            interacting_with_account_ids: vec![previous_context.contract_account_id],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<SaveToFileContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: SaveToFileContext) -> Self {
+        item.0
+    }
+}
@@ -33,6 +33,7 @@ pub(crate) fn generate_abi(
     generate_docs: bool,
     hide_warnings: bool,
     cargo_args: &[&str],
+    env: &[(&str, &str)],
     color: ColorPreference,
 ) -> color_eyre::eyre::Result<AbiRoot> {
     let root_node = crate_metadata
@@ -92,11 +93,15 @@ pub(crate) fn generate_abi(
             cargo_args,
         ]
         .concat(),
-        vec![
-            ("CARGO_PROFILE_DEV_OPT_LEVEL", "0"),
-            ("CARGO_PROFILE_DEV_DEBUG", "0"),
-            ("CARGO_PROFILE_DEV_LTO", "off"),
-        ],
+        [
+            &[
+                ("CARGO_PROFILE_DEV_OPT_LEVEL", "0"),
+                ("CARGO_PROFILE_DEV_DEBUG", "0"),
+                ("CARGO_PROFILE_DEV_LTO", "off"),
+            ],
+            env,
+        ]
+        .concat(),
         util::dylib_extension(),
         hide_warnings,
         color,
@@ -206,7 +211,7 @@ pub fn run(args: super::AbiCommand) -> crate::CliResult {
     } else {
         AbiFormat::Json
     };
-    let contract_abi = generate_abi(&crate_metadata, !args.no_doc, false, &[], color)?;
+    let contract_abi = generate_abi(&crate_metadata, !args.no_doc, false, &[], &[], color)?;
     let AbiResult { path } =
         write_to_file(&contract_abi, &crate_metadata, format, AbiCompression::NoOp)?;
 
@@ -0,0 +1,673 @@
+use camino::Utf8PathBuf;
+use color_eyre::eyre::WrapErr;
+use colored::Colorize;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+use crate::common::ColorPreference;
+use crate::types::{manifest::CargoManifestPath, metadata::CrateMetadata};
+use crate::util;
+
+mod rust;
+mod typescript;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AbiCodegenContext)]
+pub struct AbiCodegen {
+    /// Language of the generated client: rust, typescript
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_default_input_arg)]
+    lang: CodegenLanguage,
+    /// Generate the client from this ABI file instead of generating the ABI of the contract
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    abi_file: Option<crate::types::path_buf::PathBuf>,
+    /// Path to the `Cargo.toml` of the contract
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    manifest_path: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Write the client to this file (defaults to `<contract>_client.rs` or `<contract>_client.ts` in the target directory)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    out_file: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+}
+
+impl AbiCodegen {
+    fn input_lang(
+        _context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<CodegenLanguage>> {
+        let variants = CodegenLanguageDiscriminants::iter().collect::<Vec<_>>();
+        let selected = inquire::Select::new(
+            "Which language should the client be generated for?",
+            variants,
+        )
+        .prompt()?;
+        match selected {
+            CodegenLanguageDiscriminants::Rust => Ok(Some(CodegenLanguage::Rust)),
+            CodegenLanguageDiscriminants::Typescript => Ok(Some(CodegenLanguage::Typescript)),
+        }
+    }
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, clap::ValueEnum)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum CodegenLanguage {
+    #[strum_discriminants(strum(message = "rust        - A module using `unc-jsonrpc-client`"))]
+    Rust,
+    #[strum_discriminants(strum(
+        message = "typescript  - A module using plain JSON-RPC requests"
+    ))]
+    Typescript,
+}
+
+impl interactive_clap::ToCli for CodegenLanguage {
+    type CliVariant = CodegenLanguage;
+}
+
+impl std::str::FromStr for CodegenLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(Self::Rust),
+            "typescript" => Ok(Self::Typescript),
+            _ => Err(format!("unsupported codegen language: {s}")),
+        }
+    }
+}
+
+impl std::fmt::Display for CodegenLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Rust => write!(f, "rust"),
+            Self::Typescript => write!(f, "typescript"),
+        }
+    }
+}
+
+impl std::fmt::Display for CodegenLanguageDiscriminants {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.get_message().unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AbiCodegenContext;
+
+impl AbiCodegenContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<AbiCodegen as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let (abi_root, default_out_dir) = match &scope.abi_file {
+            Some(abi_file) => {
+                let abi_root: unc_abi::AbiRoot = serde_json::from_slice(
+                    &std::fs::read(abi_file)
+                        .wrap_err_with(|| format!("Failed to read the ABI file: {abi_file}"))?,
+                )
+                .wrap_err_with(|| format!("Failed to parse the ABI file: {abi_file}"))?;
+                (abi_root, Utf8PathBuf::from("."))
+            }
+            None => {
+                let crate_metadata =
+                    util::handle_step("Collecting cargo project metadata...", || {
+                        let manifest_path: Utf8PathBuf = scope
+                            .manifest_path
+                            .clone()
+                            .map_or_else(|| "Cargo.toml".into(), Into::into);
+                        CrateMetadata::collect(CargoManifestPath::try_from(manifest_path)?)
+                    })?;
+                let abi_root = super::abi::generate_abi(
+                    &crate_metadata,
+                    true,
+                    false,
+                    &[],
+                    &[],
+                    ColorPreference::Auto,
+                )?;
+                (abi_root, crate_metadata.target_directory)
+            }
+        };
+
+        let client = ClientModel::from_abi(&abi_root);
+        let (code, extension) = match scope.lang {
+            CodegenLanguage::Rust => (rust::generate(&client), "rs"),
+            CodegenLanguage::Typescript => (typescript::generate(&client), "ts"),
+        };
+        let out_file: Utf8PathBuf = scope.out_file.clone().map_or_else(
+            || {
+                default_out_dir.join(format!(
+                    "{}_client.{extension}",
+                    to_snake_case(&client.contract_name)
+                ))
+            },
+            Into::into,
+        );
+        std::fs::write(&out_file, code)
+            .wrap_err_with(|| format!("Failed to write to file: {out_file}"))?;
+
+        util::print_success(&format!("The {} client is generated!", scope.lang));
+        eprintln!("     - Client: {}", out_file.to_string().yellow().bold());
+        Ok(Self)
+    }
+}
+
+/// The language-independent description of the generated client.
+pub(super) struct ClientModel {
+    pub contract_name: String,
+    pub schema_version: String,
+    pub types: Vec<TypeDefinition>,
+    pub functions: Vec<ClientFunction>,
+}
+
+pub(super) struct TypeDefinition {
+    pub name: String,
+    pub doc: Option<String>,
+    pub kind: TypeDefinitionKind,
+}
+
+pub(super) enum TypeDefinitionKind {
+    Struct(Vec<Field>),
+    /// A string enumeration.
+    Enum(Vec<String>),
+    Alias(TypeRef),
+}
+
+pub(super) struct Field {
+    pub name: String,
+    pub doc: Option<String>,
+    pub ty: TypeRef,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TypeRef {
+    String,
+    Bool,
+    Integer {
+        signed: bool,
+        bits: u8,
+    },
+    Number,
+    Null,
+    Array(Box<TypeRef>),
+    Tuple(Vec<TypeRef>),
+    Map(Box<TypeRef>),
+    Option(Box<TypeRef>),
+    /// A type from the ABI definitions.
+    Named(String),
+    /// Any JSON value (the schema is too complex to be expressed as a type).
+    Json,
+}
+
+pub(super) struct ClientFunction {
+    pub name: String,
+    pub doc: Option<String>,
+    pub is_view: bool,
+    pub is_init: bool,
+    pub is_payable: bool,
+    pub args: FunctionArgs,
+    pub result: FunctionResult,
+}
+
+pub(super) enum FunctionArgs {
+    None,
+    Json(Vec<Field>),
+    /// Borsh-serialized arguments are passed as raw bytes.
+    Borsh,
+}
+
+pub(super) enum FunctionResult {
+    None,
+    Json(TypeRef),
+    /// Borsh-serialized results are returned as raw bytes.
+    Borsh,
+}
+
+impl ClientModel {
+    fn from_abi(abi_root: &unc_abi::AbiRoot) -> Self {
+        let types = abi_root
+            .body
+            .root_schema
+            .definitions
+            .iter()
+            .map(|(name, schema)| {
+                let schema = serde_json::to_value(schema).unwrap_or_default();
+                TypeDefinition {
+                    name: to_pascal_case(name),
+                    doc: get_description(&schema),
+                    kind: get_type_definition_kind(&schema),
+                }
+            })
+            .collect();
+        let functions = abi_root
+            .body
+            .functions
+            .iter()
+            // Private functions (e.g. callbacks) can only be called by the contract itself.
+            .filter(|function| {
+                !function
+                    .modifiers
+                    .contains(&unc_abi::AbiFunctionModifier::Private)
+            })
+            .map(|function| ClientFunction {
+                name: function.name.clone(),
+                doc: function.doc.as_deref().map(get_doc),
+                is_view: function.kind == unc_abi::AbiFunctionKind::View,
+                is_init: function
+                    .modifiers
+                    .contains(&unc_abi::AbiFunctionModifier::Init),
+                is_payable: function
+                    .modifiers
+                    .contains(&unc_abi::AbiFunctionModifier::Payable),
+                args: match &function.params {
+                    unc_abi::AbiParameters::Json { args } if args.is_empty() => FunctionArgs::None,
+                    unc_abi::AbiParameters::Json { args } => FunctionArgs::Json(
+                        args.iter()
+                            .map(|arg| {
+                                let schema =
+                                    serde_json::to_value(&arg.type_schema).unwrap_or_default();
+                                let ty = get_type_ref(&schema);
+                                Field {
+                                    name: arg.name.clone(),
+                                    doc: get_description(&schema),
+                                    optional: matches!(ty, TypeRef::Option(_)),
+                                    ty,
+                                }
+                            })
+                            .collect(),
+                    ),
+                    unc_abi::AbiParameters::Borsh { .. } => FunctionArgs::Borsh,
+                },
+                result: match &function.result {
+                    None => FunctionResult::None,
+                    Some(unc_abi::AbiType::Json { type_schema }) => FunctionResult::Json(
+                        get_type_ref(&serde_json::to_value(type_schema).unwrap_or_default()),
+                    ),
+                    Some(unc_abi::AbiType::Borsh { .. }) => FunctionResult::Borsh,
+                },
+            })
+            .collect();
+        Self {
+            contract_name: abi_root
+                .metadata
+                .name
+                .clone()
+                .unwrap_or_else(|| "contract".to_string()),
+            schema_version: abi_root.schema_version.clone(),
+            types,
+            functions,
+        }
+    }
+}
+
+/// Rustdoc comments keep the space after `///` in the ABI.
+fn get_doc(doc: &str) -> String {
+    doc.lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn get_description(schema: &serde_json::Value) -> Option<String> {
+    schema["description"].as_str().map(str::to_string)
+}
+
+fn get_type_definition_kind(schema: &serde_json::Value) -> TypeDefinitionKind {
+    if let Some(properties) = schema["properties"].as_object() {
+        let required = schema["required"].as_array().cloned().unwrap_or_default();
+        return TypeDefinitionKind::Struct(
+            properties
+                .iter()
+                .map(|(name, property_schema)| {
+                    let ty = get_type_ref(property_schema);
+                    Field {
+                        name: name.clone(),
+                        doc: get_description(property_schema),
+                        optional: !required.contains(&name.as_str().into())
+                            || matches!(ty, TypeRef::Option(_)),
+                        ty,
+                    }
+                })
+                .collect(),
+        );
+    }
+    if let Some(variants) = get_string_enum_variants(schema) {
+        return TypeDefinitionKind::Enum(variants);
+    }
+    TypeDefinitionKind::Alias(get_type_ref(schema))
+}
+
+/// Unit enum variants are either listed in `enum` or as `oneOf` single-value schemas (when documented).
+fn get_string_enum_variants(schema: &serde_json::Value) -> Option<Vec<String>> {
+    let string_values = |values: &serde_json::Value| -> Option<Vec<String>> {
+        values
+            .as_array()?
+            .iter()
+            .map(|value| value.as_str().map(str::to_string))
+            .collect()
+    };
+    if let Some(variants) = string_values(&schema["enum"]) {
+        return Some(variants);
+    }
+    schema["oneOf"]
+        .as_array()?
+        .iter()
+        .map(
+            |variant| match string_values(&variant["enum"])?.as_slice() {
+                [value] => Some(value.clone()),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+fn get_type_ref(schema: &serde_json::Value) -> TypeRef {
+    if let Some(name) = schema["$ref"]
+        .as_str()
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    {
+        return TypeRef::Named(to_pascal_case(name));
+    }
+    // `schemars` wraps documented references into `allOf`.
+    if let Some([schema]) = schema["allOf"].as_array().map(Vec::as_slice) {
+        return get_type_ref(schema);
+    }
+    if let Some(schemas) = schema["anyOf"].as_array() {
+        let (nulls, values): (Vec<_>, Vec<_>) =
+            schemas.iter().partition(|schema| schema["type"] == "null");
+        return match (&nulls[..], &values[..]) {
+            ([_], [value]) => TypeRef::Option(Box::new(get_type_ref(value))),
+            _ => TypeRef::Json,
+        };
+    }
+    match &schema["type"] {
+        serde_json::Value::String(instance_type) => get_instance_type_ref(instance_type, schema),
+        serde_json::Value::Array(instance_types) => {
+            let instance_types = instance_types
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .collect::<Vec<_>>();
+            match instance_types[..] {
+                [instance_type, "null"] | ["null", instance_type] => {
+                    TypeRef::Option(Box::new(get_instance_type_ref(instance_type, schema)))
+                }
+                _ => TypeRef::Json,
+            }
+        }
+        _ => TypeRef::Json,
+    }
+}
+
+fn get_instance_type_ref(instance_type: &str, schema: &serde_json::Value) -> TypeRef {
+    match instance_type {
+        "string" => TypeRef::String,
+        "boolean" => TypeRef::Bool,
+        "number" => TypeRef::Number,
+        "null" => TypeRef::Null,
+        "integer" => {
+            let format = schema["format"].as_str().unwrap_or("int64");
+            let signed = !format.starts_with("uint");
+            let bits = format
+                .trim_start_matches("uint")
+                .trim_start_matches("int")
+                .parse()
+                .unwrap_or(64);
+            TypeRef::Integer { signed, bits }
+        }
+        "array" => match &schema["items"] {
+            serde_json::Value::Array(items) => {
+                TypeRef::Tuple(items.iter().map(get_type_ref).collect())
+            }
+            serde_json::Value::Null => TypeRef::Array(Box::new(TypeRef::Json)),
+            items => TypeRef::Array(Box::new(get_type_ref(items))),
+        },
+        "object" => match &schema["additionalProperties"] {
+            serde_json::Value::Object(_) if schema["properties"].is_null() => {
+                TypeRef::Map(Box::new(get_type_ref(&schema["additionalProperties"])))
+            }
+            _ => TypeRef::Json,
+        },
+        _ => TypeRef::Json,
+    }
+}
+
+pub(super) fn to_pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+pub(super) fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !snake_case.ends_with('_') {
+                snake_case.push('_');
+            }
+            snake_case.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            snake_case.push(c);
+        } else if !snake_case.ends_with('_') {
+            snake_case.push('_');
+        }
+    }
+    snake_case
+}
+
+pub(super) fn to_camel_case(name: &str) -> String {
+    let pascal_case = to_pascal_case(name);
+    let mut chars = pascal_case.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_message_abi() -> unc_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": {"name": "status-message"},
+            "body": {
+                "functions": [
+                    {"name": "get_status", "kind": "view", "params": {"serialization_type": "json", "args": [
+                        {"name": "account_id", "type_schema": {"type": "string"}}
+                    ]}, "result": {"serialization_type": "json", "type_schema": {
+                        "anyOf": [{"$ref": "#/definitions/Status"}, {"type": "null"}]
+                    }}},
+                    {"name": "set_status", "kind": "call", "modifiers": ["payable"], "params": {"serialization_type": "json", "args": [
+                        {"name": "message", "type_schema": {"type": "string"}},
+                        {"name": "ttl", "type_schema": {"type": ["integer", "null"], "format": "uint64"}}
+                    ]}},
+                    {"name": "on_status_set", "kind": "call", "modifiers": ["private"]}
+                ],
+                "root_schema": {
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "String",
+                    "type": "string",
+                    "definitions": {
+                        "Status": {"type": "object", "required": ["message"], "properties": {
+                            "message": {"type": "string"},
+                            "kind": {"type": "string", "enum": ["busy", "away"]}
+                        }}
+                    },
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn build_client_model_from_abi() {
+        let client = ClientModel::from_abi(&status_message_abi());
+        assert_eq!(client.contract_name, "status-message");
+        assert_eq!(
+            client
+                .functions
+                .iter()
+                .map(|function| function.name.as_str())
+                .collect::<Vec<_>>(),
+            ["get_status", "set_status"]
+        );
+        assert!(matches!(
+            &client.functions[0].result,
+            FunctionResult::Json(TypeRef::Option(ty)) if **ty == TypeRef::Named("Status".to_string())
+        ));
+        assert!(client.functions[1].is_payable);
+
+        let rust_code = rust::generate(&client);
+        assert!(rust_code.contains("pub struct StatusMessageClient {"));
+        assert!(rust_code.contains("pub async fn get_status(&self, args: &GetStatusArgs) -> Result<Option<Status>, Error> {"));
+        assert!(rust_code.contains("pub ttl: Option<u64>,"));
+
+        let typescript_code = typescript::generate(&client);
+        assert!(typescript_code.contains("export class StatusMessageClient {"));
+        assert!(typescript_code
+            .contains("async getStatus(args: GetStatusArgs): Promise<Status | null> {"));
+        assert!(typescript_code.contains("setStatus(args: SetStatusArgs, deposit: string, gas: string = DEFAULT_GAS): FunctionCall {"));
+    }
+
+    #[test]
+    fn generated_rust_client_parses() {
+        let rust_code = rust::generate(&ClientModel::from_abi(&status_message_abi()));
+        let file = syn::parse_file(&rust_code).unwrap();
+        let item_names = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Struct(item) => Some(item.ident.to_string()),
+                syn::Item::Enum(item) => Some(item.ident.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for name in [
+            "Status",
+            "GetStatusArgs",
+            "SetStatusArgs",
+            "StatusMessageClient",
+        ] {
+            assert!(item_names.contains(&name.to_string()), "{name} is missing");
+        }
+    }
+
+    /// Checks that the brackets of the TypeScript code are balanced outside of the string literals
+    /// and comments, and returns the capitalized identifiers used in the code.
+    fn typescript_type_names(code: &str) -> std::collections::BTreeSet<String> {
+        let mut names = std::collections::BTreeSet::new();
+        let mut brackets = vec![];
+        let mut chars = code.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '/' if chars.peek() == Some(&'/') => {
+                    chars.by_ref().take_while(|&c| c != '\n').for_each(drop);
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    let mut previous = ' ';
+                    for c in chars.by_ref() {
+                        if previous == '*' && c == '/' {
+                            break;
+                        }
+                        previous = c;
+                    }
+                }
+                '"' | '`' => {
+                    let mut escaped = false;
+                    for next in chars.by_ref() {
+                        match next {
+                            _ if escaped => escaped = false,
+                            '\\' => escaped = true,
+                            _ if next == c => break,
+                            _ => {}
+                        }
+                    }
+                }
+                '(' | '{' | '[' => brackets.push(c),
+                ')' | '}' | ']' => {
+                    let open = brackets.pop();
+                    assert_eq!(
+                        open,
+                        Some(match c {
+                            ')' => '(',
+                            '}' => '{',
+                            _ => '[',
+                        }),
+                        "unbalanced `{c}` in:\n{code}"
+                    );
+                }
+                c if c.is_ascii_uppercase() => {
+                    let mut name = c.to_string();
+                    while let Some(&next) = chars.peek() {
+                        if !next.is_ascii_alphanumeric() && next != '_' {
+                            break;
+                        }
+                        name.push(next);
+                        chars.next();
+                    }
+                    names.insert(name);
+                }
+                c if c.is_ascii_alphanumeric() || c == '_' => {
+                    while chars
+                        .peek()
+                        .is_some_and(|next| next.is_ascii_alphanumeric() || *next == '_')
+                    {
+                        chars.next();
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(brackets.is_empty(), "unclosed brackets in:\n{code}");
+        names
+    }
+
+    #[test]
+    fn generated_typescript_client_is_valid() {
+        let typescript_code = typescript::generate(&ClientModel::from_abi(&status_message_abi()));
+        let declared_names = typescript_code
+            .lines()
+            .filter_map(|line| {
+                ["export interface ", "export type ", "export class "]
+                    .iter()
+                    .find_map(|prefix| line.strip_prefix(prefix))
+            })
+            .filter_map(|declaration| declaration.split([' ', '<', '=']).next())
+            .collect::<Vec<_>>();
+        const BUILTIN_NAMES: &[&str] = &[
+            "DEFAULT_GAS",
+            "Error",
+            "JSON",
+            "Promise",
+            "String",
+            "T",
+            "TextDecoder",
+            "TextEncoder",
+            "Uint8Array",
+        ];
+        for name in typescript_type_names(&typescript_code) {
+            assert!(
+                declared_names.contains(&name.as_str()) || BUILTIN_NAMES.contains(&name.as_str()),
+                "`{name}` is not declared in:\n{typescript_code}"
+            );
+        }
+        for name in [
+            "Status",
+            "GetStatusArgs",
+            "SetStatusArgs",
+            "StatusMessageClient",
+        ] {
+            assert!(declared_names.contains(&name), "{name} is missing");
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt::Write;
+
+use super::{
+    to_pascal_case, to_snake_case, ClientFunction, ClientModel, Field, FunctionArgs,
+    FunctionResult, TypeDefinition, TypeDefinitionKind, TypeRef,
+};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+/// The methods of the generated client that the contract functions must not shadow.
+const CLIENT_METHODS: &[&str] = &["new", "contract_id", "view", "function_call", "send"];
+
+/// Generates a Rust module with the ABI types and a client that uses `unc-jsonrpc-client`.
+pub(super) fn generate(client: &ClientModel) -> String {
+    let mut code = String::new();
+    let client_name = format!("{}Client", to_pascal_case(&client.contract_name));
+    let _ = writeln!(
+        code,
+        "//! A client for the `{}` contract, generated by `unc dev-tool abi-codegen` from its ABI (schema version {}).",
+        client.contract_name, client.schema_version
+    );
+    code.push_str(
+        "//!
+//! Requires the `serde`, `serde_json`, `unc-crypto`, `unc-jsonrpc-client`, `unc-jsonrpc-primitives`
+//! and `unc-primitives` crates.
+
+use unc_crypto::Signer;
+use unc_jsonrpc_client::{methods, JsonRpcClient};
+use unc_jsonrpc_primitives::types::query::QueryResponseKind;
+use unc_primitives::transaction::{Action, FunctionCallAction, SignedTransaction, Transaction};
+use unc_primitives::types::{AccountId, Balance, BlockReference, Finality, FunctionArgs, Gas};
+use unc_primitives::views::{FinalExecutionOutcomeView, QueryRequest};
+
+/// The gas to attach to a call function without a better estimate (30 Tgas).
+pub const DEFAULT_GAS: Gas = 30_000_000_000_000;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+",
+    );
+
+    for type_definition in &client.types {
+        code.push('\n');
+        write_type_definition(&mut code, type_definition);
+    }
+    for function in &client.functions {
+        if let FunctionArgs::Json(args) = &function.args {
+            code.push('\n');
+            let _ = writeln!(code, "/// The arguments of `{}`.", function.name);
+            write_struct(&mut code, &args_struct_name(function), args);
+        }
+    }
+
+    let _ = write!(
+        code,
+        "
+pub struct {client_name} {{
+    client: JsonRpcClient,
+    contract_id: AccountId,
+}}
+
+impl {client_name} {{
+    pub fn new(client: JsonRpcClient, contract_id: AccountId) -> Self {{
+        Self {{ client, contract_id }}
+    }}
+
+    pub fn contract_id(&self) -> &AccountId {{
+        &self.contract_id
+    }}
+
+    async fn view(&self, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>, Error> {{
+        let response = self
+            .client
+            .call(methods::query::RpcQueryRequest {{
+                block_reference: BlockReference::Finality(Finality::Final),
+                request: QueryRequest::CallFunction {{
+                    account_id: self.contract_id.clone(),
+                    method_name: method_name.to_string(),
+                    args: FunctionArgs::from(args),
+                }},
+            }})
+            .await?;
+        match response.kind {{
+            QueryResponseKind::CallResult(result) => Ok(result.result),
+            _ => Err(\"unexpected response to a call function query\".into()),
+        }}
+    }}
+
+    fn function_call(&self, method_name: &str, args: Vec<u8>, gas: Gas, deposit: Balance) -> Action {{
+        Action::FunctionCall(Box::new(FunctionCallAction {{
+            method_name: method_name.to_string(),
+            args,
+            gas,
+            deposit,
+        }}))
+    }}
+
+    /// Signs a transaction with the actions (returned by the call functions) to the contract and waits for its outcome.
+    pub async fn send(
+        &self,
+        signer: &unc_crypto::InMemorySigner,
+        actions: Vec<Action>,
+    ) -> Result<FinalExecutionOutcomeView, Error> {{
+        let access_key = self
+            .client
+            .call(methods::query::RpcQueryRequest {{
+                block_reference: BlockReference::latest(),
+                request: QueryRequest::ViewAccessKey {{
+                    account_id: signer.account_id.clone(),
+                    public_key: signer.public_key.clone(),
+                }},
+            }})
+            .await?;
+        let nonce = match access_key.kind {{
+            QueryResponseKind::AccessKey(access_key) => access_key.nonce,
+            _ => return Err(\"unexpected response to a view access key query\".into()),
+        }};
+        let transaction = Transaction {{
+            signer_id: signer.account_id.clone(),
+            public_key: signer.public_key.clone(),
+            nonce: nonce + 1,
+            receiver_id: self.contract_id.clone(),
+            block_hash: access_key.block_hash,
+            actions,
+        }};
+        let signature = signer.sign(transaction.get_hash_and_size().0.as_ref());
+        Ok(self
+            .client
+            .call(methods::broadcast_tx_commit::RpcBroadcastTxCommitRequest {{
+                signed_transaction: SignedTransaction::new(signature, transaction),
+            }})
+            .await?)
+    }}
+"
+    );
+    for function in &client.functions {
+        code.push('\n');
+        write_function(&mut code, function);
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn write_doc(code: &mut String, doc: Option<&str>, indent: &str) {
+    for line in doc.into_iter().flat_map(str::lines) {
+        let _ = writeln!(
+            code,
+            "{indent}///{}{}",
+            if line.is_empty() { "" } else { " " },
+            line
+        );
+    }
+}
+
+fn write_type_definition(code: &mut String, type_definition: &TypeDefinition) {
+    write_doc(code, type_definition.doc.as_deref(), "");
+    match &type_definition.kind {
+        TypeDefinitionKind::Struct(fields) => write_struct(code, &type_definition.name, fields),
+        TypeDefinitionKind::Enum(variants) => {
+            code.push_str(
+                "#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]\n",
+            );
+            let _ = writeln!(code, "pub enum {} {{", type_definition.name);
+            for variant in variants {
+                let _ = writeln!(code, "    #[serde(rename = \"{variant}\")]");
+                let _ = writeln!(code, "    {},", to_pascal_case(variant));
+            }
+            code.push_str("}\n");
+        }
+        TypeDefinitionKind::Alias(ty) => {
+            let _ = writeln!(
+                code,
+                "pub type {} = {};",
+                type_definition.name,
+                rust_type(ty)
+            );
+        }
+    }
+}
+
+fn write_struct(code: &mut String, name: &str, fields: &[Field]) {
+    code.push_str("#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\n");
+    let _ = writeln!(code, "pub struct {name} {{");
+    for field in fields {
+        write_doc(code, field.doc.as_deref(), "    ");
+        let field_name = rust_field_name(&field.name);
+        if field_name.trim_start_matches("r#") != field.name {
+            let _ = writeln!(code, "    #[serde(rename = \"{}\")]", field.name);
+        }
+        let inner_ty = match &field.ty {
+            TypeRef::Option(ty) => ty.as_ref(),
+            ty => ty,
+        };
+        let mut ty = rust_type(inner_ty);
+        // Recursive types have to be boxed.
+        if *inner_ty == TypeRef::Named(name.to_string()) {
+            ty = format!("Box<{ty}>");
+        }
+        if field.optional || matches!(field.ty, TypeRef::Option(_)) {
+            ty = format!("Option<{ty}>");
+        }
+        if field.optional {
+            code.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+        }
+        let _ = writeln!(code, "    pub {field_name}: {ty},");
+    }
+    code.push_str("}\n");
+}
+
+fn rust_field_name(name: &str) -> String {
+    let field_name = to_snake_case(name);
+    if RUST_KEYWORDS.contains(&field_name.as_str()) {
+        format!("r#{field_name}")
+    } else {
+        field_name
+    }
+}
+
+fn rust_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Bool => "bool".to_string(),
+        TypeRef::Integer { signed, bits } => {
+            let bits = if [8, 16, 32, 64, 128].contains(bits) {
+                *bits
+            } else {
+                64
+            };
+            format!("{}{bits}", if *signed { "i" } else { "u" })
+        }
+        TypeRef::Number => "f64".to_string(),
+        TypeRef::Null => "()".to_string(),
+        TypeRef::Array(item) => format!("Vec<{}>", rust_type(item)),
+        TypeRef::Tuple(items) => format!(
+            "({},)",
+            items.iter().map(rust_type).collect::<Vec<_>>().join(", ")
+        ),
+        TypeRef::Map(value) => format!("std::collections::HashMap<String, {}>", rust_type(value)),
+        TypeRef::Option(ty) => format!("Option<{}>", rust_type(ty)),
+        TypeRef::Named(name) => name.clone(),
+        TypeRef::Json => "serde_json::Value".to_string(),
+    }
+}
+
+fn args_struct_name(function: &ClientFunction) -> String {
+    format!("{}Args", to_pascal_case(&function.name))
+}
+
+fn write_function(code: &mut String, function: &ClientFunction) {
+    write_doc(code, function.doc.as_deref(), "    ");
+    if function.doc.is_some() {
+        code.push_str("    ///\n");
+    }
+    let (args_param, args_value) = match &function.args {
+        FunctionArgs::None => (String::new(), "b\"{}\".to_vec()".to_string()),
+        FunctionArgs::Json(_) => (
+            format!(", args: &{}", args_struct_name(function)),
+            "serde_json::to_vec(args)?".to_string(),
+        ),
+        FunctionArgs::Borsh => (", args: Vec<u8>".to_string(), "args".to_string()),
+    };
+    let mut method_name = rust_field_name(&function.name);
+    if CLIENT_METHODS.contains(&method_name.as_str()) {
+        method_name = format!("{method_name}_function");
+    }
+
+    if function.is_view {
+        code.push_str("    /// A view function.\n");
+        if matches!(function.args, FunctionArgs::Borsh) {
+            code.push_str("    /// The arguments are Borsh-serialized.\n");
+        }
+        let result_type = match &function.result {
+            FunctionResult::None => "()".to_string(),
+            FunctionResult::Json(ty) => rust_type(ty),
+            FunctionResult::Borsh => {
+                code.push_str("    /// The result is Borsh-serialized.\n");
+                "Vec<u8>".to_string()
+            }
+        };
+        let _ = writeln!(
+            code,
+            "    pub async fn {method_name}(&self{args_param}) -> Result<{result_type}, Error> {{"
+        );
+        let view_call = format!("self.view(\"{}\", {args_value}).await?", function.name);
+        match &function.result {
+            FunctionResult::None => {
+                let _ = writeln!(code, "        {view_call};\n        Ok(())");
+            }
+            FunctionResult::Json(_) => {
+                let _ = writeln!(code, "        let result = {view_call};");
+                code.push_str("        Ok(serde_json::from_slice(&result)?)\n");
+            }
+            FunctionResult::Borsh => {
+                let _ = writeln!(code, "        Ok({view_call})");
+            }
+        }
+        code.push_str("    }\n");
+        return;
+    }
+
+    if function.is_init {
+        code.push_str("    /// An initialization function: call it in the same transaction as the code deployment.\n");
+    } else {
+        code.push_str("    /// A call function: pass the returned action to [`Self::send`].\n");
+    }
+    if matches!(function.args, FunctionArgs::Borsh) {
+        code.push_str("    /// The arguments are Borsh-serialized.\n");
+    }
+    match &function.result {
+        FunctionResult::None => {}
+        FunctionResult::Json(ty) => {
+            let _ = writeln!(
+                code,
+                "    /// The result in the transaction outcome is JSON-serialized `{}`.",
+                rust_type(ty)
+            );
+        }
+        FunctionResult::Borsh => {
+            code.push_str("    /// The result in the transaction outcome is Borsh-serialized.\n")
+        }
+    }
+    let deposit_param = if function.is_payable {
+        code.push_str("    /// Payable: the deposit is transferred to the contract.\n");
+        ", deposit: Balance"
+    } else {
+        ""
+    };
+    let _ = writeln!(
+        code,
+        "    pub fn {method_name}(&self{args_param}, gas: Gas{deposit_param}) -> Result<Action, Error> {{"
+    );
+    let _ = writeln!(
+        code,
+        "        Ok(self.function_call(\"{}\", {args_value}, gas, {}))",
+        function.name,
+        if function.is_payable { "deposit" } else { "0" }
+    );
+    code.push_str("    }\n");
+}
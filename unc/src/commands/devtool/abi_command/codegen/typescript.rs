@@ -0,0 +1,317 @@
+use std::fmt::Write;
+
+use super::{
+    to_camel_case, to_pascal_case, ClientFunction, ClientModel, Field, FunctionArgs,
+    FunctionResult, TypeDefinition, TypeDefinitionKind, TypeRef,
+};
+
+/// The members of the generated client that the contract functions must not shadow.
+const CLIENT_MEMBERS: &[&str] = &[
+    "constructor",
+    "rpcUrl",
+    "contractId",
+    "view",
+    "functionCall",
+];
+
+/// Generates a TypeScript module with the ABI types and a client that sends plain JSON-RPC requests.
+pub(super) fn generate(client: &ClientModel) -> String {
+    let mut code = String::new();
+    let client_name = format!("{}Client", to_pascal_case(&client.contract_name));
+    let _ = writeln!(
+        code,
+        "// A client for the `{}` contract, generated by `unc dev-tool abi-codegen` from its ABI (schema version {}).",
+        client.contract_name, client.schema_version
+    );
+    code.push_str(
+        "
+/** The gas to attach to a call function without a better estimate (30 Tgas). */
+export const DEFAULT_GAS = \"30000000000000\";
+
+/** A function call action to sign and send with a wallet or a key store. */
+export interface FunctionCall {
+  receiverId: string;
+  methodName: string;
+  args: Uint8Array;
+  /** In gas units. */
+  gas: string;
+  /** In attoUNC. */
+  deposit: string;
+}
+",
+    );
+
+    for type_definition in &client.types {
+        code.push('\n');
+        write_type_definition(&mut code, type_definition);
+    }
+    for function in &client.functions {
+        if let FunctionArgs::Json(args) = &function.args {
+            code.push('\n');
+            let _ = writeln!(code, "/** The arguments of `{}`. */", function.name);
+            write_interface(&mut code, &args_interface_name(function), args);
+        }
+    }
+
+    let _ = write!(
+        code,
+        "
+export class {client_name} {{
+  constructor(readonly rpcUrl: string, readonly contractId: string) {{}}
+
+  private async view(methodName: string, args: Uint8Array): Promise<Uint8Array> {{
+    const response = await fetch(this.rpcUrl, {{
+      method: \"POST\",
+      headers: {{ \"Content-Type\": \"application/json\" }},
+      body: JSON.stringify({{
+        jsonrpc: \"2.0\",
+        id: \"unc-codegen\",
+        method: \"query\",
+        params: {{
+          request_type: \"call_function\",
+          finality: \"final\",
+          account_id: this.contractId,
+          method_name: methodName,
+          args_base64: toBase64(args),
+        }},
+      }}),
+    }});
+    const body = await response.json();
+    if (body.error) {{
+      throw new Error(`RPC error: ${{JSON.stringify(body.error)}}`);
+    }}
+    if (body.result.error) {{
+      throw new Error(`Function call error: ${{body.result.error}}`);
+    }}
+    return new Uint8Array(body.result.result);
+  }}
+
+  private functionCall(methodName: string, args: Uint8Array, gas: string, deposit: string): FunctionCall {{
+    return {{ receiverId: this.contractId, methodName, args, gas, deposit }};
+  }}
+"
+    );
+    for function in &client.functions {
+        code.push('\n');
+        write_function(&mut code, function);
+    }
+    code.push_str(
+        "}
+
+function encodeJson(value: unknown): Uint8Array {
+  return new TextEncoder().encode(JSON.stringify(value));
+}
+
+function decodeJson<T>(bytes: Uint8Array): T {
+  return JSON.parse(new TextDecoder().decode(bytes));
+}
+
+function toBase64(bytes: Uint8Array): string {
+  let binary = \"\";
+  for (const byte of bytes) {
+    binary += String.fromCharCode(byte);
+  }
+  return btoa(binary);
+}
+",
+    );
+    code
+}
+
+fn write_doc(code: &mut String, doc: &[&str], indent: &str) {
+    match doc {
+        [] => {}
+        [line] => {
+            let _ = writeln!(code, "{indent}/** {line} */");
+        }
+        lines => {
+            let _ = writeln!(code, "{indent}/**");
+            for line in lines {
+                let _ = writeln!(
+                    code,
+                    "{indent} *{}{line}",
+                    if line.is_empty() { "" } else { " " }
+                );
+            }
+            let _ = writeln!(code, "{indent} */");
+        }
+    }
+}
+
+fn doc_lines(doc: Option<&str>) -> Vec<&str> {
+    doc.map(|doc| doc.lines().collect()).unwrap_or_default()
+}
+
+fn write_type_definition(code: &mut String, type_definition: &TypeDefinition) {
+    write_doc(code, &doc_lines(type_definition.doc.as_deref()), "");
+    match &type_definition.kind {
+        TypeDefinitionKind::Struct(fields) => write_interface(code, &type_definition.name, fields),
+        TypeDefinitionKind::Enum(variants) => {
+            let _ = writeln!(
+                code,
+                "export type {} = {};",
+                type_definition.name,
+                variants
+                    .iter()
+                    .map(|variant| format!("{variant:?}"))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            );
+        }
+        TypeDefinitionKind::Alias(ty) => {
+            let _ = writeln!(
+                code,
+                "export type {} = {};",
+                type_definition.name,
+                typescript_type(ty)
+            );
+        }
+    }
+}
+
+fn write_interface(code: &mut String, name: &str, fields: &[Field]) {
+    let _ = writeln!(code, "export interface {name} {{");
+    for field in fields {
+        write_doc(code, &doc_lines(field.doc.as_deref()), "  ");
+        let is_identifier = field.name.chars().enumerate().all(|(i, c)| {
+            c == '_' || c == '$' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+        });
+        let field_name = if is_identifier {
+            field.name.clone()
+        } else {
+            format!("{:?}", field.name)
+        };
+        let _ = writeln!(
+            code,
+            "  {field_name}{}: {};",
+            if field.optional { "?" } else { "" },
+            typescript_type(&field.ty)
+        );
+    }
+    code.push_str("}\n");
+}
+
+fn typescript_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "string".to_string(),
+        TypeRef::Bool => "boolean".to_string(),
+        TypeRef::Integer { .. } | TypeRef::Number => "number".to_string(),
+        TypeRef::Null => "null".to_string(),
+        TypeRef::Array(item) => match item.as_ref() {
+            TypeRef::Option(_) => format!("({})[]", typescript_type(item)),
+            item => format!("{}[]", typescript_type(item)),
+        },
+        TypeRef::Tuple(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(typescript_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TypeRef::Map(value) => format!("Record<string, {}>", typescript_type(value)),
+        TypeRef::Option(ty) => format!("{} | null", typescript_type(ty)),
+        TypeRef::Named(name) => name.clone(),
+        TypeRef::Json => "unknown".to_string(),
+    }
+}
+
+fn args_interface_name(function: &ClientFunction) -> String {
+    format!("{}Args", to_pascal_case(&function.name))
+}
+
+fn write_function(code: &mut String, function: &ClientFunction) {
+    let mut doc = doc_lines(function.doc.as_deref());
+    if !doc.is_empty() {
+        doc.push("");
+    }
+    // The kind of the function goes first after its own documentation.
+    let kind_line = doc.len();
+    let (args_param, args_value) = match &function.args {
+        FunctionArgs::None => (String::new(), "encodeJson({})".to_string()),
+        FunctionArgs::Json(_) => (
+            format!("args: {}", args_interface_name(function)),
+            "encodeJson(args)".to_string(),
+        ),
+        FunctionArgs::Borsh => {
+            doc.push("The arguments are Borsh-serialized.");
+            ("args: Uint8Array".to_string(), "args".to_string())
+        }
+    };
+    let mut method_name = to_camel_case(&function.name);
+    if CLIENT_MEMBERS.contains(&method_name.as_str()) {
+        method_name = format!("{method_name}Function");
+    }
+
+    if function.is_view {
+        let view_call = format!("this.view({:?}, {args_value})", function.name);
+        let (result_type, body) = match &function.result {
+            FunctionResult::None => ("void".to_string(), format!("await {view_call};")),
+            FunctionResult::Json(ty) => {
+                let ty = typescript_type(ty);
+                let body = format!("return decodeJson<{ty}>(await {view_call});");
+                (ty, body)
+            }
+            FunctionResult::Borsh => {
+                doc.push("The result is Borsh-serialized.");
+                ("Uint8Array".to_string(), format!("return {view_call};"))
+            }
+        };
+        doc.insert(kind_line, "A view function.");
+        write_doc(code, &doc, "  ");
+        let _ = writeln!(
+            code,
+            "  async {method_name}({args_param}): Promise<{result_type}> {{\n    {body}"
+        );
+        code.push_str("  }\n");
+        return;
+    }
+
+    doc.insert(
+        kind_line,
+        if function.is_init {
+            "An initialization function: sign the returned action together with the code deployment."
+        } else {
+            "A call function: sign and send the returned action with a wallet or a key store."
+        },
+    );
+    let result_doc = match &function.result {
+        FunctionResult::Json(ty) => Some(format!(
+            "The result in the transaction outcome is JSON-serialized `{}`.",
+            typescript_type(ty)
+        )),
+        FunctionResult::Borsh => {
+            Some("The result in the transaction outcome is Borsh-serialized.".to_string())
+        }
+        FunctionResult::None => None,
+    };
+    if let Some(result_doc) = &result_doc {
+        doc.push(result_doc);
+    }
+    let mut params = vec![];
+    if !args_param.is_empty() {
+        params.push(args_param);
+    }
+    if function.is_payable {
+        doc.push("Payable: the deposit (in attoUNC) is transferred to the contract.");
+        params.push("deposit: string".to_string());
+    }
+    params.push("gas: string = DEFAULT_GAS".to_string());
+    write_doc(code, &doc, "  ");
+    let _ = writeln!(
+        code,
+        "  {method_name}({}): FunctionCall {{",
+        params.join(", ")
+    );
+    let _ = writeln!(
+        code,
+        "    return this.functionCall({:?}, {args_value}, gas, {});",
+        function.name,
+        if function.is_payable {
+            "deposit"
+        } else {
+            "\"0\""
+        }
+    );
+    code.push_str("  }\n");
+}
@@ -0,0 +1,512 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use colored::Colorize;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AbiDiffContext)]
+pub struct AbiDiff {
+    /// Enter the old ABI (a path to an ABI file or a contract account ID):
+    old: String,
+    /// Enter the new ABI (a path to an ABI file or a contract account ID):
+    new: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Network to fetch the ABI of contract accounts from
+    network: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AbiDiffContext;
+
+impl AbiDiffContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AbiDiff as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let old_abi = load_abi(&previous_context, &scope.old, scope.network.as_deref())?;
+        let new_abi = load_abi(&previous_context, &scope.new, scope.network.as_deref())?;
+
+        let changes = diff_abi(&old_abi, &new_abi);
+        if changes.is_empty() {
+            eprintln!("The ABIs are compatible: no changes in the contract interface");
+            return Ok(Self);
+        }
+        for change in &changes {
+            let severity = match change.severity {
+                Severity::Breaking => "breaking".red().bold(),
+                Severity::NonBreaking => "non-breaking".green().bold(),
+            };
+            println!(
+                " - [{severity}] {}: {}",
+                change.function.bold(),
+                change.description
+            );
+        }
+        let breaking_changes = changes
+            .iter()
+            .filter(|change| change.severity == Severity::Breaking)
+            .count();
+        eprintln!(
+            "\nSummary: {} breaking, {} non-breaking changes",
+            breaking_changes,
+            changes.len() - breaking_changes
+        );
+        if breaking_changes > 0 {
+            color_eyre::eyre::bail!(
+                "The new ABI has {breaking_changes} breaking change(s) compared to the old one"
+            );
+        }
+        Ok(Self)
+    }
+}
+
+/// Reads the ABI from a file (JSON, optionally zstd-compressed) or fetches it from a contract account.
+fn load_abi(
+    global_context: &crate::GlobalContext,
+    source: &str,
+    network: Option<&str>,
+) -> color_eyre::eyre::Result<unc_abi::AbiRoot> {
+    let path = std::path::Path::new(source);
+    if path.is_file() {
+        let data = std::fs::read(path)
+            .wrap_err_with(|| format!("Failed to read the ABI file: {}", path.display()))?;
+        let data = if path.extension().is_some_and(|extension| extension == "zst") {
+            zstd::decode_all(&data[..])?
+        } else {
+            data
+        };
+        return serde_json::from_slice(&data)
+            .wrap_err_with(|| format!("Failed to parse the ABI file: {}", path.display()));
+    }
+    let account_id: unc_primitives::types::AccountId = source.parse().wrap_err_with(|| {
+        format!("<{source}> is neither an existing ABI file nor a valid account ID")
+    })?;
+    if global_context.offline {
+        color_eyre::eyre::bail!(
+            "The ABI of <{account_id}> cannot be fetched in offline mode, use an ABI file instead"
+        );
+    }
+    let network_name = network.wrap_err_with(|| {
+        format!("Specify `--network` to fetch the ABI of the contract <{account_id}>")
+    })?;
+    let network_config = global_context
+        .config
+        .network_connection
+        .get(network_name)
+        .wrap_err_with(|| format!("Network <{network_name}> is not found in the CLI config"))?;
+    crate::commands::contract::call_function::abi::get_contract_abi(network_config, &account_id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Breaking,
+    NonBreaking,
+}
+
+#[derive(Debug, Clone)]
+pub struct AbiChange {
+    pub function: String,
+    pub severity: Severity,
+    pub description: String,
+}
+
+/// A function signature with the JSON schema references inlined and the docs stripped,
+/// so that two ABIs can be compared regardless of how their definitions are named.
+#[derive(Debug, PartialEq)]
+struct FunctionSignature {
+    kind: unc_abi::AbiFunctionKind,
+    modifiers: Vec<unc_abi::AbiFunctionModifier>,
+    params_serialization: &'static str,
+    params: Vec<(String, serde_json::Value)>,
+    result: Option<(&'static str, serde_json::Value)>,
+}
+
+fn get_function_signatures(abi: &unc_abi::AbiRoot) -> BTreeMap<String, FunctionSignature> {
+    let definitions = serde_json::to_value(&abi.body.root_schema.definitions)
+        .unwrap_or_default()
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    let normalize =
+        |schema: serde_json::Value| normalize_schema(&schema, &definitions, &mut Vec::new());
+
+    abi.body
+        .functions
+        .iter()
+        .map(|function| {
+            let (params_serialization, params) = match &function.params {
+                unc_abi::AbiParameters::Json { args } => (
+                    "JSON",
+                    args.iter()
+                        .map(|arg| {
+                            let schema = serde_json::to_value(&arg.type_schema).unwrap_or_default();
+                            (arg.name.clone(), normalize(schema))
+                        })
+                        .collect(),
+                ),
+                unc_abi::AbiParameters::Borsh { args } => (
+                    "Borsh",
+                    args.iter()
+                        .map(|arg| {
+                            let schema = serde_json::to_value(arg)
+                                .map(|arg| arg["type_schema"].clone())
+                                .unwrap_or_default();
+                            (arg.name.clone(), schema)
+                        })
+                        .collect(),
+                ),
+            };
+            let result = function.result.as_ref().map(|result| match result {
+                unc_abi::AbiType::Json { type_schema } => (
+                    "JSON",
+                    normalize(serde_json::to_value(type_schema).unwrap_or_default()),
+                ),
+                unc_abi::AbiType::Borsh { .. } => (
+                    "Borsh",
+                    serde_json::to_value(result)
+                        .map(|result| result["type_schema"].clone())
+                        .unwrap_or_default(),
+                ),
+            });
+            (
+                function.name.clone(),
+                FunctionSignature {
+                    kind: function.kind.clone(),
+                    modifiers: function.modifiers.clone(),
+                    params_serialization,
+                    params,
+                    result,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Inlines `#/definitions/...` references (keeping recursive ones as is) and drops the descriptive keywords.
+fn normalize_schema(
+    schema: &serde_json::Value,
+    definitions: &serde_json::Map<String, serde_json::Value>,
+    resolving: &mut Vec<String>,
+) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(object) => {
+            if let Some(name) = object
+                .get("$ref")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|reference| reference.strip_prefix("#/definitions/"))
+            {
+                if let (false, Some(definition)) =
+                    (resolving.iter().any(|r| r == name), definitions.get(name))
+                {
+                    resolving.push(name.to_string());
+                    let resolved = normalize_schema(definition, definitions, resolving);
+                    resolving.pop();
+                    return resolved;
+                }
+            }
+            serde_json::Value::Object(
+                object
+                    .iter()
+                    .filter(|(key, _)| !matches!(key.as_str(), "description" | "title"))
+                    .map(|(key, value)| {
+                        (key.clone(), normalize_schema(value, definitions, resolving))
+                    })
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .iter()
+                .map(|item| normalize_schema(item, definitions, resolving))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// Whether a JSON schema accepts `null`, so that the argument can be omitted by the callers.
+fn is_nullable(schema: &serde_json::Value) -> bool {
+    match &schema["type"] {
+        serde_json::Value::String(instance_type) if instance_type == "null" => return true,
+        serde_json::Value::Array(instance_types) if instance_types.contains(&"null".into()) => {
+            return true
+        }
+        _ => {}
+    }
+    ["anyOf", "oneOf"].iter().any(|keyword| {
+        schema[keyword]
+            .as_array()
+            .is_some_and(|schemas| schemas.iter().any(is_nullable))
+    })
+}
+
+/// Compares the functions of two ABIs and classifies every change of the contract interface.
+pub fn diff_abi(old_abi: &unc_abi::AbiRoot, new_abi: &unc_abi::AbiRoot) -> Vec<AbiChange> {
+    let old_functions = get_function_signatures(old_abi);
+    let mut new_functions = get_function_signatures(new_abi);
+    let mut changes = vec![];
+    let mut change = |function: &str, severity: Severity, description: String| {
+        changes.push(AbiChange {
+            function: function.to_string(),
+            severity,
+            description,
+        })
+    };
+
+    let mut removed_functions = vec![];
+    for (name, old_function) in &old_functions {
+        let Some(new_function) = new_functions.remove(name) else {
+            removed_functions.push((name, old_function));
+            continue;
+        };
+        diff_function(name, old_function, &new_function, &mut change);
+    }
+    for (name, old_function) in removed_functions {
+        let renamed_to = new_functions
+            .iter()
+            .find(|(_, new_function)| *new_function == old_function)
+            .map(|(new_name, _)| new_name.clone());
+        match renamed_to {
+            Some(new_name) => {
+                new_functions.remove(&new_name);
+                change(name, Severity::Breaking, format!("renamed to `{new_name}`"));
+            }
+            None => change(name, Severity::Breaking, "removed".to_string()),
+        }
+    }
+    for name in new_functions.keys() {
+        change(name, Severity::NonBreaking, "added".to_string());
+    }
+    changes
+}
+
+fn diff_function(
+    name: &str,
+    old_function: &FunctionSignature,
+    new_function: &FunctionSignature,
+    change: &mut impl FnMut(&str, Severity, String),
+) {
+    use unc_abi::{AbiFunctionKind, AbiFunctionModifier};
+
+    match (&old_function.kind, &new_function.kind) {
+        (AbiFunctionKind::View, AbiFunctionKind::Call) => change(
+            name,
+            Severity::Breaking,
+            "changed from a view to a call function".to_string(),
+        ),
+        (AbiFunctionKind::Call, AbiFunctionKind::View) => change(
+            name,
+            Severity::NonBreaking,
+            "changed from a call to a view function".to_string(),
+        ),
+        _ => {}
+    }
+
+    for (modifier, label) in [
+        (AbiFunctionModifier::Init, "init"),
+        (AbiFunctionModifier::Private, "private"),
+        (AbiFunctionModifier::Payable, "payable"),
+    ] {
+        let severity = match (
+            old_function.modifiers.contains(&modifier),
+            new_function.modifiers.contains(&modifier),
+        ) {
+            (true, false) => match modifier {
+                AbiFunctionModifier::Private => Severity::NonBreaking,
+                AbiFunctionModifier::Init | AbiFunctionModifier::Payable => Severity::Breaking,
+            },
+            (false, true) => match modifier {
+                AbiFunctionModifier::Payable => Severity::NonBreaking,
+                AbiFunctionModifier::Init | AbiFunctionModifier::Private => Severity::Breaking,
+            },
+            _ => continue,
+        };
+        let action = if new_function.modifiers.contains(&modifier) {
+            "became"
+        } else {
+            "is no longer"
+        };
+        change(name, severity, format!("{action} {label}"));
+    }
+
+    let both_have_params = !old_function.params.is_empty() && !new_function.params.is_empty();
+    if both_have_params && old_function.params_serialization != new_function.params_serialization {
+        change(
+            name,
+            Severity::Breaking,
+            format!(
+                "arguments serialization changed from {} to {}",
+                old_function.params_serialization, new_function.params_serialization
+            ),
+        );
+    } else if old_function.params_serialization == "Borsh"
+        || new_function.params_serialization == "Borsh"
+    {
+        // Borsh arguments are positional, so only their types matter.
+        let old_types = old_function.params.iter().map(|(_, schema)| schema);
+        if !old_types.eq(new_function.params.iter().map(|(_, schema)| schema)) {
+            change(
+                name,
+                Severity::Breaking,
+                "Borsh arguments changed".to_string(),
+            );
+        }
+    } else {
+        for (param_name, old_schema) in &old_function.params {
+            match new_function
+                .params
+                .iter()
+                .find(|(new_param_name, _)| new_param_name == param_name)
+            {
+                None => change(
+                    name,
+                    Severity::Breaking,
+                    format!("parameter `{param_name}` removed"),
+                ),
+                Some((_, new_schema)) if new_schema != old_schema => change(
+                    name,
+                    Severity::Breaking,
+                    format!("parameter `{param_name}` type changed"),
+                ),
+                Some(_) => {}
+            }
+        }
+        for (param_name, new_schema) in &new_function.params {
+            if old_function
+                .params
+                .iter()
+                .any(|(old_param_name, _)| old_param_name == param_name)
+            {
+                continue;
+            }
+            if is_nullable(new_schema) {
+                change(
+                    name,
+                    Severity::NonBreaking,
+                    format!("optional parameter `{param_name}` added"),
+                );
+            } else {
+                change(
+                    name,
+                    Severity::Breaking,
+                    format!("required parameter `{param_name}` added"),
+                );
+            }
+        }
+    }
+
+    match (&old_function.result, &new_function.result) {
+        (None, Some(_)) => change(name, Severity::NonBreaking, "result added".to_string()),
+        (Some(_), None) => change(name, Severity::Breaking, "result removed".to_string()),
+        (Some((old_serialization, _)), Some((new_serialization, _)))
+            if old_serialization != new_serialization =>
+        {
+            change(
+                name,
+                Severity::Breaking,
+                format!(
+                    "result serialization changed from {old_serialization} to {new_serialization}"
+                ),
+            )
+        }
+        (Some((_, old_schema)), Some((_, new_schema))) if old_schema != new_schema => {
+            change(name, Severity::Breaking, "result type changed".to_string())
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi(functions: serde_json::Value, definitions: serde_json::Value) -> unc_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": {},
+            "body": {
+                "functions": functions,
+                "root_schema": {
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "String",
+                    "type": "string",
+                    "definitions": definitions,
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn describe(changes: &[AbiChange]) -> Vec<(String, Severity, String)> {
+        changes
+            .iter()
+            .map(|change| {
+                (
+                    change.function.clone(),
+                    change.severity,
+                    change.description.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn classify_abi_changes() {
+        let old_abi = abi(
+            serde_json::json!([
+                {"name": "get_greeting", "kind": "view", "result": {"serialization_type": "json", "type_schema": {"type": "string"}}},
+                {"name": "set_greeting", "kind": "call", "params": {"serialization_type": "json", "args": [
+                    {"name": "greeting", "type_schema": {"$ref": "#/definitions/Greeting"}}
+                ]}},
+                {"name": "get_owner", "kind": "view", "result": {"serialization_type": "json", "type_schema": {"type": "string"}}},
+            ]),
+            serde_json::json!({"Greeting": {"description": "A greeting", "type": "string"}}),
+        );
+        let new_abi = abi(
+            serde_json::json!([
+                {"name": "get_greeting", "kind": "view", "result": {"serialization_type": "json", "type_schema": {"type": "integer"}}},
+                {"name": "set_greeting", "kind": "call", "modifiers": ["payable"], "params": {"serialization_type": "json", "args": [
+                    {"name": "greeting", "type_schema": {"$ref": "#/definitions/Text"}},
+                    {"name": "language", "type_schema": {"type": ["string", "null"]}}
+                ]}},
+                {"name": "owner", "kind": "view", "result": {"serialization_type": "json", "type_schema": {"type": "string"}}},
+                {"name": "get_version", "kind": "view"},
+            ]),
+            serde_json::json!({"Text": {"type": "string"}}),
+        );
+
+        assert_eq!(
+            describe(&diff_abi(&old_abi, &new_abi)),
+            vec![
+                (
+                    "get_greeting".to_string(),
+                    Severity::Breaking,
+                    "result type changed".to_string()
+                ),
+                (
+                    "set_greeting".to_string(),
+                    Severity::NonBreaking,
+                    "became payable".to_string()
+                ),
+                (
+                    "set_greeting".to_string(),
+                    Severity::NonBreaking,
+                    "optional parameter `language` added".to_string()
+                ),
+                (
+                    "get_owner".to_string(),
+                    Severity::Breaking,
+                    "renamed to `owner`".to_string()
+                ),
+                (
+                    "get_version".to_string(),
+                    Severity::NonBreaking,
+                    "added".to_string()
+                ),
+            ]
+        );
+        assert!(diff_abi(&old_abi, &old_abi).is_empty());
+    }
+}
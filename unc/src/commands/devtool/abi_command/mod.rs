@@ -1,4 +1,6 @@
 pub mod abi;
+pub mod codegen;
+pub mod diff;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -5,6 +5,10 @@ use unc_abi::BuildInfo;
 
 use super::super::abi_command::abi;
 use super::super::abi_command::abi::{AbiCompression, AbiFormat, AbiResult};
+use super::post_build;
+use super::reproducible::{
+    get_contract_source_metadata_env, ReproducibleBuildEnvironment, REPRODUCIBLE_BUILD_IMAGE_ENV,
+};
 use crate::common::ColorPreference;
 use crate::types::{manifest::CargoManifestPath, metadata::CrateMetadata};
 use crate::util;
@@ -13,18 +17,29 @@ use crate::util::wasm32_target_libdir_exists;
 const COMPILATION_TARGET: &str = "wasm32-unknown-unknown";
 
 pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::CompilationArtifact> {
-    let color = args.color.unwrap_or(ColorPreference::Auto);
+    let color = args.color.clone().unwrap_or(ColorPreference::Auto);
     color.apply();
 
     util::handle_step("Checking the host environment...", || {
-        if !wasm32_target_libdir_exists() {
+        if args.reproducible && args.wasm_opt {
+            color_eyre::eyre::bail!(
+                "`--wasm-opt` cannot be used with `--reproducible`, the output of `wasm-opt` depends on the binaryen version installed on the host"
+            );
+        }
+        if args.reproducible && args.toolchain.is_some() {
+            color_eyre::eyre::bail!(
+                "`--toolchain` cannot be used with `--reproducible`, the toolchain of a reproducible build is pinned by the container image"
+            );
+        }
+        // The wasm target is only required on the host when the contract is compiled there
+        if !args.reproducible && !wasm32_target_libdir_exists(args.toolchain.as_deref()) {
             color_eyre::eyre::bail!("rust target `{}` is not installed", COMPILATION_TARGET);
         }
         Ok(())
     })?;
 
     let crate_metadata = util::handle_step("Collecting cargo project metadata...", || {
-        let manifest_path: Utf8PathBuf = if let Some(manifest_path) = args.manifest_path {
+        let manifest_path: Utf8PathBuf = if let Some(manifest_path) = args.manifest_path.clone() {
             manifest_path.into()
         } else {
             "Cargo.toml".into()
@@ -32,14 +47,30 @@ pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::Compilat
         CrateMetadata::collect(CargoManifestPath::try_from(manifest_path)?)
     })?;
 
-    let out_dir = args
-        .out_dir
-        .map_or(Ok(crate_metadata.target_directory.clone()), |out_dir| {
-            let out_dir = Utf8PathBuf::from(out_dir);
-            util::force_canonicalize_dir(&out_dir)
-        })?;
+    let out_dir =
+        args.out_dir
+            .clone()
+            .map_or(Ok(crate_metadata.target_directory.clone()), |out_dir| {
+                let out_dir = Utf8PathBuf::from(out_dir);
+                util::force_canonicalize_dir(&out_dir)
+            })?;
+
+    if args.reproducible {
+        return run_reproducible(&args, &crate_metadata, &out_dir, color);
+    }
+    // Set when this build runs inside the reproducible build container
+    let reproducible_build_image = std::env::var(REPRODUCIBLE_BUILD_IMAGE_ENV).ok();
 
+    // The toolchain is selected per command instead of the process environment,
+    // so that the other commands of the same process are not affected
+    let toolchain_env = args
+        .toolchain
+        .as_deref()
+        .map(|toolchain| ("RUSTUP_TOOLCHAIN", toolchain))
+        .into_iter()
+        .collect::<Vec<_>>();
     let mut build_env = vec![("RUSTFLAGS", "-C link-arg=-s")];
+    build_env.extend(toolchain_env.iter().copied());
     let mut cargo_args = vec!["--target", COMPILATION_TARGET];
     let mut cargo_feature_args = vec![];
 
@@ -55,6 +86,10 @@ pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::Compilat
         cargo_feature_args.push("--no-default-features");
     }
 
+    if reproducible_build_image.is_some() {
+        cargo_feature_args.push("--locked");
+    }
+
     let mut abi = None;
     let mut min_abi_path = None;
     if !args.no_abi {
@@ -63,12 +98,13 @@ pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::Compilat
             !args.no_doc,
             true,
             &cargo_feature_args,
+            &toolchain_env,
             color.clone(),
         )?;
         contract_abi.metadata.build = Some(BuildInfo {
-            compiler: format!("rustc {}", rustc_version::version()?),
+            compiler: format!("rustc {}", get_rustc_version(args.toolchain.as_deref())?),
             builder: format!("unc {}", env!("CARGO_PKG_VERSION")),
-            image: None,
+            image: reproducible_build_image.clone(),
         });
         if !args.no_embed_abi {
             let path = util::handle_step("Compressing ABI to be embedded..", || {
@@ -87,12 +123,18 @@ pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::Compilat
 
     cargo_args.extend(cargo_feature_args);
 
-    if let (false, Some(abi_path)) = (args.no_embed_abi, &min_abi_path) {
-        cargo_args.extend(&["--features", "unc-sdk/__abi-embed"]);
-        build_env.push(("CARGO_UNC_ABI_PATH", abi_path.as_str()));
-    }
+    let embedded_abi_path = match (args.no_embed_abi, &min_abi_path) {
+        (false, Some(abi_path)) => {
+            cargo_args.extend(&["--features", "unc-sdk/__abi-embed"]);
+            Some(abi_path)
+        }
+        _ => None,
+    };
 
     util::print_step("Building contract");
+    if let Some(abi_path) = embedded_abi_path {
+        build_env.push(("CARGO_UNC_ABI_PATH", abi_path.as_str()));
+    }
     let mut wasm_artifact = util::compile_project(
         &crate_metadata.manifest_path,
         &cargo_args,
@@ -104,12 +146,27 @@ pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::Compilat
 
     wasm_artifact.path = util::copy(&wasm_artifact.path, &out_dir)?;
 
-    // todo! if we embedded, check that the binary exports the __contract_abi symbol
+    let post_build_report = util::handle_step("Optimizing and validating the binary...", || {
+        post_build::run(
+            &wasm_artifact.path,
+            post_build::PostBuildOptions {
+                strip: !args.no_optimize,
+                wasm_opt: args.wasm_opt,
+                allow_floats: !args.no_floats,
+                embedded_abi: embedded_abi_path.is_some(),
+            },
+        )
+    })?;
+
     util::print_success("Contract successfully built!");
     let mut messages = vec![(
         "Binary",
         wasm_artifact.path.to_string().bright_yellow().bold(),
     )];
+    messages.push(("Size", post_build_report.summary().yellow().bold()));
+    if let Some(image) = &reproducible_build_image {
+        messages.push(("Image", image.yellow().bold()));
+    }
     if let Some(mut abi) = abi {
         let mut hasher = Sha256::new();
         hasher.update(std::fs::read(&wasm_artifact.path)?);
@@ -133,3 +190,123 @@ pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<util::Compilat
 
     Ok(wasm_artifact)
 }
+
+/// Runs the whole build (including the ABI generation) inside the pinned container image.
+fn run_reproducible(
+    args: &super::BuildCommand,
+    crate_metadata: &CrateMetadata,
+    out_dir: &Utf8PathBuf,
+    color: ColorPreference,
+) -> color_eyre::eyre::Result<util::CompilationArtifact> {
+    let environment = util::handle_step("Preparing the reproducible build container...", || {
+        ReproducibleBuildEnvironment::prepare(
+            crate_metadata,
+            args.container_runtime.clone(),
+            args.image.clone(),
+        )
+    })?;
+    let env = get_contract_source_metadata_env(
+        crate_metadata,
+        &environment.image_digest,
+        &get_reproducible_build_command(args),
+    )?;
+
+    util::print_step("Building contract in the container");
+    let wasm_artifact =
+        environment.build(crate_metadata, &get_build_flags(args), env, out_dir, color)?;
+
+    util::print_success("Contract successfully built!");
+    let mut messages = vec![
+        (
+            "Binary",
+            wasm_artifact.path.to_string().bright_yellow().bold(),
+        ),
+        ("Image", environment.image_digest.yellow().bold()),
+        ("Compiler", environment.compiler.yellow().bold()),
+    ];
+    let abi_path = out_dir.join(format!(
+        "{}_abi.json",
+        crate_metadata.root_package.name.replace('-', "_")
+    ));
+    if abi_path.exists() {
+        messages.push(("ABI", abi_path.to_string().yellow().bold()));
+    }
+    let max_width = messages.iter().map(|(h, _)| h.len()).max().unwrap();
+    for (header, message) in messages {
+        eprintln!("     - {:>width$}: {}", header, message, width = max_width);
+    }
+
+    Ok(wasm_artifact)
+}
+
+/// The command recorded in the contract source metadata to let anyone repeat the reproducible build.
+fn get_reproducible_build_command(args: &super::BuildCommand) -> Vec<String> {
+    ["unc", "dev-tool", "build", "--reproducible"]
+        .map(String::from)
+        .into_iter()
+        .chain(get_build_flags(args))
+        .collect()
+}
+
+/// The flags of the build which affect the produced binary.
+fn get_build_flags(args: &super::BuildCommand) -> Vec<String> {
+    let mut flags = vec![];
+    for (flag, enabled) in [
+        ("--no-release", args.no_release),
+        ("--no-abi", args.no_abi),
+        ("--no-embed-abi", args.no_embed_abi),
+        ("--no-doc", args.no_doc),
+        ("--no-default-features", args.no_default_features),
+        ("--no-optimize", args.no_optimize),
+        ("--no-floats", args.no_floats),
+    ] {
+        if enabled {
+            flags.push(flag.to_string());
+        }
+    }
+    if let Some(features) = &args.features {
+        flags.extend(["--features".to_string(), features.clone()]);
+    }
+    flags
+}
+
+/// Returns the version of `rustc` of the given rustup toolchain (the default one if `None`).
+fn get_rustc_version(toolchain: Option<&str>) -> color_eyre::eyre::Result<rustc_version::Version> {
+    let mut rustc = std::process::Command::new("rustc");
+    rustc.envs(toolchain.map(|toolchain| ("RUSTUP_TOOLCHAIN", toolchain)));
+    Ok(rustc_version::VersionMeta::for_command(rustc)?.semver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible_build_command_records_the_build_flags() {
+        let args = super::super::BuildCommand {
+            no_doc: true,
+            no_optimize: true,
+            features: Some("a,b".to_string()),
+            reproducible: true,
+            image: Some("example.org/unc-builder@sha256:0123".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_build_flags(&args),
+            ["--no-doc", "--no-optimize", "--features", "a,b"]
+        );
+        assert_eq!(
+            get_reproducible_build_command(&args),
+            [
+                "unc",
+                "dev-tool",
+                "build",
+                "--reproducible",
+                "--no-doc",
+                "--no-optimize",
+                "--features",
+                "a,b"
+            ]
+        );
+    }
+}
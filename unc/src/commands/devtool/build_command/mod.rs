@@ -1,4 +1,7 @@
 pub mod build;
+pub mod post_build;
+pub mod reproducible;
+pub mod workspace;
 
 #[derive(Debug, Default, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -20,6 +23,13 @@ pub struct BuildCommand {
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     pub out_dir: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Build all contracts (members depending on `unc-sdk`) of the cargo workspace
+    #[interactive_clap(long)]
+    pub workspace: bool,
+    /// Build only the listed contracts of the cargo workspace (comma-separated package names)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub package: Option<crate::types::vec_string::VecString>,
     /// Path to the `Cargo.toml` of the contract to build
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
@@ -37,6 +47,31 @@ pub struct BuildCommand {
     #[interactive_clap(value_enum)]
     #[interactive_clap(skip_interactive_input)]
     pub color: Option<crate::common::ColorPreference>,
+    /// Do not strip custom sections (debug info, names) from the contract binary
+    #[interactive_clap(long)]
+    pub no_optimize: bool,
+    /// Optimize the size of the contract binary with `wasm-opt -Oz` (requires binaryen, the result depends on its version)
+    #[interactive_clap(long)]
+    pub wasm_opt: bool,
+    /// Reject floating-point instructions (for runtimes that do not support them)
+    #[interactive_clap(long)]
+    pub no_floats: bool,
+    /// Rust toolchain to build the contract with (e.g. 1.78.0), passed to rustup as `RUSTUP_TOOLCHAIN`
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub toolchain: Option<String>,
+    /// Build inside a pinned container image to get a reproducible binary (requires Docker or Podman)
+    #[interactive_clap(long)]
+    pub reproducible: bool,
+    /// Container image with the Rust toolchain, the wasm target and `unc` for the reproducible build (defaults to `package.metadata.unc.reproducible_build.image` from `Cargo.toml`)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub image: Option<String>,
+    /// Container runtime for the reproducible build: docker, podman (detected automatically by default)
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_interactive_input)]
+    pub container_runtime: Option<self::reproducible::ContainerRuntime>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,12 +88,25 @@ impl BuildCommandlContext {
             no_embed_abi: scope.no_embed_abi,
             no_doc: scope.no_doc,
             out_dir: scope.out_dir.clone(),
+            workspace: scope.workspace,
+            package: scope.package.clone(),
             manifest_path: scope.manifest_path.clone(),
             features: scope.features.clone(),
             no_default_features: scope.no_default_features,
             color: scope.color.clone(),
+            no_optimize: scope.no_optimize,
+            wasm_opt: scope.wasm_opt,
+            no_floats: scope.no_floats,
+            toolchain: scope.toolchain.clone(),
+            reproducible: scope.reproducible,
+            image: scope.image.clone(),
+            container_runtime: scope.container_runtime.clone(),
         };
-        self::build::run(args)?;
+        if args.workspace || args.package.is_some() {
+            self::workspace::run(args)?;
+        } else {
+            self::build::run(args)?;
+        }
         Ok(Self)
     }
 }
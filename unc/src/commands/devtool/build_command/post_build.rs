@@ -0,0 +1,356 @@
+use camino::Utf8Path;
+use color_eyre::eyre::WrapErr;
+
+/// Limits enforced by the contract runtime when the code is deployed
+/// (see `wasm_config.limit_config` in the protocol config).
+const MAX_CONTRACT_SIZE: usize = 4 * 1024 * 1024;
+const MAX_MEMORY_PAGES: u64 = 2048;
+const MAX_TABLES_PER_CONTRACT: usize = 1;
+const MAX_FUNCTIONS_NUMBER_PER_CONTRACT: usize = 10_000;
+/// All host functions are imported from this module.
+pub(crate) const ALLOWED_IMPORT_MODULE: &str = "env";
+pub(crate) const CONTRACT_ABI_EXPORT: &str = "__contract_abi";
+
+#[derive(Debug, Clone, Copy)]
+pub struct PostBuildOptions {
+    /// Strip custom sections (deterministic, done in-process).
+    pub strip: bool,
+    /// Run `wasm-opt -Oz` on the stripped module (opt-in, requires binaryen on the host).
+    pub wasm_opt: bool,
+    /// Allow floating-point instructions and types.
+    pub allow_floats: bool,
+    /// The ABI is embedded, so the `__contract_abi` export has to be present.
+    pub embedded_abi: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PostBuildReport {
+    pub original_size: usize,
+    pub stripped_size: usize,
+    pub final_size: usize,
+}
+
+/// Optimizes and validates the compiled contract in place.
+pub fn run(
+    wasm_path: &Utf8Path,
+    options: PostBuildOptions,
+) -> color_eyre::eyre::Result<PostBuildReport> {
+    let original =
+        std::fs::read(wasm_path).wrap_err_with(|| format!("Failed to read file: {}", wasm_path))?;
+    let original_size = original.len();
+
+    let (mut code, stripped_size) = if options.strip {
+        let stripped = strip_custom_sections(&original)?;
+        let stripped_size = stripped.len();
+        (stripped, stripped_size)
+    } else {
+        (original, original_size)
+    };
+    if options.wasm_opt {
+        code = optimize_with_wasm_opt(wasm_path, code)?;
+    }
+
+    validate(&code, options)?;
+
+    std::fs::write(wasm_path, &code)
+        .wrap_err_with(|| format!("Failed to write to file: {}", wasm_path))?;
+
+    Ok(PostBuildReport {
+        original_size,
+        stripped_size,
+        final_size: code.len(),
+    })
+}
+
+impl PostBuildReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} -> {} (stripped: {})",
+            bytesize::ByteSize(self.original_size as u64),
+            bytesize::ByteSize(self.final_size as u64),
+            bytesize::ByteSize(self.stripped_size as u64)
+        )
+    }
+}
+
+/// Removes all custom sections (debug info, names, producers) from the module.
+fn strip_custom_sections(wasm: &[u8]) -> color_eyre::eyre::Result<Vec<u8>> {
+    let mut stripped = Vec::with_capacity(wasm.len());
+    for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+        let payload = payload.wrap_err("Failed to parse the wasm module")?;
+        match payload {
+            wasmparser::Payload::Version { range, .. } => {
+                stripped.extend_from_slice(&wasm[range]);
+            }
+            wasmparser::Payload::CustomSection(_) => {}
+            payload => {
+                if let Some((id, range)) = payload.as_section() {
+                    stripped.push(id);
+                    write_leb128_u32(&mut stripped, range.len() as u32);
+                    stripped.extend_from_slice(&wasm[range]);
+                }
+            }
+        }
+    }
+    Ok(stripped)
+}
+
+fn write_leb128_u32(buf: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+/// Runs `wasm-opt -Oz` from binaryen installed on the host.
+fn optimize_with_wasm_opt(
+    wasm_path: &Utf8Path,
+    wasm: Vec<u8>,
+) -> color_eyre::eyre::Result<Vec<u8>> {
+    let input_path = wasm_path.with_extension("stripped.wasm");
+    let output_path = wasm_path.with_extension("optimized.wasm");
+    std::fs::write(&input_path, wasm)?;
+    let result = std::process::Command::new("wasm-opt")
+        .args(["-Oz", "--strip-debug", "--strip-producers"])
+        .arg(&input_path)
+        .arg("-o")
+        .arg(&output_path)
+        .output();
+    let optimized = match result {
+        Ok(output) if output.status.success() => std::fs::read(&output_path)?,
+        Ok(output) => {
+            let _ = std::fs::remove_file(&input_path);
+            color_eyre::eyre::bail!(
+                "`wasm-opt` failed:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Err(err) => {
+            let _ = std::fs::remove_file(&input_path);
+            return Err(color_eyre::Report::new(err)).wrap_err(
+                "Failed to execute `wasm-opt`, install binaryen or build without `--wasm-opt`",
+            );
+        }
+    };
+    let _ = std::fs::remove_file(&input_path);
+    let _ = std::fs::remove_file(&output_path);
+    Ok(optimized)
+}
+
+fn validate(wasm: &[u8], options: PostBuildOptions) -> color_eyre::eyre::Result<()> {
+    let features = wasmparser::WasmFeatures {
+        mutable_global: true,
+        sign_extension: true,
+        floats: options.allow_floats,
+        saturating_float_to_int: false,
+        reference_types: false,
+        multi_value: false,
+        bulk_memory: false,
+        simd: false,
+        relaxed_simd: false,
+        threads: false,
+        tail_call: false,
+        multi_memory: false,
+        exceptions: false,
+        memory64: false,
+        extended_const: false,
+        component_model: false,
+        function_references: false,
+        memory_control: false,
+        gc: false,
+        component_model_values: false,
+        component_model_nested_names: false,
+    };
+    wasmparser::Validator::new_with_features(features)
+        .validate_all(wasm)
+        .wrap_err("The contract uses WebAssembly features that are not supported by the runtime")?;
+
+    let mut problems = vec![];
+    if wasm.len() > MAX_CONTRACT_SIZE {
+        problems.push(format!(
+            "the contract size {} exceeds the limit of {}",
+            bytesize::ByteSize(wasm.len() as u64),
+            bytesize::ByteSize(MAX_CONTRACT_SIZE as u64)
+        ));
+    }
+
+    let mut tables_count = 0;
+    let mut functions_count = 0;
+    let mut exports = vec![];
+    for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+        match payload? {
+            wasmparser::Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import?;
+                    if import.module != ALLOWED_IMPORT_MODULE {
+                        problems.push(format!(
+                            "import <{}.{}> is not provided by the runtime (only <{ALLOWED_IMPORT_MODULE}> imports are allowed)",
+                            import.module, import.name
+                        ));
+                    }
+                    match import.ty {
+                        wasmparser::TypeRef::Func(_) => {}
+                        wasmparser::TypeRef::Memory(memory) => {
+                            check_memory(&memory, &mut problems);
+                        }
+                        wasmparser::TypeRef::Table(_) => tables_count += 1,
+                        _ => problems.push(format!(
+                            "import <{}.{}> has a kind that is not supported by the runtime",
+                            import.module, import.name
+                        )),
+                    }
+                }
+            }
+            wasmparser::Payload::MemorySection(reader) => {
+                for memory in reader {
+                    check_memory(&memory?, &mut problems);
+                }
+            }
+            wasmparser::Payload::TableSection(reader) => tables_count += reader.count() as usize,
+            wasmparser::Payload::FunctionSection(reader) => {
+                functions_count += reader.count() as usize
+            }
+            wasmparser::Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    if let wasmparser::ExternalKind::Func = export.kind {
+                        exports.push(export.name.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if tables_count > MAX_TABLES_PER_CONTRACT {
+        problems.push(format!(
+            "the contract defines {tables_count} tables, but at most {MAX_TABLES_PER_CONTRACT} is allowed"
+        ));
+    }
+    if functions_count > MAX_FUNCTIONS_NUMBER_PER_CONTRACT {
+        problems.push(format!(
+            "the contract defines {functions_count} functions, but at most {MAX_FUNCTIONS_NUMBER_PER_CONTRACT} are allowed"
+        ));
+    }
+    if options.embedded_abi && !exports.iter().any(|name| name == CONTRACT_ABI_EXPORT) {
+        problems.push(format!(
+            "the ABI was requested to be embedded, but the contract does not export <{CONTRACT_ABI_EXPORT}>"
+        ));
+    }
+
+    if !problems.is_empty() {
+        color_eyre::eyre::bail!(
+            "The contract is not deployable:\n{}",
+            problems
+                .iter()
+                .map(|problem| format!(" - {problem}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+fn check_memory(memory: &wasmparser::MemoryType, problems: &mut Vec<String>) {
+    if memory.initial > MAX_MEMORY_PAGES {
+        problems.push(format!(
+            "the initial memory size of {} pages exceeds the limit of {MAX_MEMORY_PAGES} pages",
+            memory.initial
+        ));
+    }
+    if memory
+        .maximum
+        .is_some_and(|maximum| maximum > MAX_MEMORY_PAGES)
+    {
+        problems.push(format!(
+            "the maximum memory size of {} pages exceeds the limit of {MAX_MEMORY_PAGES} pages",
+            memory.maximum.unwrap_or_default()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (module (import "<import_module>" "log" (func)) (func (export "<export_name>")) (@custom "name" ...))
+    fn sample_module(import_module: &str, export_name: &str) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // type section: 1 type, () -> ()
+        wasm.extend_from_slice(&[0x01, 0x04, 0x01, 0x60, 0x00, 0x00]);
+        // import section: <import_module>.log func type 0
+        let mut import = vec![0x01, import_module.len() as u8];
+        import.extend_from_slice(import_module.as_bytes());
+        import.extend_from_slice(&[0x03, b'l', b'o', b'g', 0x00, 0x00]);
+        wasm.push(0x02);
+        write_leb128_u32(&mut wasm, import.len() as u32);
+        wasm.extend_from_slice(&import);
+        // function section: 1 function of type 0
+        wasm.extend_from_slice(&[0x03, 0x02, 0x01, 0x00]);
+        // export section: "__contract_abi" func 1
+        let mut export = vec![0x01, export_name.len() as u8];
+        export.extend_from_slice(export_name.as_bytes());
+        export.extend_from_slice(&[0x00, 0x01]);
+        wasm.push(0x07);
+        write_leb128_u32(&mut wasm, export.len() as u32);
+        wasm.extend_from_slice(&export);
+        // code section: 1 empty body
+        wasm.extend_from_slice(&[0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b]);
+        // custom section "name" with some payload
+        wasm.extend_from_slice(&[0x00, 0x08, 0x04, b'n', b'a', b'm', b'e', 1, 2, 3]);
+        wasm
+    }
+
+    const OPTIONS: PostBuildOptions = PostBuildOptions {
+        strip: true,
+        wasm_opt: false,
+        allow_floats: true,
+        embedded_abi: true,
+    };
+
+    #[test]
+    fn strip_removes_custom_sections_only() {
+        let wasm = sample_module("env", CONTRACT_ABI_EXPORT);
+        let stripped = strip_custom_sections(&wasm).unwrap();
+        assert_eq!(stripped.len(), wasm.len() - 10);
+        assert!(wasmparser::Parser::new(0)
+            .parse_all(&stripped)
+            .all(|payload| !matches!(payload, Ok(wasmparser::Payload::CustomSection(_)))));
+        validate(&stripped, OPTIONS).unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_foreign_imports_and_missing_abi() {
+        validate(&sample_module("env", CONTRACT_ABI_EXPORT), OPTIONS).unwrap();
+        assert!(validate(
+            &sample_module("wasi_snapshot_preview1", CONTRACT_ABI_EXPORT),
+            OPTIONS
+        )
+        .is_err());
+        assert!(validate(&sample_module("env", "main"), OPTIONS).is_err());
+    }
+
+    #[test]
+    fn default_post_build_is_deterministic() {
+        let wasm_path =
+            std::env::temp_dir().join(format!("unc-post-build-{}.wasm", std::process::id()));
+        let wasm_path = Utf8Path::from_path(&wasm_path).unwrap();
+        let mut outputs = vec![];
+        for _ in 0..2 {
+            std::fs::write(wasm_path, sample_module("env", CONTRACT_ABI_EXPORT)).unwrap();
+            let report = run(wasm_path, OPTIONS).unwrap();
+            assert_eq!(report.final_size, report.stripped_size);
+            outputs.push(std::fs::read(wasm_path).unwrap());
+        }
+        std::fs::remove_file(wasm_path).unwrap();
+        assert_eq!(outputs[0], outputs[1]);
+        assert_eq!(
+            outputs[0],
+            strip_custom_sections(&sample_module("env", CONTRACT_ABI_EXPORT)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,447 @@
+use std::process::Command;
+use std::str::FromStr;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use crate::common::ColorPreference;
+use crate::types::metadata::CrateMetadata;
+use crate::util;
+
+/// Set by the host `unc` for the `unc dev-tool build` running inside the container, so that
+/// the build records the image and uses the locked dependency set.
+pub const REPRODUCIBLE_BUILD_IMAGE_ENV: &str = "UNC_REPRODUCIBLE_BUILD_IMAGE";
+
+/// The paths where the cargo workspace and the output directory are mounted inside the
+/// container, so the build does not depend on where the sources live on the host.
+const CONTAINER_SOURCE_DIR: &str = "/home/unc/code";
+const CONTAINER_OUT_DIR: &str = "/home/unc/out";
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+impl interactive_clap::ToCli for ContainerRuntime {
+    type CliVariant = ContainerRuntime;
+}
+
+impl std::fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Docker => write!(f, "docker"),
+            Self::Podman => write!(f, "podman"),
+        }
+    }
+}
+
+impl FromStr for ContainerRuntime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "docker" => Ok(Self::Docker),
+            "podman" => Ok(Self::Podman),
+            _ => Err(format!("invalid container runtime: {}", s)),
+        }
+    }
+}
+
+impl ContainerRuntime {
+    fn command(&self) -> Command {
+        Command::new(self.to_string())
+    }
+
+    /// Picks the first container runtime available on the host (Docker, then Podman).
+    fn detect() -> color_eyre::eyre::Result<Self> {
+        [Self::Docker, Self::Podman]
+            .into_iter()
+            .find(|runtime| {
+                runtime
+                    .command()
+                    .arg("--version")
+                    .output()
+                    .is_ok_and(|output| output.status.success())
+            })
+            .wrap_err("Reproducible builds require Docker or Podman, but neither was found")
+    }
+}
+
+/// A pinned container image used to build the contract.
+///
+/// The image has to provide the Rust toolchain with the `wasm32-unknown-unknown` target and the
+/// `unc` CLI: the whole build (including the ABI generation) runs inside the container, and
+/// nothing is installed into it during the build.
+pub struct ReproducibleBuildEnvironment {
+    runtime: ContainerRuntime,
+    /// The image reference including its digest (e.g. `docker.io/library/rust@sha256:...`).
+    pub image_digest: String,
+    /// The compiler version in the image, in the same format as `BuildInfo.compiler`.
+    pub compiler: String,
+    /// `uid:gid` of the current user, so the files created in the mounted directories are not owned by root.
+    user: Option<String>,
+}
+
+impl ReproducibleBuildEnvironment {
+    pub(crate) fn prepare(
+        crate_metadata: &CrateMetadata,
+        runtime: Option<ContainerRuntime>,
+        image: Option<String>,
+    ) -> color_eyre::eyre::Result<Self> {
+        let runtime = match runtime {
+            Some(runtime) => runtime,
+            None => ContainerRuntime::detect()?,
+        };
+        let image = image
+            .or_else(|| get_image_from_manifest(crate_metadata))
+            .wrap_err(
+                "Reproducible builds need a container image with the Rust toolchain, the `wasm32-unknown-unknown` target and the `unc` CLI installed. \
+                Pass `--image` or set `image` in `[package.metadata.unc.reproducible_build]` of `Cargo.toml` (preferably pinned by digest: `<image>@sha256:<digest>`)",
+            )?;
+
+        let image_digest = if image.contains("@sha256:") {
+            image
+        } else {
+            run(runtime.command().args(["pull", "--quiet", &image]))?;
+            let repo_digests = run(runtime.command().args([
+                "image",
+                "inspect",
+                "--format",
+                "{{join .RepoDigests \"\\n\"}}",
+                &image,
+            ]))?;
+            repo_digests
+                .lines()
+                .next()
+                .map(str::to_string)
+                .wrap_err_with(|| format!("The image <{image}> has no digest"))?
+        };
+        let user = get_current_user();
+
+        let mut cmd = runtime.command();
+        cmd.args(["run", "--rm", "--network", "none"]);
+        if let Some(user) = &user {
+            cmd.args(["--user", user]);
+        }
+        cmd.args([
+            "--entrypoint",
+            "sh",
+            &image_digest,
+            "-c",
+            "rustc --version; rustup target list --installed; command -v unc || true",
+        ]);
+        let compiler = get_image_compiler(&run(&mut cmd)?).wrap_err_with(|| {
+            format!("The image <{image_digest}> cannot be used for reproducible builds")
+        })?;
+
+        Ok(Self {
+            runtime,
+            image_digest,
+            compiler,
+            user,
+        })
+    }
+
+    /// Runs `unc dev-tool build` with `build_flags` inside the container and returns the built
+    /// contract, which is written to `out_dir` on the host.
+    pub(crate) fn build(
+        &self,
+        crate_metadata: &CrateMetadata,
+        build_flags: &[String],
+        env: Vec<(&str, String)>,
+        out_dir: &Utf8Path,
+        color: ColorPreference,
+    ) -> color_eyre::eyre::Result<util::CompilationArtifact> {
+        let workspace_root = &crate_metadata.raw_metadata.workspace_root;
+        if !workspace_root.join("Cargo.lock").exists() {
+            color_eyre::eyre::bail!(
+                "Reproducible builds require a locked dependency set, but there is no `Cargo.lock` in <{workspace_root}>"
+            );
+        }
+        let relative_manifest_path = crate_metadata
+            .manifest_path
+            .path
+            .strip_prefix(workspace_root)
+            .wrap_err("The contract has to be located inside its cargo workspace")?;
+
+        let mut cmd = self.build_command(
+            workspace_root,
+            relative_manifest_path,
+            out_dir,
+            build_flags,
+            env,
+            color,
+        );
+        log::info!("Invoking unc in a container: {:?}", cmd);
+        let status = cmd
+            .status()
+            .wrap_err_with(|| format!("Error executing `{:?}`", cmd))?;
+        if !status.success() {
+            color_eyre::eyre::bail!(
+                "The build in the container <{}> failed with exit code: {:?}",
+                self.image_digest,
+                status.code()
+            );
+        }
+
+        let wasm_path = out_dir.join(format!("{}.wasm", get_lib_name(crate_metadata)));
+        if !wasm_path.exists() {
+            color_eyre::eyre::bail!("The build in the container did not produce <{wasm_path}>");
+        }
+        Ok(util::CompilationArtifact {
+            path: wasm_path,
+            fresh: true,
+        })
+    }
+
+    fn build_command(
+        &self,
+        workspace_root: &Utf8Path,
+        relative_manifest_path: &Utf8Path,
+        out_dir: &Utf8Path,
+        build_flags: &[String],
+        env: Vec<(&str, String)>,
+        color: ColorPreference,
+    ) -> Command {
+        let mut cmd = self.runtime.command();
+        cmd.args(["run", "--rm"]);
+        if let Some(user) = &self.user {
+            cmd.args(["--user", user]);
+        }
+        cmd.arg("--volume")
+            .arg(format!("{workspace_root}:{CONTAINER_SOURCE_DIR}"));
+        cmd.arg("--volume")
+            .arg(format!("{out_dir}:{CONTAINER_OUT_DIR}"));
+        cmd.arg("--workdir").arg(CONTAINER_SOURCE_DIR);
+        let container_target_dir = format!("{CONTAINER_SOURCE_DIR}/target/unc-reproducible");
+        for (key, value) in [
+            ("HOME", format!("{container_target_dir}/home")),
+            ("CARGO_HOME", format!("{container_target_dir}/cargo-home")),
+            ("CARGO_TARGET_DIR", container_target_dir.clone()),
+            (REPRODUCIBLE_BUILD_IMAGE_ENV, self.image_digest.clone()),
+        ]
+        .into_iter()
+        .chain(env)
+        {
+            cmd.args(["--env", &format!("{key}={value}")]);
+        }
+        cmd.args(["--entrypoint", "unc", &self.image_digest]);
+        cmd.args(["dev-tool", "build"]);
+        cmd.args(build_flags);
+        cmd.arg("--manifest-path")
+            .arg(Utf8Path::new(CONTAINER_SOURCE_DIR).join(relative_manifest_path));
+        cmd.args(["--out-dir", CONTAINER_OUT_DIR, "--color", color.as_str()]);
+        cmd
+    }
+}
+
+/// Environment variables read by the contract SDK to populate NEP-0330 `contract_source_metadata`.
+pub(crate) fn get_contract_source_metadata_env(
+    crate_metadata: &CrateMetadata,
+    image_digest: &str,
+    build_command: &[String],
+) -> color_eyre::eyre::Result<Vec<(&'static str, String)>> {
+    let package = &crate_metadata.root_package;
+    let workspace_root = &crate_metadata.raw_metadata.workspace_root;
+    let contract_path = crate_metadata
+        .manifest_path
+        .directory()?
+        .strip_prefix(workspace_root)
+        .map(Utf8PathBuf::from)
+        .unwrap_or_default();
+
+    let mut env = vec![
+        ("NEP330_VERSION", package.version.to_string()),
+        (
+            "NEP330_BUILD_INFO_BUILD_ENVIRONMENT",
+            image_digest.to_string(),
+        ),
+        (
+            "NEP330_BUILD_INFO_BUILD_COMMAND",
+            serde_json::to_string(build_command)?,
+        ),
+        ("NEP330_BUILD_INFO_CONTRACT_PATH", contract_path.to_string()),
+    ];
+    if let Some(repository) = &package.repository {
+        env.push(("NEP330_LINK", repository.clone()));
+        if let Some(revision) = get_git_revision(workspace_root) {
+            env.push((
+                "NEP330_BUILD_INFO_SOURCE_CODE_SNAPSHOT",
+                format!("git+{repository}?rev={revision}"),
+            ));
+        }
+    }
+    Ok(env)
+}
+
+/// Extracts the compiler version from the image inspection output and checks that the image
+/// provides everything the build needs without network access.
+fn get_image_compiler(output: &str) -> color_eyre::eyre::Result<String> {
+    let mut lines = output.lines().map(str::trim);
+    let compiler = lines
+        .next()
+        .filter(|line| line.starts_with("rustc "))
+        .map(|line| {
+            line.split_whitespace()
+                .take(2)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .wrap_err("The image has no Rust toolchain (`rustc --version` failed)")?;
+    let lines = lines.collect::<Vec<_>>();
+    if !lines.contains(&util::COMPILATION_TARGET) {
+        color_eyre::eyre::bail!(
+            "The image has no `{}` target installed",
+            util::COMPILATION_TARGET
+        );
+    }
+    if !lines.iter().any(|line| line.ends_with("/unc")) {
+        color_eyre::eyre::bail!("The image has no `unc` CLI installed");
+    }
+    Ok(compiler)
+}
+
+/// Returns `uid:gid` of the current user to run the container as.
+#[cfg(unix)]
+fn get_current_user() -> Option<String> {
+    let id = |flag| {
+        run(Command::new("id").arg(flag))
+            .ok()
+            .map(|id| id.trim().to_string())
+    };
+    Some(format!("{}:{}", id("-u")?, id("-g")?))
+}
+
+/// Docker Desktop maps the file ownership of the mounted directories on Windows.
+#[cfg(not(unix))]
+fn get_current_user() -> Option<String> {
+    None
+}
+
+/// The name of the `.wasm` file produced for the package.
+fn get_lib_name(crate_metadata: &CrateMetadata) -> String {
+    let package = &crate_metadata.root_package;
+    package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| kind == "cdylib"))
+        .map_or(&package.name, |target| &target.name)
+        .replace('-', "_")
+}
+
+fn get_image_from_manifest(crate_metadata: &CrateMetadata) -> Option<String> {
+    crate_metadata
+        .root_package
+        .metadata
+        .pointer("/unc/reproducible_build/image")
+        .and_then(|image| image.as_str())
+        .map(str::to_string)
+}
+
+/// Returns the checked out commit, unless the working tree has uncommitted changes.
+fn get_git_revision(workspace_root: &Utf8Path) -> Option<String> {
+    let status = run(Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(workspace_root))
+    .ok()?;
+    if !status.trim().is_empty() {
+        eprintln!("Warning: the working tree has uncommitted changes, so the source code snapshot is not recorded in the contract metadata");
+        return None;
+    }
+    run(Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(workspace_root))
+    .ok()
+    .map(|revision| revision.trim().to_string())
+}
+
+fn run(cmd: &mut Command) -> color_eyre::eyre::Result<String> {
+    log::info!("Invoking: {:?}", cmd);
+    let output = cmd
+        .output()
+        .wrap_err_with(|| format!("Error executing `{:?}`", cmd))?;
+    if !output.status.success() {
+        color_eyre::eyre::bail!(
+            "`{:?}` failed with exit code: {:?}\n{}",
+            cmd,
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_has_to_provide_the_target_and_unc() {
+        let output = "rustc 1.78.0 (9b00956e5 2024-04-29)\nwasm32-unknown-unknown\nx86_64-unknown-linux-gnu\n/usr/local/cargo/bin/unc\n";
+        assert_eq!(get_image_compiler(output).unwrap(), "rustc 1.78.0");
+
+        let without_target = "rustc 1.78.0 (9b00956e5 2024-04-29)\nx86_64-unknown-linux-gnu\n/usr/local/cargo/bin/unc\n";
+        assert!(get_image_compiler(without_target)
+            .unwrap_err()
+            .to_string()
+            .contains("wasm32-unknown-unknown"));
+
+        let without_unc = "rustc 1.78.0 (9b00956e5 2024-04-29)\nwasm32-unknown-unknown\n";
+        assert!(get_image_compiler(without_unc)
+            .unwrap_err()
+            .to_string()
+            .contains("`unc`"));
+
+        assert!(get_image_compiler("wasm32-unknown-unknown\n/usr/bin/unc\n").is_err());
+    }
+
+    #[test]
+    fn container_build_runs_unc_without_root_and_downloads() {
+        let environment = ReproducibleBuildEnvironment {
+            runtime: ContainerRuntime::Docker,
+            image_digest: "example.org/unc-builder@sha256:0123".to_string(),
+            compiler: "rustc 1.78.0".to_string(),
+            user: Some("1000:1000".to_string()),
+        };
+        let cmd = environment.build_command(
+            Utf8Path::new("/work/project"),
+            Utf8Path::new("contracts/token/Cargo.toml"),
+            Utf8Path::new("/work/project/target/unc/token"),
+            &["--no-doc".to_string()],
+            vec![("NEP330_VERSION", "1.0.0".to_string())],
+            ColorPreference::Never,
+        );
+        let args = cmd
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        let position = |arg: &str| args.iter().position(|a| a == arg).unwrap();
+
+        assert_eq!(cmd.get_program(), "docker");
+        assert_eq!(args[position("--user") + 1], "1000:1000");
+        assert!(args.contains(&"/work/project:/home/unc/code".to_string()));
+        assert!(args.contains(&"/work/project/target/unc/token:/home/unc/out".to_string()));
+        assert!(args.contains(&format!(
+            "{REPRODUCIBLE_BUILD_IMAGE_ENV}=example.org/unc-builder@sha256:0123"
+        )));
+        assert!(args.contains(&"NEP330_VERSION=1.0.0".to_string()));
+        assert_eq!(args[position("--entrypoint") + 1], "unc");
+        assert_eq!(
+            args[position("--entrypoint") + 2..],
+            [
+                "example.org/unc-builder@sha256:0123",
+                "dev-tool",
+                "build",
+                "--no-doc",
+                "--manifest-path",
+                "/home/unc/code/contracts/token/Cargo.toml",
+                "--out-dir",
+                "/home/unc/out",
+                "--color",
+                "never",
+            ]
+        );
+        assert!(!args.iter().any(|arg| arg.contains("rustup")));
+    }
+}
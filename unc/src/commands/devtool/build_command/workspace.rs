@@ -0,0 +1,330 @@
+use camino::Utf8PathBuf;
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+
+use crate::types::manifest::CargoManifestPath;
+use crate::util;
+
+const SDK_CRATE_NAME: &str = "unc-sdk";
+const ARTIFACTS_MANIFEST_FILE_NAME: &str = "artifacts.json";
+
+/// The manifest written next to the workspace build artifacts.
+#[derive(Debug, serde::Serialize)]
+pub struct ArtifactsManifest {
+    pub contracts: Vec<ContractArtifact>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ContractArtifact {
+    pub name: String,
+    pub version: String,
+    /// Relative to the artifacts directory.
+    pub wasm: Utf8PathBuf,
+    /// Relative to the artifacts directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abi: Option<Utf8PathBuf>,
+    /// The SHA-256 hash of the wasm in Base58 format (the same as the on-chain code hash).
+    pub wasm_hash: String,
+    pub size: u64,
+}
+
+/// Builds every contract of the cargo workspace (or the selected `--package`s) one after another.
+///
+/// Cargo serializes builds that share a target directory anyway, so the contracts are built
+/// sequentially while cargo parallelizes the compilation of each of them.
+pub fn run(args: super::BuildCommand) -> color_eyre::eyre::Result<()> {
+    let manifest_path: Utf8PathBuf = args
+        .manifest_path
+        .clone()
+        .map(Into::into)
+        .unwrap_or_else(|| "Cargo.toml".into());
+    let manifest_path = CargoManifestPath::try_from(manifest_path)?;
+
+    let metadata = util::handle_step("Collecting cargo workspace metadata...", || {
+        cargo_metadata::MetadataCommand::new()
+            .manifest_path(&manifest_path.path)
+            .exec()
+            .map_err(color_eyre::Report::from)
+    })?;
+
+    let contracts = get_contract_packages(&metadata, args.package.as_ref().map(|p| &p.0[..]))?;
+    if contracts.is_empty() {
+        color_eyre::eyre::bail!(
+            "There are no contracts (packages depending on `{SDK_CRATE_NAME}`) in the workspace <{}>",
+            metadata.workspace_root
+        );
+    }
+
+    let artifacts_dir = match &args.out_dir {
+        Some(out_dir) => util::force_canonicalize_dir(out_dir.as_ref())?,
+        None => util::force_canonicalize_dir(&metadata.target_directory.join("unc/workspace"))?,
+    };
+
+    let mut built_contracts = vec![];
+    let mut failed_contracts = vec![];
+    for package in &contracts {
+        util::print_step(&format!("Building contract <{}>", package.name));
+        let contract_dir = artifacts_dir.join(&package.name);
+        let result = super::build::run(super::BuildCommand {
+            workspace: false,
+            package: None,
+            manifest_path: Some(package.manifest_path.clone().into()),
+            out_dir: Some(contract_dir.clone().into()),
+            ..args.clone()
+        })
+        .and_then(|wasm_artifact| {
+            get_contract_artifact(package, &artifacts_dir, &contract_dir, wasm_artifact.path)
+        });
+        match result {
+            Ok(artifact) => built_contracts.push(artifact),
+            Err(err) => {
+                eprintln!(
+                    " {} Failed to build <{}>: {}",
+                    "✗".bold().red(),
+                    package.name,
+                    err
+                );
+                failed_contracts.push((package.name.clone(), err));
+            }
+        }
+    }
+
+    let manifest_file_path = artifacts_dir.join(ARTIFACTS_MANIFEST_FILE_NAME);
+    std::fs::write(
+        &manifest_file_path,
+        serde_json::to_vec_pretty(&ArtifactsManifest {
+            contracts: built_contracts,
+        })?,
+    )?;
+
+    eprintln!();
+    for package in &contracts {
+        match failed_contracts
+            .iter()
+            .find(|(name, _)| name == &package.name)
+        {
+            Some(_) => eprintln!("     - {}: {}", package.name, "failed".red().bold()),
+            None => eprintln!("     - {}: {}", package.name, "ok".green().bold()),
+        }
+    }
+    eprintln!(
+        "     - Artifacts manifest: {}",
+        manifest_file_path.to_string().yellow().bold()
+    );
+
+    if !failed_contracts.is_empty() {
+        color_eyre::eyre::bail!(
+            "{} of {} contracts failed to build:\n{}",
+            failed_contracts.len(),
+            contracts.len(),
+            failed_contracts
+                .iter()
+                .map(|(name, err)| format!(" - {name}: {err}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    util::print_success(&format!(
+        "{} contracts successfully built!",
+        contracts.len()
+    ));
+    Ok(())
+}
+
+/// Returns the workspace members that depend on the contract SDK, optionally restricted to `selected_packages`.
+fn get_contract_packages<'a>(
+    metadata: &'a cargo_metadata::Metadata,
+    selected_packages: Option<&[String]>,
+) -> color_eyre::eyre::Result<Vec<&'a cargo_metadata::Package>> {
+    let members = metadata.workspace_packages();
+    if let Some(selected_packages) = selected_packages {
+        for name in selected_packages {
+            match members.iter().find(|package| &package.name == name) {
+                None => {
+                    color_eyre::eyre::bail!("Package <{name}> is not a member of the workspace")
+                }
+                Some(package) if !is_contract(package) => color_eyre::eyre::bail!(
+                    "Package <{name}> is not a contract (it does not depend on `{SDK_CRATE_NAME}`)"
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+    Ok(members
+        .into_iter()
+        .filter(|package| is_contract(package))
+        .filter(|package| {
+            selected_packages.map_or(true, |selected_packages| {
+                selected_packages.contains(&package.name)
+            })
+        })
+        .collect())
+}
+
+fn is_contract(package: &cargo_metadata::Package) -> bool {
+    package.dependencies.iter().any(|dependency| {
+        dependency.name == SDK_CRATE_NAME
+            && dependency.kind == cargo_metadata::DependencyKind::Normal
+    })
+}
+
+fn get_contract_artifact(
+    package: &cargo_metadata::Package,
+    artifacts_dir: &camino::Utf8Path,
+    contract_dir: &camino::Utf8Path,
+    wasm_path: Utf8PathBuf,
+) -> color_eyre::eyre::Result<ContractArtifact> {
+    let wasm = std::fs::read(&wasm_path)?;
+    let abi_path = contract_dir.join(format!("{}_abi.json", package.name.replace('-', "_")));
+    let relative_path = |path: &camino::Utf8Path| {
+        path.strip_prefix(artifacts_dir)
+            .map(Utf8PathBuf::from)
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+    Ok(ContractArtifact {
+        name: package.name.clone(),
+        version: package.version.to_string(),
+        wasm: relative_path(&wasm_path),
+        abi: abi_path.exists().then(|| relative_path(&abi_path)),
+        wasm_hash: bs58::encode(Sha256::digest(&wasm)).into_string(),
+        size: wasm.len() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> Utf8PathBuf {
+        let dir = std::env::temp_dir().join(format!("unc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Utf8PathBuf::from_path_buf(dir).unwrap()
+    }
+
+    fn write_package(workspace_root: &camino::Utf8Path, name: &str, dependencies: &str) {
+        let package_dir = workspace_root.join(name);
+        std::fs::create_dir_all(package_dir.join("src")).unwrap();
+        std::fs::write(package_dir.join("src/lib.rs"), "").unwrap();
+        std::fs::write(
+            package_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n{dependencies}"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn contracts_are_the_members_depending_on_the_sdk() {
+        let workspace_root = temp_dir("workspace-members");
+        std::fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"token\", \"nft\", \"tool\", \"tests\"]\n",
+        )
+        .unwrap();
+        write_package(
+            &workspace_root,
+            "token",
+            "[dependencies]\nunc-sdk = \"2\"\n",
+        );
+        write_package(&workspace_root, "nft", "[dependencies]\nunc-sdk = \"2\"\n");
+        write_package(&workspace_root, "tool", "[dependencies]\n");
+        write_package(
+            &workspace_root,
+            "tests",
+            "[dev-dependencies]\nunc-sdk = \"2\"\n",
+        );
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(workspace_root.join("Cargo.toml"))
+            .no_deps()
+            .other_options(vec!["--offline".to_string()])
+            .exec()
+            .unwrap();
+
+        let names = |packages: Vec<&cargo_metadata::Package>| {
+            let mut names = packages
+                .into_iter()
+                .map(|package| package.name.clone())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        assert_eq!(
+            names(get_contract_packages(&metadata, None).unwrap()),
+            ["nft", "token"]
+        );
+        assert_eq!(
+            names(get_contract_packages(&metadata, Some(&["token".to_string()])).unwrap()),
+            ["token"]
+        );
+        assert!(get_contract_packages(&metadata, Some(&["tool".to_string()])).is_err());
+        assert!(get_contract_packages(&metadata, Some(&["missing".to_string()])).is_err());
+
+        std::fs::remove_dir_all(&workspace_root).unwrap();
+    }
+
+    #[test]
+    fn artifacts_manifest_has_relative_paths_and_code_hashes() {
+        let artifacts_dir = temp_dir("workspace-artifacts");
+        let token_dir = artifacts_dir.join("my-token");
+        std::fs::create_dir_all(&token_dir).unwrap();
+        std::fs::write(token_dir.join("my_token.wasm"), b"\0asm").unwrap();
+        std::fs::write(token_dir.join("my_token_abi.json"), b"{}").unwrap();
+        let nft_dir = artifacts_dir.join("nft");
+        std::fs::create_dir_all(&nft_dir).unwrap();
+        std::fs::write(nft_dir.join("nft.wasm"), b"\0asm\x01").unwrap();
+
+        let package = |name: &str| -> cargo_metadata::Package {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "version": "1.2.3",
+                "id": format!("{name} 1.2.3"),
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": format!("/{name}/Cargo.toml"),
+            }))
+            .unwrap()
+        };
+        let token = get_contract_artifact(
+            &package("my-token"),
+            &artifacts_dir,
+            &token_dir,
+            token_dir.join("my_token.wasm"),
+        )
+        .unwrap();
+        let nft = get_contract_artifact(
+            &package("nft"),
+            &artifacts_dir,
+            &nft_dir,
+            nft_dir.join("nft.wasm"),
+        )
+        .unwrap();
+        std::fs::remove_dir_all(&artifacts_dir).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(ArtifactsManifest {
+                contracts: vec![token, nft],
+            })
+            .unwrap(),
+            serde_json::json!({
+                "contracts": [
+                    {
+                        "name": "my-token",
+                        "version": "1.2.3",
+                        "wasm": "my-token/my_token.wasm",
+                        "abi": "my-token/my_token_abi.json",
+                        "wasm_hash": bs58::encode(Sha256::digest(b"\0asm")).into_string(),
+                        "size": 4,
+                    },
+                    {
+                        "name": "nft",
+                        "version": "1.2.3",
+                        "wasm": "nft/nft.wasm",
+                        "wasm_hash": bs58::encode(Sha256::digest(b"\0asm\x01")).into_string(),
+                        "size": 5,
+                    },
+                ]
+            })
+        );
+    }
+}
@@ -54,14 +54,13 @@ impl From<RandomAccountContext> for NewAccountContext {
 
 pub fn random_account_id(
     networks: &linked_hash_map::LinkedHashMap<String, crate::config::NetworkConfig>,
-) -> color_eyre::eyre::Result<crate::types::account_id::AccountId> {
+) -> color_eyre::eyre::Result<unc_primitives::types::AccountId> {
     loop {
         let mut generator = Generator::default();
         let random_name = generator.next().wrap_err("Random name generator error")?;
-        let account_id = crate::types::account_id::AccountId::from_str(&format!(
-            "{random_name}.testnet"
-        ))?;
-        if !crate::common::is_account_exist(networks, account_id.clone().into()) {
+        let account_id =
+            unc_primitives::types::AccountId::from_str(&format!("{random_name}.testnet"))?;
+        if !crate::common::is_account_exist(networks, account_id.clone()) {
             return Ok(account_id);
         }
     }
@@ -0,0 +1,730 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use colored::Colorize;
+
+use crate::commands::devtool::build_command;
+use crate::util;
+
+pub const DEFAULT_DEPLOY_MANIFEST_FILE_NAME: &str = "unc-deploy.toml";
+const DEFAULT_HISTORY_FILE_NAME: &str = "unc-deploy-history.json";
+const DEFAULT_GAS: &str = "100 Tgas";
+
+/// The deployment manifest (`unc-deploy.toml`) deployed with `dev-tool deploy-env <environment>`:
+///
+/// ```toml
+/// [environments.staging]
+/// network = "testnet"
+///
+/// [[contracts]]
+/// name = "token"
+/// manifest_path = "contracts/token/Cargo.toml"
+/// init = { method = "new_default_meta", args = { owner_id = "owner.testnet" } }
+/// migrations = [{ name = "v2", method = "migrate" }]
+///
+/// [contracts.environments.staging]
+/// account_id = "token.staging.testnet"
+/// ```
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeployManifest {
+    pub environments: BTreeMap<String, Environment>,
+    pub contracts: Vec<ContractManifest>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Environment {
+    /// The network name from the CLI config.
+    pub network: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractManifest {
+    pub name: String,
+    /// Relative to the deployment manifest; defaults to its `Cargo.toml`.
+    pub manifest_path: Option<Utf8PathBuf>,
+    pub features: Option<String>,
+    #[serde(default)]
+    pub no_default_features: bool,
+    /// Called in the same transaction as the first deployment to the account.
+    pub init: Option<FunctionCall>,
+    /// Called in the same transaction as an upgrade, each of them only once per account.
+    #[serde(default)]
+    pub migrations: Vec<Migration>,
+    pub environments: BTreeMap<String, ContractEnvironment>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractEnvironment {
+    pub account_id: unc_primitives::types::AccountId,
+    /// Overrides the `init.args` of the contract in this environment.
+    pub init_args: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FunctionCall {
+    pub method: String,
+    pub args: Option<serde_json::Value>,
+    /// For example, "100 Tgas" (the default).
+    pub gas: Option<String>,
+    /// For example, "1 unc" (no deposit by default).
+    pub deposit: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Migration {
+    pub name: String,
+    #[serde(flatten)]
+    pub call: FunctionCall,
+}
+
+/// An entry of the deployment history file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DeploymentRecord {
+    pub environment: String,
+    pub network: String,
+    pub contract: String,
+    pub account_id: unc_primitives::types::AccountId,
+    pub previous_code_hash: unc_primitives::hash::CryptoHash,
+    pub code_hash: unc_primitives::hash::CryptoHash,
+    pub transaction_hash: unc_primitives::hash::CryptoHash,
+    pub initialized: bool,
+    pub migrations: Vec<String>,
+    /// Seconds since the Unix epoch.
+    pub deployed_at: u64,
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[interactive_clap(skip_default_from_cli)]
+pub struct DeployEnvironment {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which environment of the deployment manifest do you want to deploy?
+    environment: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Path to the deployment manifest (defaults to `unc-deploy.toml` in the current directory)
+    deploy_manifest: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// File to record the deployment history to (defaults to `unc-deploy-history.json` next to the deployment manifest)
+    history_file: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(long)]
+    /// Only show which contracts of the environment would be deployed
+    dry_run: bool,
+    #[interactive_clap(subcommand)]
+    transaction_signature_options: crate::transaction_signature_options::SignWith,
+}
+
+impl interactive_clap::FromCli for DeployEnvironment {
+    type FromCliContext = crate::GlobalContext;
+    type FromCliError = color_eyre::eyre::Error;
+    fn from_cli(
+        optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+
+        let manifest_file: std::path::PathBuf = clap_variant
+            .deploy_manifest
+            .clone()
+            .map(Into::into)
+            .unwrap_or_else(|| DEFAULT_DEPLOY_MANIFEST_FILE_NAME.into());
+        let manifest = match read_manifest(&manifest_file) {
+            Ok(manifest) => manifest,
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        };
+        if clap_variant.environment.is_none() {
+            clap_variant.environment = match Self::input_environment(&manifest) {
+                Ok(Some(environment)) => Some(environment),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let environment = clap_variant.environment.clone().expect("Unexpected error");
+
+        let plan = match plan_environment(
+            &context,
+            &manifest_file,
+            &manifest,
+            &environment,
+            clap_variant.history_file.clone().map(Into::into),
+        ) {
+            Ok(plan) => plan,
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        };
+        if clap_variant.dry_run || plan.deployments.is_empty() {
+            return match plan.finish(&environment, None) {
+                Ok(()) => interactive_clap::ResultFromCli::Ok(clap_variant),
+                Err(err) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+
+        let transactions = plan
+            .deployments
+            .iter()
+            .map(|deployment| deployment.transaction(&plan.history_file))
+            .collect::<Vec<_>>();
+        let results = match crate::transaction_signature_options::sign_batch(
+            &context,
+            &plan.network_config,
+            &mut clap_variant.transaction_signature_options,
+            transactions,
+        ) {
+            Some(results) => results,
+            None => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+        };
+        match plan.finish(&environment, Some(results)) {
+            Ok(()) => interactive_clap::ResultFromCli::Ok(clap_variant),
+            Err(err) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        }
+    }
+}
+
+impl DeployEnvironment {
+    fn input_environment(manifest: &DeployManifest) -> color_eyre::eyre::Result<Option<String>> {
+        Ok(Some(
+            inquire::Select::new(
+                "Which environment of the deployment manifest do you want to deploy?",
+                manifest.environments.keys().cloned().collect(),
+            )
+            .prompt()?,
+        ))
+    }
+}
+
+fn read_manifest(manifest_file: &std::path::Path) -> color_eyre::eyre::Result<DeployManifest> {
+    toml::from_str(
+        &std::fs::read_to_string(manifest_file).wrap_err_with(|| {
+            format!("Failed to read the deployment manifest {manifest_file:?}")
+        })?,
+    )
+    .wrap_err_with(|| format!("Failed to parse the deployment manifest {manifest_file:?}"))
+}
+
+/// The contracts of an environment compared with their deployed code.
+struct EnvironmentPlan {
+    network_config: crate::config::NetworkConfig,
+    history_file: std::path::PathBuf,
+    /// The contracts that are up to date or failed to build.
+    statuses: Vec<(String, color_eyre::eyre::Result<()>)>,
+    deployments: Vec<Deployment>,
+}
+
+/// A contract whose local build differs from the deployed code.
+struct Deployment {
+    record: DeploymentRecord,
+    actions: Vec<unc_primitives::transaction::Action>,
+}
+
+/// Builds the contracts of the environment and plans the deployment of those whose code differs
+/// from the on-chain one (or, in offline mode, from the last recorded deployment).
+fn plan_environment(
+    global_context: &crate::GlobalContext,
+    manifest_file: &std::path::Path,
+    manifest: &DeployManifest,
+    environment_name: &str,
+    history_file: Option<std::path::PathBuf>,
+) -> color_eyre::eyre::Result<EnvironmentPlan> {
+    let manifest_dir = Utf8PathBuf::try_from(
+        manifest_file
+            .canonicalize()?
+            .parent()
+            .wrap_err("Unable to infer the directory of the deployment manifest")?
+            .to_path_buf(),
+    )?;
+    let history_file =
+        history_file.unwrap_or_else(|| manifest_dir.join(DEFAULT_HISTORY_FILE_NAME).into());
+
+    let environment = manifest
+        .environments
+        .get(environment_name)
+        .wrap_err_with(|| {
+            format!(
+                "Environment <{environment_name}> is not defined in the deployment manifest (available: {})",
+                manifest
+                    .environments
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+    let network_config = global_context
+        .config
+        .network_connection
+        .get(&environment.network)
+        .wrap_err_with(|| {
+            format!(
+                "Network <{}> of environment <{environment_name}> is not found in the CLI config",
+                environment.network
+            )
+        })?
+        .clone();
+    if global_context.offline {
+        eprintln!(
+            "{} The on-chain code cannot be fetched in offline mode, the local builds are compared with the last deployments recorded in {history_file:?}",
+            "Warning:".yellow().bold()
+        );
+    }
+    let history = read_history(&history_file)?;
+
+    let mut statuses = vec![];
+    let mut deployments = vec![];
+    for contract in &manifest.contracts {
+        let Some(contract_environment) = contract.environments.get(environment_name) else {
+            eprintln!(
+                " {} Contract <{}> is not deployed to <{environment_name}>, skipping",
+                "-".bold(),
+                contract.name,
+            );
+            continue;
+        };
+        util::print_step(&format!(
+            "Planning <{}> for <{}> on <{}>",
+            contract.name, contract_environment.account_id, network_config.network_name
+        ));
+        match plan_contract(
+            global_context,
+            &network_config,
+            &manifest_dir,
+            environment_name,
+            contract,
+            contract_environment,
+            &history,
+        ) {
+            Ok(Some(deployment)) => deployments.push(deployment),
+            Ok(None) => statuses.push((contract.name.clone(), Ok(()))),
+            Err(err) => {
+                eprintln!(
+                    " {} Failed to plan <{}>: {}",
+                    "✗".bold().red(),
+                    contract.name,
+                    err
+                );
+                statuses.push((contract.name.clone(), Err(err)));
+            }
+        }
+    }
+    Ok(EnvironmentPlan {
+        network_config,
+        history_file,
+        statuses,
+        deployments,
+    })
+}
+
+fn read_history(history_file: &std::path::Path) -> color_eyre::eyre::Result<Vec<DeploymentRecord>> {
+    if !history_file.exists() {
+        return Ok(vec![]);
+    }
+    serde_json::from_slice(&std::fs::read(history_file)?)
+        .wrap_err_with(|| format!("Failed to parse the deployment history {history_file:?}"))
+}
+
+fn plan_contract(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    manifest_dir: &Utf8Path,
+    environment_name: &str,
+    contract: &ContractManifest,
+    contract_environment: &ContractEnvironment,
+    history: &[DeploymentRecord],
+) -> color_eyre::eyre::Result<Option<Deployment>> {
+    let wasm_artifact = build_command::build::run(build_command::BuildCommand {
+        manifest_path: Some(
+            manifest_dir
+                .join(
+                    contract
+                        .manifest_path
+                        .as_deref()
+                        .unwrap_or(Utf8Path::new("Cargo.toml")),
+                )
+                .into(),
+        ),
+        features: contract.features.clone(),
+        no_default_features: contract.no_default_features,
+        ..Default::default()
+    })?;
+    let code = std::fs::read(wasm_artifact.path)?;
+    let code_hash = unc_primitives::hash::CryptoHash::hash_bytes(&code);
+
+    let account_id = &contract_environment.account_id;
+    let previous_code_hash = if global_context.offline {
+        history
+            .iter()
+            .rev()
+            .find(|record| {
+                &record.account_id == account_id && record.network == network_config.network_name
+            })
+            .map(|record| record.code_hash)
+            .unwrap_or_default()
+    } else {
+        crate::common::get_account_state(
+            network_config.clone(),
+            account_id.clone(),
+            unc_primitives::types::BlockReference::latest(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the state of <{account_id}> on network <{}> (the account has to exist before the deployment)",
+                network_config.network_name
+            )
+        })?
+        .code_hash
+    };
+
+    if previous_code_hash == code_hash {
+        eprintln!("   The deployed code is up to date ({code_hash})");
+        return Ok(None);
+    }
+
+    let initialized = previous_code_hash == unc_primitives::hash::CryptoHash::default();
+    let (function_calls, migrations) = function_calls(
+        contract,
+        contract_environment,
+        history,
+        &network_config.network_name,
+        initialized,
+    );
+
+    eprintln!("   Code hash: {previous_code_hash} -> {code_hash}");
+    for function_call in &function_calls {
+        eprintln!(
+            "   Call: {}({})",
+            function_call.method,
+            function_call
+                .args
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        );
+    }
+
+    let mut actions = vec![unc_primitives::transaction::Action::DeployContract(
+        unc_primitives::transaction::DeployContractAction { code },
+    )];
+    for function_call in function_calls {
+        actions.push(function_call.into_action()?);
+    }
+    Ok(Some(Deployment {
+        record: DeploymentRecord {
+            environment: environment_name.to_string(),
+            network: network_config.network_name.clone(),
+            contract: contract.name.clone(),
+            account_id: account_id.clone(),
+            previous_code_hash,
+            code_hash,
+            transaction_hash: Default::default(),
+            initialized,
+            migrations,
+            deployed_at: 0,
+        },
+        actions,
+    }))
+}
+
+/// The calls to make after deploying the code and the migrations they apply. The first deployment
+/// makes the init call and marks all the migrations as applied, since the contract is initialized
+/// with the current code; later deployments run the migrations missing in the history.
+fn function_calls(
+    contract: &ContractManifest,
+    contract_environment: &ContractEnvironment,
+    history: &[DeploymentRecord],
+    network_name: &str,
+    initialized: bool,
+) -> (Vec<FunctionCall>, Vec<String>) {
+    if initialized {
+        let init_call = contract.init.as_ref().map(|init| FunctionCall {
+            args: contract_environment
+                .init_args
+                .clone()
+                .or_else(|| init.args.clone()),
+            ..init.clone()
+        });
+        return (
+            init_call.into_iter().collect(),
+            contract
+                .migrations
+                .iter()
+                .map(|migration| migration.name.clone())
+                .collect(),
+        );
+    }
+    let applied_migrations = history
+        .iter()
+        .filter(|record| {
+            record.account_id == contract_environment.account_id && record.network == network_name
+        })
+        .flat_map(|record| record.migrations.iter())
+        .collect::<Vec<_>>();
+    contract
+        .migrations
+        .iter()
+        .filter(|migration| !applied_migrations.contains(&&migration.name))
+        .map(|migration| (migration.call.clone(), migration.name.clone()))
+        .unzip()
+}
+
+impl Deployment {
+    /// The deployment transaction that records itself in the history file once it succeeds.
+    fn transaction(
+        &self,
+        history_file: &std::path::Path,
+    ) -> crate::transaction_signature_options::BatchTransaction {
+        let record = self.record.clone();
+        let history_file = history_file.to_path_buf();
+        crate::transaction_signature_options::BatchTransaction {
+            prepopulated_transaction: crate::commands::PrepopulatedTransaction {
+                signer_id: self.record.account_id.clone(),
+                receiver_id: self.record.account_id.clone(),
+                actions: self.actions.clone(),
+            },
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config, _message| Ok(()),
+            ),
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                move |outcome_view, _network_config| {
+                    if let Some(err) = crate::common::outcome_error(outcome_view) {
+                        color_eyre::eyre::bail!("The deployment transaction failed: {err}");
+                    }
+                    let mut history = read_history(&history_file)?;
+                    history.push(DeploymentRecord {
+                        transaction_hash: outcome_view.transaction_outcome.id,
+                        deployed_at: std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)?
+                            .as_secs(),
+                        ..record.clone()
+                    });
+                    std::fs::write(&history_file, serde_json::to_vec_pretty(&history)?)
+                        .wrap_err_with(|| format!("Failed to write to file: {history_file:?}"))
+                },
+            ),
+        }
+    }
+}
+
+impl EnvironmentPlan {
+    /// Prints the status of each contract; `results` are the results of the sent deployments
+    /// (`None` when nothing was sent).
+    fn finish(
+        self,
+        environment_name: &str,
+        results: Option<Vec<crate::CliResult>>,
+    ) -> crate::CliResult {
+        let sent = results.is_some();
+        let mut statuses = self
+            .statuses
+            .into_iter()
+            .map(|(name, result)| (name, result.map(|()| "up to date".bold())))
+            .collect::<Vec<_>>();
+        match results {
+            Some(results) => {
+                for (deployment, result) in self.deployments.iter().zip(results) {
+                    statuses.push((
+                        deployment.record.contract.clone(),
+                        result.map(|()| "signed".green().bold()),
+                    ));
+                }
+            }
+            None => {
+                for deployment in &self.deployments {
+                    statuses.push((
+                        deployment.record.contract.clone(),
+                        Ok("changed".yellow().bold()),
+                    ));
+                }
+            }
+        }
+
+        eprintln!();
+        for (name, status) in &statuses {
+            match status {
+                Ok(status) => eprintln!("     - {name}: {status}"),
+                Err(_) => eprintln!("     - {name}: {}", "failed".red().bold()),
+            }
+        }
+        if sent {
+            eprintln!(
+                "     - Deployment history (the sent deployments are recorded): {}",
+                format!("{:?}", self.history_file).yellow().bold()
+            );
+        }
+
+        let failed_contracts = statuses
+            .iter()
+            .filter_map(|(name, status)| {
+                status.as_ref().err().map(|err| format!(" - {name}: {err}"))
+            })
+            .collect::<Vec<_>>();
+        if !failed_contracts.is_empty() {
+            color_eyre::eyre::bail!(
+                "{} of {} contracts failed to deploy to <{environment_name}>:\n{}",
+                failed_contracts.len(),
+                statuses.len(),
+                failed_contracts.join("\n")
+            );
+        }
+        if self.deployments.is_empty() {
+            util::print_success(&format!("Environment <{environment_name}> is up to date!"));
+        }
+        Ok(())
+    }
+}
+
+impl FunctionCall {
+    fn into_action(self) -> color_eyre::eyre::Result<unc_primitives::transaction::Action> {
+        let gas = crate::common::UncGas::from_str(self.gas.as_deref().unwrap_or(DEFAULT_GAS))
+            .map_err(|err| color_eyre::eyre::eyre!("Invalid gas for <{}>: {err}", self.method))?;
+        let deposit = match &self.deposit {
+            Some(deposit) => {
+                crate::types::unc_token::UncToken::from_str(deposit).map_err(|err| {
+                    color_eyre::eyre::eyre!("Invalid deposit for <{}>: {err}", self.method)
+                })?
+            }
+            None => crate::types::unc_token::UncToken::from_attounc(0),
+        };
+        Ok(unc_primitives::transaction::Action::FunctionCall(Box::new(
+            unc_primitives::transaction::FunctionCallAction {
+                args: serde_json::to_vec(&self.args.unwrap_or_else(|| serde_json::json!({})))?,
+                method_name: self.method,
+                gas: gas.as_gas(),
+                deposit: deposit.as_attounc(),
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_deploy_manifest() {
+        let manifest: DeployManifest = toml::from_str(
+            r#"
+            [environments.staging]
+            network = "testnet"
+
+            [[contracts]]
+            name = "token"
+            manifest_path = "contracts/token/Cargo.toml"
+            init = { method = "new", args = { owner_id = "owner.testnet" } }
+            migrations = [{ name = "v2", method = "migrate", gas = "50 Tgas" }]
+
+            [contracts.environments.staging]
+            account_id = "token.staging.testnet"
+            init_args = { owner_id = "staging.testnet" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.environments["staging"].network, "testnet");
+        let contract = &manifest.contracts[0];
+        assert_eq!(contract.migrations[0].name, "v2");
+        assert_eq!(contract.migrations[0].call.method, "migrate");
+        assert_eq!(
+            contract.environments["staging"].init_args,
+            Some(serde_json::json!({"owner_id": "staging.testnet"}))
+        );
+
+        let action = contract.migrations[0].call.clone().into_action().unwrap();
+        let unc_primitives::transaction::Action::FunctionCall(function_call) = action else {
+            panic!("Expected a function call action");
+        };
+        assert_eq!(function_call.gas, 50_000_000_000_000);
+        assert_eq!(function_call.args, b"{}");
+    }
+
+    fn record(migrations: &[&str]) -> DeploymentRecord {
+        DeploymentRecord {
+            environment: "staging".to_string(),
+            network: "testnet".to_string(),
+            contract: "token".to_string(),
+            account_id: "token.staging.testnet".parse().unwrap(),
+            previous_code_hash: Default::default(),
+            code_hash: Default::default(),
+            transaction_hash: Default::default(),
+            initialized: migrations.is_empty(),
+            migrations: migrations.iter().map(ToString::to_string).collect(),
+            deployed_at: 0,
+        }
+    }
+
+    #[test]
+    fn first_deployment_marks_migrations_as_applied() {
+        let manifest: DeployManifest = toml::from_str(
+            r#"
+            [environments.staging]
+            network = "testnet"
+
+            [[contracts]]
+            name = "token"
+            init = { method = "new", args = { owner_id = "owner.testnet" } }
+            migrations = [{ name = "v2", method = "migrate_v2" }, { name = "v3", method = "migrate_v3" }]
+
+            [contracts.environments.staging]
+            account_id = "token.staging.testnet"
+            init_args = { owner_id = "staging.testnet" }
+            "#,
+        )
+        .unwrap();
+        let contract = &manifest.contracts[0];
+        let contract_environment = &contract.environments["staging"];
+
+        let (calls, migrations) =
+            function_calls(contract, contract_environment, &[], "testnet", true);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].method, "new");
+        assert_eq!(
+            calls[0].args,
+            Some(serde_json::json!({"owner_id": "staging.testnet"}))
+        );
+        assert_eq!(migrations, ["v2", "v3"]);
+
+        // The migrations recorded by the first deployment are not run by the next one.
+        let (calls, migrations) = function_calls(
+            contract,
+            contract_environment,
+            &[record(
+                &migrations.iter().map(String::as_str).collect::<Vec<_>>(),
+            )],
+            "testnet",
+            false,
+        );
+        assert!(calls.is_empty());
+        assert!(migrations.is_empty());
+
+        let (calls, migrations) = function_calls(
+            contract,
+            contract_environment,
+            &[record(&["v2"])],
+            "testnet",
+            false,
+        );
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].method, "migrate_v3");
+        assert_eq!(migrations, ["v3"]);
+
+        // The history of another network does not count.
+        let (_, migrations) = function_calls(
+            contract,
+            contract_environment,
+            &[record(&["v2"])],
+            "mainnet",
+            false,
+        );
+        assert_eq!(migrations, ["v2", "v3"]);
+    }
+}
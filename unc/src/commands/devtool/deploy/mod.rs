@@ -2,6 +2,8 @@ use crate::commands::contract::deploy::initialize_mode::InitializeMode;
 
 use crate::commands::devtool::build_command;
 
+pub mod manifest;
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = ContractContext)]
@@ -26,11 +28,14 @@ impl ContractContext {
         scope: &<Contract as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         let file_path = build_command::build::run(scope.build_command_args.clone())?.path;
+        let contract_account_id = scope
+            .contract_account_id
+            .resolve(&previous_context.config, None)?;
         Ok(Self(
             crate::commands::contract::deploy::ContractFileContext {
                 global_context: previous_context,
-                receiver_account_id: scope.contract_account_id.clone().into(),
-                signer_account_id: scope.contract_account_id.clone().into(),
+                receiver_account_id: contract_account_id.clone(),
+                signer_account_id: contract_account_id,
                 code: std::fs::read(file_path)?,
             },
         ))
@@ -66,10 +71,19 @@ impl interactive_clap::FromCli for Contract {
                     no_embed_abi: cli_build_command_args.no_embed_abi,
                     no_doc: cli_build_command_args.no_doc,
                     out_dir: cli_build_command_args.out_dir.clone(),
+                    workspace: cli_build_command_args.workspace,
+                    package: cli_build_command_args.package.clone(),
                     manifest_path: cli_build_command_args.manifest_path.clone(),
                     features: cli_build_command_args.features.clone(),
                     no_default_features: cli_build_command_args.no_default_features,
                     color: cli_build_command_args.color.clone(),
+                    no_optimize: cli_build_command_args.no_optimize,
+                    wasm_opt: cli_build_command_args.wasm_opt,
+                    no_floats: cli_build_command_args.no_floats,
+                    toolchain: cli_build_command_args.toolchain.clone(),
+                    reproducible: cli_build_command_args.reproducible,
+                    image: cli_build_command_args.image.clone(),
+                    container_runtime: cli_build_command_args.container_runtime.clone(),
                 }
             } else {
                 build_command::BuildCommand::default()
@@ -0,0 +1,503 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8PathBuf;
+use color_eyre::eyre::WrapErr;
+use colored::Colorize;
+use unc_abi::{AbiFunctionKind, AbiFunctionModifier};
+
+use super::abi_command::abi;
+use super::build_command::{build, post_build, BuildCommand};
+use crate::common::ColorPreference;
+use crate::types::{manifest::CargoManifestPath, metadata::CrateMetadata};
+use crate::util;
+
+mod source;
+
+const UNCHECKED_DEPOSIT: &str = "unchecked-deposit";
+const UNLISTED_STATE_CHANGE: &str = "unlisted-state-change";
+const PUBLIC_CALLBACK: &str = "public-callback";
+const UNBOUNDED_COLLECTION: &str = "unbounded-collection";
+const UNLISTED_EXPORT: &str = "unlisted-export";
+const RISKY_IMPORT: &str = "risky-import";
+const LINTS: &[&str] = &[
+    UNCHECKED_DEPOSIT,
+    UNLISTED_STATE_CHANGE,
+    PUBLIC_CALLBACK,
+    UNBOUNDED_COLLECTION,
+    UNLISTED_EXPORT,
+    RISKY_IMPORT,
+];
+
+/// Host functions that let the contract act on accounts beyond calling and paying them.
+const RISKY_IMPORTS: &[(&str, &str)] = &[
+    (
+        "promise_batch_action_deploy_contract",
+        "the contract can deploy code, make sure only the owner can upgrade it",
+    ),
+    (
+        "promise_batch_action_delete_account",
+        "the contract can delete accounts (including its own), make sure it is guarded",
+    ),
+    (
+        "promise_batch_action_add_key_with_full_access",
+        "the contract can add full access keys, which gives away the control of the account",
+    ),
+    (
+        "promise_batch_action_delete_key",
+        "the contract can delete access keys, which can lock the owner out of the account",
+    ),
+];
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = LintCommandContext)]
+pub struct LintCommand {
+    /// Path to the `Cargo.toml` of the contract to lint
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    manifest_path: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Set compile-time feature flags.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    features: Option<String>,
+    /// Disables default feature flags.
+    #[interactive_clap(long)]
+    no_default_features: bool,
+    /// Lints to skip (comma-separated), e.g. "unbounded-collection,risky-import"
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    allow: Option<crate::types::vec_string::VecString>,
+    /// Fail on warnings as well as on errors
+    #[interactive_clap(long)]
+    deny_warnings: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct LintCommandContext;
+
+impl LintCommandContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<LintCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let allowed_lints = scope
+            .allow
+            .as_ref()
+            .map_or_else(Vec::new, |allow| allow.0.clone());
+        if let Some(lint) = allowed_lints
+            .iter()
+            .find(|lint| !LINTS.contains(&lint.as_str()))
+        {
+            color_eyre::eyre::bail!("Unknown lint <{lint}> (known lints: {})", LINTS.join(", "));
+        }
+
+        let manifest_path: Utf8PathBuf = scope
+            .manifest_path
+            .clone()
+            .map_or_else(|| "Cargo.toml".into(), Into::into);
+        let crate_metadata = util::handle_step("Collecting cargo project metadata...", || {
+            CrateMetadata::collect(CargoManifestPath::try_from(manifest_path)?)
+        })?;
+        let project_dir = crate_metadata.manifest_path.directory()?.as_std_path();
+
+        let contract_source = util::handle_step("Parsing the contract source code...", || {
+            source::ContractSource::collect(project_dir)
+        })?;
+
+        let mut cargo_feature_args = vec![];
+        if let Some(features) = &scope.features {
+            cargo_feature_args.extend(["--features", features]);
+        }
+        if scope.no_default_features {
+            cargo_feature_args.push("--no-default-features");
+        }
+        let contract_abi = abi::generate_abi(
+            &crate_metadata,
+            false,
+            true,
+            &cargo_feature_args,
+            &[],
+            ColorPreference::Auto,
+        )?;
+
+        let artifact = build::run(BuildCommand {
+            no_abi: true,
+            manifest_path: scope.manifest_path.clone(),
+            features: scope.features.clone(),
+            no_default_features: scope.no_default_features,
+            ..Default::default()
+        })?;
+        let wasm = std::fs::read(&artifact.path)
+            .wrap_err_with(|| format!("Failed to read file: {}", artifact.path))?;
+        let wasm_interface = WasmInterface::parse(&wasm)?;
+
+        let findings = lint(&contract_source, &contract_abi, &wasm_interface)
+            .into_iter()
+            .filter(|finding| !allowed_lints.iter().any(|lint| lint == finding.lint))
+            .collect::<Vec<_>>();
+        if findings.is_empty() {
+            util::print_success("No problems found in the contract");
+            return Ok(Self);
+        }
+        for finding in &findings {
+            let severity = match finding.severity {
+                Severity::Error => "error".red().bold(),
+                Severity::Warning => "warning".yellow().bold(),
+            };
+            println!(
+                " - [{severity}] {}: {} ({})",
+                finding.location.bold(),
+                finding.message,
+                finding.lint
+            );
+        }
+        let errors = findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .count();
+        let warnings = findings.len() - errors;
+        eprintln!("\nSummary: {errors} error(s), {warnings} warning(s)");
+        if errors > 0 || (scope.deny_warnings && warnings > 0) {
+            color_eyre::eyre::bail!(
+                "The lint found {errors} error(s) and {warnings} warning(s) in the contract"
+            );
+        }
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub lint: &'static str,
+    pub location: String,
+    pub message: String,
+}
+
+/// The functions exported by the compiled contract and the host functions it imports.
+#[derive(Debug, Default)]
+struct WasmInterface {
+    exports: Vec<String>,
+    imports: Vec<String>,
+}
+
+impl WasmInterface {
+    fn parse(wasm: &[u8]) -> color_eyre::eyre::Result<Self> {
+        let mut interface = Self::default();
+        for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+            match payload.wrap_err("Failed to parse the wasm module")? {
+                wasmparser::Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        if import.module == post_build::ALLOWED_IMPORT_MODULE {
+                            interface.imports.push(import.name.to_string());
+                        }
+                    }
+                }
+                wasmparser::Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        if let wasmparser::ExternalKind::Func = export.kind {
+                            interface.exports.push(export.name.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(interface)
+    }
+}
+
+fn lint(
+    contract_source: &source::ContractSource,
+    contract_abi: &unc_abi::AbiRoot,
+    wasm_interface: &WasmInterface,
+) -> Vec<Finding> {
+    let mut findings = vec![];
+    let mut finding = |severity, lint, location: &str, message: &str| {
+        findings.push(Finding {
+            severity,
+            lint,
+            location: location.to_string(),
+            message: message.to_string(),
+        })
+    };
+
+    let abi_functions = contract_abi
+        .body
+        .functions
+        .iter()
+        .map(|function| (function.name.as_str(), function))
+        .collect::<BTreeMap<_, _>>();
+    for method in &contract_source.methods {
+        let Some(function) = abi_functions.get(method.name.as_str()) else {
+            if method.is_public && method.mutates_state {
+                finding(
+                    Severity::Warning,
+                    UNLISTED_STATE_CHANGE,
+                    &method.location,
+                    "the public method changes the state, but it is not in the ABI, so it cannot be called on chain \
+                    (move it to a `#[unc_bindgen]` impl or make it private)",
+                );
+            }
+            continue;
+        };
+        if function.modifiers.contains(&AbiFunctionModifier::Payable) && !method.checks_deposit {
+            finding(
+                Severity::Warning,
+                UNCHECKED_DEPOSIT,
+                &method.location,
+                "the `#[payable]` method never checks the attached deposit",
+            );
+        }
+        if function.modifiers.contains(&AbiFunctionModifier::Private) || method.checks_predecessor {
+            continue;
+        }
+        if !function.callbacks.is_empty()
+            || function.callbacks_vec.is_some()
+            || method.has_callback_params
+        {
+            finding(
+                Severity::Error,
+                PUBLIC_CALLBACK,
+                &method.location,
+                "the callback is exported without `#[private]`, so anyone can call it with forged promise results",
+            );
+        } else if function.kind == AbiFunctionKind::Call
+            && (method.name.starts_with("on_") || method.name.ends_with("_callback"))
+        {
+            finding(
+                Severity::Warning,
+                PUBLIC_CALLBACK,
+                &method.location,
+                "the method looks like a callback, but it is exported without `#[private]`",
+            );
+        }
+    }
+
+    for field in &contract_source.state_fields {
+        finding(
+            Severity::Warning,
+            UNBOUNDED_COLLECTION,
+            &field.location,
+            &format!(
+                "`{}` is read and written as a whole on every call, use a collection from `unc_sdk::store` instead",
+                field.collection
+            ),
+        );
+    }
+
+    for export in &wasm_interface.exports {
+        if !abi_functions.contains_key(export.as_str()) && export != post_build::CONTRACT_ABI_EXPORT
+        {
+            finding(
+                Severity::Warning,
+                UNLISTED_EXPORT,
+                &format!("export {export}"),
+                "the wasm exports the function, but it is missing from the ABI",
+            );
+        }
+    }
+
+    for import in &wasm_interface.imports {
+        if let Some((_, reason)) = RISKY_IMPORTS.iter().find(|(name, _)| name == import) {
+            finding(
+                Severity::Warning,
+                RISKY_IMPORT,
+                &format!("import {}.{import}", post_build::ALLOWED_IMPORT_MODULE),
+                reason,
+            );
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_contract() {
+        let mut contract_source = source::ContractSource::default();
+        contract_source
+            .add_file(
+                "src/lib.rs",
+                r#"
+                #[unc_bindgen]
+                #[derive(BorshDeserialize, BorshSerialize)]
+                pub struct Contract {
+                    owner_id: AccountId,
+                    donations: Vec<Donation>,
+                    balances: LookupMap<AccountId, UncToken>,
+                }
+
+                #[unc_bindgen]
+                impl Contract {
+                    #[payable]
+                    pub fn donate(&mut self) {
+                        self.donations.push(Donation::new(env::predecessor_account_id()));
+                    }
+
+                    #[payable]
+                    pub fn deposit(&mut self) {
+                        require!(env::attached_deposit() > UncToken::from_unc(1), "Deposit more");
+                    }
+
+                    pub fn on_transfer(&mut self, #[callback_result] result: Result<(), PromiseError>) {}
+
+                    #[private]
+                    pub fn on_refund(&mut self, #[callback_unwrap] refunded: bool) {}
+
+                    pub fn on_withdraw(&mut self) {}
+                }
+
+                impl Contract {
+                    pub fn reset(&mut self) {
+                        self.donations.clear();
+                    }
+
+                    fn internal_reset(&mut self) {}
+                }
+
+                #[cfg(test)]
+                mod tests {
+                    impl Contract {
+                        pub fn set_owner(&mut self) {}
+                    }
+                }
+                "#,
+            )
+            .unwrap();
+
+        let contract_abi: unc_abi::AbiRoot = serde_json::from_value(serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": {},
+            "body": {
+                "functions": [
+                    {"name": "donate", "kind": "call", "modifiers": ["payable"]},
+                    {"name": "deposit", "kind": "call", "modifiers": ["payable"]},
+                    {"name": "on_transfer", "kind": "call", "callbacks": [
+                        {"serialization_type": "json", "type_schema": {"type": "null"}}
+                    ]},
+                    {"name": "on_refund", "kind": "call", "modifiers": ["private"]},
+                    {"name": "on_withdraw", "kind": "call"}
+                ],
+                "root_schema": {"$schema": "http://json-schema.org/draft-07/schema#", "type": "string"},
+            },
+        }))
+        .unwrap();
+        let wasm_interface = WasmInterface {
+            exports: [
+                "donate",
+                "deposit",
+                "on_transfer",
+                "on_refund",
+                "on_withdraw",
+                "migrate",
+            ]
+            .map(String::from)
+            .to_vec(),
+            imports: ["attached_deposit", "promise_batch_action_deploy_contract"]
+                .map(String::from)
+                .to_vec(),
+        };
+
+        let findings = lint(&contract_source, &contract_abi, &wasm_interface)
+            .into_iter()
+            .map(|finding| (finding.severity, finding.lint, finding.location))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings,
+            [
+                (
+                    Severity::Warning,
+                    UNCHECKED_DEPOSIT,
+                    "src/lib.rs: Contract::donate".to_string()
+                ),
+                (
+                    Severity::Error,
+                    PUBLIC_CALLBACK,
+                    "src/lib.rs: Contract::on_transfer".to_string()
+                ),
+                (
+                    Severity::Warning,
+                    PUBLIC_CALLBACK,
+                    "src/lib.rs: Contract::on_withdraw".to_string()
+                ),
+                (
+                    Severity::Warning,
+                    UNLISTED_STATE_CHANGE,
+                    "src/lib.rs: Contract::reset".to_string()
+                ),
+                (
+                    Severity::Warning,
+                    UNBOUNDED_COLLECTION,
+                    "src/lib.rs: Contract.donations".to_string()
+                ),
+                (
+                    Severity::Warning,
+                    UNLISTED_EXPORT,
+                    "export migrate".to_string()
+                ),
+                (
+                    Severity::Warning,
+                    RISKY_IMPORT,
+                    "import env.promise_batch_action_deploy_contract".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn deposit_named_identifiers_do_not_check_the_deposit() {
+        let mut contract_source = source::ContractSource::default();
+        contract_source
+            .add_file(
+                "src/lib.rs",
+                r#"
+                #[unc_bindgen]
+                impl Contract {
+                    #[payable]
+                    pub fn store(&mut self, deposit_amount: U128) {
+                        let storage_deposit = self.storage_deposit_of(deposit_amount);
+                        self.deposits.insert(env::predecessor_account_id(), storage_deposit);
+                    }
+
+                    #[payable]
+                    pub fn store_checked(&mut self) {
+                        assert!(env::attached_deposit() >= self.storage_deposit, "Deposit more");
+                        self.deposits.insert(env::predecessor_account_id(), env::attached_deposit());
+                    }
+
+                    #[payable]
+                    pub fn withdraw(&mut self) {
+                        assert_one_atto();
+                        self.deposits.remove(&env::predecessor_account_id());
+                    }
+                }
+                "#,
+            )
+            .unwrap();
+        let checks_deposit = contract_source
+            .methods
+            .iter()
+            .map(|method| (method.name.as_str(), method.checks_deposit))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checks_deposit,
+            [
+                ("store", false),
+                ("store_checked", true),
+                ("withdraw", true)
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use color_eyre::eyre::WrapErr;
+use syn::visit::Visit;
+
+/// Collections of `std` that are (de)serialized as a whole with the contract state.
+const UNBOUNDED_COLLECTIONS: &[&str] = &[
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "BinaryHeap",
+    "HashMap",
+    "HashSet",
+    "BTreeMap",
+    "BTreeSet",
+];
+const CALLBACK_PARAM_ATTRIBUTES: &[&str] = &[
+    "callback",
+    "callback_unwrap",
+    "callback_result",
+    "callback_vec",
+];
+
+/// What the lints need to know about the contract source code.
+#[derive(Debug, Default)]
+pub(super) struct ContractSource {
+    pub methods: Vec<SourceMethod>,
+    pub state_fields: Vec<StateField>,
+}
+
+/// A method of an `impl` block of the contract (or of any `#[unc_bindgen]` impl block).
+#[derive(Debug)]
+pub(super) struct SourceMethod {
+    pub name: String,
+    /// For example, "src/lib.rs: Contract::set_status".
+    pub location: String,
+    pub is_public: bool,
+    /// Takes `&mut self`.
+    pub mutates_state: bool,
+    pub has_callback_params: bool,
+    /// Calls `env::attached_deposit()` or `assert_one_atto()` in the method body.
+    pub checks_deposit: bool,
+    /// Calls `assert_self()` in the method body, which is what `#[private]` does.
+    pub checks_predecessor: bool,
+}
+
+/// A field of the contract state with a collection type from `std`.
+#[derive(Debug)]
+pub(super) struct StateField {
+    /// For example, "src/lib.rs: Contract.accounts".
+    pub location: String,
+    pub collection: String,
+}
+
+impl ContractSource {
+    /// Parses all the Rust files in the `src` directory of the project.
+    pub fn collect(project_dir: &std::path::Path) -> color_eyre::eyre::Result<Self> {
+        let mut files = vec![];
+        collect_rust_files(&project_dir.join("src"), &mut files)?;
+        files.sort();
+        let mut source = Self::default();
+        for file in files {
+            let content = std::fs::read_to_string(&file)
+                .wrap_err_with(|| format!("Failed to read file: {file:?}"))?;
+            let relative_path = file.strip_prefix(project_dir).unwrap_or(&file);
+            source.add_file(&relative_path.display().to_string(), &content)?;
+        }
+        Ok(source)
+    }
+
+    pub fn add_file(&mut self, path: &str, content: &str) -> crate::CliResult {
+        let file = syn::parse_file(content)
+            .map_err(|err| color_eyre::eyre::eyre!("Failed to parse {path}: {err}"))?;
+        let mut visitor = FileVisitor {
+            path,
+            contract_types: vec![],
+            impls: vec![],
+            state_fields: vec![],
+        };
+        visitor.visit_file(&file);
+
+        for (self_ty, is_bindgen, is_trait_impl, methods) in visitor.impls {
+            if !is_bindgen && !visitor.contract_types.contains(&self_ty) {
+                continue;
+            }
+            for method in methods {
+                self.methods.push(SourceMethod {
+                    location: format!("{path}: {self_ty}::{}", method.sig.ident),
+                    name: method.sig.ident.to_string(),
+                    is_public: is_trait_impl || matches!(method.vis, syn::Visibility::Public(_)),
+                    mutates_state: method.sig.receiver().is_some_and(|receiver| {
+                        receiver.reference.is_some() && receiver.mutability.is_some()
+                    }),
+                    has_callback_params: method.sig.inputs.iter().any(|input| {
+                        matches!(input, syn::FnArg::Typed(arg) if arg.attrs.iter().any(|attr| {
+                            CALLBACK_PARAM_ATTRIBUTES.iter().any(|name| attr.path().is_ident(name))
+                        }))
+                    }),
+                    checks_deposit: calls(&method.block, &["attached_deposit", "assert_one_atto"]),
+                    checks_predecessor: calls(&method.block, &["assert_self"]),
+                });
+            }
+        }
+        self.state_fields.extend(visitor.state_fields);
+        Ok(())
+    }
+}
+
+fn collect_rust_files(
+    dir: &std::path::Path,
+    files: &mut Vec<std::path::PathBuf>,
+) -> crate::CliResult {
+    let entries =
+        std::fs::read_dir(dir).wrap_err_with(|| format!("Failed to read directory: {dir:?}"))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+struct FileVisitor<'a> {
+    path: &'a str,
+    contract_types: Vec<String>,
+    /// The self type, whether it is a `#[unc_bindgen]` impl, whether it is a trait impl and the methods.
+    impls: Vec<(String, bool, bool, Vec<syn::ImplItemFn>)>,
+    state_fields: Vec<StateField>,
+}
+
+impl<'ast> Visit<'ast> for FileVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &'ast syn::ItemMod) {
+        // Unit tests are not part of the contract.
+        if !item.attrs.iter().any(is_cfg_test) {
+            syn::visit::visit_item_mod(self, item);
+        }
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        if !item.attrs.iter().any(is_contract_state) {
+            return;
+        }
+        let struct_name = item.ident.to_string();
+        for field in &item.fields {
+            let (Some(field_name), Some(collection)) =
+                (&field.ident, unbounded_collection(&field.ty))
+            else {
+                continue;
+            };
+            self.state_fields.push(StateField {
+                location: format!("{}: {struct_name}.{field_name}", self.path),
+                collection,
+            });
+        }
+        self.contract_types.push(struct_name);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        let syn::Type::Path(self_ty) = &*item.self_ty else {
+            return;
+        };
+        let Some(self_ty) = self_ty.path.segments.last() else {
+            return;
+        };
+        let is_bindgen = item
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("unc_bindgen") || attr.path().is_ident("unc"));
+        let methods = item
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::ImplItem::Fn(method) => Some(method.clone()),
+                _ => None,
+            })
+            .collect();
+        self.impls.push((
+            self_ty.ident.to_string(),
+            is_bindgen,
+            item.trait_.is_some(),
+            methods,
+        ));
+    }
+}
+
+fn is_cfg_test(attr: &syn::Attribute) -> bool {
+    matches!(&attr.meta, syn::Meta::List(list) if list.path.is_ident("cfg") && list.tokens.to_string() == "test")
+}
+
+/// `#[unc_bindgen]` or `#[unc(contract_state)]`.
+fn is_contract_state(attr: &syn::Attribute) -> bool {
+    match &attr.meta {
+        syn::Meta::Path(path) => path.is_ident("unc_bindgen"),
+        syn::Meta::List(list) => {
+            list.path.is_ident("unc_bindgen")
+                || (list.path.is_ident("unc") && list.tokens.to_string().contains("contract_state"))
+        }
+        syn::Meta::NameValue(_) => false,
+    }
+}
+
+fn unbounded_collection(ty: &syn::Type) -> Option<String> {
+    let syn::Type::Path(ty) = ty else {
+        return None;
+    };
+    let collection = ty.path.segments.last()?.ident.to_string();
+    UNBOUNDED_COLLECTIONS
+        .contains(&collection.as_str())
+        .then_some(collection)
+}
+
+/// Whether the block calls one of the functions (e.g. `env::attached_deposit()`), including inside
+/// macro calls such as `require!` and `assert!`.
+fn calls(block: &syn::Block, function_names: &[&str]) -> bool {
+    struct CallFinder<'a> {
+        function_names: &'a [&'a str],
+        found: bool,
+    }
+
+    impl CallFinder<'_> {
+        fn is_function(&self, ident: &syn::Ident) -> bool {
+            self.function_names.iter().any(|name| ident == name)
+        }
+    }
+
+    impl<'ast> Visit<'ast> for CallFinder<'_> {
+        fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+            if let syn::Expr::Path(function) = &*call.func {
+                self.found |= function
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| self.is_function(&segment.ident));
+            }
+            syn::visit::visit_expr_call(self, call);
+        }
+
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            self.found |= self.is_function(&call.method);
+            syn::visit::visit_expr_method_call(self, call);
+        }
+
+        /// `syn` does not parse macro arguments, so the arguments of `require!`, `assert!` and
+        /// similar macros are parsed as expressions separated by commas.
+        fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+            syn::visit::visit_macro(self, mac);
+            if let Ok(args) = mac.parse_body_with(
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            ) {
+                for arg in &args {
+                    <Self as Visit<'_>>::visit_expr(self, arg);
+                }
+            }
+        }
+    }
+
+    let mut finder = CallFinder {
+        function_names,
+        found: false,
+    };
+    finder.visit_block(block);
+    finder.found
+}
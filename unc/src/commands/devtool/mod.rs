@@ -4,7 +4,10 @@ pub mod abi_command;
 pub mod build_command;
 pub mod create_dev_account;
 pub mod deploy;
+pub mod lint_command;
 pub mod new;
+pub mod sandbox;
+pub mod test_command;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = crate::GlobalContext)]
@@ -35,6 +38,16 @@ pub enum DevCommandsType {
     ))]
     /// Generates ABI for the contract
     Abi(self::abi_command::AbiCommand),
+    #[strum_discriminants(strum(
+        message = "abi-diff            -  Compare two ABIs and report breaking changes of the contract interface"
+    ))]
+    /// Compare two ABIs (files or deployed contracts) and report breaking changes of the contract interface
+    AbiDiff(self::abi_command::diff::AbiDiff),
+    #[strum_discriminants(strum(
+        message = "abi-codegen         -  Generate a typed Rust or TypeScript client from the ABI of the contract"
+    ))]
+    /// Generate a typed Rust or TypeScript client from the ABI of the contract
+    AbiCodegen(self::abi_command::codegen::AbiCodegen),
     #[strum_discriminants(strum(
         message = "create-dev-account  -  Create a development account using a faucet service sponsor to receive some unc tokens (testnet only).
                          To create an account on a different network, use UNC CLI [https://unc.cli.rs]"
@@ -45,4 +58,24 @@ pub enum DevCommandsType {
     #[strum_discriminants(strum(message = "deploy              -  Add a new contract code"))]
     /// Add a new contract code
     Deploy(self::deploy::Contract),
+    #[strum_discriminants(strum(
+        message = "deploy-env          -  Deploy the changed contracts of an environment from the deployment manifest"
+    ))]
+    /// Deploy the changed contracts of an environment from the deployment manifest
+    DeployEnv(self::deploy::manifest::DeployEnvironment),
+    #[strum_discriminants(strum(
+        message = "sandbox             -  Start, stop, reset or check the local sandbox node"
+    ))]
+    /// Start, stop, reset or check the local sandbox node
+    Sandbox(self::sandbox::SandboxCommands),
+    #[strum_discriminants(strum(
+        message = "test                -  Build the contract once and run its unit and integration tests with chain fixtures"
+    ))]
+    /// Build the contract once and run its unit and integration tests with chain fixtures
+    Test(self::test_command::TestCommand),
+    #[strum_discriminants(strum(
+        message = "lint                -  Check the contract source and wasm for common security and storage problems"
+    ))]
+    /// Check the contract source and wasm for common security and storage problems
+    Lint(self::lint_command::LintCommand),
 }
@@ -1,11 +1,38 @@
 use color_eyre::eyre::{ContextCompat, WrapErr};
 
+mod template;
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = NewContext)]
 pub struct New {
     /// Enter a new project name (path to the project) to create a contract:
     pub project_dir: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Built-in template name (hello, fungible-token, non-fungible-token, pledging-pool, factory, empty-lib), a local template directory or a git URL
+    template: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Project description for Cargo.toml
+    description: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Account that owns the contract after the initialization call (generates `unc-deploy.toml` to deploy the contract to it)
+    owner_account_id: Option<crate::types::account_id::AccountId>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Version of unc-sdk used by the project
+    sdk_version: Option<String>,
+    #[interactive_clap(long)]
+    /// Do not initialize a git repository in the project directory
+    no_git_init: bool,
+}
+
+impl New {
+    fn input_template(_context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        template::input_builtin_template()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -13,7 +40,7 @@ pub struct NewContext;
 
 impl NewContext {
     pub fn from_previous_context(
-        _previous_context: crate::GlobalContext,
+        previous_context: crate::GlobalContext,
         scope: &<New as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         let project_dir: &std::path::Path = scope.project_dir.as_ref();
@@ -31,25 +58,46 @@ impl NewContext {
             .to_str()
             .wrap_err("Project name has to be a valid UTF-8 string")?;
 
-        for new_project_file in NEW_PROJECT_FILES {
-            let new_file_path = project_dir.join(new_project_file.file_path);
+        let template_source: template::TemplateSource = scope
+            .template
+            .as_deref()
+            .unwrap_or(template::BuiltinTemplate::Hello.name())
+            .parse()?;
+        let placeholders = template::Placeholders {
+            project_name: project_name.to_string(),
+            description: scope
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("{project_name} smart contract")),
+            owner_account_id: scope
+                .owner_account_id
+                .as_ref()
+                .map(|owner_account_id| owner_account_id.resolve(&previous_context.config, None))
+                .transpose()?
+                .map(|owner_account_id| owner_account_id.to_string()),
+            sdk_version: scope
+                .sdk_version
+                .clone()
+                .unwrap_or_else(|| template::DEFAULT_SDK_VERSION.to_string()),
+        };
+
+        for new_project_file in template_source.files(&placeholders)? {
+            let new_file_path = project_dir.join(&new_project_file.file_path);
             std::fs::create_dir_all(new_file_path.parent().wrap_err_with(|| {
                 format!("Impossible to get parent for `{}`", new_file_path.display())
             })?)?;
-            std::fs::write(
-                &new_file_path,
-                new_project_file
-                    .content
-                    .replace("unc-new-project-name", project_name),
-            )
-            .wrap_err_with(|| format!("Failed to write to file: {}", new_file_path.display()))?;
+            std::fs::write(&new_file_path, new_project_file.content).wrap_err_with(|| {
+                format!("Failed to write to file: {}", new_file_path.display())
+            })?;
         }
 
-        std::process::Command::new("git")
-            .arg("init")
-            .current_dir(project_dir)
-            .output()
-            .wrap_err("Failed to execute process: `git init`")?;
+        if !scope.no_git_init {
+            std::process::Command::new("git")
+                .arg("init")
+                .current_dir(project_dir)
+                .output()
+                .wrap_err("Failed to execute process: `git init`")?;
+        }
 
         println!("New project is created at '{}'.\n", project_dir.display());
         println!("Now you can build, test, and deploy your project using unc:");
@@ -66,51 +114,3 @@ impl NewContext {
         Ok(Self)
     }
 }
-
-struct NewProjectFile {
-    file_path: &'static str,
-    content: &'static str,
-}
-
-const NEW_PROJECT_FILES: &[NewProjectFile] = &[
-    NewProjectFile {
-        file_path: ".github/workflows/deploy-production.yml",
-        content: include_str!("new-project-template/.github/workflows/deploy-production.yml"),
-    },
-    NewProjectFile {
-        file_path: ".github/workflows/deploy-dev.yml",
-        content: include_str!("new-project-template/.github/workflows/deploy-dev.yml"),
-    },
-    NewProjectFile {
-        file_path: ".github/workflows/test.yml",
-        content: include_str!("new-project-template/.github/workflows/test.yml"),
-    },
-    NewProjectFile {
-        file_path: ".github/workflows/undeploy-dev.yml",
-        content: include_str!("new-project-template/.github/workflows/undeploy-dev.yml"),
-    },
-    NewProjectFile {
-        file_path: "src/lib.rs",
-        content: include_str!("new-project-template/src/lib.rs"),
-    },
-    NewProjectFile {
-        file_path: "tests/test_basics.rs",
-        content: include_str!("new-project-template/tests/test_basics.rs"),
-    },
-    NewProjectFile {
-        file_path: ".gitignore",
-        content: include_str!("new-project-template/.gitignore"),
-    },
-    NewProjectFile {
-        file_path: "Cargo.toml",
-        content: include_str!("new-project-template/Cargo.toml.template"),
-    },
-    NewProjectFile {
-        file_path: "README.md",
-        content: include_str!("new-project-template/README.md"),
-    },
-    NewProjectFile {
-        file_path: "rust-toolchain.toml",
-        content: include_str!("new-project-template/rust-toolchain.toml"),
-    },
-];
@@ -0,0 +1,85 @@
+//! Helpers for the tests run by `unc dev-tool test`; they also work with a plain `cargo test`.
+#![allow(dead_code)]
+
+use std::io::Write;
+
+use utility_workspaces::network::Sandbox;
+use utility_workspaces::result::ExecutionFinalResult;
+use utility_workspaces::{Account, Worker};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Connects to the sandbox node of `unc dev-tool test --sandbox` (with the fixtures loaded),
+/// or starts a new sandbox node for the test.
+pub async fn sandbox() -> Result<Worker<Sandbox>> {
+    match (
+        std::env::var("UNC_SANDBOX_RPC_URL"),
+        std::env::var("UNC_SANDBOX_HOME"),
+    ) {
+        (Ok(rpc_url), Ok(home_dir)) => Ok(utility_workspaces::sandbox()
+            .rpc_addr(&rpc_url)
+            .home_dir(home_dir)
+            .await?),
+        _ => Ok(utility_workspaces::sandbox().await?),
+    }
+}
+
+/// The contract binary: `unc dev-tool test` builds it once and passes its path.
+pub async fn contract_wasm() -> Result<Vec<u8>> {
+    match std::env::var("UNC_CONTRACT_WASM") {
+        Ok(contract_wasm_path) => Ok(std::fs::read(contract_wasm_path)?),
+        Err(_) => Ok(utility_workspaces::compile_project("./").await?),
+    }
+}
+
+/// The accounts and contracts of the fixtures; `unc dev-tool test --sandbox` adds the key of the
+/// sandbox root account to each of them, so they sign with it.
+pub fn fixture_accounts(sandbox: &Worker<Sandbox>) -> Result<Vec<Account>> {
+    let (Ok(fixtures_path), Ok(home_dir)) = (
+        std::env::var("UNC_TEST_FIXTURES"),
+        std::env::var("UNC_SANDBOX_HOME"),
+    ) else {
+        return Ok(vec![]);
+    };
+    let fixtures: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(fixtures_path)?)?;
+    let validator_key: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        std::path::Path::new(&home_dir).join("validator_key.json"),
+    )?)?;
+    let secret_key: utility_workspaces::types::SecretKey = validator_key["secret_key"]
+        .as_str()
+        .ok_or("The validator key has no secret key")?
+        .parse()?;
+    ["accounts", "contracts"]
+        .iter()
+        .flat_map(|section| fixtures[section].as_array().into_iter().flatten())
+        .map(|fixture| {
+            let account_id = fixture["account_id"]
+                .as_str()
+                .ok_or("The fixture has no account_id")?
+                .parse()?;
+            Ok(Account::from_secret_key(
+                account_id,
+                secret_key.clone(),
+                sandbox,
+            ))
+        })
+        .collect()
+}
+
+/// Appends the gas burnt by the transaction to the gas report that `unc dev-tool test` prints.
+pub fn record_gas(test: &str, outcome: &ExecutionFinalResult) -> Result<()> {
+    let Ok(gas_report_path) = std::env::var("UNC_TEST_GAS_REPORT") else {
+        return Ok(());
+    };
+    let mut gas_report = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(gas_report_path)?;
+    writeln!(
+        gas_report,
+        "{}",
+        serde_json::json!({"test": test, "gas_burnt": outcome.total_gas_burnt.as_gas()})
+    )?;
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use serde_json::json;
 
+mod common;
+
 #[tokio::test]
-async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>> {
-    let sandbox = utility_workspaces::sandbox().await?;
-    let contract_wasm = utility_workspaces::compile_project("./").await?;
+async fn test_contract_is_operational() -> common::Result<()> {
+    let sandbox = common::sandbox().await?;
+    let contract_wasm = common::contract_wasm().await?;
 
     let contract = sandbox.dev_deploy(&contract_wasm).await?;
 
@@ -14,6 +16,7 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         .args_json(json!({"greeting": "Hello World!"}))
         .transact()
         .await?;
+    common::record_gas("test_contract_is_operational", &outcome)?;
     assert!(outcome.is_success());
 
     let user_message_outcome = contract
@@ -0,0 +1,567 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+pub const DEFAULT_SDK_VERSION: &str = "2.0.3";
+
+#[derive(Debug, Clone, Copy, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum BuiltinTemplate {
+    #[strum_discriminants(strum(
+        message = "hello               - A contract that stores and returns a greeting"
+    ))]
+    Hello,
+    #[strum_discriminants(strum(
+        message = "fungible-token      - A fungible token (NEP-141) with storage management"
+    ))]
+    FungibleToken,
+    #[strum_discriminants(strum(
+        message = "non-fungible-token  - A non-fungible token (NEP-171) with enumeration and metadata"
+    ))]
+    NonFungibleToken,
+    #[strum_discriminants(strum(
+        message = "pledging-pool       - A pool that accepts pledges and releases them after a lock period"
+    ))]
+    PledgingPool,
+    #[strum_discriminants(strum(
+        message = "factory             - A contract that deploys its stored code to new sub-accounts"
+    ))]
+    Factory,
+    #[strum_discriminants(strum(message = "empty-lib           - An empty contract library"))]
+    EmptyLib,
+}
+
+impl BuiltinTemplate {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hello => "hello",
+            Self::FungibleToken => "fungible-token",
+            Self::NonFungibleToken => "non-fungible-token",
+            Self::PledgingPool => "pledging-pool",
+            Self::Factory => "factory",
+            Self::EmptyLib => "empty-lib",
+        }
+    }
+
+    fn all() -> [Self; 6] {
+        [
+            Self::Hello,
+            Self::FungibleToken,
+            Self::NonFungibleToken,
+            Self::PledgingPool,
+            Self::Factory,
+            Self::EmptyLib,
+        ]
+    }
+
+    /// Extra `[dependencies]` of the template (the SDK version placeholder is substituted later).
+    fn dependencies(&self) -> &'static str {
+        match self {
+            Self::FungibleToken | Self::NonFungibleToken => {
+                "unc-contract-standards = \"unc-sdk-version\"\n"
+            }
+            Self::Hello | Self::PledgingPool | Self::Factory | Self::EmptyLib => "",
+        }
+    }
+
+    /// The initialization method of the contract and its arguments.
+    fn init_function(&self) -> Option<(&'static str, serde_json::Value)> {
+        match self {
+            Self::Hello | Self::EmptyLib => None,
+            Self::FungibleToken => Some((
+                "new_default_meta",
+                serde_json::json!({ "owner_id": "unc-owner-account-id", "total_supply": "1000000000" }),
+            )),
+            Self::NonFungibleToken => Some((
+                "new_default_meta",
+                serde_json::json!({ "owner_id": "unc-owner-account-id" }),
+            )),
+            Self::PledgingPool => Some((
+                "new",
+                serde_json::json!({ "owner_id": "unc-owner-account-id", "reward_fee_bps": 500 }),
+            )),
+            Self::Factory => Some((
+                "new",
+                serde_json::json!({ "owner_id": "unc-owner-account-id" }),
+            )),
+        }
+    }
+
+    /// The `dev-tool deploy` initialization arguments shown in the README.
+    fn init_call(&self) -> String {
+        match self.init_function() {
+            Some((method, args)) => format!("with-init-call {method} json-args '{args}'"),
+            None => "without-init-call".to_string(),
+        }
+    }
+
+    /// The `unc-deploy.toml` which deploys the contract to the owner account and initializes it.
+    fn deploy_manifest(&self) -> color_eyre::eyre::Result<TemplateFile> {
+        let mut manifest = "[environments.dev]\nnetwork = \"testnet\"\n\n[[contracts]]\nname = \"unc-new-project-name\"\n".to_string();
+        if let Some((method, args)) = self.init_function() {
+            manifest.push_str(&format!(
+                "init = {{ method = \"{method}\", args = {} }}\n",
+                toml::Value::try_from(args)?
+            ));
+        }
+        manifest
+            .push_str("\n[contracts.environments.dev]\naccount_id = \"unc-owner-account-id\"\n");
+        Ok(TemplateFile {
+            file_path: super::super::deploy::manifest::DEFAULT_DEPLOY_MANIFEST_FILE_NAME.into(),
+            content: manifest.into_bytes(),
+        })
+    }
+
+    fn files(&self) -> Vec<TemplateFile> {
+        let specific_files: &[NewProjectFile] = match self {
+            Self::Hello => HELLO_FILES,
+            Self::FungibleToken => FUNGIBLE_TOKEN_FILES,
+            Self::NonFungibleToken => NON_FUNGIBLE_TOKEN_FILES,
+            Self::PledgingPool => PLEDGING_POOL_FILES,
+            Self::Factory => FACTORY_FILES,
+            Self::EmptyLib => EMPTY_LIB_FILES,
+        };
+        COMMON_FILES
+            .iter()
+            .chain(specific_files)
+            .map(|file| TemplateFile {
+                file_path: file.file_path.into(),
+                content: file
+                    .content
+                    .replace("unc-template-dependencies\n", self.dependencies())
+                    .replace("unc-template-init-call", &self.init_call())
+                    .into_bytes(),
+            })
+            .collect()
+    }
+}
+
+impl From<BuiltinTemplateDiscriminants> for BuiltinTemplate {
+    fn from(item: BuiltinTemplateDiscriminants) -> Self {
+        Self::all()
+            .into_iter()
+            .find(|template| BuiltinTemplateDiscriminants::from(template) == item)
+            .expect("Every discriminant has a template")
+    }
+}
+
+impl std::fmt::Display for BuiltinTemplateDiscriminants {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.get_message().unwrap_or_default())
+    }
+}
+
+/// Where the files of a new project come from.
+#[derive(Debug, Clone)]
+pub enum TemplateSource {
+    Builtin(BuiltinTemplate),
+    LocalDirectory(std::path::PathBuf),
+    GitRepository(String),
+}
+
+impl std::str::FromStr for TemplateSource {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(template) = BuiltinTemplate::all()
+            .into_iter()
+            .find(|template| template.name() == s)
+        {
+            return Ok(Self::Builtin(template));
+        }
+        if s.contains("://") || s.starts_with("git@") || s.ends_with(".git") {
+            return Ok(Self::GitRepository(s.to_string()));
+        }
+        let path = std::path::PathBuf::from(s);
+        if path.is_dir() {
+            return Ok(Self::LocalDirectory(path));
+        }
+        color_eyre::eyre::bail!(
+            "Unknown template <{s}>. Use one of the built-in templates ({}), a local template directory or a git URL",
+            BuiltinTemplate::all()
+                .iter()
+                .map(BuiltinTemplate::name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Values substituted for the placeholders in the template files.
+pub struct Placeholders {
+    pub project_name: String,
+    pub description: String,
+    /// The deployment manifest is generated only when the owner is known.
+    pub owner_account_id: Option<String>,
+    pub sdk_version: String,
+}
+
+impl Placeholders {
+    fn apply(&self, content: &str) -> String {
+        content
+            .replace("unc-new-project-name", &self.project_name)
+            .replace("unc-new-project-description", &self.description)
+            .replace(
+                "unc-owner-account-id",
+                self.owner_account_id
+                    .as_deref()
+                    .unwrap_or("<owner-account-id>"),
+            )
+            .replace("unc-sdk-version", &self.sdk_version)
+            // Custom templates may reuse the built-in skeleton without these sections.
+            .replace("unc-template-dependencies\n", "")
+            .replace("unc-template-init-call", "without-init-call")
+    }
+}
+
+pub struct TemplateFile {
+    pub file_path: std::path::PathBuf,
+    pub content: Vec<u8>,
+}
+
+impl TemplateSource {
+    /// Collects the template files with the placeholders substituted (in UTF-8 files only).
+    pub fn files(
+        &self,
+        placeholders: &Placeholders,
+    ) -> color_eyre::eyre::Result<Vec<TemplateFile>> {
+        let files = match self {
+            Self::Builtin(template) => {
+                let mut files = template.files();
+                if placeholders.owner_account_id.is_some() {
+                    files.push(template.deploy_manifest()?);
+                }
+                files
+            }
+            Self::LocalDirectory(path) => read_template_directory(path)?,
+            Self::GitRepository(url) => {
+                let checkout_dir = std::env::temp_dir().join(format!(
+                    "unc-new-template-{}-{}",
+                    std::process::id(),
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)?
+                        .as_nanos()
+                ));
+                let output = std::process::Command::new("git")
+                    .args(["clone", "--quiet", "--depth", "1", url])
+                    .arg(&checkout_dir)
+                    .output()
+                    .wrap_err("Failed to execute process: `git clone`")?;
+                let files = if output.status.success() {
+                    read_template_directory(&checkout_dir)
+                } else {
+                    Err(color_eyre::eyre::eyre!(
+                        "Failed to clone the template repository <{url}>:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ))
+                };
+                let _ = std::fs::remove_dir_all(&checkout_dir);
+                files?
+            }
+        };
+        Ok(files
+            .into_iter()
+            .map(|file| TemplateFile {
+                content: match String::from_utf8(file.content) {
+                    Ok(content) => placeholders.apply(&content).into_bytes(),
+                    Err(err) => err.into_bytes(),
+                },
+                ..file
+            })
+            .collect())
+    }
+}
+
+/// Reads all files of a template directory except `.git` and `target`;
+/// the `.template` suffix is dropped from the file names (e.g. `Cargo.toml.template`).
+fn read_template_directory(root: &std::path::Path) -> color_eyre::eyre::Result<Vec<TemplateFile>> {
+    let mut files = vec![];
+    let mut directories = vec![root.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in std::fs::read_dir(&directory)
+            .wrap_err_with(|| format!("Failed to read directory: {}", directory.display()))?
+        {
+            let path = entry?.path();
+            let file_name = path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .wrap_err_with(|| format!("Unsupported file name: {}", path.display()))?;
+            if path.is_dir() {
+                if file_name != ".git" && file_name != "target" {
+                    directories.push(path);
+                }
+                continue;
+            }
+            let relative_path = path.strip_prefix(root)?;
+            files.push(TemplateFile {
+                file_path: match file_name.strip_suffix(".template") {
+                    Some(file_name) => relative_path.with_file_name(file_name),
+                    None => relative_path.to_path_buf(),
+                },
+                content: std::fs::read(&path)
+                    .wrap_err_with(|| format!("Failed to read file: {}", path.display()))?,
+            });
+        }
+    }
+    if files.is_empty() {
+        color_eyre::eyre::bail!("The template <{}> has no files", root.display());
+    }
+    Ok(files)
+}
+
+pub fn input_builtin_template() -> color_eyre::eyre::Result<Option<String>> {
+    const CUSTOM_TEMPLATE: &str =
+        "custom              - A local template directory or a git repository URL";
+    let mut options = BuiltinTemplateDiscriminants::iter()
+        .map(|template| template.to_string())
+        .collect::<Vec<_>>();
+    options.push(CUSTOM_TEMPLATE.to_string());
+    let selection = inquire::Select::new("Select a project template:", options).prompt()?;
+    if selection == CUSTOM_TEMPLATE {
+        return Ok(Some(
+            inquire::Text::new("Enter a path to the template directory or a git URL:").prompt()?,
+        ));
+    }
+    let template = BuiltinTemplateDiscriminants::iter()
+        .find(|template| template.to_string() == selection)
+        .map(BuiltinTemplate::from)
+        .wrap_err("Unexpected template selection")?;
+    Ok(Some(template.name().to_string()))
+}
+
+struct NewProjectFile {
+    file_path: &'static str,
+    content: &'static str,
+}
+
+const COMMON_FILES: &[NewProjectFile] = &[
+    NewProjectFile {
+        file_path: ".github/workflows/deploy-production.yml",
+        content: include_str!("new-project-template/.github/workflows/deploy-production.yml"),
+    },
+    NewProjectFile {
+        file_path: ".github/workflows/deploy-dev.yml",
+        content: include_str!("new-project-template/.github/workflows/deploy-dev.yml"),
+    },
+    NewProjectFile {
+        file_path: ".github/workflows/test.yml",
+        content: include_str!("new-project-template/.github/workflows/test.yml"),
+    },
+    NewProjectFile {
+        file_path: ".github/workflows/undeploy-dev.yml",
+        content: include_str!("new-project-template/.github/workflows/undeploy-dev.yml"),
+    },
+    NewProjectFile {
+        file_path: ".gitignore",
+        content: include_str!("new-project-template/.gitignore"),
+    },
+    NewProjectFile {
+        file_path: "Cargo.toml",
+        content: include_str!("new-project-template/Cargo.toml.template"),
+    },
+    NewProjectFile {
+        file_path: "README.md",
+        content: include_str!("new-project-template/README.md"),
+    },
+    NewProjectFile {
+        file_path: "rust-toolchain.toml",
+        content: include_str!("new-project-template/rust-toolchain.toml"),
+    },
+    NewProjectFile {
+        file_path: "tests/common/mod.rs",
+        content: include_str!("new-project-template/tests/common/mod.rs"),
+    },
+];
+
+const HELLO_FILES: &[NewProjectFile] = &[
+    NewProjectFile {
+        file_path: "src/lib.rs",
+        content: include_str!("new-project-template/src/lib.rs"),
+    },
+    NewProjectFile {
+        file_path: "tests/test_basics.rs",
+        content: include_str!("new-project-template/tests/test_basics.rs"),
+    },
+];
+
+const FUNGIBLE_TOKEN_FILES: &[NewProjectFile] = &[
+    NewProjectFile {
+        file_path: "src/lib.rs",
+        content: include_str!("templates/fungible-token/src/lib.rs"),
+    },
+    NewProjectFile {
+        file_path: "tests/test_basics.rs",
+        content: include_str!("templates/fungible-token/tests/test_basics.rs"),
+    },
+];
+
+const NON_FUNGIBLE_TOKEN_FILES: &[NewProjectFile] = &[
+    NewProjectFile {
+        file_path: "src/lib.rs",
+        content: include_str!("templates/non-fungible-token/src/lib.rs"),
+    },
+    NewProjectFile {
+        file_path: "tests/test_basics.rs",
+        content: include_str!("templates/non-fungible-token/tests/test_basics.rs"),
+    },
+];
+
+const PLEDGING_POOL_FILES: &[NewProjectFile] = &[
+    NewProjectFile {
+        file_path: "src/lib.rs",
+        content: include_str!("templates/pledging-pool/src/lib.rs"),
+    },
+    NewProjectFile {
+        file_path: "tests/test_basics.rs",
+        content: include_str!("templates/pledging-pool/tests/test_basics.rs"),
+    },
+];
+
+const FACTORY_FILES: &[NewProjectFile] = &[
+    NewProjectFile {
+        file_path: "src/lib.rs",
+        content: include_str!("templates/factory/src/lib.rs"),
+    },
+    NewProjectFile {
+        file_path: "tests/test_basics.rs",
+        content: include_str!("templates/factory/tests/test_basics.rs"),
+    },
+];
+
+const EMPTY_LIB_FILES: &[NewProjectFile] = &[NewProjectFile {
+    file_path: "src/lib.rs",
+    content: include_str!("templates/empty-lib/src/lib.rs"),
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(owner_account_id: Option<&str>) -> Placeholders {
+        Placeholders {
+            project_name: "my-token".to_string(),
+            description: "My token".to_string(),
+            owner_account_id: owner_account_id.map(str::to_string),
+            sdk_version: "2.0.3".to_string(),
+        }
+    }
+
+    fn file<'a>(files: &'a [TemplateFile], file_path: &str) -> Option<&'a str> {
+        files
+            .iter()
+            .find(|file| file.file_path == std::path::Path::new(file_path))
+            .map(|file| std::str::from_utf8(&file.content).unwrap())
+    }
+
+    #[test]
+    fn template_source_from_str() {
+        assert!(matches!(
+            "fungible-token".parse::<TemplateSource>().unwrap(),
+            TemplateSource::Builtin(BuiltinTemplate::FungibleToken)
+        ));
+        for url in [
+            "https://github.com/utnet-org/template",
+            "git@github.com:utnet-org/template",
+            "template.git",
+        ] {
+            assert!(
+                matches!(url.parse::<TemplateSource>().unwrap(), TemplateSource::GitRepository(parsed) if parsed == url)
+            );
+        }
+        let directory = std::env::temp_dir();
+        assert!(matches!(
+            directory.to_str().unwrap().parse::<TemplateSource>().unwrap(),
+            TemplateSource::LocalDirectory(path) if path == directory
+        ));
+        let err = "no-such-template".parse::<TemplateSource>().unwrap_err();
+        assert!(err.to_string().contains("fungible-token"));
+    }
+
+    #[test]
+    fn read_template_directory_skips_build_dirs_and_strips_suffix() {
+        let root =
+            std::env::temp_dir().join(format!("unc-new-template-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join(".git/HEAD"), "ref").unwrap();
+        std::fs::write(root.join("target/out.wasm"), "wasm").unwrap();
+        std::fs::write(
+            root.join("Cargo.toml.template"),
+            "name = \"unc-new-project-name\"",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/lib.rs"), "// unc-owner-account-id").unwrap();
+
+        let files = read_template_directory(&root);
+        let empty_dir = root.join("target");
+        std::fs::remove_file(empty_dir.join("out.wasm")).unwrap();
+        let empty = read_template_directory(&empty_dir);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let mut paths = files
+            .unwrap()
+            .into_iter()
+            .map(|file| file.file_path)
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                std::path::PathBuf::from("Cargo.toml"),
+                std::path::PathBuf::from("src/lib.rs")
+            ]
+        );
+        assert!(empty.is_err());
+    }
+
+    #[test]
+    fn placeholders_are_substituted() {
+        let content = "unc-new-project-name unc-new-project-description unc-owner-account-id unc-sdk-version\nunc-template-dependencies\nunc-template-init-call";
+        assert_eq!(
+            placeholders(Some("alice.testnet")).apply(content),
+            "my-token My token alice.testnet 2.0.3\nwithout-init-call"
+        );
+        assert_eq!(
+            placeholders(None).apply("unc-owner-account-id"),
+            "<owner-account-id>"
+        );
+    }
+
+    #[test]
+    fn owner_account_id_generates_deploy_manifest() {
+        let source = TemplateSource::Builtin(BuiltinTemplate::FungibleToken);
+        let files = source.files(&placeholders(None)).unwrap();
+        assert!(file(&files, "unc-deploy.toml").is_none());
+        assert!(file(&files, "README.md")
+            .unwrap()
+            .contains(r#""owner_id":"<owner-account-id>""#));
+
+        let files = source.files(&placeholders(Some("alice.testnet"))).unwrap();
+        let manifest: crate::commands::devtool::deploy::manifest::DeployManifest =
+            toml::from_str(file(&files, "unc-deploy.toml").unwrap()).unwrap();
+        assert_eq!(manifest.environments["dev"].network, "testnet");
+        let contract = &manifest.contracts[0];
+        assert_eq!(contract.name, "my-token");
+        assert_eq!(
+            contract.environments["dev"].account_id.as_str(),
+            "alice.testnet"
+        );
+        let init = contract.init.as_ref().unwrap();
+        assert_eq!(init.method, "new_default_meta");
+        assert_eq!(
+            init.args.as_ref().unwrap()["owner_id"],
+            serde_json::json!("alice.testnet")
+        );
+        assert!(file(&files, "README.md")
+            .unwrap()
+            .contains(r#""owner_id":"alice.testnet""#));
+
+        let files = TemplateSource::Builtin(BuiltinTemplate::EmptyLib)
+            .files(&placeholders(Some("alice.testnet")))
+            .unwrap();
+        let manifest: crate::commands::devtool::deploy::manifest::DeployManifest =
+            toml::from_str(file(&files, "unc-deploy.toml").unwrap()).unwrap();
+        assert!(manifest.contracts[0].init.is_none());
+    }
+}
@@ -0,0 +1,11 @@
+// Find all our documentation at https://docs.unc.org
+use unc_sdk::unc;
+
+// Define the contract structure
+#[unc(contract_state)]
+#[derive(Default)]
+pub struct Contract {}
+
+// Implement the contract structure
+#[unc]
+impl Contract {}
@@ -0,0 +1,117 @@
+// Find all our documentation at https://docs.unc.org
+use unc_sdk::{
+    env, log, require, unc, AccountId, Gas, PanicOnDefault, Promise, PromiseError, UncToken,
+};
+
+/// The deposit required to cover the storage of the deployed code (10^19 attoUNC per byte).
+const STORAGE_COST_PER_BYTE: u128 = 10_000_000_000_000_000_000;
+const INIT_CALL_GAS: Gas = Gas::from_tgas(50);
+const CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+// Define the contract structure
+#[unc(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct Contract {
+    owner_id: AccountId,
+    /// The code deployed to every new sub-account.
+    code: Vec<u8>,
+}
+
+// Implement the contract structure
+#[unc]
+impl Contract {
+    #[init]
+    pub fn new(owner_id: AccountId) -> Self {
+        Self {
+            owner_id,
+            code: vec![],
+        }
+    }
+
+    /// Replaces the code deployed by the factory; the raw wasm is passed as the function input.
+    pub fn set_code(&mut self) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the owner can update the code"
+        );
+        self.code = env::input().expect("The code has to be passed as the input");
+        log!("The factory code was updated ({} bytes)", self.code.len());
+    }
+
+    /// Creates `<name>.<factory>`, deploys the stored code to it and calls `new` with `init_args` (JSON).
+    #[payable]
+    pub fn create(&mut self, name: String, init_args: Option<String>) -> Promise {
+        require!(!self.code.is_empty(), "The factory code is not set");
+        let account_id: AccountId = format!("{}.{}", name, env::current_account_id())
+            .parse()
+            .unwrap_or_else(|_| env::panic_str("Invalid sub-account name"));
+
+        let attached_deposit = env::attached_deposit();
+        let required_deposit =
+            UncToken::from_attounc(self.code.len() as u128 * STORAGE_COST_PER_BYTE);
+        require!(
+            attached_deposit >= required_deposit,
+            format!("Attach at least {required_deposit} to cover the code storage")
+        );
+
+        let mut promise = Promise::new(account_id.clone())
+            .create_account()
+            .transfer(attached_deposit)
+            .deploy_contract(self.code.clone());
+        if let Some(init_args) = init_args {
+            promise = promise.function_call(
+                "new".to_string(),
+                init_args.into_bytes(),
+                UncToken::from_attounc(0),
+                INIT_CALL_GAS,
+            );
+        }
+        promise.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(CALLBACK_GAS)
+                .on_create(account_id, env::predecessor_account_id(), attached_deposit),
+        )
+    }
+
+    /// Refunds the deposit if the sub-account could not be created.
+    #[private]
+    pub fn on_create(
+        &mut self,
+        account_id: AccountId,
+        predecessor_account_id: AccountId,
+        attached_deposit: UncToken,
+        #[callback_result] create_result: Result<(), PromiseError>,
+    ) -> bool {
+        if create_result.is_ok() {
+            log!("Created @{}", account_id);
+            true
+        } else {
+            log!("Failed to create @{}, refunding the deposit", account_id);
+            Promise::new(predecessor_account_id).transfer(attached_deposit);
+            false
+        }
+    }
+
+    pub fn get_code_size(&self) -> u64 {
+        self.code.len() as u64
+    }
+}
+
+/*
+ * The rest of this file holds the inline tests for the code above
+ * Learn more about Rust tests: https://doc.rust-lang.org/book/ch11-01-writing-tests.html
+ */
+#[cfg(test)]
+mod tests {
+    use unc_sdk::test_utils::{accounts, VMContextBuilder};
+    use unc_sdk::testing_env;
+
+    use super::*;
+
+    #[test]
+    fn new_factory_has_no_code() {
+        testing_env!(VMContextBuilder::new().build());
+        let contract = Contract::new(accounts(0));
+        assert_eq!(contract.get_code_size(), 0);
+    }
+}
@@ -0,0 +1,33 @@
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_factory_requires_code() -> common::Result<()> {
+    let sandbox = common::sandbox().await?;
+    let contract_wasm = common::contract_wasm().await?;
+
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+
+    let outcome = contract
+        .call("new")
+        .args_json(json!({"owner_id": contract.id()}))
+        .transact()
+        .await?;
+    common::record_gas("test_factory_requires_code", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = contract
+        .call("create")
+        .args_json(json!({"name": "child"}))
+        .deposit(utility_workspaces::types::UncToken::from_unc(1))
+        .transact()
+        .await?;
+    common::record_gas("test_factory_requires_code", &outcome)?;
+    assert!(outcome.is_failure());
+
+    let code_size = contract.view("get_code_size").await?;
+    assert_eq!(code_size.json::<u64>()?, 0);
+
+    Ok(())
+}
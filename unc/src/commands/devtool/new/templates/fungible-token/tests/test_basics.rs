@@ -0,0 +1,47 @@
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_transfer_between_registered_accounts() -> common::Result<()> {
+    let sandbox = common::sandbox().await?;
+    let contract_wasm = common::contract_wasm().await?;
+
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let owner_account = sandbox.dev_create_account().await?;
+    let user_account = sandbox.dev_create_account().await?;
+
+    let outcome = contract
+        .call("new_default_meta")
+        .args_json(json!({"owner_id": owner_account.id(), "total_supply": "1000"}))
+        .transact()
+        .await?;
+    common::record_gas("test_transfer_between_registered_accounts", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = user_account
+        .call(contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(utility_workspaces::types::UncToken::from_milliunc(125))
+        .transact()
+        .await?;
+    common::record_gas("test_transfer_between_registered_accounts", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = owner_account
+        .call(contract.id(), "ft_transfer")
+        .args_json(json!({"receiver_id": user_account.id(), "amount": "100"}))
+        .deposit(utility_workspaces::types::UncToken::from_attounc(1))
+        .transact()
+        .await?;
+    common::record_gas("test_transfer_between_registered_accounts", &outcome)?;
+    assert!(outcome.is_success());
+
+    let user_balance = contract
+        .view("ft_balance_of")
+        .args_json(json!({"account_id": user_account.id()}))
+        .await?;
+    assert_eq!(user_balance.json::<String>()?, "100");
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// Find all our documentation at https://docs.unc.org
+use std::collections::HashMap;
+
+use unc_contract_standards::non_fungible_token::core::{
+    NonFungibleTokenCore, NonFungibleTokenResolver,
+};
+use unc_contract_standards::non_fungible_token::enumeration::NonFungibleTokenEnumeration;
+use unc_contract_standards::non_fungible_token::metadata::{
+    NFTContractMetadata, NonFungibleTokenMetadataProvider, TokenMetadata, NFT_METADATA_SPEC,
+};
+use unc_contract_standards::non_fungible_token::{NonFungibleToken, Token, TokenId};
+use unc_sdk::borsh::BorshSerialize;
+use unc_sdk::collections::LazyOption;
+use unc_sdk::json_types::U128;
+use unc_sdk::{env, require, unc, AccountId, BorshStorageKey, PanicOnDefault, PromiseOrValue};
+
+// Define the contract structure
+#[unc(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct Contract {
+    tokens: NonFungibleToken,
+    metadata: LazyOption<NFTContractMetadata>,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "unc_sdk::borsh")]
+enum StorageKey {
+    NonFungibleToken,
+    Metadata,
+    TokenMetadata,
+    Enumeration,
+}
+
+// Implement the contract structure
+#[unc]
+impl Contract {
+    /// Initializes the contract owned by `owner_id` with the given metadata.
+    #[init]
+    pub fn new(owner_id: AccountId, metadata: NFTContractMetadata) -> Self {
+        require!(!env::state_exists(), "Already initialized");
+        metadata.assert_valid();
+        Self {
+            tokens: NonFungibleToken::new(
+                StorageKey::NonFungibleToken,
+                owner_id,
+                Some(StorageKey::TokenMetadata),
+                Some(StorageKey::Enumeration),
+                None::<StorageKey>,
+            ),
+            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+        }
+    }
+
+    /// Initializes the contract with the default metadata of "unc-new-project-name".
+    #[init]
+    pub fn new_default_meta(owner_id: AccountId) -> Self {
+        Self::new(
+            owner_id,
+            NFTContractMetadata {
+                spec: NFT_METADATA_SPEC.to_string(),
+                name: "unc-new-project-name".to_string(),
+                symbol: "NFT".to_string(),
+                icon: None,
+                base_uri: None,
+                reference: None,
+                reference_hash: None,
+            },
+        )
+    }
+
+    /// Mints a new token; only the contract owner can mint and the caller pays for the storage.
+    #[payable]
+    pub fn nft_mint(
+        &mut self,
+        token_id: TokenId,
+        token_owner_id: AccountId,
+        token_metadata: TokenMetadata,
+    ) -> Token {
+        require!(
+            env::predecessor_account_id() == self.tokens.owner_id,
+            "Only the contract owner can mint tokens"
+        );
+        self.tokens
+            .internal_mint(token_id, token_owner_id, Some(token_metadata))
+    }
+}
+
+#[unc]
+impl NonFungibleTokenCore for Contract {
+    #[payable]
+    fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        self.tokens
+            .nft_transfer(receiver_id, token_id, approval_id, memo);
+    }
+
+    #[payable]
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        self.tokens
+            .nft_transfer_call(receiver_id, token_id, approval_id, memo, msg)
+    }
+
+    fn nft_token(&self, token_id: TokenId) -> Option<Token> {
+        self.tokens.nft_token(token_id)
+    }
+}
+
+#[unc]
+impl NonFungibleTokenResolver for Contract {
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool {
+        self.tokens.nft_resolve_transfer(
+            previous_owner_id,
+            receiver_id,
+            token_id,
+            approved_account_ids,
+        )
+    }
+}
+
+#[unc]
+impl NonFungibleTokenEnumeration for Contract {
+    fn nft_total_supply(&self) -> U128 {
+        self.tokens.nft_total_supply()
+    }
+
+    fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+        self.tokens.nft_tokens(from_index, limit)
+    }
+
+    fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        self.tokens.nft_supply_for_owner(account_id)
+    }
+
+    fn nft_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Token> {
+        self.tokens
+            .nft_tokens_for_owner(account_id, from_index, limit)
+    }
+}
+
+#[unc]
+impl NonFungibleTokenMetadataProvider for Contract {
+    fn nft_metadata(&self) -> NFTContractMetadata {
+        self.metadata.get().unwrap()
+    }
+}
+
+/*
+ * The rest of this file holds the inline tests for the code above
+ * Learn more about Rust tests: https://doc.rust-lang.org/book/ch11-01-writing-tests.html
+ */
+#[cfg(test)]
+mod tests {
+    use unc_sdk::test_utils::{accounts, VMContextBuilder};
+    use unc_sdk::{testing_env, UncToken};
+
+    use super::*;
+
+    #[test]
+    fn owner_can_mint() {
+        let mut context = VMContextBuilder::new();
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+
+        testing_env!(context
+            .attached_deposit(UncToken::from_milliunc(100))
+            .predecessor_account_id(accounts(0))
+            .build());
+        let token = contract.nft_mint(
+            "0".to_string(),
+            accounts(1),
+            TokenMetadata {
+                title: Some("unc-new-project-name #0".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(token.owner_id, accounts(1));
+        assert_eq!(contract.nft_total_supply().0, 1);
+    }
+}
@@ -0,0 +1,51 @@
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_mint_and_transfer() -> common::Result<()> {
+    let sandbox = common::sandbox().await?;
+    let contract_wasm = common::contract_wasm().await?;
+
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let user_account = sandbox.dev_create_account().await?;
+
+    let outcome = contract
+        .call("new_default_meta")
+        .args_json(json!({"owner_id": contract.id()}))
+        .transact()
+        .await?;
+    common::record_gas("test_mint_and_transfer", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = contract
+        .call("nft_mint")
+        .args_json(json!({
+            "token_id": "0",
+            "token_owner_id": contract.id(),
+            "token_metadata": {"title": "First token"},
+        }))
+        .deposit(utility_workspaces::types::UncToken::from_milliunc(100))
+        .transact()
+        .await?;
+    common::record_gas("test_mint_and_transfer", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = contract
+        .call("nft_transfer")
+        .args_json(json!({"receiver_id": user_account.id(), "token_id": "0"}))
+        .deposit(utility_workspaces::types::UncToken::from_attounc(1))
+        .transact()
+        .await?;
+    common::record_gas("test_mint_and_transfer", &outcome)?;
+    assert!(outcome.is_success());
+
+    let token = contract
+        .view("nft_token")
+        .args_json(json!({"token_id": "0"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(token["owner_id"], json!(user_account.id()));
+
+    Ok(())
+}
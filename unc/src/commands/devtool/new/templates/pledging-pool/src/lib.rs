@@ -0,0 +1,152 @@
+// Find all our documentation at https://docs.unc.org
+use unc_sdk::json_types::U128;
+use unc_sdk::store::LookupMap;
+use unc_sdk::{
+    env, log, require, unc, AccountId, EpochHeight, PanicOnDefault, Promise, UncToken,
+};
+
+/// The number of epochs the pledged tokens stay locked after they were requested to be unpledged.
+const NUM_EPOCHS_TO_UNLOCK: EpochHeight = 4;
+
+#[unc(serializers=[borsh, json])]
+#[derive(Clone, Default)]
+pub struct Delegator {
+    /// The amount of tokens pledged to the pool.
+    pub pledged: U128,
+    /// The amount of tokens requested to be unpledged.
+    pub unpledged: U128,
+    /// The epoch when the unpledged tokens become available for withdrawal.
+    pub unlock_epoch: EpochHeight,
+}
+
+// Define the contract structure
+#[unc(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct Contract {
+    owner_id: AccountId,
+    /// The fee taken by the pool owner from the rewards, in basis points.
+    reward_fee_bps: u16,
+    total_pledged: u128,
+    delegators: LookupMap<AccountId, Delegator>,
+}
+
+// Implement the contract structure
+#[unc]
+impl Contract {
+    #[init]
+    pub fn new(owner_id: AccountId, reward_fee_bps: u16) -> Self {
+        require!(reward_fee_bps <= 10_000, "The reward fee cannot exceed 100%");
+        Self {
+            owner_id,
+            reward_fee_bps,
+            total_pledged: 0,
+            delegators: LookupMap::new(b"d"),
+        }
+    }
+
+    /// Pledges the attached deposit on behalf of the caller.
+    #[payable]
+    pub fn deposit_and_pledge(&mut self) -> Delegator {
+        let amount = env::attached_deposit().as_attounc();
+        require!(amount > 0, "Attach a deposit to pledge");
+        let account_id = env::predecessor_account_id();
+        let mut delegator = self.get_delegator_or_default(&account_id);
+        delegator.pledged = (delegator.pledged.0 + amount).into();
+        self.total_pledged += amount;
+        log!("@{} pledged {}", account_id, amount);
+        self.delegators.insert(account_id, delegator.clone());
+        delegator
+    }
+
+    /// Requests to unpledge the given amount; it can be withdrawn after `NUM_EPOCHS_TO_UNLOCK` epochs.
+    pub fn unpledge(&mut self, amount: U128) -> Delegator {
+        let account_id = env::predecessor_account_id();
+        let mut delegator = self.get_delegator_or_default(&account_id);
+        require!(
+            delegator.pledged.0 >= amount.0,
+            "Not enough pledged tokens to unpledge"
+        );
+        delegator.pledged = (delegator.pledged.0 - amount.0).into();
+        delegator.unpledged = (delegator.unpledged.0 + amount.0).into();
+        delegator.unlock_epoch = env::epoch_height() + NUM_EPOCHS_TO_UNLOCK;
+        self.total_pledged -= amount.0;
+        log!("@{} unpledged {}", account_id, amount.0);
+        self.delegators.insert(account_id, delegator.clone());
+        delegator
+    }
+
+    /// Withdraws all unlocked unpledged tokens of the caller.
+    pub fn withdraw_all(&mut self) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let mut delegator = self.get_delegator_or_default(&account_id);
+        require!(delegator.unpledged.0 > 0, "Nothing to withdraw");
+        require!(
+            env::epoch_height() >= delegator.unlock_epoch,
+            "The unpledged tokens are not unlocked yet"
+        );
+        let amount = delegator.unpledged.0;
+        delegator.unpledged = 0.into();
+        self.delegators.insert(account_id.clone(), delegator);
+        log!("@{} withdrew {}", account_id, amount);
+        Promise::new(account_id).transfer(UncToken::from_attounc(amount))
+    }
+
+    pub fn get_delegator(&self, account_id: AccountId) -> Option<Delegator> {
+        self.delegators.get(&account_id).cloned()
+    }
+
+    pub fn get_total_pledged(&self) -> U128 {
+        self.total_pledged.into()
+    }
+
+    pub fn get_owner_id(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_reward_fee_bps(&self) -> u16 {
+        self.reward_fee_bps
+    }
+
+    /// Updates the reward fee; only the pool owner can call it.
+    pub fn set_reward_fee_bps(&mut self, reward_fee_bps: u16) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the owner can change the reward fee"
+        );
+        require!(reward_fee_bps <= 10_000, "The reward fee cannot exceed 100%");
+        self.reward_fee_bps = reward_fee_bps;
+    }
+
+    fn get_delegator_or_default(&self, account_id: &AccountId) -> Delegator {
+        self.delegators.get(account_id).cloned().unwrap_or_default()
+    }
+}
+
+/*
+ * The rest of this file holds the inline tests for the code above
+ * Learn more about Rust tests: https://doc.rust-lang.org/book/ch11-01-writing-tests.html
+ */
+#[cfg(test)]
+mod tests {
+    use unc_sdk::test_utils::{accounts, VMContextBuilder};
+    use unc_sdk::testing_env;
+
+    use super::*;
+
+    #[test]
+    fn pledge_and_unpledge() {
+        let mut context = VMContextBuilder::new();
+        testing_env!(context
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(UncToken::from_unc(10))
+            .build());
+        let mut contract = Contract::new(accounts(0), 500);
+        contract.deposit_and_pledge();
+        assert_eq!(contract.get_total_pledged().0, UncToken::from_unc(10).as_attounc());
+
+        let delegator = contract.unpledge(UncToken::from_unc(4).as_attounc().into());
+        assert_eq!(delegator.pledged.0, UncToken::from_unc(6).as_attounc());
+        assert_eq!(delegator.unpledged.0, UncToken::from_unc(4).as_attounc());
+        assert_eq!(delegator.unlock_epoch, NUM_EPOCHS_TO_UNLOCK);
+    }
+}
@@ -0,0 +1,44 @@
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_pledge_and_unpledge() -> common::Result<()> {
+    let sandbox = common::sandbox().await?;
+    let contract_wasm = common::contract_wasm().await?;
+
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let user_account = sandbox.dev_create_account().await?;
+
+    let outcome = contract
+        .call("new")
+        .args_json(json!({"owner_id": contract.id(), "reward_fee_bps": 500}))
+        .transact()
+        .await?;
+    common::record_gas("test_pledge_and_unpledge", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = user_account
+        .call(contract.id(), "deposit_and_pledge")
+        .deposit(utility_workspaces::types::UncToken::from_unc(5))
+        .transact()
+        .await?;
+    common::record_gas("test_pledge_and_unpledge", &outcome)?;
+    assert!(outcome.is_success());
+
+    let outcome = user_account
+        .call(contract.id(), "unpledge")
+        .args_json(json!({"amount": "1000000000000000000000000"}))
+        .transact()
+        .await?;
+    common::record_gas("test_pledge_and_unpledge", &outcome)?;
+    assert!(outcome.is_success());
+
+    let total_pledged = contract.view("get_total_pledged").await?;
+    assert_eq!(
+        total_pledged.json::<String>()?,
+        "4000000000000000000000000"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,341 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod reset;
+mod start;
+mod status;
+mod stop;
+
+/// The name of the network connection registered for the local sandbox node.
+pub const SANDBOX_CONNECTION_NAME: &str = "sandbox";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SandboxContext)]
+pub struct SandboxCommands {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The directory with the sandbox node data, binary and logs (defaults to the unc-cli data directory)
+    home_dir: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(subcommand)]
+    sandbox_actions: SandboxActions,
+}
+
+#[derive(Debug, Clone)]
+pub struct SandboxContext {
+    global_context: crate::GlobalContext,
+    sandbox: Sandbox,
+}
+
+impl SandboxContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SandboxCommands as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let sandbox = match &scope.home_dir {
+            Some(home_dir) => Sandbox {
+                dir: home_dir.0.clone(),
+            },
+            None => Sandbox::in_default_dir()?,
+        };
+        Ok(Self {
+            global_context: previous_context,
+            sandbox,
+        })
+    }
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = SandboxContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// What do you want to do with the local sandbox node?
+pub enum SandboxActions {
+    #[strum_discriminants(strum(
+        message = "start   - Initialize (if needed) and start the sandbox node in the background"
+    ))]
+    /// Initialize (if needed) and start the sandbox node in the background
+    Start(self::start::Start),
+    #[strum_discriminants(strum(message = "stop    - Stop the running sandbox node"))]
+    /// Stop the running sandbox node
+    Stop(self::stop::Stop),
+    #[strum_discriminants(strum(
+        message = "reset   - Stop the sandbox node and wipe its chain data and keys"
+    ))]
+    /// Stop the sandbox node and wipe its chain data and keys
+    Reset(self::reset::Reset),
+    #[strum_discriminants(strum(
+        message = "status  - Show whether the sandbox node is running and its connection details"
+    ))]
+    /// Show whether the sandbox node is running and its connection details
+    Status(self::status::Status),
+}
+
+/// The running sandbox node, as recorded by `sandbox start`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SandboxState {
+    pub pid: u32,
+    pub binary_path: std::path::PathBuf,
+    pub rpc_url: url::Url,
+    pub root_account_id: unc_primitives::types::AccountId,
+}
+
+/// The layout of the sandbox directory.
+#[derive(Debug, Clone)]
+pub(crate) struct Sandbox {
+    dir: std::path::PathBuf,
+}
+
+impl Sandbox {
+    pub(crate) fn in_default_dir() -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            dir: dirs::data_local_dir()
+                .wrap_err("Impossible to get your data dir!")?
+                .join("unc-cli")
+                .join("sandbox"),
+        })
+    }
+
+    /// Makes sure the sandbox node is running for the tests: a running node is used as is, otherwise
+    /// the node is started with the default options on its existing chain. With `reset`, the chain
+    /// data and keys are wiped first, so that the state of earlier runs does not leak in; a running
+    /// node is restarted with the same binary.
+    /// Returns the state of the node and whether it was running before.
+    pub(crate) fn start_for_tests(
+        &self,
+        config: crate::config::Config,
+        reset: bool,
+    ) -> color_eyre::eyre::Result<(SandboxState, bool)> {
+        let previous_state = self.running_state()?;
+        if reset {
+            self.reset(&config)?;
+        } else if let Some(state) = previous_state {
+            eprintln!(
+                "The sandbox node is already running (pid {}) at {}",
+                state.pid, state.rpc_url
+            );
+            return Ok((state, true));
+        }
+        let state = self::start::start_node(
+            self,
+            config,
+            previous_state
+                .as_ref()
+                .map(|state| state.binary_path.as_path()),
+            None,
+            self::start::DEFAULT_RPC_PORT,
+            self::start::DEFAULT_NETWORK_PORT,
+            None,
+        )?;
+        Ok((state, previous_state.is_some()))
+    }
+
+    /// Stops the node and wipes its chain data, keys and logs; the downloaded binary is kept.
+    pub(crate) fn reset(&self, config: &crate::config::Config) -> crate::CliResult {
+        if self.stop()? {
+            eprintln!("The sandbox node is stopped");
+        }
+        let credentials_dir = config.credentials_home_dir.join(SANDBOX_CONNECTION_NAME);
+        for dir in [self.node_home(), credentials_dir] {
+            if dir.is_dir() {
+                std::fs::remove_dir_all(&dir)
+                    .wrap_err_with(|| format!("Failed to remove directory: {dir:?}"))?;
+            }
+        }
+        let log_file = self.log_file();
+        if log_file.is_file() {
+            std::fs::remove_file(&log_file)
+                .wrap_err_with(|| format!("Failed to remove file: {log_file:?}"))?;
+        }
+        Ok(())
+    }
+
+    /// The node home directory with the genesis, the keys and the chain data.
+    pub(crate) fn node_home(&self) -> std::path::PathBuf {
+        self.dir.join("node")
+    }
+
+    /// The key of the root account funded in the genesis.
+    pub(crate) fn validator_key(&self) -> color_eyre::eyre::Result<ValidatorKey> {
+        let validator_key_file = self.node_home().join("validator_key.json");
+        serde_json::from_str(
+            &std::fs::read_to_string(&validator_key_file)
+                .wrap_err_with(|| format!("Failed to read file: {validator_key_file:?}"))?,
+        )
+        .wrap_err_with(|| format!("Failed to parse file: {validator_key_file:?}"))
+    }
+
+    fn bin_dir(&self) -> std::path::PathBuf {
+        self.dir.join("bin")
+    }
+
+    fn log_file(&self) -> std::path::PathBuf {
+        self.dir.join("sandbox.log")
+    }
+
+    fn state_file(&self) -> std::path::PathBuf {
+        self.dir.join("sandbox.json")
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.node_home().join("genesis.json").is_file()
+    }
+
+    /// Returns the state of the sandbox node if it is still running.
+    fn running_state(&self) -> color_eyre::eyre::Result<Option<SandboxState>> {
+        let state_file = self.state_file();
+        if !state_file.is_file() {
+            return Ok(None);
+        }
+        let state: SandboxState = serde_json::from_str(
+            &std::fs::read_to_string(&state_file)
+                .wrap_err_with(|| format!("Failed to read file: {state_file:?}"))?,
+        )
+        .wrap_err_with(|| format!("Failed to parse file: {state_file:?}"))?;
+        if self.is_node_process(&state) {
+            Ok(Some(state))
+        } else {
+            // The node exited since it was started, or the machine rebooted and the pid is free
+            // or belongs to an unrelated process now.
+            std::fs::remove_file(&state_file)
+                .wrap_err_with(|| format!("Failed to remove file: {state_file:?}"))?;
+            Ok(None)
+        }
+    }
+
+    /// Whether the recorded pid still belongs to the node of this sandbox, so it is safe to signal.
+    fn is_node_process(&self, state: &SandboxState) -> bool {
+        is_sandbox_node_process(state.pid, &state.binary_path, &self.node_home())
+    }
+
+    fn write_state(&self, state: &SandboxState) -> crate::CliResult {
+        let state_file = self.state_file();
+        std::fs::write(&state_file, serde_json::to_vec_pretty(state)?)
+            .wrap_err_with(|| format!("Failed to write to file: {state_file:?}"))
+    }
+
+    /// Stops the sandbox node if it is running, returning whether it was.
+    pub(crate) fn stop(&self) -> color_eyre::eyre::Result<bool> {
+        let Some(state) = self.running_state()? else {
+            return Ok(false);
+        };
+        kill_process(state.pid)?;
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while self.is_node_process(&state) {
+            if std::time::Instant::now() > deadline {
+                color_eyre::eyre::bail!(
+                    "The sandbox node (pid {}) did not stop within 10 seconds",
+                    state.pid
+                );
+            }
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        }
+        std::fs::remove_file(self.state_file())?;
+        Ok(true)
+    }
+}
+
+/// The validator key generated by the node `init`: its account is funded in the genesis.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub(crate) struct ValidatorKey {
+    pub account_id: unc_primitives::types::AccountId,
+    pub public_key: unc_crypto::PublicKey,
+    pub secret_key: unc_crypto::SecretKey,
+}
+
+#[cfg(unix)]
+fn is_process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(windows)]
+fn is_process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
+/// Checks the command line of the process: the node runs with `--home <node home>`.
+#[cfg(unix)]
+fn is_sandbox_node_process(
+    pid: u32,
+    _binary_path: &std::path::Path,
+    node_home: &std::path::Path,
+) -> bool {
+    let command_line = if let Ok(cmdline) = std::fs::read(format!("/proc/{pid}/cmdline")) {
+        cmdline
+            .split(|byte| *byte == 0)
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect::<Vec<_>>()
+    } else {
+        // There is no procfs on macOS.
+        match std::process::Command::new("ps")
+            .args(["-o", "command=", "-p", &pid.to_string()])
+            .output()
+        {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            _ => return false,
+        }
+    };
+    let node_home = node_home.to_string_lossy();
+    command_line
+        .windows(2)
+        .any(|args| args[0] == "--home" && args[1] == node_home)
+}
+
+/// `tasklist` only reports the image name of the process, so it is compared with the binary name.
+#[cfg(windows)]
+fn is_sandbox_node_process(
+    pid: u32,
+    binary_path: &std::path::Path,
+    _node_home: &std::path::Path,
+) -> bool {
+    let Some(binary_name) = binary_path.file_name() else {
+        return false;
+    };
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.split(',').next())
+                .any(|image_name| {
+                    image_name
+                        .trim_matches('"')
+                        .eq_ignore_ascii_case(&binary_name.to_string_lossy())
+                })
+        })
+}
+
+#[cfg(unix)]
+fn kill_process(pid: u32) -> crate::CliResult {
+    let status = std::process::Command::new("kill")
+        .arg(pid.to_string())
+        .status()
+        .wrap_err("Failed to run `kill`")?;
+    if !status.success() {
+        color_eyre::eyre::bail!("Failed to stop the sandbox node (pid {pid})");
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn kill_process(pid: u32) -> crate::CliResult {
+    let status = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .status()
+        .wrap_err("Failed to run `taskkill`")?;
+    if !status.success() {
+        color_eyre::eyre::bail!("Failed to stop the sandbox node (pid {pid})");
+    }
+    Ok(())
+}
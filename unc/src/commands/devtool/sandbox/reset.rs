@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::SandboxContext)]
+#[interactive_clap(output_context = ResetContext)]
+pub struct Reset {}
+
+#[derive(Debug, Clone)]
+pub struct ResetContext;
+
+impl ResetContext {
+    pub fn from_previous_context(
+        previous_context: super::SandboxContext,
+        _scope: &<Reset as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        previous_context
+            .sandbox
+            .reset(&previous_context.global_context.config)?;
+        crate::util::print_success(
+            "The sandbox is reset: the next `sandbox start` initializes a new chain",
+        );
+        Ok(Self)
+    }
+}
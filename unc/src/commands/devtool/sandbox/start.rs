@@ -0,0 +1,356 @@
+use std::io::Read;
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use colored::Colorize;
+
+use crate::common::JsonRpcClientExt;
+
+/// The environment variable with the path to the sandbox node binary.
+const SANDBOX_BIN_PATH_ENV: &str = "UNC_SANDBOX_BIN_PATH";
+pub(super) const DEFAULT_RPC_PORT: u16 = 3034;
+pub(super) const DEFAULT_NETWORK_PORT: u16 = 24567;
+const STARTUP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::SandboxContext)]
+#[interactive_clap(output_context = StartContext)]
+pub struct Start {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Path to the sandbox node binary (defaults to $UNC_SANDBOX_BIN_PATH, a downloaded binary or `unc-sandbox` in $PATH)
+    binary_path: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// URL to download the sandbox node binary from (a plain binary or a .tar.gz archive with it)
+    download_url: Option<crate::types::url::Url>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The port of the RPC endpoint (default: 3034)
+    rpc_port: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The port of the peer-to-peer network (default: 24567)
+    network_port: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The root account funded in the genesis (only used when the sandbox is initialized)
+    root_account_id: Option<crate::types::account_id::AccountId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StartContext;
+
+impl StartContext {
+    pub fn from_previous_context(
+        previous_context: super::SandboxContext,
+        scope: &<Start as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let sandbox = &previous_context.sandbox;
+        let config = previous_context.global_context.config;
+        if let Some(state) = sandbox.running_state()? {
+            eprintln!(
+                "The sandbox node is already running (pid {}) at {}",
+                state.pid, state.rpc_url
+            );
+            return Ok(Self);
+        }
+
+        let rpc_port = port(scope.rpc_port, DEFAULT_RPC_PORT)?;
+        let network_port = port(scope.network_port, DEFAULT_NETWORK_PORT)?;
+        let root_account_id = scope
+            .root_account_id
+            .as_ref()
+            .map(|account_id| account_id.resolve(&config, None))
+            .transpose()?;
+        start_node(
+            sandbox,
+            config,
+            scope.binary_path.as_ref().map(|path| path.0.as_path()),
+            scope.download_url.as_ref().map(|url| &url.0),
+            rpc_port,
+            network_port,
+            root_account_id.as_ref(),
+        )?;
+        Ok(Self)
+    }
+}
+
+/// Initializes the sandbox (if needed), runs the node, registers its network connection and imports the root account key.
+pub(super) fn start_node(
+    sandbox: &super::Sandbox,
+    mut config: crate::config::Config,
+    binary_path: Option<&std::path::Path>,
+    download_url: Option<&url::Url>,
+    rpc_port: u16,
+    network_port: u16,
+    root_account_id: Option<&unc_primitives::types::AccountId>,
+) -> color_eyre::eyre::Result<super::SandboxState> {
+    std::fs::create_dir_all(&sandbox.dir)
+        .wrap_err_with(|| format!("Failed to create directory: {:?}", sandbox.dir))?;
+    let binary_path = locate_binary(sandbox, binary_path, download_url)?;
+
+    if !sandbox.is_initialized() {
+        initialize(sandbox, &binary_path, root_account_id)?;
+        // The keys of the previous sandbox chain are useless for the new one.
+        let credentials_dir = config
+            .credentials_home_dir
+            .join(super::SANDBOX_CONNECTION_NAME);
+        if credentials_dir.is_dir() {
+            std::fs::remove_dir_all(&credentials_dir)
+                .wrap_err_with(|| format!("Failed to remove directory: {credentials_dir:?}"))?;
+        }
+    }
+
+    let rpc_url: url::Url = format!("http://127.0.0.1:{rpc_port}").parse()?;
+    let network_config = crate::config::NetworkConfig {
+        network_name: super::SANDBOX_CONNECTION_NAME.to_string(),
+        rpc_url: rpc_url.clone(),
+        rpc_api_key: None,
+        // There is no wallet or explorer for the local chain; the RPC endpoint stands in for them.
+        wallet_url: rpc_url.clone(),
+        explorer_transaction_url: rpc_url.clone(),
+        linkdrop_account_id: None,
+        faucet_url: None,
+        meta_transaction_relayer_url: None,
+    };
+
+    let pid = run_node(sandbox, &binary_path, rpc_port, network_port)?;
+    crate::util::handle_step("Waiting for the sandbox node to start...", || {
+        wait_for_rpc(&network_config, pid)
+    })
+    .wrap_err_with(|| {
+        format!(
+            "The sandbox node failed to start, see the logs in {:?}",
+            sandbox.log_file()
+        )
+    })?;
+
+    let validator_key = sandbox.validator_key()?;
+
+    let state = super::SandboxState {
+        pid,
+        binary_path,
+        rpc_url: rpc_url.clone(),
+        root_account_id: validator_key.account_id.clone(),
+    };
+    sandbox.write_state(&state)?;
+
+    let network_config = crate::config::NetworkConfig {
+        linkdrop_account_id: Some(validator_key.account_id.clone()),
+        ..network_config
+    };
+    config.network_connection.insert(
+        super::SANDBOX_CONNECTION_NAME.to_string(),
+        network_config.clone(),
+    );
+    crate::common::write_config_toml(config.clone())?;
+
+    let key_pair_properties_buf = serde_json::json!({
+        "account_id": validator_key.account_id,
+        "public_key": validator_key.public_key,
+        "private_key": validator_key.secret_key,
+    })
+    .to_string();
+    let storage_message = crate::common::save_access_key_to_legacy_keychain(
+        network_config,
+        config.credentials_home_dir.clone(),
+        &key_pair_properties_buf,
+        &validator_key.public_key.to_string(),
+        validator_key.account_id.as_ref(),
+    )?;
+    crate::common::update_used_account_list_as_signer(
+        &config.credentials_home_dir,
+        &validator_key.account_id,
+    );
+
+    crate::util::print_success(&format!("The sandbox node is running (pid {pid})"));
+    eprintln!("     - RPC: {}", rpc_url.to_string().yellow().bold());
+    eprintln!(
+        "     - Network connection: {}",
+        super::SANDBOX_CONNECTION_NAME.yellow().bold()
+    );
+    eprintln!(
+        "     - Root account: {}",
+        validator_key.account_id.to_string().yellow().bold()
+    );
+    eprintln!(
+        "     - Home: {}",
+        sandbox.node_home().display().to_string().yellow().bold()
+    );
+    eprintln!("{storage_message}");
+    Ok(state)
+}
+
+fn port(port: Option<u64>, default: u16) -> color_eyre::eyre::Result<u16> {
+    port.map_or(Ok(default), |port| {
+        u16::try_from(port).wrap_err_with(|| format!("Invalid port: {port}"))
+    })
+}
+
+fn binary_name() -> String {
+    format!("unc-sandbox{}", std::env::consts::EXE_SUFFIX)
+}
+
+/// Finds the sandbox node binary, downloading it if a URL is given.
+fn locate_binary(
+    sandbox: &super::Sandbox,
+    binary_path: Option<&std::path::Path>,
+    download_url: Option<&url::Url>,
+) -> color_eyre::eyre::Result<std::path::PathBuf> {
+    if let Some(binary_path) = binary_path {
+        if !binary_path.is_file() {
+            color_eyre::eyre::bail!(
+                "The sandbox binary <{}> does not exist",
+                binary_path.display()
+            );
+        }
+        return Ok(binary_path.to_path_buf());
+    }
+    if let Some(binary_path) = std::env::var_os(SANDBOX_BIN_PATH_ENV) {
+        let binary_path = std::path::PathBuf::from(binary_path);
+        if !binary_path.is_file() {
+            color_eyre::eyre::bail!(
+                "The sandbox binary <{}> from ${SANDBOX_BIN_PATH_ENV} does not exist",
+                binary_path.display()
+            );
+        }
+        return Ok(binary_path);
+    }
+    let downloaded_binary_path = sandbox.bin_dir().join(binary_name());
+    if let Some(download_url) = download_url {
+        download_binary(download_url, &downloaded_binary_path)?;
+        return Ok(downloaded_binary_path);
+    }
+    if downloaded_binary_path.is_file() {
+        return Ok(downloaded_binary_path);
+    }
+    std::env::var_os("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|path| path.join(binary_name()))
+                .find(|path| path.is_file())
+        })
+        .wrap_err_with(|| {
+            format!(
+                "The sandbox binary is not found. Pass --binary-path, set ${SANDBOX_BIN_PATH_ENV}, pass --download-url or put `{}` in $PATH",
+                binary_name()
+            )
+        })
+}
+
+fn download_binary(download_url: &url::Url, binary_path: &std::path::Path) -> crate::CliResult {
+    let data = crate::util::handle_step("Downloading the sandbox binary...", || {
+        Ok(reqwest::blocking::get(download_url.clone())?
+            .error_for_status()?
+            .bytes()?)
+    })?;
+    let binary =
+        if download_url.path().ends_with(".tar.gz") || download_url.path().ends_with(".tgz") {
+            let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(&data[..]));
+            let mut entry = archive
+                .entries()?
+                .filter_map(Result::ok)
+                .find(|entry| {
+                    entry
+                        .path()
+                        .is_ok_and(|path| path.file_name() == Some(binary_name().as_ref()))
+                })
+                .wrap_err_with(|| format!("There is no `{}` in the archive", binary_name()))?;
+            let mut binary = vec![];
+            entry.read_to_end(&mut binary)?;
+            binary
+        } else {
+            data.to_vec()
+        };
+
+    let bin_dir = binary_path.parent().wrap_err("Invalid binary path")?;
+    std::fs::create_dir_all(bin_dir)
+        .wrap_err_with(|| format!("Failed to create directory: {bin_dir:?}"))?;
+    std::fs::write(binary_path, binary)
+        .wrap_err_with(|| format!("Failed to write to file: {binary_path:?}"))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(binary_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+/// Creates the node home directory with the genesis (which funds the validator account) and the keys.
+fn initialize(
+    sandbox: &super::Sandbox,
+    binary_path: &std::path::Path,
+    root_account_id: Option<&unc_primitives::types::AccountId>,
+) -> crate::CliResult {
+    let mut cmd = std::process::Command::new(binary_path);
+    cmd.arg("--home").arg(sandbox.node_home()).arg("init");
+    if let Some(root_account_id) = root_account_id {
+        cmd.args(["--account-id", root_account_id.as_str()]);
+    }
+    let output = crate::util::handle_step("Initializing the sandbox node...", || {
+        cmd.output()
+            .wrap_err_with(|| format!("Failed to run the sandbox binary {binary_path:?}"))
+    })?;
+    if !output.status.success() {
+        color_eyre::eyre::bail!(
+            "Failed to initialize the sandbox node:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
+
+/// Polls the node status until the RPC endpoint responds.
+fn wait_for_rpc(network_config: &crate::config::NetworkConfig, pid: u32) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let deadline = std::time::Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        if json_rpc_client
+            .blocking_call(unc_jsonrpc_client::methods::status::RpcStatusRequest)
+            .is_ok()
+        {
+            return Ok(());
+        }
+        if !super::is_process_alive(pid) {
+            color_eyre::eyre::bail!("The sandbox node exited");
+        }
+        if std::time::Instant::now() > deadline {
+            color_eyre::eyre::bail!(
+                "The sandbox node RPC did not respond within {} seconds",
+                STARTUP_TIMEOUT.as_secs()
+            );
+        }
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+}
+
+/// Starts the node in the background with its output appended to the log file.
+fn run_node(
+    sandbox: &super::Sandbox,
+    binary_path: &std::path::Path,
+    rpc_port: u16,
+    network_port: u16,
+) -> color_eyre::eyre::Result<u32> {
+    let log_file_path = sandbox.log_file();
+    let log_file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_file_path)
+        .wrap_err_with(|| format!("Failed to open file: {log_file_path:?}"))?;
+    let mut cmd = std::process::Command::new(binary_path);
+    cmd.arg("--home")
+        .arg(sandbox.node_home())
+        .arg("run")
+        .args(["--rpc-addr", &format!("127.0.0.1:{rpc_port}")])
+        .args(["--network-addr", &format!("127.0.0.1:{network_port}")])
+        .stdin(std::process::Stdio::null())
+        .stdout(log_file.try_clone()?)
+        .stderr(log_file);
+    // Keep the node running when the terminal sends a signal to the foreground process group.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    let child = cmd
+        .spawn()
+        .wrap_err_with(|| format!("Failed to run the sandbox binary {binary_path:?}"))?;
+    Ok(child.id())
+}
@@ -0,0 +1,66 @@
+use colored::Colorize;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::SandboxContext)]
+#[interactive_clap(output_context = StatusContext)]
+pub struct Status {}
+
+#[derive(Debug, Clone)]
+pub struct StatusContext;
+
+impl StatusContext {
+    pub fn from_previous_context(
+        previous_context: super::SandboxContext,
+        _scope: &<Status as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let sandbox = &previous_context.sandbox;
+        let Some(state) = sandbox.running_state()? else {
+            if sandbox.is_initialized() {
+                eprintln!(
+                    "The sandbox node is {} (initialized in {})",
+                    "not running".red().bold(),
+                    sandbox.node_home().display()
+                );
+            } else {
+                eprintln!(
+                    "The sandbox node is {} and not initialized",
+                    "not running".red().bold()
+                );
+            }
+            return Ok(Self);
+        };
+
+        eprintln!(
+            "The sandbox node is {} (pid {})",
+            "running".green().bold(),
+            state.pid
+        );
+        eprintln!("     - RPC: {}", state.rpc_url.to_string().yellow().bold());
+        eprintln!(
+            "     - Network connection: {}",
+            super::SANDBOX_CONNECTION_NAME.yellow().bold()
+        );
+        eprintln!(
+            "     - Root account: {}",
+            state.root_account_id.to_string().yellow().bold()
+        );
+        eprintln!("     - Home: {}", sandbox.node_home().display());
+        eprintln!("     - Binary: {}", state.binary_path.display());
+        eprintln!("     - Logs: {}", sandbox.log_file().display());
+        match unc_jsonrpc_client::JsonRpcClient::connect(state.rpc_url.as_str())
+            .blocking_call(unc_jsonrpc_client::methods::status::RpcStatusRequest)
+        {
+            Ok(status) => {
+                eprintln!("     - Chain ID: {}", status.chain_id);
+                eprintln!(
+                    "     - Latest block height: {}",
+                    status.sync_info.latest_block_height
+                );
+            }
+            Err(err) => eprintln!("     - The RPC endpoint does not respond: {err}"),
+        }
+        Ok(Self)
+    }
+}
@@ -0,0 +1,21 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::SandboxContext)]
+#[interactive_clap(output_context = StopContext)]
+pub struct Stop {}
+
+#[derive(Debug, Clone)]
+pub struct StopContext;
+
+impl StopContext {
+    pub fn from_previous_context(
+        previous_context: super::SandboxContext,
+        _scope: &<Stop as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.sandbox.stop()? {
+            crate::util::print_success("The sandbox node is stopped");
+        } else {
+            eprintln!("The sandbox node is not running");
+        }
+        Ok(Self)
+    }
+}
@@ -0,0 +1,311 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+/// The fixtures file of the project used when `--fixtures` is not passed.
+pub const DEFAULT_FIXTURES_FILE: &str = "unc-test-fixtures.json";
+const DEFAULT_BALANCE: &str = "100 unc";
+
+/// The chain state the tests expect, e.g.:
+///
+/// ```json
+/// {
+///   "accounts": [{ "account_id": "alice.test.unc", "balance": "100 unc" }],
+///   "contracts": [
+///     { "account_id": "ft.test.unc", "from_account_id": "ft.testnet", "network": "testnet" },
+///     { "account_id": "nft.test.unc", "wasm_file": "res/nft.wasm" }
+///   ],
+///   "state": [{ "account_id": "ft.test.unc", "snapshot": "fixtures/ft-state.json" }]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixtures {
+    #[serde(default)]
+    pub accounts: Vec<AccountFixture>,
+    #[serde(default)]
+    pub contracts: Vec<ContractFixture>,
+    #[serde(default)]
+    pub state: Vec<StateFixture>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountFixture {
+    pub account_id: unc_primitives::types::AccountId,
+    /// For example, "5 unc" (100 unc by default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<String>,
+}
+
+/// A dependency contract deployed before the tests, from a local file or from an account of another network.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractFixture {
+    pub account_id: unc_primitives::types::AccountId,
+    /// For example, "5 unc" (100 unc by default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_file: Option<std::path::PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_account_id: Option<unc_primitives::types::AccountId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+}
+
+/// Contract storage loaded from a snapshot: the JSON output of a `view_state` query or a Borsh-serialized `Vec<(key, value)>`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateFixture {
+    pub account_id: unc_primitives::types::AccountId,
+    pub snapshot: std::path::PathBuf,
+}
+
+fn parse_balance(
+    account_id: &unc_primitives::types::AccountId,
+    balance: Option<&str>,
+) -> color_eyre::eyre::Result<unc_primitives::types::Balance> {
+    crate::types::unc_token::UncToken::from_str(balance.unwrap_or(DEFAULT_BALANCE))
+        .map(|balance| balance.as_attounc())
+        .map_err(|err| color_eyre::eyre::eyre!("Invalid balance for <{account_id}>: {err}"))
+}
+
+impl Fixtures {
+    pub fn read(path: &std::path::Path) -> color_eyre::eyre::Result<Self> {
+        serde_json::from_str(
+            &std::fs::read_to_string(path)
+                .wrap_err_with(|| format!("Failed to read the fixtures file: {path:?}"))?,
+        )
+        .wrap_err_with(|| format!("Failed to parse the fixtures file: {path:?}"))
+    }
+
+    /// Makes the paths absolute and downloads the contracts of other networks into `download_dir`,
+    /// so that the tests only need local files.
+    pub fn resolve(
+        mut self,
+        config: &crate::config::Config,
+        base_dir: &std::path::Path,
+        download_dir: &std::path::Path,
+    ) -> color_eyre::eyre::Result<Self> {
+        for account in &self.accounts {
+            parse_balance(&account.account_id, account.balance.as_deref())?;
+        }
+        for contract in &mut self.contracts {
+            parse_balance(&contract.account_id, contract.balance.as_deref())?;
+            let wasm_file = match (&contract.wasm_file, &contract.from_account_id) {
+                (Some(wasm_file), None) => base_dir.join(wasm_file),
+                (None, Some(from_account_id)) => {
+                    let network = contract.network.as_deref().unwrap_or("testnet");
+                    let wasm_file = download_dir.join(format!("{from_account_id}.{network}.wasm"));
+                    if !wasm_file.is_file() {
+                        download_contract(config, network, from_account_id, &wasm_file)?;
+                    }
+                    wasm_file
+                }
+                _ => color_eyre::eyre::bail!(
+                    "The contract fixture <{}> must have either `wasm_file` or `from_account_id`",
+                    contract.account_id
+                ),
+            };
+            contract.wasm_file = Some(wasm_file);
+            contract.from_account_id = None;
+            contract.network = None;
+        }
+        for state in &mut self.state {
+            state.snapshot = base_dir.join(&state.snapshot);
+        }
+        Ok(self)
+    }
+
+    /// Creates the accounts, deploys the contracts and loads the state into the sandbox node directly,
+    /// with `public_key` as a full access key of every account.
+    pub fn apply_to_sandbox(
+        &self,
+        rpc_url: &url::Url,
+        public_key: &unc_crypto::PublicKey,
+    ) -> crate::CliResult {
+        let mut records = vec![];
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|account| (&account.account_id, account.balance.as_deref(), None))
+            .chain(self.contracts.iter().map(|contract| {
+                (
+                    &contract.account_id,
+                    contract.balance.as_deref(),
+                    contract.wasm_file.as_ref(),
+                )
+            }));
+        for (account_id, balance, wasm_file) in accounts {
+            let code = match wasm_file {
+                Some(wasm_file) => std::fs::read(wasm_file)
+                    .wrap_err_with(|| format!("Failed to read file: {wasm_file:?}"))?,
+                None => vec![],
+            };
+            let code_hash = if code.is_empty() {
+                unc_primitives::hash::CryptoHash::default()
+            } else {
+                unc_primitives::hash::CryptoHash::hash_bytes(&code)
+            };
+            records.push(unc_primitives::state_record::StateRecord::Account {
+                account_id: account_id.clone(),
+                // The storage usage is approximate: the sandbox does not verify it.
+                account: unc_primitives::account::Account::new(
+                    parse_balance(account_id, balance)?,
+                    0,
+                    0,
+                    code_hash,
+                    ACCOUNT_STORAGE_USAGE + code.len() as u64,
+                ),
+            });
+            records.push(unc_primitives::state_record::StateRecord::AccessKey {
+                account_id: account_id.clone(),
+                public_key: public_key.clone(),
+                access_key: unc_primitives::account::AccessKey::full_access(),
+            });
+            if !code.is_empty() {
+                records.push(unc_primitives::state_record::StateRecord::Contract {
+                    account_id: account_id.clone(),
+                    code,
+                });
+            }
+        }
+        for state in &self.state {
+            for (key, value) in read_snapshot(&state.snapshot)? {
+                records.push(unc_primitives::state_record::StateRecord::Data {
+                    account_id: state.account_id.clone(),
+                    data_key: key.into(),
+                    value: value.into(),
+                });
+            }
+        }
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        unc_jsonrpc_client::JsonRpcClient::connect(rpc_url.as_str())
+            .blocking_call(
+                unc_jsonrpc_client::methods::sandbox_patch_state::RpcSandboxPatchStateRequest {
+                    records,
+                },
+            )
+            .wrap_err("Failed to load the fixtures into the sandbox node")?;
+        Ok(())
+    }
+}
+
+/// The storage usage of an account record with one full access key.
+const ACCOUNT_STORAGE_USAGE: u64 = 182;
+
+fn download_contract(
+    config: &crate::config::Config,
+    network: &str,
+    account_id: &unc_primitives::types::AccountId,
+    wasm_file: &std::path::Path,
+) -> crate::CliResult {
+    let network_config = config.network_connection.get(network).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "The network <{network}> is not found in the config (known networks: {})",
+            config.network_names().join(", ")
+        )
+    })?;
+    let response = crate::util::handle_step(
+        &format!("Downloading the contract of <{account_id}> on <{network}>..."),
+        || {
+            network_config
+                .json_rpc_client()
+                .blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+                    block_reference: unc_primitives::types::Finality::Final.into(),
+                    request: unc_primitives::views::QueryRequest::ViewCode {
+                        account_id: account_id.clone(),
+                    },
+                })
+                .wrap_err_with(|| {
+                    format!(
+                        "Failed to fetch query ViewCode for <{account_id}> on network <{network}>"
+                    )
+                })
+        },
+    )?;
+    let unc_jsonrpc_primitives::types::query::QueryResponseKind::ViewCode(contract_code) =
+        response.kind
+    else {
+        color_eyre::eyre::bail!("Error call result");
+    };
+    if let Some(download_dir) = wasm_file.parent() {
+        std::fs::create_dir_all(download_dir)
+            .wrap_err_with(|| format!("Failed to create directory: {download_dir:?}"))?;
+    }
+    std::fs::write(wasm_file, contract_code.code)
+        .wrap_err_with(|| format!("Failed to write to file: {wasm_file:?}"))
+}
+
+fn read_snapshot(path: &std::path::Path) -> color_eyre::eyre::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    let data =
+        std::fs::read(path).wrap_err_with(|| format!("Failed to read the snapshot: {path:?}"))?;
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        let view_state: unc_primitives::views::ViewStateResult = serde_json::from_slice(&data)
+            .wrap_err_with(|| format!("Failed to parse the snapshot: {path:?}"))?;
+        Ok(view_state
+            .values
+            .into_iter()
+            .map(|item| (item.key.into(), item.value.into()))
+            .collect())
+    } else {
+        borsh::from_slice(&data).wrap_err_with(|| format!("Failed to parse the snapshot: {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_local_fixtures() {
+        let fixtures: Fixtures = serde_json::from_value(serde_json::json!({
+            "accounts": [{"account_id": "alice.test.unc", "balance": "5 unc"}],
+            "contracts": [{"account_id": "nft.test.unc", "wasm_file": "res/nft.wasm"}],
+            "state": [{"account_id": "nft.test.unc", "snapshot": "fixtures/nft.json"}],
+        }))
+        .unwrap();
+        assert_eq!(
+            parse_balance(
+                &fixtures.accounts[0].account_id,
+                fixtures.accounts[0].balance.as_deref()
+            )
+            .unwrap(),
+            5 * 10u128.pow(24)
+        );
+
+        let base_dir = std::path::Path::new("/project");
+        let fixtures = fixtures
+            .resolve(
+                &crate::config::Config::default(),
+                base_dir,
+                std::path::Path::new("/project/target/unc/fixtures"),
+            )
+            .unwrap();
+        assert_eq!(
+            fixtures.contracts[0].wasm_file.as_deref(),
+            Some(std::path::Path::new("/project/res/nft.wasm"))
+        );
+        assert_eq!(
+            fixtures.state[0].snapshot,
+            std::path::Path::new("/project/fixtures/nft.json")
+        );
+
+        let invalid: Fixtures = serde_json::from_value(serde_json::json!({
+            "contracts": [{"account_id": "nft.test.unc"}],
+        }))
+        .unwrap();
+        assert!(invalid
+            .resolve(&crate::config::Config::default(), base_dir, base_dir)
+            .is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use color_eyre::eyre::WrapErr;
+use prettytable::Table;
+
+/// A line of the gas report: the tests append one for every transaction they want to account for, e.g.
+/// `{"test": "test_transfer", "gas_burnt": 2428050684172}`.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct GasRecord {
+    test: String,
+    gas_burnt: unc_primitives::types::Gas,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct TestGasUsage {
+    pub test: String,
+    pub transactions: usize,
+    pub total_gas_burnt: unc_primitives::types::Gas,
+    pub max_gas_burnt: unc_primitives::types::Gas,
+}
+
+/// Reads the gas report and sums up the gas burnt by every test, in the order of the first record of each test.
+pub(super) fn read(path: &std::path::Path) -> color_eyre::eyre::Result<Vec<TestGasUsage>> {
+    let data = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read the gas report: {path:?}"))?;
+    aggregate(&data).wrap_err_with(|| format!("Failed to parse the gas report: {path:?}"))
+}
+
+fn aggregate(data: &str) -> color_eyre::eyre::Result<Vec<TestGasUsage>> {
+    let mut usages: Vec<TestGasUsage> = vec![];
+    for line in data.lines().filter(|line| !line.trim().is_empty()) {
+        let record: GasRecord = serde_json::from_str(line)?;
+        let position = match usages.iter().position(|usage| usage.test == record.test) {
+            Some(position) => position,
+            None => {
+                usages.push(TestGasUsage {
+                    test: record.test,
+                    ..Default::default()
+                });
+                usages.len() - 1
+            }
+        };
+        let usage = &mut usages[position];
+        usage.transactions += 1;
+        usage.total_gas_burnt += record.gas_burnt;
+        usage.max_gas_burnt = usage.max_gas_burnt.max(record.gas_burnt);
+    }
+    Ok(usages)
+}
+
+pub(super) fn print(usages: &[TestGasUsage]) {
+    if usages.is_empty() {
+        return;
+    }
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+    table.set_titles(prettytable::row![Fg->"Test", Fg->"Transactions", Fg->"Total gas burnt", Fg->"Max gas burnt"]);
+    for usage in usages {
+        table.add_row(prettytable::row![
+            usage.test,
+            usage.transactions,
+            Fy->unc_gas::UncGas::from_gas(usage.total_gas_burnt),
+            unc_gas::UncGas::from_gas(usage.max_gas_burnt)
+        ]);
+    }
+    eprintln!("\nGas usage per test:");
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_gas_per_test() {
+        let usages = aggregate(
+            r#"{"test": "test_transfer", "gas_burnt": 300}
+{"test": "test_mint", "gas_burnt": 50}
+
+{"test": "test_transfer", "gas_burnt": 500}
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            usages,
+            vec![
+                TestGasUsage {
+                    test: "test_transfer".to_string(),
+                    transactions: 2,
+                    total_gas_burnt: 800,
+                    max_gas_burnt: 500,
+                },
+                TestGasUsage {
+                    test: "test_mint".to_string(),
+                    transactions: 1,
+                    total_gas_burnt: 50,
+                    max_gas_burnt: 50,
+                },
+            ]
+        );
+        assert!(aggregate("not json").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use camino::Utf8PathBuf;
+use color_eyre::eyre::WrapErr;
+use colored::Colorize;
+
+use super::build_command::{build, BuildCommand};
+use crate::commands::devtool::sandbox::Sandbox;
+use crate::types::{manifest::CargoManifestPath, metadata::CrateMetadata};
+use crate::util;
+
+pub mod fixtures;
+mod gas_report;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = TestCommandContext)]
+pub struct TestCommand {
+    /// Build contract in debug mode, without optimizations and bigger is size
+    #[interactive_clap(long)]
+    no_release: bool,
+    /// Path to the `Cargo.toml` of the contract to test
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    manifest_path: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Set compile-time feature flags.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    features: Option<String>,
+    /// Disables default feature flags.
+    #[interactive_clap(long)]
+    no_default_features: bool,
+    /// The fixtures file (defaults to `unc-test-fixtures.json` next to `Cargo.toml`, if it exists)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    fixtures: Option<crate::types::path_buf::PathBuf>,
+    /// Run the tests on the local sandbox node (started if needed) with the fixtures loaded
+    #[interactive_clap(long)]
+    sandbox: bool,
+    /// Wipe the chain data and keys of the sandbox and start it on a new chain before the tests
+    #[interactive_clap(long)]
+    reset: bool,
+    /// Run only the tests with names containing this string
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    filter: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestCommandContext;
+
+impl TestCommandContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<TestCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let config = previous_context.config;
+        if scope.reset && !scope.sandbox {
+            color_eyre::eyre::bail!("Only the sandbox is reset, so --reset requires --sandbox");
+        }
+        let manifest_path: Utf8PathBuf = scope
+            .manifest_path
+            .clone()
+            .map_or_else(|| "Cargo.toml".into(), Into::into);
+        let crate_metadata = util::handle_step("Collecting cargo project metadata...", || {
+            CrateMetadata::collect(CargoManifestPath::try_from(manifest_path)?)
+        })?;
+        let project_dir = crate_metadata.manifest_path.directory()?.as_std_path();
+        let target_dir = crate_metadata.target_directory.as_std_path();
+
+        // The contract is built once here instead of in every test.
+        let artifact = build::run(BuildCommand {
+            no_release: scope.no_release,
+            manifest_path: scope.manifest_path.clone(),
+            features: scope.features.clone(),
+            no_default_features: scope.no_default_features,
+            ..Default::default()
+        })?;
+
+        // The fixtures are loaded into the sandbox node, so there is nothing to load them into without it.
+        let fixtures_path = match (&scope.fixtures, scope.sandbox) {
+            (Some(fixtures_path), true) => Some(fixtures_path.0.clone()),
+            (Some(_), false) => {
+                color_eyre::eyre::bail!("The fixtures are only loaded with --sandbox")
+            }
+            (None, sandbox) => {
+                let fixtures_path = project_dir.join(fixtures::DEFAULT_FIXTURES_FILE);
+                if !fixtures_path.is_file() {
+                    None
+                } else if sandbox {
+                    Some(fixtures_path)
+                } else {
+                    eprintln!(
+                        "Warning: {fixtures_path:?} is ignored, the fixtures are only loaded with --sandbox"
+                    );
+                    None
+                }
+            }
+        };
+        let fixtures = match &fixtures_path {
+            Some(fixtures_path) => {
+                let base_dir = fixtures_path.parent().unwrap_or(project_dir);
+                Some(fixtures::Fixtures::read(fixtures_path)?.resolve(
+                    &config,
+                    base_dir,
+                    &target_dir.join("fixtures"),
+                )?)
+            }
+            None => None,
+        };
+
+        let mut cmd = std::process::Command::new("cargo");
+        cmd.arg("test")
+            .arg("--manifest-path")
+            .arg(crate_metadata.manifest_path.path.as_std_path());
+        if let Some(features) = &scope.features {
+            cmd.args(["--features", features]);
+        }
+        if scope.no_default_features {
+            cmd.arg("--no-default-features");
+        }
+        if let Some(filter) = &scope.filter {
+            cmd.args(["--", filter]);
+        }
+        cmd.env("UNC_CONTRACT_WASM", artifact.path.as_std_path());
+
+        if let Some(fixtures) = &fixtures {
+            let resolved_fixtures_path = target_dir.join("test-fixtures.json");
+            std::fs::write(
+                &resolved_fixtures_path,
+                serde_json::to_vec_pretty(fixtures)?,
+            )
+            .wrap_err_with(|| format!("Failed to write to file: {resolved_fixtures_path:?}"))?;
+            cmd.env("UNC_TEST_FIXTURES", resolved_fixtures_path);
+        }
+
+        let gas_report_path = target_dir.join("test-gas-report.jsonl");
+        if gas_report_path.is_file() {
+            std::fs::remove_file(&gas_report_path)
+                .wrap_err_with(|| format!("Failed to remove file: {gas_report_path:?}"))?;
+        }
+        cmd.env("UNC_TEST_GAS_REPORT", &gas_report_path);
+
+        let sandbox = if scope.sandbox {
+            let sandbox = Sandbox::in_default_dir()?;
+            let (state, was_running) = sandbox.start_for_tests(config.clone(), scope.reset)?;
+            if let Some(fixtures) = &fixtures {
+                util::handle_step("Loading the fixtures into the sandbox node...", || {
+                    fixtures.apply_to_sandbox(&state.rpc_url, &sandbox.validator_key()?.public_key)
+                })?;
+            }
+            cmd.env("UNC_SANDBOX_RPC_URL", state.rpc_url.as_str())
+                .env("UNC_SANDBOX_HOME", sandbox.node_home())
+                .env(
+                    "UNC_SANDBOX_ROOT_ACCOUNT_ID",
+                    state.root_account_id.as_str(),
+                );
+            (!was_running).then_some(sandbox)
+        } else {
+            None
+        };
+
+        eprintln!(" {} Running the tests...", "•".bold().cyan());
+        let status = cmd
+            .status()
+            .wrap_err_with(|| format!("Error executing `{:?}`", cmd));
+
+        // A sandbox node started for the tests does not outlive them.
+        let stop_result = match sandbox {
+            Some(sandbox) => sandbox.stop().map(drop),
+            None => Ok(()),
+        };
+        if gas_report_path.is_file() {
+            gas_report::print(&gas_report::read(&gas_report_path)?);
+        }
+
+        let status = status?;
+        match (status.success(), stop_result) {
+            (true, Ok(())) => {}
+            (true, Err(err)) => {
+                return Err(err.wrap_err("The tests passed, but the sandbox node failed to stop"))
+            }
+            (false, Ok(())) => {
+                color_eyre::eyre::bail!("The tests failed with exit code: {:?}", status.code())
+            }
+            (false, Err(err)) => color_eyre::eyre::bail!(
+                "The tests failed with exit code: {:?}, and the sandbox node failed to stop: {err:#}",
+                status.code()
+            ),
+        }
+        util::print_success("The tests passed");
+        Ok(Self)
+    }
+}
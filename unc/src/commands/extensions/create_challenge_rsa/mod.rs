@@ -26,10 +26,11 @@ impl CreateChallengeRsaContext {
         previous_context: crate::GlobalContext,
         scope: &<CreateChallengeRsaCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            receiver_account_id: scope.account_id.clone().into(),
-            signer_account_id: scope.account_id.clone().into(),
+            receiver_account_id: account_id.clone(),
+            signer_account_id: account_id,
         })
     }
 }
@@ -31,9 +31,10 @@ impl PledgeDelegationContext {
         previous_context: crate::GlobalContext,
         scope: &<PledgeDelegation as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let account_id = scope.account_id.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            account_id: scope.account_id.clone().into(),
+            account_id,
         })
     }
 }
@@ -18,11 +18,13 @@ impl PledgeAllContext {
         previous_context: super::PledgeDelegationContext,
         scope: &<PledgeAll as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let validator_account_id = scope
+            .validator_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
                 let signer_id = previous_context.account_id.clone();
-                let validator_account_id: unc_primitives::types::AccountId =
-                    scope.validator_account_id.clone().into();
+                let validator_account_id = validator_account_id.clone();
 
                 move |_network_config| {
                     Ok(crate::commands::PrepopulatedTransaction {
@@ -42,7 +44,7 @@ impl PledgeAllContext {
 
         let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
             let signer_id = previous_context.account_id.clone();
-            let validator_id = scope.validator_account_id.clone();
+            let validator_id = validator_account_id.clone();
 
             move |outcome_view, _network_config| {
                 if let unc_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
@@ -54,10 +56,7 @@ impl PledgeAllContext {
 
         Ok(Self(crate::commands::ActionContext {
             global_context: previous_context.global_context,
-            interacting_with_account_ids: vec![
-                previous_context.account_id,
-                scope.validator_account_id.clone().into(),
-            ],
+            interacting_with_account_ids: vec![previous_context.account_id, validator_account_id],
             on_after_getting_network_callback,
             on_before_signing_callback: std::sync::Arc::new(
                 |_prepolulated_unsinged_transaction, _network_config| Ok(()),
@@ -20,11 +20,13 @@ impl UnpledgeContext {
         previous_context: super::PledgeDelegationContext,
         scope: &<Unpledge as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let validator_account_id = scope
+            .validator_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
                 let signer_id = previous_context.account_id.clone();
-                let validator_account_id: unc_primitives::types::AccountId =
-                    scope.validator_account_id.clone().into();
+                let validator_account_id = validator_account_id.clone();
                 let amount = scope.amount;
 
                 move |_network_config| {
@@ -47,7 +49,7 @@ impl UnpledgeContext {
 
         let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
             let signer_id = previous_context.account_id.clone();
-            let validator_id = scope.validator_account_id.clone();
+            let validator_id = validator_account_id.clone();
             let amount = scope.amount;
 
             move |outcome_view, _network_config| {
@@ -60,10 +62,7 @@ impl UnpledgeContext {
 
         Ok(Self(crate::commands::ActionContext {
             global_context: previous_context.global_context,
-            interacting_with_account_ids: vec![
-                previous_context.account_id,
-                scope.validator_account_id.clone().into(),
-            ],
+            interacting_with_account_ids: vec![previous_context.account_id, validator_account_id],
             on_after_getting_network_callback,
             on_before_signing_callback: std::sync::Arc::new(
                 |_prepolulated_unsinged_transaction, _network_config| Ok(()),
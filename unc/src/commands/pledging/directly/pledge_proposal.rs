@@ -30,9 +30,10 @@ impl PledgeProposalContext {
         previous_context: crate::GlobalContext,
         scope: &<PledgeProposal as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let validator = scope.validator.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            validator: scope.validator.clone().into(),
+            validator,
             public_key: scope.public_key.clone().into(),
             pledge: scope.pledge,
         })
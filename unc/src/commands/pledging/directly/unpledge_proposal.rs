@@ -24,9 +24,10 @@ impl UnpledgeProposalContext {
         previous_context: crate::GlobalContext,
         scope: &<UnpledgeProposal as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let validator = scope.validator.resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            validator: scope.validator.clone().into(),
+            validator,
             public_key: scope.public_key.clone().into(),
         })
     }
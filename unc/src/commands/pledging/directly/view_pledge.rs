@@ -22,8 +22,11 @@ impl ViewPledgeContext {
         previous_context: crate::GlobalContext,
         scope: &<ViewPledge as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let validator_account_id = scope
+            .validator_account_id
+            .resolve(&previous_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
-            let validator_account_id: unc_primitives::types::AccountId = scope.validator_account_id.clone().into();
+            let validator_account_id = validator_account_id.clone();
 
             move |network_config, block_reference| {
                 let json_rpc_client = network_config.json_rpc_client();
@@ -46,7 +49,7 @@ impl ViewPledgeContext {
         });
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
             config: previous_context.config,
-            interacting_with_account_ids: vec![scope.validator_account_id.clone().into()],
+            interacting_with_account_ids: vec![validator_account_id],
             on_after_getting_block_reference_callback,
         }))
     }
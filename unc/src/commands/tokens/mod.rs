@@ -29,9 +29,12 @@ impl TokensCommandsContext {
         previous_context: crate::GlobalContext,
         scope: &<TokensCommands as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id = scope
+            .owner_account_id
+            .resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            owner_account_id: scope.owner_account_id.clone().into(),
+            owner_account_id,
         })
     }
 }
@@ -31,6 +31,12 @@ impl AmountFtContext {
         previous_context: super::SendFtCommandContext,
         scope: &<AmountFt as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.global_context.offline {
+            color_eyre::eyre::bail!(
+                "The metadata of the FT contract <{}> is needed for the amount, it cannot be fetched in offline mode",
+                previous_context.ft_contract_account_id
+            );
+        }
         let network_config = crate::common::find_network_where_account_exist(
             &previous_context.global_context,
             previous_context.ft_contract_account_id.clone(),
@@ -61,6 +67,12 @@ impl AmountFt {
     fn input_amount_ft(
         context: &super::SendFtCommandContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::ft_properties::FungibleToken>> {
+        if context.global_context.offline {
+            color_eyre::eyre::bail!(
+                "The metadata of the FT contract <{}> is needed for the amount, it cannot be fetched in offline mode",
+                context.ft_contract_account_id
+            );
+        }
         let network_config = crate::common::find_network_where_account_exist(
             &context.global_context,
             context.ft_contract_account_id.clone(),
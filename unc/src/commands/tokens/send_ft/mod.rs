@@ -28,11 +28,17 @@ impl SendFtCommandContext {
         previous_context: super::TokensCommandsContext,
         scope: &<SendFtCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let ft_contract_account_id = scope
+            .ft_contract_account_id
+            .resolve(&previous_context.global_context.config, None)?;
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         Ok(Self {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.owner_account_id,
-            ft_contract_account_id: scope.ft_contract_account_id.clone().into(),
-            receiver_account_id: scope.receiver_account_id.clone().into(),
+            ft_contract_account_id,
+            receiver_account_id,
         })
     }
 }
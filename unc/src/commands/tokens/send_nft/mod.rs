@@ -42,11 +42,17 @@ impl SendNftCommandContext {
         previous_context: super::TokensCommandsContext,
         scope: &<SendNftCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let nft_contract_account_id = scope
+            .nft_contract_account_id
+            .resolve(&previous_context.global_context.config, None)?;
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         Ok(Self {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.owner_account_id,
-            nft_contract_account_id: scope.nft_contract_account_id.clone().into(),
-            receiver_account_id: scope.receiver_account_id.clone().into(),
+            nft_contract_account_id,
+            receiver_account_id,
             token_id: scope.token_id.clone(),
             gas: scope.gas,
             deposit: scope.deposit,
@@ -25,10 +25,13 @@ impl SendUncCommandContext {
         previous_context: super::TokensCommandsContext,
         scope: &<SendUncCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         Ok(Self {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.owner_account_id,
-            receiver_account_id: scope.receiver_account_id.clone().into(),
+            receiver_account_id,
             amount_in_unc: scope.amount_in_unc,
         })
     }
@@ -24,10 +24,12 @@ impl ViewFtBalanceContext {
         previous_context: super::TokensCommandsContext,
         scope: &<ViewFtBalance as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let ft_contract_account_id = scope
+            .ft_contract_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let owner_account_id = previous_context.owner_account_id.clone();
-            let ft_contract_account_id: unc_primitives::types::AccountId =
-                scope.ft_contract_account_id.clone().into();
+            let ft_contract_account_id = ft_contract_account_id.clone();
 
             move |network_config, block_reference| {
                 let crate::types::ft_properties::FtMetadata { decimals, symbol } = crate::types::ft_properties::params_ft_metadata(
@@ -70,7 +72,7 @@ impl ViewFtBalanceContext {
             config: previous_context.global_context.config,
             on_after_getting_block_reference_callback,
             interacting_with_account_ids: vec![
-                scope.ft_contract_account_id.clone().into(),
+                ft_contract_account_id,
                 previous_context.owner_account_id,
             ],
         }))
@@ -24,10 +24,12 @@ impl ViewNftAssetsContext {
         previous_context: super::TokensCommandsContext,
         scope: &<ViewNftAssets as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let nft_contract_account_id = scope
+            .nft_contract_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let owner_account_id = previous_context.owner_account_id.clone();
-            let nft_contract_account_id: unc_primitives::types::AccountId =
-                scope.nft_contract_account_id.clone().into();
+            let nft_contract_account_id = nft_contract_account_id.clone();
 
             move |network_config, block_reference| {
                 let args = serde_json::to_vec(&json!({
@@ -59,7 +61,7 @@ impl ViewNftAssetsContext {
             config: previous_context.global_context.config,
             on_after_getting_block_reference_callback,
             interacting_with_account_ids: vec![
-                scope.nft_contract_account_id.clone().into(),
+                nft_contract_account_id,
                 previous_context.owner_account_id,
             ],
         }))
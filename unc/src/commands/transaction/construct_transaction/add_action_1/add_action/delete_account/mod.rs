@@ -20,7 +20,9 @@ impl DeleteAccountActionContext {
         previous_context: super::super::super::ConstructTransactionContext,
         scope: &<DeleteAccountAction as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
-        let beneficiary_id: unc_primitives::types::AccountId = scope.beneficiary_id.clone().into();
+        let beneficiary_id: unc_primitives::types::AccountId = scope
+            .beneficiary_id
+            .resolve(&previous_context.global_context.config, None)?;
         let action = unc_primitives::transaction::Action::DeleteAccount(
             unc_primitives::transaction::DeleteAccountAction { beneficiary_id },
         );
@@ -50,6 +52,7 @@ impl DeleteAccountAction {
                 CustomType::new("What is the beneficiary account ID?").prompt()?;
 
             if context.global_context.offline {
+                crate::common::warn_account_check_skipped_offline(&beneficiary_account_id);
                 return Ok(Some(beneficiary_account_id));
             }
 
@@ -73,7 +76,7 @@ impl DeleteAccountAction {
             if let ConfirmOptions::Yes { account_id } = select_choose_input {
                 if crate::common::find_network_where_account_exist(
                     &context.global_context,
-                    account_id.clone().into(),
+                    account_id.resolve(&context.global_context.config, None)?,
                 )
                 .is_none()
                 {
@@ -71,7 +71,10 @@ impl FunctionCallTypeContext {
         let access_key_permission = unc_primitives::account::AccessKeyPermission::FunctionCall(
             unc_primitives::account::FunctionCallPermission {
                 allowance: scope.allowance.map(|allowance| allowance.as_attounc()),
-                receiver_id: scope.receiver_account_id.to_string(),
+                receiver_id: scope
+                    .receiver_account_id
+                    .resolve(&previous_context.global_context.config, None)?
+                    .to_string(),
                 method_names: scope.method_names.clone().into(),
             },
         );
@@ -31,10 +31,16 @@ impl ConstructTransactionContext {
         previous_context: crate::GlobalContext,
         scope: &<ConstructTransaction as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let sender_account_id = scope
+            .sender_account_id
+            .resolve(&previous_context.config, None)?;
+        let receiver_account_id = scope
+            .receiver_account_id
+            .resolve(&previous_context.config, None)?;
         Ok(Self {
             global_context: previous_context,
-            signer_account_id: scope.sender_account_id.clone().into(),
-            receiver_account_id: scope.receiver_account_id.clone().into(),
+            signer_account_id: sender_account_id,
+            receiver_account_id,
             actions: vec![],
         })
     }
@@ -2,6 +2,7 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 pub mod construct_transaction;
+mod prepare_online_context;
 mod print_transaction;
 mod reconstruct_transaction;
 mod send_meta_transaction;
@@ -57,4 +58,9 @@ pub enum TransactionActions {
     ))]
     /// Act as a relayer to send a signed delegate action (meta-transaction)
     SendMetaTransaction(self::send_meta_transaction::SendMetaTransaction),
+    #[strum_discriminants(strum(
+        message = "prepare-online-context   - Save a recent block and access key nonces for signing transactions in offline mode"
+    ))]
+    /// Save a recent block and access key nonces for signing transactions in offline mode
+    PrepareOnlineContext(self::prepare_online_context::PrepareOnlineContext),
 }
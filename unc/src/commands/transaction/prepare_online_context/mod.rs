@@ -0,0 +1,145 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+use crate::transaction_signature_options::online_context::{OnlineAccessKey, OnlineContext};
+
+const DEFAULT_OUTPUT_FILE: &str = "online-context.json";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = PrepareOnlineContextContext)]
+pub struct PrepareOnlineContext {
+    /// Which accounts will sign transactions offline (comma-separated)?
+    account_ids: crate::types::vec_string::VecString,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Where to save the online context (online-context.json by default)
+    output_file: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct PrepareOnlineContextContext(crate::network::NetworkContext);
+
+impl PrepareOnlineContextContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<PrepareOnlineContext as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.offline {
+            color_eyre::eyre::bail!("The online context is prepared from the network, it cannot be done in offline mode");
+        }
+        let mut account_ids = scope
+            .account_ids
+            .0
+            .iter()
+            .filter(|account_id| !account_id.is_empty())
+            .map(|account_id| {
+                account_id
+                    .parse::<crate::types::account_id::AccountId>()
+                    .wrap_err_with(|| format!("Invalid account ID: {account_id}"))?
+                    .resolve(&previous_context.config, None)
+            })
+            .collect::<color_eyre::eyre::Result<Vec<unc_primitives::types::AccountId>>>()?;
+        account_ids.sort();
+        account_ids.dedup();
+        if account_ids.is_empty() {
+            color_eyre::eyre::bail!("There are no accounts to prepare the online context for");
+        }
+        let output_file = scope
+            .output_file
+            .as_ref()
+            .map(|output_file| output_file.0.clone())
+            .unwrap_or_else(|| DEFAULT_OUTPUT_FILE.into());
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_ids = account_ids.clone();
+
+                move |network_config| {
+                    let online_context = crate::util::handle_step(
+                        "Fetching the recent block and the access keys...",
+                        || fetch_online_context(network_config, &account_ids),
+                    )?;
+                    online_context.write(&output_file)?;
+                    eprintln!(
+                        "The online context for {} access key(s) at block #{} is saved to {}. Pass it to `unc --online-context` on the offline machine.",
+                        online_context.access_keys.len(),
+                        online_context.block_height,
+                        output_file.display()
+                    );
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: account_ids,
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<PrepareOnlineContextContext> for crate::network::NetworkContext {
+    fn from(item: PrepareOnlineContextContext) -> Self {
+        item.0
+    }
+}
+
+/// The nonces of all access keys of the accounts, all taken at the same final block.
+fn fetch_online_context(
+    network_config: &crate::config::NetworkConfig,
+    account_ids: &[unc_primitives::types::AccountId],
+) -> color_eyre::eyre::Result<OnlineContext> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let block = json_rpc_client
+        .blocking_call(unc_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: unc_primitives::types::Finality::Final.into(),
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the final block on network <{}>",
+                network_config.network_name
+            )
+        })?;
+
+    let mut access_keys = Vec::new();
+    for account_id in account_ids {
+        let access_key_list = json_rpc_client
+            .blocking_call_view_access_key_list(
+                account_id,
+                unc_primitives::types::BlockReference::BlockId(
+                    unc_primitives::types::BlockId::Hash(block.header.hash),
+                ),
+            )
+            .wrap_err_with(|| format!("Failed to fetch the access keys of <{account_id}>"))?
+            .access_key_list_view()?;
+        if access_key_list.keys.is_empty() {
+            eprintln!("Warning: account <{account_id}> has no access keys.");
+        }
+        access_keys.extend(
+            access_key_list
+                .keys
+                .into_iter()
+                .map(|access_key| OnlineAccessKey {
+                    account_id: account_id.clone(),
+                    public_key: access_key.public_key,
+                    nonce: access_key.access_key.nonce,
+                    full_access: matches!(
+                        access_key.access_key.permission,
+                        unc_primitives::views::AccessKeyPermissionView::FullAccess
+                    ),
+                }),
+        );
+    }
+
+    Ok(OnlineContext {
+        network_name: network_config.network_name.clone(),
+        block_hash: block.header.hash,
+        block_height: block.header.height,
+        created_at: crate::common::unix_time_now(),
+        access_keys,
+    })
+}
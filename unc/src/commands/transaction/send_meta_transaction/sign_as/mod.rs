@@ -20,10 +20,12 @@ impl RelayerAccountIdContext {
         previous_context: super::SendMetaTransactionContext,
         scope: &<RelayerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let relayer_account_id = scope
+            .relayer_account_id
+            .resolve(&previous_context.global_context.config, None)?;
         let on_after_getting_network_callback: crate::commands::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
-                let signer_id: unc_primitives::types::AccountId =
-                    scope.relayer_account_id.clone().into();
+                let signer_id = relayer_account_id.clone();
                 let signed_delegate_action = previous_context.signed_delegate_action.clone();
 
                 move |_network_config| {
@@ -50,7 +52,7 @@ impl RelayerAccountIdContext {
 
         Ok(Self(crate::commands::ActionContext {
             global_context: previous_context.global_context,
-            interacting_with_account_ids: vec![scope.relayer_account_id.clone().into()],
+            interacting_with_account_ids: vec![relayer_account_id],
             on_after_getting_network_callback,
             on_before_signing_callback,
             on_before_sending_transaction_callback: std::sync::Arc::new(
@@ -85,12 +87,13 @@ impl RelayerAccountId {
             };
 
             if context.global_context.offline {
+                crate::common::warn_account_check_skipped_offline(&relayer_account_id);
                 return Ok(Some(relayer_account_id));
             }
 
             if !crate::common::is_account_exist(
                 &context.global_context.config.network_connection,
-                relayer_account_id.clone().into(),
+                relayer_account_id.resolve(&context.global_context.config, None)?,
             ) {
                 eprintln!(
                     "\nThe account <{relayer_account_id}> does not exist on [{}] networks.",
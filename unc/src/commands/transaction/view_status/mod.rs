@@ -44,6 +44,25 @@ impl TransactionInfoContext {
                         )
                     })?;
                     eprintln!("Transaction status: {:#?}", query_view_transaction_status);
+                    if let unc_primitives::views::FinalExecutionStatus::SuccessValue(result) =
+                        &query_view_transaction_status.status
+                    {
+                        if !result.is_empty() {
+                            let decoded_result =
+                                match serde_json::from_slice::<serde_json::Value>(result) {
+                                    Ok(json_result) => Some(json_result),
+                                    Err(_) => crate::commands::contract::call_function::abi::decode_transaction_result_or_warn(
+                                        network_config,
+                                        &query_view_transaction_status,
+                                        result,
+                                    ),
+                                };
+                            if let Some(decoded_result) = decoded_result {
+                                eprintln!("Result:");
+                                println!("{}", serde_json::to_string_pretty(&decoded_result)?);
+                            }
+                        }
+                    }
                     Ok(())
                 }
             });
@@ -180,13 +180,13 @@ pub fn get_account_transfer_allowance(
             pessimistic_transaction_fee: unc_token::UncToken::from_unc(0),
         });
     };
-    let storage_amount_per_byte = tokio::runtime::Runtime::new()
-        .unwrap()
-        .block_on(network_config.json_rpc_client().call(
+    let storage_amount_per_byte = network_config
+        .json_rpc_client()
+        .blocking_call(
             unc_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
                 block_reference: unc_primitives::types::Finality::Final.into(),
             },
-        ))
+        )
         .wrap_err("RpcError")?
         .runtime_config
         .storage_amount_per_byte;
@@ -285,10 +285,19 @@ pub fn is_account_exist(
     false
 }
 
+/// Checks of accounts on the network are skipped in offline mode, with a warning.
+pub fn warn_account_check_skipped_offline(account_id: &impl std::fmt::Display) {
+    eprintln!("Offline mode: the check of account <{account_id}> on the network is skipped.");
+}
+
 pub fn find_network_where_account_exist(
     context: &crate::GlobalContext,
     new_account_id: unc_primitives::types::AccountId,
 ) -> Option<crate::config::NetworkConfig> {
+    if context.offline {
+        warn_account_check_skipped_offline(&new_account_id);
+        return None;
+    }
     for (_, network_config) in context.config.network_connection.iter() {
         if get_account_state(
             network_config.clone(),
@@ -1239,6 +1248,14 @@ pub fn print_transaction_status(
                 serde_json::from_slice::<serde_json::Value>(bytes_result)
             {
                 println!("{}", serde_json::to_string_pretty(&json_result)?);
+            } else if let Some(decoded_result) =
+                crate::commands::contract::call_function::abi::decode_transaction_result_or_warn(
+                    network_config,
+                    transaction_info,
+                    bytes_result,
+                )
+            {
+                println!("{}", serde_json::to_string_pretty(&decoded_result)?);
             } else if let Ok(string_result) = String::from_utf8(bytes_result.clone()) {
                 println!("{string_result}");
             } else {
@@ -1267,6 +1284,75 @@ pub fn print_async_transaction_status(
     Ok(())
 }
 
+/// Describes why a sent transaction did not succeed: the transaction or one of its receipts
+/// failed, or the called contract returned `false`.
+pub fn outcome_error(outcome: &unc_primitives::views::FinalExecutionOutcomeView) -> Option<String> {
+    match &outcome.status {
+        unc_primitives::views::FinalExecutionStatus::Failure(err) => return Some(err.to_string()),
+        unc_primitives::views::FinalExecutionStatus::SuccessValue(value)
+            if value.as_slice() == b"false" =>
+        {
+            return Some("the contract returned `false`".to_string())
+        }
+        _ => {}
+    }
+    outcome
+        .receipts_outcome
+        .iter()
+        .find_map(|receipt| match &receipt.outcome.status {
+            unc_primitives::views::ExecutionStatusView::Failure(err) => Some(err.to_string()),
+            _ => None,
+        })
+}
+
+const TRANSACTION_OUTCOME_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Polls the status of a transaction sent with `broadcast_tx_async` until its final outcome is available.
+pub fn wait_for_transaction_outcome(
+    network_config: &crate::config::NetworkConfig,
+    tx_hash: &CryptoHash,
+    sender_account_id: &unc_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<unc_primitives::views::FinalExecutionOutcomeView> {
+    eprintln!("Waiting for the transaction to be final ...");
+    let deadline = std::time::Instant::now() + TRANSACTION_OUTCOME_TIMEOUT;
+    while std::time::Instant::now() < deadline {
+        match network_config.json_rpc_client().blocking_call(
+            unc_jsonrpc_client::methods::tx::RpcTransactionStatusRequest {
+                transaction_info: unc_jsonrpc_client::methods::tx::TransactionInfo::TransactionId {
+                    tx_hash: *tx_hash,
+                    sender_account_id: sender_account_id.clone(),
+                },
+            },
+        ) {
+            Ok(outcome)
+                if !matches!(
+                    outcome.status,
+                    unc_primitives::views::FinalExecutionStatus::NotStarted
+                        | unc_primitives::views::FinalExecutionStatus::Started
+                ) =>
+            {
+                return Ok(outcome)
+            }
+            // The transaction is not included into a block yet.
+            Ok(_)
+            | Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+                unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    unc_jsonrpc_client::methods::tx::RpcTransactionError::UnknownTransaction {
+                        ..
+                    },
+                ),
+            )) => {}
+            Err(err) => rpc_transaction_error(err)?,
+        }
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    color_eyre::eyre::bail!(
+        "The outcome of transaction {tx_hash} is not available yet, check it later with:\n$ {} transaction view-status {tx_hash} network-config {}",
+        get_unc_exec_path(),
+        network_config.network_name
+    )
+}
+
 pub fn save_access_key_to_keychain(
     network_config: crate::config::NetworkConfig,
     key_pair_properties_buf: &str,
@@ -1508,14 +1594,16 @@ pub fn input_pledging_pool_validator_account_id(
                 .cloned()
                 .collect())
         })
-        .with_validator(|account_id_str: &str| {
-            match unc_primitives::types::AccountId::validate(account_id_str) {
+        .with_validator(
+            |account_id_str: &str| match crate::types::account_id::AccountId::from_str(
+                account_id_str,
+            ) {
                 Ok(_) => Ok(inquire::validator::Validation::Valid),
                 Err(err) => Ok(inquire::validator::Validation::Invalid(
                     inquire::validator::ErrorMessage::Custom(format!("Invalid account ID: {err}")),
                 )),
-            }
-        })
+            },
+        )
         .prompt()
     {
         Ok(value) => value,
@@ -1527,10 +1615,9 @@ pub fn input_pledging_pool_validator_account_id(
     };
     let validator_account_id =
         crate::types::account_id::AccountId::from_str(&validator_account_id_str)?;
-    update_used_account_list_as_non_signer(
-        &config.credentials_home_dir,
-        validator_account_id.as_ref(),
-    );
+    if let Some(account_id) = validator_account_id.as_account_id() {
+        update_used_account_list_as_non_signer(&config.credentials_home_dir, account_id);
+    }
     Ok(Some(validator_account_id))
 }
 
@@ -1555,24 +1642,11 @@ pub fn get_validator_list(
 
     let validators_pledge = get_validators_pledge(&json_rpc_client)?;
 
-    let runtime = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    let concurrency = 10;
-
-    let mut validator_list = runtime.block_on(
-        futures::stream::iter(validators_pledge.iter())
-            .map(|(validator_account_id, pledge)| async {
-                get_pledging_pool_info(
-                    &json_rpc_client.clone(),
-                    validator_account_id.clone(),
-                    *pledge,
-                )
-                .await
-            })
-            .buffer_unordered(concurrency)
-            .try_collect::<Vec<_>>(),
-    )?;
+    let mut validator_list = block_on_concurrently(validators_pledge.iter().map(
+        |(validator_account_id, pledge)| {
+            get_pledging_pool_info(&json_rpc_client, validator_account_id.clone(), *pledge)
+        },
+    ))?;
     validator_list.sort_by(|a, b| b.pledge.cmp(&a.pledge));
     Ok(validator_list)
 }
@@ -1624,17 +1698,26 @@ async fn get_pledging_pool_info(
     validator_account_id: unc_primitives::types::AccountId,
     pledge: u128,
 ) -> color_eyre::Result<PledgingPoolInfo> {
-    let fee = match json_rpc_client
-        .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+    let (fee_response, delegators_response) = futures::join!(
+        json_rpc_client.call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
             block_reference: unc_primitives::types::Finality::Final.into(),
             request: unc_primitives::views::QueryRequest::CallFunction {
                 account_id: validator_account_id.clone(),
                 method_name: "get_reward_fee_fraction".to_string(),
                 args: unc_primitives::types::FunctionArgs::from(vec![]),
             },
-        })
-        .await
-    {
+        }),
+        json_rpc_client.call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: unc_primitives::types::Finality::Final.into(),
+            request: unc_primitives::views::QueryRequest::CallFunction {
+                account_id: validator_account_id.clone(),
+                method_name: "get_number_of_accounts".to_string(),
+                args: unc_primitives::types::FunctionArgs::from(vec![]),
+            },
+        }),
+    );
+
+    let fee = match fee_response {
         Ok(response) => Some(
             response
                 .call_result()?
@@ -1654,17 +1737,7 @@ async fn get_pledging_pool_info(
         Err(err) => return Err(err.into()),
     };
 
-    let delegators = match json_rpc_client
-        .call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
-            block_reference: unc_primitives::types::Finality::Final.into(),
-            request: unc_primitives::views::QueryRequest::CallFunction {
-                account_id: validator_account_id.clone(),
-                method_name: "get_number_of_accounts".to_string(),
-                args: unc_primitives::types::FunctionArgs::from(vec![]),
-            },
-        })
-        .await
-    {
+    let delegators = match delegators_response {
         Ok(response) => Some(
             response
                 .call_result()?
@@ -1890,6 +1963,41 @@ pub fn input_network_name(
     }
 }
 
+/// The number of RPC requests that bulk commands keep in flight at the same time.
+pub const RPC_CONCURRENCY: usize = 10;
+
+/// The async runtime shared by all the RPC calls of the process.
+///
+/// It is created on the first use and lives until the process exits, so that the connections
+/// opened by one call stay in the pool for the next ones.
+pub fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create the async runtime")
+    })
+}
+
+/// Runs the future to completion on the shared runtime.
+pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    runtime().block_on(future)
+}
+
+/// Runs the futures on the shared runtime with at most [`RPC_CONCURRENCY`] of them in flight
+/// and collects their results in the order of completion, stopping at the first error.
+pub fn block_on_concurrently<T, E, F>(futures: impl IntoIterator<Item = F>) -> Result<Vec<T>, E>
+where
+    F: std::future::Future<Output = Result<T, E>>,
+{
+    block_on(
+        futures::stream::iter(futures)
+            .buffer_unordered(RPC_CONCURRENCY)
+            .try_collect(),
+    )
+}
+
 #[easy_ext::ext(JsonRpcClientExt)]
 pub impl unc_jsonrpc_client::JsonRpcClient {
     fn blocking_call<M>(
@@ -1899,9 +2007,7 @@ pub impl unc_jsonrpc_client::JsonRpcClient {
     where
         M: unc_jsonrpc_client::methods::RpcMethod,
     {
-        tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(self.call(method))
+        block_on(self.call(method))
     }
 
     /// A helper function to make a view-funcation call using JSON encoding for the function
@@ -2063,10 +2169,21 @@ pub impl unc_primitives::views::CallResult {
     }
 }
 
+/// The current Unix time in seconds.
+pub fn unix_time_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct UsedAccount {
     pub account_id: unc_primitives::types::AccountId,
     pub used_as_signer: bool,
+    /// Unix time (in seconds) of the last use of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<u64>,
 }
 
 fn get_used_account_list_path(credentials_home_dir: &std::path::Path) -> std::path::PathBuf {
@@ -2106,6 +2223,7 @@ pub fn create_used_account_list_from_keychain(
                 .map(|account_id| UsedAccount {
                     account_id,
                     used_as_signer: true,
+                    last_used_at: None,
                 })
                 .collect::<Vec<_>>(),
         )?;
@@ -2142,7 +2260,7 @@ fn update_used_account_list(
 ) {
     let mut used_account_list = get_used_account_list(credentials_home_dir);
 
-    let used_account = if let Some(mut used_account) = used_account_list
+    let mut used_account = if let Some(mut used_account) = used_account_list
         .iter()
         .position(|used_account| &used_account.account_id == account_id)
         .and_then(|position| used_account_list.remove(position))
@@ -2153,8 +2271,10 @@ fn update_used_account_list(
         UsedAccount {
             account_id: account_id.clone(),
             used_as_signer: account_is_signer,
+            last_used_at: None,
         }
     };
+    used_account.last_used_at = Some(unix_time_now());
     used_account_list.push_front(used_account);
 
     let used_account_list_path = get_used_account_list_path(credentials_home_dir);
@@ -2177,6 +2297,142 @@ pub fn is_used_account_list_exist(credentials_home_dir: &std::path::Path) -> boo
     get_used_account_list_path(credentials_home_dir).exists()
 }
 
+/// Named aliases of accounts (e.g. `@treasury`), which can be used wherever an account ID is expected.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AddressBook {
+    #[serde(default)]
+    pub aliases: std::collections::BTreeMap<String, AddressBookEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AddressBookEntry {
+    /// The account on the networks without their own mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<unc_primitives::types::AccountId>,
+    /// The accounts by network name.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub networks: std::collections::BTreeMap<String, unc_primitives::types::AccountId>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl AddressBookEntry {
+    pub fn is_empty(&self) -> bool {
+        self.account_id.is_none() && self.networks.is_empty()
+    }
+
+    pub fn account_ids(&self) -> impl Iterator<Item = &unc_primitives::types::AccountId> {
+        self.account_id.iter().chain(self.networks.values())
+    }
+}
+
+impl AddressBook {
+    /// Resolves an alias reference: `alias` or `alias:network`. A bare alias takes the mapping of
+    /// the network of the command (when it is known), or the account of the networks without their own mapping.
+    pub fn resolve(
+        &self,
+        reference: &str,
+        network_name: Option<&str>,
+    ) -> Result<unc_primitives::types::AccountId, String> {
+        let (alias, network_name) = match reference.split_once(':') {
+            Some((alias, network_name)) => (alias, Some(network_name)),
+            None => (reference, network_name),
+        };
+        let entry = self
+            .aliases
+            .get(alias)
+            .ok_or_else(|| format!("There is no alias @{alias} in the address book"))?;
+        match network_name {
+            Some(network_name) => entry
+                .networks
+                .get(network_name)
+                .or(entry.account_id.as_ref())
+                .cloned()
+                .ok_or_else(|| {
+                    format!("The alias @{alias} has no account on network <{network_name}>")
+                }),
+            // Without the network, the account of another network must not be picked by mistake.
+            None if entry.networks.is_empty() => entry
+                .account_id
+                .clone()
+                .ok_or_else(|| format!("The alias @{alias} has no account")),
+            None => Err(format!(
+                "The alias @{alias} has accounts for networks {}: use @{alias}:<network>",
+                entry
+                    .networks
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// The aliases that point to the account, with the network if the mapping is for one network only.
+    pub fn aliases_of(&self, account_id: &unc_primitives::types::AccountId) -> Vec<String> {
+        let mut aliases = vec![];
+        for (alias, entry) in &self.aliases {
+            if entry.account_id.as_ref() == Some(account_id) {
+                aliases.push(format!("@{alias}"));
+            }
+            for (network_name, _) in entry
+                .networks
+                .iter()
+                .filter(|(_, network_account_id)| *network_account_id == account_id)
+            {
+                aliases.push(format!("@{alias}:{network_name}"));
+            }
+        }
+        aliases
+    }
+}
+
+pub fn is_valid_alias(alias: &str) -> bool {
+    !alias.is_empty()
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn get_address_book_path(credentials_home_dir: &std::path::Path) -> std::path::PathBuf {
+    credentials_home_dir.join("address-book.json")
+}
+
+pub fn get_address_book(
+    credentials_home_dir: &std::path::Path,
+) -> color_eyre::eyre::Result<AddressBook> {
+    let address_book_path = get_address_book_path(credentials_home_dir);
+    match std::fs::read_to_string(&address_book_path) {
+        Ok(data) => serde_json::from_str(&data).wrap_err_with(|| {
+            format!(
+                "Failed to parse the address book: {}",
+                address_book_path.display()
+            )
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(AddressBook::default()),
+        Err(err) => Err(err).wrap_err_with(|| {
+            format!(
+                "Failed to read the address book: {}",
+                address_book_path.display()
+            )
+        }),
+    }
+}
+
+pub fn save_address_book(
+    credentials_home_dir: &std::path::Path,
+    address_book: &AddressBook,
+) -> crate::CliResult {
+    std::fs::create_dir_all(credentials_home_dir)
+        .wrap_err_with(|| format!("Failed to create directory: {credentials_home_dir:?}"))?;
+    let address_book_path = get_address_book_path(credentials_home_dir);
+    std::fs::write(
+        &address_book_path,
+        serde_json::to_string_pretty(address_book)?,
+    )
+    .wrap_err_with(|| format!("Failed to write to file: {}", address_book_path.display()))
+}
+
 pub fn input_signer_account_id_from_used_account_list(
     credentials_home_dir: &std::path::Path,
     message: &str,
@@ -2198,11 +2454,14 @@ fn input_account_id_from_used_account_list(
     message: &str,
     account_is_signer: bool,
 ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
-    let used_account_list = get_used_account_list(credentials_home_dir)
+    let mut used_account_list = get_used_account_list(credentials_home_dir)
         .into_iter()
         .filter(|account| !account_is_signer || account.used_as_signer)
         .map(|account| account.account_id.to_string())
         .collect::<Vec<_>>();
+    if let Ok(address_book) = get_address_book(credentials_home_dir) {
+        used_account_list.extend(address_book.aliases.keys().map(|alias| format!("@{alias}")));
+    }
     let account_id_str = match Text::new(message)
         .with_autocomplete(move |val: &str| {
             Ok(used_account_list
@@ -2211,14 +2470,16 @@ fn input_account_id_from_used_account_list(
                 .cloned()
                 .collect())
         })
-        .with_validator(|account_id_str: &str| {
-            match unc_primitives::types::AccountId::validate(account_id_str) {
+        .with_validator(
+            |account_id_str: &str| match crate::types::account_id::AccountId::from_str(
+                account_id_str,
+            ) {
                 Ok(_) => Ok(inquire::validator::Validation::Valid),
                 Err(err) => Ok(inquire::validator::Validation::Invalid(
                     inquire::validator::ErrorMessage::Custom(format!("Invalid account ID: {err}")),
                 )),
-            }
-        })
+            },
+        )
         .prompt()
     {
         Ok(value) => value,
@@ -2229,7 +2490,9 @@ fn input_account_id_from_used_account_list(
         Err(err) => return Err(err.into()),
     };
     let account_id = crate::types::account_id::AccountId::from_str(&account_id_str)?;
-    update_used_account_list(credentials_home_dir, account_id.as_ref(), account_is_signer);
+    if let Some(account_id) = account_id.as_account_id() {
+        update_used_account_list(credentials_home_dir, account_id, account_is_signer);
+    }
     Ok(Some(account_id))
 }
 
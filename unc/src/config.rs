@@ -2,6 +2,12 @@
 pub struct Config {
     pub credentials_home_dir: std::path::PathBuf,
     pub network_connection: linked_hash_map::LinkedHashMap<String, NetworkConfig>,
+    /// Named presets of function-call access keys for `account grant-session-key`.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub session_key_presets: std::collections::BTreeMap<String, SessionKeyPreset>,
+    /// Named factory contracts that create accounts (`--factory` of `account create-account fund-myself`).
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub account_factories: std::collections::BTreeMap<String, AccountFactory>,
 }
 
 impl Default for Config {
@@ -57,6 +63,8 @@ impl Default for Config {
         Self {
             credentials_home_dir,
             network_connection,
+            session_key_presets: Default::default(),
+            account_factories: Default::default(),
         }
     }
 }
@@ -70,6 +78,112 @@ impl Config {
     }
 }
 
+/// A reusable kind of function-call access key granted to dapps.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SessionKeyPreset {
+    /// The allowance of the key; the key is not limited without it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowance: Option<crate::types::unc_token::UncToken>,
+    /// The methods the key can call; without them, the non-payable call methods of the contract ABI are used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub method_names: Vec<String>,
+    /// Only the ABI methods matching one of these patterns (`*` matches any characters) are used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub method_filter: Vec<String>,
+    /// How long a session lasts; it is only recorded locally, access keys do not expire on chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in_hours: Option<u64>,
+}
+
+impl SessionKeyPreset {
+    /// The ABI functions that a function-call access key of this preset can call.
+    pub fn abi_method_names(&self, abi_root: &unc_abi::AbiRoot) -> Vec<String> {
+        abi_root
+            .body
+            .functions
+            .iter()
+            .filter(|function| {
+                function.kind == unc_abi::AbiFunctionKind::Call
+                    && function.modifiers.iter().all(|modifier| {
+                        !matches!(
+                            modifier,
+                            unc_abi::AbiFunctionModifier::Payable
+                                | unc_abi::AbiFunctionModifier::Init
+                                | unc_abi::AbiFunctionModifier::Private
+                        )
+                    })
+            })
+            .map(|function| function.name.clone())
+            .filter(|name| {
+                self.method_filter.is_empty()
+                    || self
+                        .method_filter
+                        .iter()
+                        .any(|pattern| matches_pattern(pattern, name))
+            })
+            .collect()
+    }
+}
+
+/// Matches a name against a pattern where `*` stands for any characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// A contract that creates accounts with a function call.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AccountFactory {
+    pub factory_account_id: unc_primitives::types::AccountId,
+    pub method_name: String,
+    /// The JSON arguments, where `{new_account_id}`, `{name}` (the new account ID without
+    /// the factory suffix) and `{new_public_key}` are replaced with the values of the new account.
+    pub args: String,
+    #[serde(default = "AccountFactory::default_tgas")]
+    pub tgas: u64,
+}
+
+impl AccountFactory {
+    fn default_tgas() -> u64 {
+        100
+    }
+
+    /// The arguments of the factory call for the new account.
+    pub fn args_for(
+        &self,
+        new_account_id: &unc_primitives::types::AccountId,
+        new_public_key: &unc_crypto::PublicKey,
+    ) -> color_eyre::eyre::Result<Vec<u8>> {
+        let name = new_account_id
+            .as_str()
+            .strip_suffix(&format!(".{}", self.factory_account_id))
+            .unwrap_or(new_account_id.as_str());
+        let args = self
+            .args
+            .replace("{new_account_id}", new_account_id.as_str())
+            .replace("{name}", name)
+            .replace("{new_public_key}", &new_public_key.to_string());
+        let args: serde_json::Value = serde_json::from_str(&args).map_err(|err| {
+            color_eyre::eyre::eyre!("The arguments of the factory are not valid JSON: {err}")
+        })?;
+        Ok(serde_json::to_vec(&args)?)
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NetworkConfig {
     pub network_name: String,
@@ -82,13 +196,86 @@ pub struct NetworkConfig {
     pub meta_transaction_relayer_url: Option<url::Url>,
 }
 
+type JsonRpcClientCache = std::sync::Mutex<
+    std::collections::HashMap<
+        (url::Url, Option<crate::types::api_key::ApiKey>),
+        unc_jsonrpc_client::JsonRpcClient,
+    >,
+>;
+
 impl NetworkConfig {
+    /// Returns the client of the RPC server of the network.
+    ///
+    /// The clients are created once per RPC URL and API key and share one connection pool,
+    /// so the connections to the server are reused by all the calls of the process.
     pub fn json_rpc_client(&self) -> unc_jsonrpc_client::JsonRpcClient {
-        let mut json_rpc_client = unc_jsonrpc_client::JsonRpcClient::connect(self.rpc_url.as_ref());
-        if let Some(rpc_api_key) = &self.rpc_api_key {
-            json_rpc_client =
-                json_rpc_client.header(unc_jsonrpc_client::auth::ApiKey::from(rpc_api_key.clone()))
-        };
-        json_rpc_client
+        static CONNECTOR: std::sync::OnceLock<unc_jsonrpc_client::JsonRpcClientConnector> =
+            std::sync::OnceLock::new();
+        static CLIENTS: std::sync::OnceLock<JsonRpcClientCache> = std::sync::OnceLock::new();
+
+        let mut clients = CLIENTS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        clients
+            .entry((self.rpc_url.clone(), self.rpc_api_key.clone()))
+            .or_insert_with(|| {
+                let connector = CONNECTOR.get_or_init(|| {
+                    let mut headers = reqwest::header::HeaderMap::new();
+                    headers.insert(
+                        reqwest::header::CONTENT_TYPE,
+                        reqwest::header::HeaderValue::from_static("application/json"),
+                    );
+                    unc_jsonrpc_client::JsonRpcClient::with(
+                        reqwest::Client::builder()
+                            .default_headers(headers)
+                            .tcp_keepalive(std::time::Duration::from_secs(60))
+                            .build()
+                            .expect("Failed to create the HTTP client"),
+                    )
+                });
+                let mut json_rpc_client = connector.connect(self.rpc_url.as_ref());
+                if let Some(rpc_api_key) = &self.rpc_api_key {
+                    json_rpc_client = json_rpc_client
+                        .header(unc_jsonrpc_client::auth::ApiKey::from(rpc_api_key.clone()))
+                };
+                json_rpc_client
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_key_preset_abi_method_names() {
+        let abi_root: unc_abi::AbiRoot = serde_json::from_value(serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": {},
+            "body": {
+                "functions": [
+                    {"name": "get_status", "kind": "view"},
+                    {"name": "set_status", "kind": "call"},
+                    {"name": "set_greeting", "kind": "call"},
+                    {"name": "buy", "kind": "call", "modifiers": ["payable"]},
+                    {"name": "new", "kind": "call", "modifiers": ["init"]},
+                    {"name": "play_move", "kind": "call"}
+                ],
+                "root_schema": {}
+            }
+        }))
+        .unwrap();
+        let mut preset = SessionKeyPreset::default();
+        assert_eq!(
+            preset.abi_method_names(&abi_root),
+            vec!["set_status", "set_greeting", "play_move"]
+        );
+        preset.method_filter = vec!["set_*s".to_string(), "play_move".to_string()];
+        assert_eq!(
+            preset.abi_method_names(&abi_root),
+            vec!["set_status", "play_move"]
+        );
     }
 }
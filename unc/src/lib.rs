@@ -17,4 +17,6 @@ pub mod utils_command;
 pub struct GlobalContext {
     pub config: crate::config::Config,
     pub offline: bool,
+    /// The file with the nonces and the recent block to sign transactions with in offline mode.
+    pub online_context: Option<std::path::PathBuf>,
 }
@@ -26,6 +26,10 @@ struct Cmd {
     /// Offline mode
     #[interactive_clap(long)]
     offline: bool,
+    /// Sign offline with the nonces and the recent block from this file (made by `transaction prepare-online-context`), implies --offline
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    online_context: Option<crate::types::path_buf::PathBuf>,
     #[interactive_clap(subcommand)]
     top_level: crate::commands::TopLevelCommand,
 }
@@ -40,7 +44,8 @@ impl CmdContext {
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self(crate::GlobalContext {
             config: previous_context.0,
-            offline: scope.offline,
+            offline: scope.offline || scope.online_context.is_some(),
+            online_context: scope.online_context.clone().map(Into::into),
         }))
     }
 }
@@ -169,6 +174,7 @@ fn main() -> crate::common::CliResult {
                 );
                 let self_update_cli_cmd = CliCmd {
                     offline: false,
+                    online_context: None,
                     top_level:
                         Some(crate::commands::CliTopLevelCommand::Extensions(
                             crate::commands::extensions::CliExtensionsCommands {
@@ -0,0 +1,802 @@
+use color_eyre::eyre::WrapErr;
+use prettytable::Table;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+/// The balance given to the accounts copied to the sandbox node, so that deposits do not fail.
+const SANDBOX_BALANCE: unc_primitives::types::Balance = 10u128.pow(33);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::commands::TransactionContext)]
+#[interactive_clap(output_context = EstimateContext)]
+pub struct Estimate {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The RPC URL of a sandbox node to measure the gas of the function calls on (e.g. the one started with `dev-tool sandbox start`)
+    sandbox_rpc_url: Option<crate::types::url::Url>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EstimateContext;
+
+impl EstimateContext {
+    pub fn from_previous_context(
+        previous_context: crate::commands::TransactionContext,
+        scope: &<Estimate as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.global_context.offline {
+            return Err(color_eyre::Report::msg(
+                "Estimating a transaction requires access to the network, it cannot be done in offline mode",
+            ));
+        }
+        let sandbox_json_rpc_client = scope.sandbox_rpc_url.as_ref().map(|sandbox_rpc_url| {
+            unc_jsonrpc_client::JsonRpcClient::connect(url::Url::from(sandbox_rpc_url.clone()))
+        });
+        let estimation = estimate_transaction(
+            &previous_context.network_config,
+            &previous_context.prepopulated_transaction,
+            sandbox_json_rpc_client.as_ref(),
+        )?;
+        display_transaction_estimation(&previous_context.prepopulated_transaction, &estimation);
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionEstimation {
+    /// Gas burnt to convert the transaction into a receipt
+    pub gas_burnt_for_conversion: unc_primitives::types::Gas,
+    /// Gas burnt when the receipt actions are executed (without the function-call prepaid gas)
+    pub gas_burnt_for_execution: unc_primitives::types::Gas,
+    /// Gas attached to the function calls of the transaction
+    pub prepaid_gas: unc_primitives::types::Gas,
+    pub gas_price: unc_primitives::types::Balance,
+    /// The maximum gas that a transaction can attach, from the runtime config of the network
+    pub max_prepaid_gas: unc_primitives::types::Gas,
+    /// Number of bytes the transaction adds to the storage of the receiver account (upper bound),
+    /// unknown when the function calls were not run on a sandbox node
+    pub storage_increase: Option<u64>,
+    pub storage_amount_per_byte: unc_primitives::types::Balance,
+    pub function_calls: Vec<FunctionCallEstimation>,
+    /// Actions whose fees the protocol config does not expose, so they are not included
+    pub actions_without_fees: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionCallEstimation {
+    pub method_name: String,
+    pub attached_gas: unc_primitives::types::Gas,
+    pub dry_run: DryRunResult,
+}
+
+impl FunctionCallEstimation {
+    /// The gas burnt on the sandbox node with a safety margin of 10%, if it was measured.
+    pub fn recommended_gas(
+        &self,
+        max_prepaid_gas: unc_primitives::types::Gas,
+    ) -> Option<unc_primitives::types::Gas> {
+        match self.dry_run {
+            DryRunResult::Measured { gas_burnt } => Some(
+                gas_burnt
+                    .saturating_add(gas_burnt / 10)
+                    .min(max_prepaid_gas),
+            ),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DryRunResult {
+    /// The view call succeeded (the RPC does not report the gas used by view calls)
+    SucceededAsView,
+    /// Executed on the sandbox node with the contract account copied from the network
+    Measured {
+        gas_burnt: unc_primitives::types::Gas,
+    },
+    /// A call method (per the ABI) which cannot be replayed as a view call
+    NotReplayed,
+    /// The view call failed, which is expected for the methods that modify the state
+    ViewCallFailed(String),
+    Failed(String),
+}
+
+impl std::fmt::Display for DryRunResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SucceededAsView => write!(f, "succeeded as a view call"),
+            Self::Measured { gas_burnt } => write!(
+                f,
+                "succeeded on the sandbox node, {} burnt",
+                unc_gas::UncGas::from_gas(*gas_burnt)
+            ),
+            Self::NotReplayed => write!(
+                f,
+                "not replayed, the method modifies the state (pass --sandbox-rpc-url to measure it)"
+            ),
+            Self::ViewCallFailed(error) => write!(
+                f,
+                "could not be replayed as a view call, the method may modify the state: {error}"
+            ),
+            Self::Failed(error) => write!(f, "failed: {error}"),
+        }
+    }
+}
+
+impl TransactionEstimation {
+    pub fn total_gas(&self) -> unc_primitives::types::Gas {
+        self.gas_burnt_for_conversion + self.gas_burnt_for_execution + self.prepaid_gas
+    }
+
+    /// The cost of the gas which is going to be burnt for sure
+    pub fn expected_cost(&self) -> unc_primitives::types::Balance {
+        u128::from(self.gas_burnt_for_conversion + self.gas_burnt_for_execution) * self.gas_price
+    }
+
+    /// The cost of the transaction if all the prepaid gas is used
+    pub fn max_cost(&self) -> unc_primitives::types::Balance {
+        u128::from(self.total_gas()) * self.gas_price
+    }
+
+    pub fn storage_cost(&self) -> Option<unc_primitives::types::Balance> {
+        self.storage_increase
+            .map(|storage_increase| u128::from(storage_increase) * self.storage_amount_per_byte)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct ActionsCost {
+    send: unc_primitives::types::Gas,
+    exec: unc_primitives::types::Gas,
+    prepaid: unc_primitives::types::Gas,
+    storage_increase: u64,
+    /// The actions include function calls which are not run on the sandbox node (delegated ones)
+    storage_unknown: bool,
+    without_fees: Vec<&'static str>,
+}
+
+impl std::ops::AddAssign for ActionsCost {
+    fn add_assign(&mut self, other: Self) {
+        self.send += other.send;
+        self.exec += other.exec;
+        self.prepaid += other.prepaid;
+        self.storage_increase += other.storage_increase;
+        self.storage_unknown |= other.storage_unknown;
+        self.without_fees.extend(other.without_fees);
+    }
+}
+
+pub fn estimate_transaction(
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+    sandbox_json_rpc_client: Option<&unc_jsonrpc_client::JsonRpcClient>,
+) -> color_eyre::eyre::Result<TransactionEstimation> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let runtime_config = json_rpc_client
+        .blocking_call(
+            unc_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: unc_primitives::types::Finality::Final.into(),
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the protocol config on network <{}>",
+                network_config.network_name
+            )
+        })?
+        .runtime_config;
+    let gas_price = json_rpc_client
+        .blocking_call(unc_jsonrpc_client::methods::gas_price::RpcGasPriceRequest {
+            block_id: None,
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the gas price on network <{}>",
+                network_config.network_name
+            )
+        })?
+        .gas_price;
+    let max_prepaid_gas = runtime_config
+        .wasm_config
+        .limit_config
+        .max_total_prepaid_gas;
+
+    let fees = &runtime_config.transaction_costs;
+    let sir = prepopulated_transaction.signer_id == prepopulated_transaction.receiver_id;
+    let actions_cost = get_actions_cost(fees, &prepopulated_transaction.actions, sir);
+
+    let function_call_actions = prepopulated_transaction
+        .actions
+        .iter()
+        .filter_map(|action| match action {
+            unc_primitives::transaction::Action::FunctionCall(function_call_action) => {
+                Some(function_call_action.as_ref())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let (dry_runs, function_calls_storage_increase) = if function_call_actions.is_empty() {
+        (Vec::new(), Some(0))
+    } else if let Some(sandbox_json_rpc_client) = sandbox_json_rpc_client {
+        let (dry_runs, storage_increase) = crate::util::handle_step(
+            "Replaying the function calls on the sandbox node...",
+            || {
+                measure_function_calls_in_sandbox(
+                    network_config,
+                    sandbox_json_rpc_client,
+                    &prepopulated_transaction.signer_id,
+                    &prepopulated_transaction.receiver_id,
+                    &function_call_actions,
+                    max_prepaid_gas,
+                )
+            },
+        )?;
+        (dry_runs, Some(storage_increase))
+    } else {
+        let abi_root = crate::commands::contract::call_function::abi::get_contract_abi(
+            network_config,
+            &prepopulated_transaction.receiver_id,
+        )
+        .ok();
+        let dry_runs = function_call_actions
+            .iter()
+            .map(|function_call_action| {
+                replay_as_view_call(
+                    &json_rpc_client,
+                    abi_root.as_ref(),
+                    &prepopulated_transaction.receiver_id,
+                    function_call_action,
+                )
+            })
+            .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
+        (dry_runs, None)
+    };
+    let storage_increase = function_calls_storage_increase
+        .filter(|_| !actions_cost.storage_unknown)
+        .map(|storage_increase| actions_cost.storage_increase + storage_increase);
+    let function_calls = function_call_actions
+        .iter()
+        .zip(dry_runs)
+        .map(|(function_call_action, dry_run)| FunctionCallEstimation {
+            method_name: function_call_action.method_name.clone(),
+            attached_gas: function_call_action.gas,
+            dry_run,
+        })
+        .collect();
+
+    Ok(TransactionEstimation {
+        gas_burnt_for_conversion: fees.action_receipt_creation_config.send_fee(sir)
+            + actions_cost.send,
+        gas_burnt_for_execution: fees.action_receipt_creation_config.exec_fee() + actions_cost.exec,
+        prepaid_gas: actions_cost.prepaid,
+        gas_price,
+        max_prepaid_gas,
+        storage_increase,
+        storage_amount_per_byte: runtime_config.storage_amount_per_byte,
+        function_calls,
+        actions_without_fees: actions_cost.without_fees,
+    })
+}
+
+fn get_actions_cost(
+    fees: &unc_parameters::RuntimeFeesConfigView,
+    actions: &[unc_primitives::transaction::Action],
+    sir: bool,
+) -> ActionsCost {
+    let mut total = ActionsCost::default();
+    for action in actions {
+        total += get_action_cost(fees, action, sir);
+    }
+    total
+}
+
+fn get_action_cost(
+    fees: &unc_parameters::RuntimeFeesConfigView,
+    action: &unc_primitives::transaction::Action,
+    sir: bool,
+) -> ActionsCost {
+    let config = &fees.action_creation_config;
+    let fee_cost = |fee: &unc_parameters::Fee, times: u64| ActionsCost {
+        send: fee.send_fee(sir) * times,
+        exec: fee.exec_fee() * times,
+        ..Default::default()
+    };
+    match action {
+        unc_primitives::transaction::Action::CreateAccount(_) => {
+            let mut cost = fee_cost(&config.create_account_cost, 1);
+            cost.storage_increase = fees.storage_usage_config.num_bytes_account;
+            cost
+        }
+        unc_primitives::transaction::Action::DeployContract(deploy_contract_action) => {
+            let code_length = deploy_contract_action.code.len() as u64;
+            let mut cost = fee_cost(&config.deploy_contract_cost, 1);
+            cost += fee_cost(&config.deploy_contract_cost_per_byte, code_length);
+            cost.storage_increase = code_length;
+            cost
+        }
+        unc_primitives::transaction::Action::FunctionCall(function_call_action) => {
+            let mut cost = fee_cost(&config.function_call_cost, 1);
+            cost += fee_cost(
+                &config.function_call_cost_per_byte,
+                (function_call_action.method_name.len() + function_call_action.args.len()) as u64,
+            );
+            cost.prepaid = function_call_action.gas;
+            cost
+        }
+        unc_primitives::transaction::Action::Transfer(_) => fee_cost(&config.transfer_cost, 1),
+        unc_primitives::transaction::Action::Pledge(_) => fee_cost(&config.pledge_cost, 1),
+        unc_primitives::transaction::Action::AddKey(add_key_action) => {
+            let mut cost = match &add_key_action.access_key.permission {
+                unc_primitives::account::AccessKeyPermission::FullAccess => {
+                    fee_cost(&config.add_key_cost.full_access_cost, 1)
+                }
+                unc_primitives::account::AccessKeyPermission::FunctionCall(permission) => {
+                    let mut cost = fee_cost(&config.add_key_cost.function_call_cost, 1);
+                    cost += fee_cost(
+                        &config.add_key_cost.function_call_cost_per_byte,
+                        permission
+                            .method_names
+                            .iter()
+                            .map(|method_name| method_name.len() as u64 + 1)
+                            .sum(),
+                    );
+                    cost
+                }
+            };
+            cost.storage_increase = borsh_len(&add_key_action.public_key)
+                + borsh_len(&add_key_action.access_key)
+                + fees.storage_usage_config.num_extra_bytes_record;
+            cost
+        }
+        unc_primitives::transaction::Action::DeleteKey(_) => fee_cost(&config.delete_key_cost, 1),
+        unc_primitives::transaction::Action::DeleteAccount(_) => {
+            fee_cost(&config.delete_account_cost, 1)
+        }
+        unc_primitives::transaction::Action::Delegate(signed_delegate_action) => {
+            let delegate_action = &signed_delegate_action.delegate_action;
+            let inner_sir = delegate_action.sender_id == delegate_action.receiver_id;
+            let inner_actions = delegate_action.get_actions();
+            let mut cost = fee_cost(&config.delegate_cost, 1);
+            let mut inner_cost = get_actions_cost(fees, &inner_actions, inner_sir);
+            // The inner actions are executed in a separate receipt which is paid at the execution step
+            cost.exec += fees.action_receipt_creation_config.send_fee(inner_sir)
+                + fees.action_receipt_creation_config.exec_fee()
+                + inner_cost.send
+                + inner_cost.exec;
+            cost.prepaid += inner_cost.prepaid;
+            cost.storage_increase += inner_cost.storage_increase;
+            cost.storage_unknown = inner_actions.iter().any(|action| {
+                matches!(action, unc_primitives::transaction::Action::FunctionCall(_))
+            }) || inner_cost.storage_unknown;
+            cost.without_fees.append(&mut inner_cost.without_fees);
+            cost
+        }
+        // The protocol config does not expose the fees of these actions
+        unc_primitives::transaction::Action::RegisterRsa2048Keys(_) => ActionsCost {
+            without_fees: vec!["RegisterRsa2048Keys"],
+            ..Default::default()
+        },
+        unc_primitives::transaction::Action::CreateRsa2048Challenge(_) => ActionsCost {
+            without_fees: vec!["CreateRsa2048Challenge"],
+            ..Default::default()
+        },
+    }
+}
+
+fn borsh_len<T: unc_primitives::borsh::BorshSerialize>(value: &T) -> u64 {
+    unc_primitives::borsh::object_length(value).unwrap_or_default() as u64
+}
+
+/// Replays the function call as a view call. The RPC does not report the gas used by view calls,
+/// so this only tells whether the call is likely to succeed.
+fn replay_as_view_call(
+    json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    abi_root: Option<&unc_abi::AbiRoot>,
+    receiver_id: &unc_primitives::types::AccountId,
+    function_call_action: &unc_primitives::transaction::FunctionCallAction,
+) -> color_eyre::eyre::Result<DryRunResult> {
+    let abi_function_kind = abi_root.and_then(|abi_root| {
+        abi_root
+            .body
+            .functions
+            .iter()
+            .find(|function| function.name == function_call_action.method_name)
+            .map(|function| function.kind.clone())
+    });
+    if abi_function_kind == Some(unc_abi::AbiFunctionKind::Call) {
+        return Ok(DryRunResult::NotReplayed);
+    }
+    let view_call_result =
+        json_rpc_client.blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: unc_primitives::types::Finality::Final.into(),
+            request: unc_primitives::views::QueryRequest::CallFunction {
+                account_id: receiver_id.clone(),
+                method_name: function_call_action.method_name.clone(),
+                args: function_call_action.args.clone().into(),
+            },
+        });
+    Ok(match view_call_result {
+        Ok(_) => DryRunResult::SucceededAsView,
+        Err(unc_jsonrpc_client::errors::JsonRpcError::ServerError(
+            unc_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                unc_jsonrpc_client::methods::query::RpcQueryError::ContractExecutionError {
+                    vm_error,
+                    ..
+                },
+            ),
+        )) => {
+            if abi_function_kind == Some(unc_abi::AbiFunctionKind::View) {
+                DryRunResult::Failed(vm_error)
+            } else {
+                DryRunResult::ViewCallFailed(vm_error)
+            }
+        }
+        Err(err) => {
+            return Err(color_eyre::Report::new(err)).wrap_err_with(|| {
+                format!(
+                    "Failed to replay <{}> as a view call",
+                    function_call_action.method_name
+                )
+            })
+        }
+    })
+}
+
+/// Measures the gas of the function calls on the sandbox node: the receiver account with its
+/// code and state is copied from the network, the signer gets a temporary full access key, and
+/// every function call is sent as a separate transaction with the maximum prepaid gas.
+/// Also returns the number of bytes the function calls added to the storage of the receiver.
+fn measure_function_calls_in_sandbox(
+    network_config: &crate::config::NetworkConfig,
+    sandbox_json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    signer_id: &unc_primitives::types::AccountId,
+    receiver_id: &unc_primitives::types::AccountId,
+    function_call_actions: &[&unc_primitives::transaction::FunctionCallAction],
+    max_prepaid_gas: unc_primitives::types::Gas,
+) -> color_eyre::eyre::Result<(Vec<DryRunResult>, u64)> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let view_account_response = json_rpc_client
+        .blocking_call_view_account(receiver_id, unc_primitives::types::Finality::Final.into())
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewAccount for <{receiver_id}> on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    let receiver_account = view_account_response.account_view()?;
+    let block_reference = unc_primitives::types::BlockReference::BlockId(
+        unc_primitives::types::BlockId::Hash(view_account_response.block_hash),
+    );
+
+    let mut records = vec![unc_primitives::state_record::StateRecord::Account {
+        account_id: receiver_id.clone(),
+        account: unc_primitives::account::Account::new(
+            receiver_account.amount.max(SANDBOX_BALANCE),
+            receiver_account.pledging,
+            receiver_account.power,
+            receiver_account.code_hash,
+            receiver_account.storage_usage,
+        ),
+    }];
+    if receiver_account.code_hash != unc_primitives::hash::CryptoHash::default() {
+        let view_code_response = json_rpc_client
+            .blocking_call(unc_jsonrpc_client::methods::query::RpcQueryRequest {
+                block_reference: block_reference.clone(),
+                request: unc_primitives::views::QueryRequest::ViewCode {
+                    account_id: receiver_id.clone(),
+                },
+            })
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to fetch query ViewCode for <{receiver_id}> on network <{}>",
+                    network_config.network_name
+                )
+            })?;
+        if let unc_jsonrpc_primitives::types::query::QueryResponseKind::ViewCode(contract_code) =
+            view_code_response.kind
+        {
+            records.push(unc_primitives::state_record::StateRecord::Contract {
+                account_id: receiver_id.clone(),
+                code: contract_code.code,
+            });
+        }
+        let contract_state =
+            crate::commands::contract::view_storage::contract_state::fetch_contract_state(
+                network_config,
+                &block_reference,
+                receiver_id,
+                &Vec::new().into(),
+            )?;
+        contract_state.warn_about_unfetched_keys();
+        records.extend(contract_state.values.into_iter().map(|item| {
+            unc_primitives::state_record::StateRecord::Data {
+                account_id: receiver_id.clone(),
+                data_key: item.key,
+                value: item.value,
+            }
+        }));
+    }
+    if signer_id != receiver_id {
+        records.push(unc_primitives::state_record::StateRecord::Account {
+            account_id: signer_id.clone(),
+            account: unc_primitives::account::Account::new(
+                SANDBOX_BALANCE,
+                0,
+                0,
+                unc_primitives::hash::CryptoHash::default(),
+                0,
+            ),
+        });
+    }
+    let signer_secret_key = unc_crypto::SecretKey::from_random(unc_crypto::KeyType::ED25519);
+    records.push(unc_primitives::state_record::StateRecord::AccessKey {
+        account_id: signer_id.clone(),
+        public_key: signer_secret_key.public_key(),
+        access_key: unc_primitives::account::AccessKey::full_access(),
+    });
+    sandbox_json_rpc_client
+        .blocking_call(
+            unc_jsonrpc_client::methods::sandbox_patch_state::RpcSandboxPatchStateRequest {
+                records,
+            },
+        )
+        .wrap_err("Failed to copy the contract account to the sandbox node")?;
+    let storage_usage_before = sandbox_storage_usage(sandbox_json_rpc_client, receiver_id)?;
+
+    let block_hash = sandbox_json_rpc_client
+        .blocking_call(unc_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: unc_primitives::types::Finality::Final.into(),
+        })
+        .wrap_err("Failed to fetch the final block of the sandbox node")?
+        .header
+        .hash;
+    let mut dry_runs = Vec::new();
+    for (nonce, function_call_action) in (1..).zip(function_call_actions) {
+        let transaction = unc_primitives::transaction::Transaction {
+            signer_id: signer_id.clone(),
+            public_key: signer_secret_key.public_key(),
+            nonce,
+            receiver_id: receiver_id.clone(),
+            block_hash,
+            actions: vec![unc_primitives::transaction::Action::FunctionCall(Box::new(
+                unc_primitives::transaction::FunctionCallAction {
+                    gas: max_prepaid_gas,
+                    ..(*function_call_action).clone()
+                },
+            ))],
+        };
+        let signature = signer_secret_key.sign(transaction.get_hash_and_size().0.as_ref());
+        let outcome = sandbox_json_rpc_client
+            .blocking_call(
+                unc_jsonrpc_client::methods::broadcast_tx_commit::RpcBroadcastTxCommitRequest {
+                    signed_transaction: unc_primitives::transaction::SignedTransaction::new(
+                        signature,
+                        transaction,
+                    ),
+                },
+            )
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to replay <{}> on the sandbox node",
+                    function_call_action.method_name
+                )
+            })?;
+        dry_runs.push(get_sandbox_dry_run_result(&outcome));
+    }
+    let storage_usage_after = sandbox_storage_usage(sandbox_json_rpc_client, receiver_id)?;
+    Ok((
+        dry_runs,
+        storage_usage_after.saturating_sub(storage_usage_before),
+    ))
+}
+
+fn sandbox_storage_usage(
+    sandbox_json_rpc_client: &unc_jsonrpc_client::JsonRpcClient,
+    account_id: &unc_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<unc_primitives::types::StorageUsage> {
+    Ok(sandbox_json_rpc_client
+        .blocking_call_view_account(account_id, unc_primitives::types::Finality::None.into())
+        .wrap_err_with(|| {
+            format!("Failed to fetch query ViewAccount for <{account_id}> on the sandbox node")
+        })?
+        .account_view()?
+        .storage_usage)
+}
+
+/// All the gas burnt by the receipts (the function call and the receipts it created) has to be
+/// covered by the prepaid gas.
+fn get_sandbox_dry_run_result(
+    outcome: &unc_primitives::views::FinalExecutionOutcomeView,
+) -> DryRunResult {
+    match &outcome.status {
+        unc_primitives::views::FinalExecutionStatus::SuccessValue(_) => DryRunResult::Measured {
+            gas_burnt: outcome
+                .receipts_outcome
+                .iter()
+                .map(|receipt_outcome| receipt_outcome.outcome.gas_burnt)
+                .sum(),
+        },
+        unc_primitives::views::FinalExecutionStatus::Failure(error) => {
+            DryRunResult::Failed(error.to_string())
+        }
+        status => DryRunResult::Failed(format!("the transaction did not finish ({status:?})")),
+    }
+}
+
+fn display_transaction_estimation(
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+    estimation: &TransactionEstimation,
+) {
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+
+    table.add_row(prettytable::row![
+        Fy->format!(
+            "<{}> -> <{}>",
+            prepopulated_transaction.signer_id, prepopulated_transaction.receiver_id
+        ),
+        format!("{} action(s)", prepopulated_transaction.actions.len())
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Gas burnt to convert the transaction",
+        Fy->unc_gas::UncGas::from_gas(estimation.gas_burnt_for_conversion)
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Gas burnt to execute the actions",
+        Fy->unc_gas::UncGas::from_gas(estimation.gas_burnt_for_execution)
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Prepaid gas attached to function calls",
+        Fy->unc_gas::UncGas::from_gas(estimation.prepaid_gas)
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Total gas",
+        Fy->unc_gas::UncGas::from_gas(estimation.total_gas())
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Current gas price",
+        Fy->format!("{} attounc per gas", estimation.gas_price)
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Expected cost (static fees)",
+        Fy->crate::types::unc_token::UncToken::from_attounc(estimation.expected_cost())
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Maximum cost (all prepaid gas used)",
+        Fy->crate::types::unc_token::UncToken::from_attounc(estimation.max_cost())
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Storage added to the receiver account",
+        Fy->estimation
+            .storage_increase
+            .map(|storage_increase| bytesize::ByteSize(storage_increase).to_string())
+            .unwrap_or_else(|| "unknown (pass --sandbox-rpc-url to measure the function calls)".to_string())
+    ]);
+    table.add_row(prettytable::row![
+        Fg->"Storage staking impact",
+        Fy->estimation
+            .storage_cost()
+            .map(|storage_cost| crate::types::unc_token::UncToken::from_attounc(storage_cost).to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    ]);
+
+    for function_call in &estimation.function_calls {
+        table.add_row(prettytable::row![
+            Fg->format!("Function <{}>", function_call.method_name),
+            format!(
+                "attached: {}\nrecommended prepaid gas: {}\ndry run: {}",
+                unc_gas::UncGas::from_gas(function_call.attached_gas),
+                function_call
+                    .recommended_gas(estimation.max_prepaid_gas)
+                    .map(|recommended_gas| unc_gas::UncGas::from_gas(recommended_gas).to_string())
+                    .unwrap_or_else(|| "not measured".to_string()),
+                function_call.dry_run
+            )
+        ]);
+    }
+    table.printstd();
+
+    if !estimation.actions_without_fees.is_empty() {
+        eprintln!(
+            "\nWarning: the protocol config does not expose the fees of {} actions, they are not included in the gas and the cost above.",
+            estimation.actions_without_fees.join(", ")
+        );
+    }
+    if estimation
+        .function_calls
+        .iter()
+        .any(|function_call| matches!(function_call.dry_run, DryRunResult::Failed(_)))
+    {
+        eprintln!("\nWarning: some of the function calls failed during the dry run, the transaction is likely to fail.");
+    }
+    eprintln!("\nThe transaction was not signed and was not sent to the network.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_cost_from_protocol_fees() {
+        let fees = unc_parameters::RuntimeConfigView::from(unc_parameters::RuntimeConfig::test())
+            .transaction_costs;
+        let config = &fees.action_creation_config;
+        let actions = vec![
+            unc_primitives::transaction::Action::Transfer(
+                unc_primitives::transaction::TransferAction { deposit: 1 },
+            ),
+            unc_primitives::transaction::Action::FunctionCall(Box::new(
+                unc_primitives::transaction::FunctionCallAction {
+                    method_name: "set".to_string(),
+                    args: b"{}".to_vec(),
+                    gas: 10_000_000_000_000,
+                    deposit: 0,
+                },
+            )),
+            unc_primitives::transaction::Action::RegisterRsa2048Keys(Box::new(
+                unc_primitives::action::RegisterRsa2048KeysAction {
+                    public_key: unc_crypto::PublicKey::empty(unc_crypto::KeyType::ED25519),
+                    operation_type: 0,
+                    args: Vec::new(),
+                },
+            )),
+        ];
+
+        let cost = get_actions_cost(&fees, &actions, false);
+        assert_eq!(
+            cost.send,
+            config.transfer_cost.send_fee(false)
+                + config.function_call_cost.send_fee(false)
+                + config.function_call_cost_per_byte.send_fee(false) * 5
+        );
+        assert_eq!(
+            cost.exec,
+            config.transfer_cost.exec_fee()
+                + config.function_call_cost.exec_fee()
+                + config.function_call_cost_per_byte.exec_fee() * 5
+        );
+        assert_eq!(cost.prepaid, 10_000_000_000_000);
+        assert_eq!(cost.storage_increase, 0);
+        assert!(!cost.storage_unknown);
+        // The fees of the RSA actions are unknown, so they are reported instead of costing nothing.
+        assert_eq!(cost.without_fees, vec!["RegisterRsa2048Keys"]);
+    }
+
+    #[test]
+    fn recommended_gas_is_measured_in_sandbox() {
+        let max_prepaid_gas =
+            unc_parameters::RuntimeConfigView::from(unc_parameters::RuntimeConfig::test())
+                .wasm_config
+                .limit_config
+                .max_total_prepaid_gas;
+        let function_call = |dry_run| FunctionCallEstimation {
+            method_name: "set".to_string(),
+            attached_gas: 100_000_000_000_000,
+            dry_run,
+        };
+
+        // The recommendation can be lower than the attached gas.
+        assert_eq!(
+            function_call(DryRunResult::Measured {
+                gas_burnt: 10_000_000_000_000
+            })
+            .recommended_gas(max_prepaid_gas),
+            Some(11_000_000_000_000)
+        );
+        assert_eq!(
+            function_call(DryRunResult::Measured {
+                gas_burnt: 290_000_000_000_000
+            })
+            .recommended_gas(max_prepaid_gas),
+            Some(max_prepaid_gas)
+        );
+        assert_eq!(
+            function_call(DryRunResult::SucceededAsView).recommended_gas(max_prepaid_gas),
+            None
+        );
+        assert_eq!(
+            function_call(DryRunResult::NotReplayed).recommended_gas(max_prepaid_gas),
+            None
+        );
+    }
+}
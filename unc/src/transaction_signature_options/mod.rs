@@ -3,6 +3,8 @@ use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 use crate::common::JsonRpcClientExt;
 
+pub mod estimate;
+pub mod online_context;
 pub mod sign_later;
 pub mod sign_with_access_key_file;
 pub mod sign_with_keychain;
@@ -55,6 +57,11 @@ pub enum SignWith {
     ))]
     /// Prepare unsigned transaction to sign it later
     SignLater(self::sign_later::Display),
+    #[strum_discriminants(strum(
+        message = "estimate                         - Estimate the gas and the cost of the transaction without signing it"
+    ))]
+    /// Estimate the gas and the cost of the transaction without signing it
+    Estimate(self::estimate::Estimate),
 }
 
 #[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
@@ -89,6 +96,22 @@ impl interactive_clap::FromCli for Submit {
     {
         let mut storage_message = String::new();
 
+        if context.global_context.offline {
+            match optional_clap_variant {
+                Some(CliSubmit::Send) => {
+                    return interactive_clap::ResultFromCli::Err(
+                        optional_clap_variant,
+                        color_eyre::Report::msg(format!(
+                            "The transaction cannot be sent in offline mode. Use `display` and send the signed transaction from an online machine with:\n$ {} transaction send-signed-transaction",
+                            crate::common::get_unc_exec_path()
+                        )),
+                    );
+                }
+                None => optional_clap_variant = Some(CliSubmit::Display),
+                Some(CliSubmit::Display) => {}
+            }
+        }
+
         if optional_clap_variant.is_none() {
             match Self::choose_variant(context.clone()) {
                 interactive_clap::ResultFromCli::Ok(cli_submit) => {
@@ -144,47 +167,38 @@ impl interactive_clap::FromCli for Submit {
                             color_eyre::Report::msg(report),
                         );
                     };
-
-                    // FIXME: 异步调用后续逻辑处理, 在Final后，后续逻辑处理
-                    eprintln!("Processing transaction...\nPlease wait for 6 blocks to confirm, use command: unc transaction view-status <tx_hash>");
-
-                    // let transaction_info = loop {
-                    //     let transaction_info_result = context.network_config.json_rpc_client()
-                    //     .blocking_call(
-                    //         unc_jsonrpc_client::methods::tx::RpcTransactionStatusRequest {
-                    //             transaction_info:
-                    //                 unc_jsonrpc_client::methods::tx::TransactionInfo::TransactionId {
-                    //                     tx_hash: transaction_hash.into(),
-                    //                     sender_account_id: signed_transaction.transaction.signer_id.clone(),
-                    //                 },
-                    //         },
-                    //     );
-                    //     match transaction_info_result {
-                    //         Ok(response) => {
-                    //             break response;
-                    //         }
-                    //         Err(err) => match crate::common::rpc_transaction_error(err) {
-                    //             Ok(_) => std::thread::sleep(std::time::Duration::from_millis(100)),
-                    //             Err(report) => {
-                    //                 return interactive_clap::ResultFromCli::Err(
-                    //                     optional_clap_variant,
-                    //                     color_eyre::Report::msg(report),
-                    //                 )
-                    //             }
-                    //         },
-                    //     };
-                    // };
-
-                    // if let Err(report) = (context.on_after_sending_transaction_callback)(
-                    //     &transaction_info,
-                    //     &context.network_config,
-                    // ) {
-                    //     return interactive_clap::ResultFromCli::Err(
-                    //         optional_clap_variant,
-                    //         color_eyre::Report::msg(report),
-                    //     );
-                    // };
+                    // Printed before waiting, so that the user learns where a new key is saved
+                    // even if the outcome is not available in time.
                     eprintln!("{storage_message}");
+
+                    let transaction_info = match crate::common::wait_for_transaction_outcome(
+                        &context.network_config,
+                        &transaction_hash,
+                        &signed_transaction.transaction.signer_id,
+                    ) {
+                        Ok(transaction_info) => transaction_info,
+                        Err(report) => {
+                            return interactive_clap::ResultFromCli::Err(
+                                optional_clap_variant,
+                                report,
+                            )
+                        }
+                    };
+                    if let Err(report) = crate::common::print_transaction_status(
+                        &transaction_info,
+                        &context.network_config,
+                    ) {
+                        return interactive_clap::ResultFromCli::Err(optional_clap_variant, report);
+                    };
+                    if let Err(report) = (context.on_after_sending_transaction_callback)(
+                        &transaction_info,
+                        &context.network_config,
+                    ) {
+                        return interactive_clap::ResultFromCli::Err(
+                            optional_clap_variant,
+                            color_eyre::Report::msg(report),
+                        );
+                    };
                     interactive_clap::ResultFromCli::Ok(CliSubmit::Send)
                 }
                 SignedTransactionOrSignedDelegateAction::SignedDelegateAction(
@@ -380,3 +394,61 @@ pub fn get_signed_delegate_action(
         signature,
     }
 }
+
+/// A transaction of a batch submitted with [`sign_batch`].
+pub struct BatchTransaction {
+    pub prepopulated_transaction: crate::commands::PrepopulatedTransaction,
+    pub on_before_sending_transaction_callback: OnBeforeSendingTransactionCallback,
+    pub on_after_sending_transaction_callback: OnAfterSendingTransactionCallback,
+}
+
+/// Signs and submits the transactions one after another. The signing options chosen interactively
+/// for the first transaction (including whether to send it) are reused for the rest of the batch,
+/// so the batch is confirmed once; in offline mode only the options given on the command line are
+/// reused, since the nonce and the public key are entered for each transaction.
+///
+/// Returns the result of each transaction, or `None` if the batch was cancelled.
+pub fn sign_batch(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    sign_with: &mut Option<CliSignWith>,
+    transactions: Vec<BatchTransaction>,
+) -> Option<Vec<crate::CliResult>> {
+    let mut results = vec![];
+    for transaction in transactions {
+        if network_config.meta_transaction_relayer_url.is_some() {
+            eprintln!("\nUnsigned delegate action:\n");
+        } else {
+            eprintln!("\nUnsigned transaction:\n");
+        }
+        crate::common::print_unsigned_transaction(&transaction.prepopulated_transaction);
+        eprintln!();
+        let transaction_context = crate::commands::TransactionContext {
+            global_context: global_context.clone(),
+            network_config: network_config.clone(),
+            prepopulated_transaction: transaction.prepopulated_transaction,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: transaction
+                .on_before_sending_transaction_callback,
+            on_after_sending_transaction_callback: transaction
+                .on_after_sending_transaction_callback,
+        };
+        let (cli_sign_with, result) = match <SignWith as interactive_clap::FromCli>::from_cli(
+            sign_with.clone(),
+            transaction_context,
+        ) {
+            interactive_clap::ResultFromCli::Ok(cli_sign_with) => (Some(cli_sign_with), Ok(())),
+            interactive_clap::ResultFromCli::Err(cli_sign_with, err) => (cli_sign_with, Err(err)),
+            interactive_clap::ResultFromCli::Cancel(_) | interactive_clap::ResultFromCli::Back => {
+                return None
+            }
+        };
+        if !global_context.offline {
+            *sign_with = cli_sign_with;
+        }
+        results.push(result);
+    }
+    Some(results)
+}
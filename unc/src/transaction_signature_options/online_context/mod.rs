@@ -0,0 +1,209 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+/// Transactions refer to a recent block, and the network rejects those whose block is
+/// older than about a day.
+const ONLINE_CONTEXT_MAX_AGE_SECS: u64 = 12 * 60 * 60;
+
+/// What an offline machine needs from the network to sign transactions: a recent block
+/// and the nonces of the access keys, prepared with `transaction prepare-online-context`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OnlineContext {
+    pub network_name: String,
+    pub block_hash: unc_primitives::hash::CryptoHash,
+    pub block_height: unc_primitives::types::BlockHeight,
+    /// Unix time (in seconds) when the context was prepared.
+    pub created_at: u64,
+    pub access_keys: Vec<OnlineAccessKey>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OnlineAccessKey {
+    pub account_id: unc_primitives::types::AccountId,
+    pub public_key: unc_crypto::PublicKey,
+    /// The last nonce used by the access key; signing offline takes the next one.
+    pub nonce: unc_primitives::types::Nonce,
+    pub full_access: bool,
+}
+
+impl OnlineContext {
+    pub fn read(path: &std::path::Path) -> color_eyre::eyre::Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read the online context file {path:?}"))?;
+        serde_json::from_str(&data)
+            .wrap_err_with(|| format!("Failed to parse the online context file {path:?}"))
+    }
+
+    pub fn write(&self, path: &std::path::Path) -> crate::CliResult {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("Failed to write the online context file {path:?}"))
+    }
+
+    /// The full access key of the signer, when the context has exactly one.
+    pub fn signer_public_key(
+        &self,
+        signer_id: &unc_primitives::types::AccountId,
+    ) -> Option<unc_crypto::PublicKey> {
+        let mut full_access_keys = self
+            .access_keys
+            .iter()
+            .filter(|access_key| &access_key.account_id == signer_id && access_key.full_access);
+        match (full_access_keys.next(), full_access_keys.next()) {
+            (Some(access_key), None) => Some(access_key.public_key.clone()),
+            _ => None,
+        }
+    }
+
+    /// Takes the values given on the command line and the rest from the context. A nonce taken
+    /// from the context is recorded as used, so the next transaction gets the one after it.
+    pub fn signing_params(
+        &mut self,
+        network_name: &str,
+        signer_id: &unc_primitives::types::AccountId,
+        public_key: &unc_crypto::PublicKey,
+        nonce: Option<unc_primitives::types::Nonce>,
+        block_hash: Option<unc_primitives::hash::CryptoHash>,
+        block_height: Option<unc_primitives::types::BlockHeight>,
+    ) -> color_eyre::eyre::Result<(
+        unc_primitives::types::Nonce,
+        unc_primitives::hash::CryptoHash,
+        unc_primitives::types::BlockHeight,
+    )> {
+        if self.network_name != network_name {
+            color_eyre::eyre::bail!(
+                "The online context was prepared for network <{}>, not <{network_name}>",
+                self.network_name
+            );
+        }
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => {
+                let access_key = self
+                    .access_keys
+                    .iter_mut()
+                    .find(|access_key| {
+                        &access_key.account_id == signer_id && &access_key.public_key == public_key
+                    })
+                    .wrap_err_with(|| {
+                        format!("The online context has no access key {public_key} of <{signer_id}>, pass --nonce")
+                    })?;
+                access_key.nonce += 1;
+                access_key.nonce
+            }
+        };
+        Ok((
+            nonce,
+            block_hash.unwrap_or(self.block_hash),
+            block_height.unwrap_or(self.block_height),
+        ))
+    }
+}
+
+/// The nonce, the recent block hash and its height to sign a transaction with in offline mode:
+/// from the command line or, for those that are not given there, from the online context file.
+pub fn offline_signing_params(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    signer_id: &unc_primitives::types::AccountId,
+    public_key: &unc_crypto::PublicKey,
+    nonce: Option<unc_primitives::types::Nonce>,
+    block_hash: Option<crate::types::crypto_hash::CryptoHash>,
+    block_height: Option<unc_primitives::types::BlockHeight>,
+) -> color_eyre::eyre::Result<(
+    unc_primitives::types::Nonce,
+    unc_primitives::hash::CryptoHash,
+    unc_primitives::types::BlockHeight,
+)> {
+    let block_hash = block_hash.map(|block_hash| block_hash.0);
+    let Some(online_context_path) = &global_context.online_context else {
+        return Ok((
+            nonce.wrap_err("Nonce is required to sign a transaction in offline mode")?,
+            block_hash.wrap_err("Block Hash is required to sign a transaction in offline mode")?,
+            block_height
+                .wrap_err("Block Height is required to sign a transaction in offline mode")?,
+        ));
+    };
+    let mut online_context = OnlineContext::read(online_context_path)?;
+    if crate::common::unix_time_now().saturating_sub(online_context.created_at)
+        > ONLINE_CONTEXT_MAX_AGE_SECS
+    {
+        eprintln!("Warning: the online context was prepared more than 12 hours ago, the network may reject transactions that refer to its block. Prepare a new one if sending fails.");
+    }
+    let signing_params = online_context.signing_params(
+        &network_config.network_name,
+        signer_id,
+        public_key,
+        nonce,
+        block_hash,
+        block_height,
+    )?;
+    if nonce.is_none() {
+        online_context.write(online_context_path)?;
+    }
+    Ok(signing_params)
+}
+
+/// The public key to sign with in offline mode when it is not given on the command line.
+pub fn offline_signer_public_key(
+    global_context: &crate::GlobalContext,
+    signer_id: &unc_primitives::types::AccountId,
+    signer_public_key: Option<crate::types::public_key::PublicKey>,
+) -> color_eyre::eyre::Result<unc_crypto::PublicKey> {
+    if let Some(signer_public_key) = signer_public_key {
+        return Ok(signer_public_key.into());
+    }
+    global_context
+        .online_context
+        .as_deref()
+        .map(OnlineContext::read)
+        .transpose()?
+        .and_then(|online_context| online_context.signer_public_key(signer_id))
+        .wrap_err("Signer public key is required to sign a transaction in offline mode")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn online_context_signing_params() {
+        let signer_id: unc_primitives::types::AccountId = "alice.unc".parse().unwrap();
+        let public_key: unc_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        let block_hash = unc_primitives::hash::CryptoHash::default();
+        let mut online_context = OnlineContext {
+            network_name: "testnet".to_string(),
+            block_hash,
+            block_height: 100,
+            created_at: 0,
+            access_keys: vec![OnlineAccessKey {
+                account_id: signer_id.clone(),
+                public_key: public_key.clone(),
+                nonce: 7,
+                full_access: true,
+            }],
+        };
+
+        assert_eq!(
+            online_context.signer_public_key(&signer_id),
+            Some(public_key.clone())
+        );
+        assert_eq!(
+            online_context
+                .signing_params("testnet", &signer_id, &public_key, None, None, None)
+                .unwrap(),
+            (8, block_hash, 100)
+        );
+        // The nonce taken from the context is not handed out twice.
+        assert_eq!(
+            online_context
+                .signing_params("testnet", &signer_id, &public_key, None, None, Some(120))
+                .unwrap(),
+            (9, block_hash, 120)
+        );
+        assert!(online_context
+            .signing_params("mainnet", &signer_id, &public_key, None, None, None)
+            .is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::CustomType;
 
 use crate::common::JsonRpcClientExt;
@@ -44,24 +44,18 @@ impl SignAccessKeyFileContext {
     ) -> color_eyre::eyre::Result<Self> {
         let network_config = previous_context.network_config.clone();
 
-        let data =
-            std::fs::read_to_string(&scope.file_path).wrap_err("Access key file not found!")?;
-        let account_json: super::AccountKeyPair = serde_json::from_str(&data)
-            .wrap_err_with(|| format!("Error reading data from file: {:?}", &scope.file_path))?;
+        let account_json = read_access_key_file(&scope.file_path.0)?;
 
         let (nonce, block_hash, block_height) = if previous_context.global_context.offline {
-            (
-                scope
-                    .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
-                scope
-                    .block_hash
-                    .wrap_err("Block Hash is required to sign a transaction in offline mode")?
-                    .0,
-                scope
-                    .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
-            )
+            super::online_context::offline_signing_params(
+                &previous_context.global_context,
+                &network_config,
+                &previous_context.prepopulated_transaction.signer_id,
+                &account_json.public_key,
+                scope.nonce,
+                scope.block_hash,
+                scope.block_height,
+            )?
         } else {
             let rpc_query_response = network_config
             .json_rpc_client()
@@ -158,11 +152,67 @@ impl From<SignAccessKeyFileContext> for super::SubmitContext {
     }
 }
 
+/// A key pair encrypted with a password (see `account rotate-key`); the public part stays readable.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EncryptedKeyFile {
+    account_id: unc_primitives::types::AccountId,
+    public_key: unc_crypto::PublicKey,
+    /// The encrypted JSON of the key pair, the same as in the legacy keychain.
+    encrypted_key_pair: crate::util::encryption::EncryptedData,
+}
+
+impl EncryptedKeyFile {
+    pub fn encrypt(
+        password: &str,
+        key_pair_properties: &crate::common::KeyPairProperties,
+        account_id: unc_primitives::types::AccountId,
+        public_key: unc_crypto::PublicKey,
+    ) -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            account_id,
+            public_key,
+            encrypted_key_pair: crate::util::encryption::EncryptedData::encrypt(
+                password,
+                serde_json::to_string(key_pair_properties)?.as_bytes(),
+            )?,
+        })
+    }
+
+    pub fn decrypt(&self, password: &str) -> color_eyre::eyre::Result<super::AccountKeyPair> {
+        let account_key_pair: super::AccountKeyPair =
+            serde_json::from_slice(&self.encrypted_key_pair.decrypt(password)?)
+                .wrap_err("Error reading the decrypted key pair")?;
+        if account_key_pair.public_key != self.public_key {
+            color_eyre::eyre::bail!(
+                "The decrypted key does not match the public key <{}> of the file",
+                self.public_key
+            );
+        }
+        Ok(account_key_pair)
+    }
+}
+
+/// Reads an access key file, asking for the password if the file is encrypted.
+fn read_access_key_file(
+    file_path: &std::path::Path,
+) -> color_eyre::eyre::Result<super::AccountKeyPair> {
+    let data = std::fs::read_to_string(file_path).wrap_err("Access key file not found!")?;
+    if let Ok(encrypted_key_file) = serde_json::from_str::<EncryptedKeyFile>(&data) {
+        let password = crate::util::encryption::input_password(&format!(
+            "Enter the password of the encrypted key file {}:",
+            file_path.display()
+        ))?;
+        return encrypted_key_file.decrypt(&password);
+    }
+    serde_json::from_str(&data)
+        .wrap_err_with(|| format!("Error reading data from file: {file_path:?}"))
+}
+
 impl SignAccessKeyFile {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<u64>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
             ));
@@ -173,7 +223,7 @@ impl SignAccessKeyFile {
     fn input_block_hash(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::crypto_hash::CryptoHash>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<crate::types::crypto_hash::CryptoHash>::new(
                     "Enter recent block hash:",
@@ -187,7 +237,7 @@ impl SignAccessKeyFile {
     fn input_block_height(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<unc_primitives::types::BlockHeight>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<unc_primitives::types::BlockHeight>::new("Enter recent block height:")
                     .prompt()?,
@@ -196,3 +246,31 @@ impl SignAccessKeyFile {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypt_encrypted_key_file() {
+        let key_pair_properties = crate::common::generate_ed25519_keypair().unwrap();
+        let public_key: unc_crypto::PublicKey = key_pair_properties.public_key_str.parse().unwrap();
+        let encrypted_key_file = EncryptedKeyFile::encrypt(
+            "password",
+            &key_pair_properties,
+            key_pair_properties.account_id.clone(),
+            public_key.clone(),
+        )
+        .unwrap();
+        let encrypted_key_file: EncryptedKeyFile =
+            serde_json::from_str(&serde_json::to_string(&encrypted_key_file).unwrap()).unwrap();
+
+        let account_key_pair = encrypted_key_file.decrypt("password").unwrap();
+        assert_eq!(account_key_pair.public_key, public_key);
+        assert_eq!(
+            account_key_pair.private_key.to_string(),
+            key_pair_properties.secret_keypair_str
+        );
+        assert!(encrypted_key_file.decrypt("wrong password").is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::CustomType;
 
 use crate::common::JsonRpcClientExt;
@@ -70,8 +70,10 @@ impl SignKeychainContext {
                 &format!(
                     "{}:{}",
                     previous_context.prepopulated_transaction.signer_id,
-                    scope.signer_public_key.clone().wrap_err(
-                        "Signer public key is required to sign a transaction in offline mode"
+                    super::online_context::offline_signer_public_key(
+                        &previous_context.global_context,
+                        &previous_context.prepopulated_transaction.signer_id,
+                        scope.signer_public_key.clone(),
                     )?
                 ),
             )?
@@ -139,25 +141,42 @@ impl SignKeychainContext {
         let account_json: super::AccountKeyPair =
             serde_json::from_str(&password).wrap_err("Error reading data")?;
 
-        let rpc_query_response = network_config
-            .json_rpc_client()
-            .blocking_call_view_access_key(
+        let (nonce, block_hash, block_height) = if previous_context.global_context.offline {
+            super::online_context::offline_signing_params(
+                &previous_context.global_context,
+                &network_config,
                 &previous_context.prepopulated_transaction.signer_id,
                 &account_json.public_key,
-                unc_primitives::types::BlockReference::latest(),
+                scope.nonce,
+                scope.block_hash,
+                scope.block_height,
+            )?
+        } else {
+            let rpc_query_response = network_config
+                .json_rpc_client()
+                .blocking_call_view_access_key(
+                    &previous_context.prepopulated_transaction.signer_id,
+                    &account_json.public_key,
+                    unc_primitives::types::BlockReference::latest(),
+                )
+                .wrap_err_with(||
+                    format!("Cannot sign a transaction due to an error while fetching the most recent nonce value on network <{}>", network_config.network_name)
+                )?;
+            (
+                rpc_query_response
+                    .access_key_view()
+                    .wrap_err("Error current_nonce")?
+                    .nonce
+                    + 1,
+                rpc_query_response.block_hash,
+                rpc_query_response.block_height,
             )
-            .wrap_err_with(||
-                format!("Cannot sign a transaction due to an error while fetching the most recent nonce value on network <{}>", network_config.network_name)
-            )?;
-        let current_nonce = rpc_query_response
-            .access_key_view()
-            .wrap_err("Error current_nonce")?
-            .nonce;
+        };
 
         let mut unsigned_transaction = unc_primitives::transaction::Transaction {
             public_key: account_json.public_key.clone(),
-            block_hash: rpc_query_response.block_hash,
-            nonce: current_nonce + 1,
+            block_hash,
+            nonce,
             signer_id: previous_context.prepopulated_transaction.signer_id,
             receiver_id: previous_context.prepopulated_transaction.receiver_id,
             actions: previous_context.prepopulated_transaction.actions,
@@ -170,7 +189,7 @@ impl SignKeychainContext {
             .sign(unsigned_transaction.get_hash_and_size().0.as_ref());
 
         if network_config.meta_transaction_relayer_url.is_some() {
-            let max_block_height = rpc_query_response.block_height
+            let max_block_height = block_height
                 + scope
                     .meta_transaction_valid_for
                     .unwrap_or(super::META_TRANSACTION_VALID_FOR_DEFAULT);
@@ -254,6 +273,13 @@ impl SignKeychain {
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::public_key::PublicKey>> {
         if context.global_context.offline {
+            if let Ok(public_key) = super::online_context::offline_signer_public_key(
+                &context.global_context,
+                &context.prepopulated_transaction.signer_id,
+                None,
+            ) {
+                return Ok(Some(public_key.into()));
+            }
             return Ok(Some(
                 CustomType::<crate::types::public_key::PublicKey>::new("Enter public_key:")
                     .prompt()?,
@@ -265,7 +291,7 @@ impl SignKeychain {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<u64>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
             ));
@@ -276,7 +302,7 @@ impl SignKeychain {
     fn input_block_hash(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::crypto_hash::CryptoHash>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<crate::types::crypto_hash::CryptoHash>::new(
                     "Enter recent block hash:",
@@ -290,7 +316,7 @@ impl SignKeychain {
     fn input_block_height(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<unc_primitives::types::BlockHeight>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<unc_primitives::types::BlockHeight>::new("Enter recent block height:")
                     .prompt()?,
@@ -1,4 +1,4 @@
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::CustomType;
 use unc_primitives::borsh;
 
@@ -47,15 +47,17 @@ impl SignLedgerContext {
         let public_key: unc_crypto::PublicKey = scope.signer_public_key.clone().into();
 
         let (nonce, block_hash) = if previous_context.global_context.offline {
-            (
-                scope
-                    .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
-                scope
-                    .block_hash
-                    .wrap_err("Block Hash is required to sign a transaction in offline mode")?
-                    .0,
-            )
+            let (nonce, block_hash, _) = super::online_context::offline_signing_params(
+                &previous_context.global_context,
+                &network_config,
+                &previous_context.prepopulated_transaction.signer_id,
+                &public_key,
+                scope.nonce,
+                scope.block_hash,
+                // The Ledger flow does not use the block height.
+                Some(0),
+            )?;
+            (nonce, block_hash)
         } else {
             let rpc_query_response = network_config
                 .json_rpc_client()
@@ -247,7 +249,7 @@ impl SignLedger {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<u64>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
             ));
@@ -258,7 +260,7 @@ impl SignLedger {
     fn input_block_hash(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::crypto_hash::CryptoHash>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<crate::types::crypto_hash::CryptoHash>::new(
                     "Enter recent block hash:",
@@ -2,7 +2,7 @@ extern crate dirs;
 
 use std::str::FromStr;
 
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::{CustomType, Select};
 
 use crate::common::JsonRpcClientExt;
@@ -70,14 +70,13 @@ impl SignLegacyKeychainContext {
                 );
                 path.push(&format!(
                     "{}.json",
-                    scope
-                        .signer_public_key
-                        .clone()
-                        .wrap_err(
-                            "Signer public key is required to sign a transaction in offline mode"
-                        )?
-                        .to_string()
-                        .replace(':', "_")
+                    super::online_context::offline_signer_public_key(
+                        &previous_context.global_context,
+                        &previous_context.prepopulated_transaction.signer_id,
+                        scope.signer_public_key.clone(),
+                    )?
+                    .to_string()
+                    .replace(':', "_")
                 ));
                 path
             } else {
@@ -157,18 +156,15 @@ impl SignLegacyKeychainContext {
             .wrap_err_with(|| format!("Error reading data from file: {:?}", &data_path))?;
 
         let (nonce, block_hash, block_height) = if previous_context.global_context.offline {
-            (
-                scope
-                    .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
-                scope
-                    .block_hash
-                    .wrap_err("Block Hash is required to sign a transaction in offline mode")?
-                    .0,
-                scope
-                    .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
-            )
+            super::online_context::offline_signing_params(
+                &previous_context.global_context,
+                &network_config,
+                &previous_context.prepopulated_transaction.signer_id,
+                &account_json.public_key,
+                scope.nonce,
+                scope.block_hash,
+                scope.block_height,
+            )?
         } else {
             let rpc_query_response = network_config
                 .json_rpc_client()
@@ -269,6 +265,13 @@ impl SignLegacyKeychain {
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::public_key::PublicKey>> {
         if context.global_context.offline {
+            if let Ok(public_key) = super::online_context::offline_signer_public_key(
+                &context.global_context,
+                &context.prepopulated_transaction.signer_id,
+                None,
+            ) {
+                return Ok(Some(public_key.into()));
+            }
             let network_config = context.network_config.clone();
 
             let mut path =
@@ -300,7 +303,7 @@ impl SignLegacyKeychain {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<u64>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
             ));
@@ -311,7 +314,7 @@ impl SignLegacyKeychain {
     fn input_block_hash(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::crypto_hash::CryptoHash>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<crate::types::crypto_hash::CryptoHash>::new(
                     "Enter recent block hash:",
@@ -325,7 +328,7 @@ impl SignLegacyKeychain {
     fn input_block_height(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<unc_primitives::types::BlockHeight>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<unc_primitives::types::BlockHeight>::new("Enter recent block height:")
                     .prompt()?,
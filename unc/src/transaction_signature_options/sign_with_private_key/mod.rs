@@ -1,4 +1,4 @@
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::CustomType;
 
 use crate::common::JsonRpcClientExt;
@@ -51,18 +51,15 @@ impl SignPrivateKeyContext {
         let public_key: unc_crypto::PublicKey = scope.signer_public_key.clone().into();
 
         let (nonce, block_hash, block_height) = if previous_context.global_context.offline {
-            (
-                scope
-                    .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
-                scope
-                    .block_hash
-                    .wrap_err("Block Hash is required to sign a transaction in offline mode")?
-                    .0,
-                scope
-                    .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
-            )
+            super::online_context::offline_signing_params(
+                &previous_context.global_context,
+                &network_config,
+                &previous_context.prepopulated_transaction.signer_id,
+                &public_key,
+                scope.nonce,
+                scope.block_hash,
+                scope.block_height,
+            )?
         } else {
             let rpc_query_response = network_config
                 .json_rpc_client()
@@ -160,7 +157,7 @@ impl SignPrivateKey {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<u64>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
             ));
@@ -171,7 +168,7 @@ impl SignPrivateKey {
     fn input_block_hash(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<crate::types::crypto_hash::CryptoHash>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<crate::types::crypto_hash::CryptoHash>::new(
                     "Enter recent block hash:",
@@ -185,7 +182,7 @@ impl SignPrivateKey {
     fn input_block_height(
         context: &crate::commands::TransactionContext,
     ) -> color_eyre::eyre::Result<Option<unc_primitives::types::BlockHeight>> {
-        if context.global_context.offline {
+        if context.global_context.offline && context.global_context.online_context.is_none() {
             return Ok(Some(
                 CustomType::<unc_primitives::types::BlockHeight>::new("Enter recent block height:")
                     .prompt()?,
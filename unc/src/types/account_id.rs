@@ -1,44 +1,80 @@
 use std::str::FromStr;
 
+/// An account ID, or a reference to an account of the address book: `@alias` or `@alias:network`.
+/// References are kept as they are until the command resolves them with [`AccountId::resolve`].
 #[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
-pub struct AccountId(pub unc_primitives::types::AccountId);
-
-impl From<AccountId> for unc_primitives::types::AccountId {
-    fn from(account_id: AccountId) -> Self {
-        account_id.0
-    }
+pub enum AccountId {
+    AccountId(unc_primitives::types::AccountId),
+    Alias(String),
 }
 
 impl From<unc_primitives::types::AccountId> for AccountId {
     fn from(account_id: unc_primitives::types::AccountId) -> Self {
-        Self(account_id)
+        Self::AccountId(account_id)
     }
 }
 
 impl std::fmt::Display for AccountId {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        self.0.fmt(f)
+        match self {
+            Self::AccountId(account_id) => account_id.fmt(f),
+            Self::Alias(reference) => write!(f, "@{reference}"),
+        }
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ParseAccountIdError {
+    #[error(transparent)]
+    Invalid(#[from] unc_primitives::account::id::ParseAccountError),
+    #[error("{0}")]
+    Alias(String),
+}
+
 impl std::str::FromStr for AccountId {
-    type Err = <unc_primitives::types::AccountId as std::str::FromStr>::Err;
+    type Err = ParseAccountIdError;
 
     fn from_str(account_id: &str) -> Result<Self, Self::Err> {
+        if let Some(reference) = account_id.strip_prefix('@') {
+            let alias = reference
+                .split_once(':')
+                .map_or(reference, |(alias, _)| alias);
+            if !crate::common::is_valid_alias(alias) {
+                return Err(ParseAccountIdError::Alias(format!(
+                    "Invalid alias @{alias}: use lowercase letters, digits, `-` and `_`"
+                )));
+            }
+            return Ok(Self::Alias(reference.to_string()));
+        }
         let account_id = unc_primitives::types::AccountId::from_str(account_id)?;
-        Ok(Self(account_id))
+        Ok(Self::AccountId(account_id))
     }
 }
 
-impl AsRef<str> for AccountId {
-    fn as_ref(&self) -> &str {
-        self.0.as_ref()
+impl AccountId {
+    /// Returns the account ID when it is not a reference to the address book.
+    pub fn as_account_id(&self) -> Option<&unc_primitives::types::AccountId> {
+        match self {
+            Self::AccountId(account_id) => Some(account_id),
+            Self::Alias(_) => None,
+        }
     }
-}
 
-impl AsRef<unc_primitives::types::AccountId> for AccountId {
-    fn as_ref(&self) -> &unc_primitives::types::AccountId {
-        &self.0
+    /// Returns the account ID, looking up a reference in the address book of the config.
+    /// A bare `@alias` takes the mapping of `network_name`, if the command already knows its network.
+    pub fn resolve(
+        &self,
+        config: &crate::config::Config,
+        network_name: Option<&str>,
+    ) -> color_eyre::eyre::Result<unc_primitives::types::AccountId> {
+        match self {
+            Self::AccountId(account_id) => Ok(account_id.clone()),
+            Self::Alias(reference) => {
+                crate::common::get_address_book(&config.credentials_home_dir)?
+                    .resolve(reference, network_name)
+                    .map_err(|err| color_eyre::eyre::eyre!(err))
+            }
+        }
     }
 }
 
@@ -53,3 +89,26 @@ impl AccountId {
         Self::from_str(owner_account_id).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_id_from_str() {
+        let account_id = AccountId::from_str("alice.unc").unwrap();
+        assert_eq!(account_id.to_string(), "alice.unc".to_string());
+        assert!(account_id.as_account_id().is_some());
+    }
+    #[test]
+    fn alias_from_str() {
+        let account_id = AccountId::from_str("@treasury:testnet").unwrap();
+        assert_eq!(account_id, AccountId::Alias("treasury:testnet".to_string()));
+        assert_eq!(account_id.to_string(), "@treasury:testnet".to_string());
+        assert!(account_id.as_account_id().is_none());
+    }
+    #[test]
+    fn invalid_alias_from_str() {
+        assert!(AccountId::from_str("@Treasury").is_err());
+    }
+}
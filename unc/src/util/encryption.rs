@@ -0,0 +1,166 @@
+use color_eyre::eyre::WrapErr;
+use ring::aead;
+
+/// The password is read from this environment variable, if it is set, instead of prompting for it.
+pub(crate) const PASSWORD_ENV_VAR: &str = "UNC_ENCRYPTION_PASSWORD";
+
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+
+/// Data encrypted with a password: the key is derived with PBKDF2-HMAC-SHA256
+/// and the data is encrypted with ChaCha20-Poly1305.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct EncryptedData {
+    pub kdf: String,
+    pub kdf_iterations: u32,
+    pub cipher: String,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl EncryptedData {
+    const KDF: &'static str = "pbkdf2-hmac-sha256";
+    const CIPHER: &'static str = "chacha20-poly1305";
+
+    pub fn encrypt(password: &str, plaintext: &[u8]) -> color_eyre::eyre::Result<Self> {
+        let rng = ring::rand::SystemRandom::new();
+        let salt: [u8; SALT_LEN] = random(&rng)?;
+        let nonce: [u8; aead::NONCE_LEN] = random(&rng)?;
+        let key = derive_key(password, &salt, KDF_ITERATIONS)?;
+        let mut ciphertext = plaintext.to_vec();
+        key.seal_in_place_append_tag(
+            aead::Nonce::assume_unique_for_key(nonce),
+            aead::Aad::empty(),
+            &mut ciphertext,
+        )
+        .map_err(|_| color_eyre::eyre::eyre!("Failed to encrypt the data"))?;
+        Ok(Self {
+            kdf: Self::KDF.to_string(),
+            kdf_iterations: KDF_ITERATIONS,
+            cipher: Self::CIPHER.to_string(),
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+    pub fn decrypt(&self, password: &str) -> color_eyre::eyre::Result<Vec<u8>> {
+        if self.kdf != Self::KDF || self.cipher != Self::CIPHER {
+            color_eyre::eyre::bail!("Unsupported encryption: {} with {}", self.cipher, self.kdf);
+        }
+        // The count comes from the file, and a lowered one would make the password cheaper to brute-force.
+        if self.kdf_iterations < KDF_ITERATIONS {
+            color_eyre::eyre::bail!(
+                "The encrypted data uses {} KDF iterations, fewer than the required {KDF_ITERATIONS}",
+                self.kdf_iterations
+            );
+        }
+        let salt = hex::decode(&self.salt).wrap_err("Invalid salt")?;
+        let nonce = aead::Nonce::try_assume_unique_for_key(
+            &hex::decode(&self.nonce).wrap_err("Invalid nonce")?,
+        )
+        .map_err(|_| color_eyre::eyre::eyre!("Invalid nonce"))?;
+        let mut ciphertext = hex::decode(&self.ciphertext).wrap_err("Invalid ciphertext")?;
+        let key = derive_key(password, &salt, self.kdf_iterations)?;
+        let plaintext = key
+            .open_in_place(nonce, aead::Aad::empty(), &mut ciphertext)
+            .map_err(|_| {
+                color_eyre::eyre::eyre!(
+                    "Failed to decrypt the data: wrong password or corrupted data"
+                )
+            })?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+fn random<const N: usize>(rng: &ring::rand::SystemRandom) -> color_eyre::eyre::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    ring::rand::SecureRandom::fill(rng, &mut bytes)
+        .map_err(|_| color_eyre::eyre::eyre!("Failed to generate random bytes"))?;
+    Ok(bytes)
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    iterations: u32,
+) -> color_eyre::eyre::Result<aead::LessSafeKey> {
+    let iterations = std::num::NonZeroU32::new(iterations)
+        .ok_or_else(|| color_eyre::eyre::eyre!("Invalid number of KDF iterations"))?;
+    let mut key = [0; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+    let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key)
+        .map_err(|_| color_eyre::eyre::eyre!("Failed to create the encryption key"))?;
+    Ok(aead::LessSafeKey::new(key))
+}
+
+/// Asks for a new password (twice) unless it is set in the environment.
+pub(crate) fn input_new_password(message: &str) -> color_eyre::eyre::Result<String> {
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return non_empty_password(password);
+    }
+    non_empty_password(
+        inquire::Password::new(message)
+            .with_display_mode(inquire::PasswordDisplayMode::Masked)
+            .with_custom_confirmation_message("Confirm the password:")
+            .prompt()?,
+    )
+}
+
+/// Asks for the password of existing encrypted data unless it is set in the environment.
+pub(crate) fn input_password(message: &str) -> color_eyre::eyre::Result<String> {
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return non_empty_password(password);
+    }
+    non_empty_password(
+        inquire::Password::new(message)
+            .with_display_mode(inquire::PasswordDisplayMode::Masked)
+            .without_confirmation()
+            .prompt()?,
+    )
+}
+
+fn non_empty_password(password: String) -> color_eyre::eyre::Result<String> {
+    if password.is_empty() {
+        color_eyre::eyre::bail!(
+            "The password must not be empty (check {PASSWORD_ENV_VAR} if it is set)"
+        );
+    }
+    Ok(password)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let encrypted = EncryptedData::encrypt("correct horse", b"secret key").unwrap();
+        let encrypted: EncryptedData =
+            serde_json::from_str(&serde_json::to_string(&encrypted).unwrap()).unwrap();
+        assert_eq!(encrypted.decrypt("correct horse").unwrap(), b"secret key");
+        assert!(encrypted.decrypt("wrong horse").is_err());
+    }
+
+    #[test]
+    fn decrypt_rejects_fewer_kdf_iterations() {
+        let mut encrypted = EncryptedData::encrypt("correct horse", b"secret key").unwrap();
+        encrypted.kdf_iterations = 1;
+        assert!(encrypted.decrypt("correct horse").is_err());
+    }
+
+    #[test]
+    fn empty_password_is_rejected() {
+        assert!(non_empty_password(String::new()).is_err());
+        assert_eq!(
+            non_empty_password("correct horse".to_string()).unwrap(),
+            "correct horse"
+        );
+    }
+}
@@ -16,6 +16,7 @@ use log::{error, info};
 use crate::common::ColorPreference;
 use crate::types::manifest::CargoManifestPath;
 
+pub(crate) mod encryption;
 mod print;
 pub(crate) use print::*;
 
@@ -322,8 +323,11 @@ pub(crate) fn extract_abi_entries(
 
 pub(crate) const COMPILATION_TARGET: &str = "wasm32-unknown-unknown";
 
-fn get_rustc_wasm32_unknown_unknown_target_libdir() -> color_eyre::eyre::Result<PathBuf> {
+fn get_rustc_wasm32_unknown_unknown_target_libdir(
+    toolchain: Option<&str>,
+) -> color_eyre::eyre::Result<PathBuf> {
     let command = Command::new("rustc")
+        .envs(toolchain.map(|toolchain| ("RUSTUP_TOOLCHAIN", toolchain)))
         .args(["--target", COMPILATION_TARGET, "--print", "target-libdir"])
         .output()?;
 
@@ -337,8 +341,9 @@ fn get_rustc_wasm32_unknown_unknown_target_libdir() -> color_eyre::eyre::Result<
     }
 }
 
-pub fn wasm32_target_libdir_exists() -> bool {
-    let result = get_rustc_wasm32_unknown_unknown_target_libdir();
+/// Checks that the wasm target is installed for the given rustup toolchain (the default one if `None`).
+pub fn wasm32_target_libdir_exists(toolchain: Option<&str>) -> bool {
+    let result = get_rustc_wasm32_unknown_unknown_target_libdir(toolchain);
 
     match result {
         Ok(wasm32_target_libdir_path) => {
@@ -359,7 +364,11 @@ pub fn wasm32_target_libdir_exists() -> bool {
         Err(_) => {
             error!("Some error in getting the target libdir, trying rustup..");
 
-            invoke_rustup(["target", "list", "--installed"])
+            let mut rustup_args = vec!["target", "list", "--installed"];
+            if let Some(toolchain) = toolchain {
+                rustup_args.extend(["--toolchain", toolchain]);
+            }
+            invoke_rustup(rustup_args)
                 .map(|stdout| {
                     stdout
                         .lines()